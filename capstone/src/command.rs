@@ -0,0 +1,205 @@
+// Commands
+
+/*
+Each line of input becomes one Command (Chapter 6: the variants carry
+different data). parse() splits the line on whitespace and matches on the
+first word. Anything wrong with the line comes back as an
+InventoryError::Parse with a message for the user, never a panic.
+
+    add <sku> <category> <quantity> <price> <name...>
+    restock <sku> <quantity>
+    sell <sku> <quantity>
+    remove <sku>
+    list [category]
+    low <threshold>
+    report
+    help
+    quit
+*/
+
+use crate::error::InventoryError;
+use crate::item::{parse_price, Category};
+
+pub const HELP: &str = "\
+commands:
+  add <sku> <category> <quantity> <price> <name...>
+  restock <sku> <quantity>
+  sell <sku> <quantity>
+  remove <sku>
+  list [category]
+  low <threshold>
+  report
+  help
+  quit";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Add {
+        sku: String,
+        name: String,
+        category: Category,
+        quantity: u32,
+        price_cents: u32,
+    },
+    Restock {
+        sku: String,
+        quantity: u32,
+    },
+    Sell {
+        sku: String,
+        quantity: u32,
+    },
+    Remove {
+        sku: String,
+    },
+    List(Option<Category>),
+    LowStock(u32),
+    Report,
+    Help,
+    Quit,
+}
+
+fn parse_error(message: String) -> InventoryError {
+    InventoryError::Parse(message)
+}
+
+fn quantity(word: &str) -> Result<u32, InventoryError> {
+    word.parse()
+        .map_err(|_| parse_error(format!("'{}' isn't a whole number", word)))
+}
+
+impl Command {
+    // Ok(None) for a blank line, there's nothing to do but no need to complain
+    pub fn parse(line: &str) -> Result<Option<Command>, InventoryError> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let command = match words.as_slice() {
+            [] => return Ok(None),
+            ["add", sku, category, count, price, name @ ..] if !name.is_empty() => Command::Add {
+                sku: sku.to_string(),
+                name: name.join(" "),
+                category: category.parse().map_err(parse_error)?,
+                quantity: quantity(count)?,
+                price_cents: parse_price(price).map_err(parse_error)?,
+            },
+            ["restock", sku, count] => Command::Restock {
+                sku: sku.to_string(),
+                quantity: quantity(count)?,
+            },
+            ["sell", sku, count] => Command::Sell {
+                sku: sku.to_string(),
+                quantity: quantity(count)?,
+            },
+            ["remove", sku] => Command::Remove {
+                sku: sku.to_string(),
+            },
+            ["list"] => Command::List(None),
+            ["list", category] => Command::List(Some(category.parse().map_err(parse_error)?)),
+            ["low", threshold] => Command::LowStock(quantity(threshold)?),
+            ["report"] => Command::Report,
+            ["help"] => Command::Help,
+            ["quit"] => Command::Quit,
+            // a known command with the wrong number of words
+            [word, ..]
+                if [
+                    "add", "restock", "sell", "remove", "list", "low", "report", "help", "quit",
+                ]
+                .contains(word) =>
+            {
+                return Err(parse_error(format!(
+                    "wrong arguments for '{}', try 'help'",
+                    word
+                )))
+            }
+            [word, ..] => {
+                return Err(parse_error(format!(
+                    "unknown command '{}', try 'help'",
+                    word
+                )))
+            }
+        };
+        Ok(Some(command))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(line: &str) -> Command {
+        Command::parse(line).unwrap().unwrap()
+    }
+
+    fn message(line: &str) -> String {
+        Command::parse(line).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn add_takes_the_rest_of_the_line_as_the_name() {
+        assert_eq!(
+            parse("add H1 tool 4 19.99 claw   hammer"),
+            Command::Add {
+                sku: String::from("H1"),
+                name: String::from("claw hammer"),
+                category: Category::Tool,
+                quantity: 4,
+                price_cents: 1999,
+            }
+        );
+    }
+
+    #[test]
+    fn simple_commands() {
+        assert_eq!(
+            parse("  sell H1 2 "),
+            Command::Sell {
+                sku: String::from("H1"),
+                quantity: 2
+            }
+        );
+        assert_eq!(
+            parse("restock H1 10"),
+            Command::Restock {
+                sku: String::from("H1"),
+                quantity: 10
+            }
+        );
+        assert_eq!(
+            parse("remove H1"),
+            Command::Remove {
+                sku: String::from("H1")
+            }
+        );
+        assert_eq!(parse("list"), Command::List(None));
+        assert_eq!(parse("list part"), Command::List(Some(Category::Part)));
+        assert_eq!(parse("low 3"), Command::LowStock(3));
+        assert_eq!(parse("report"), Command::Report);
+        assert_eq!(parse("help"), Command::Help);
+        assert_eq!(parse("quit"), Command::Quit);
+    }
+
+    #[test]
+    fn blank_lines_are_skipped() {
+        assert_eq!(Command::parse("   "), Ok(None));
+        assert_eq!(Command::parse(""), Ok(None));
+    }
+
+    #[test]
+    fn helpful_errors() {
+        assert_eq!(message("dance"), "unknown command 'dance', try 'help'");
+        assert_eq!(message("sell H1"), "wrong arguments for 'sell', try 'help'");
+        assert_eq!(
+            message("add H1 tool 4 19.99"),
+            "wrong arguments for 'add', try 'help'"
+        );
+        assert_eq!(message("sell H1 two"), "'two' isn't a whole number");
+        assert_eq!(message("sell H1 -2"), "'-2' isn't a whole number");
+        assert_eq!(
+            message("add H1 snack 1 1.00 chips"),
+            "unknown category 'snack' (tool, part or consumable)"
+        );
+        assert_eq!(
+            message("add H1 tool 1 1.999 hammer"),
+            "'1.999' isn't a price like 12.50"
+        );
+    }
+}
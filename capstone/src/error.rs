@@ -0,0 +1,58 @@
+// Errors
+
+/*
+Everything a command can get wrong, in one enum. A bad command doesn't end the
+session: session.rs prints the error (via Display) and reads the next line.
+*/
+
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
+pub enum InventoryError {
+    // the line couldn't be turned into a Command, with a message saying why
+    Parse(String),
+    UnknownSku(String),
+    DuplicateSku(String),
+    NotEnoughStock { sku: String, have: u32, want: u32 },
+}
+
+impl fmt::Display for InventoryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InventoryError::Parse(message) => write!(f, "{}", message),
+            InventoryError::UnknownSku(sku) => write!(f, "no item with SKU {}", sku),
+            InventoryError::DuplicateSku(sku) => write!(f, "SKU {} is already in use", sku),
+            InventoryError::NotEnoughStock { sku, have, want } => {
+                write!(f, "only {} of {} in stock, can't take {}", have, sku, want)
+            }
+        }
+    }
+}
+
+impl Error for InventoryError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages() {
+        assert_eq!(
+            InventoryError::UnknownSku(String::from("X1")).to_string(),
+            "no item with SKU X1"
+        );
+        let short = InventoryError::NotEnoughStock {
+            sku: String::from("H1"),
+            have: 2,
+            want: 5,
+        };
+        assert_eq!(short.to_string(), "only 2 of H1 in stock, can't take 5");
+    }
+
+    #[test]
+    fn works_as_a_boxed_error() {
+        let error: Box<dyn Error> = Box::new(InventoryError::DuplicateSku(String::from("H1")));
+        assert_eq!(error.to_string(), "SKU H1 is already in use");
+    }
+}
@@ -0,0 +1,295 @@
+// The Inventory
+
+/*
+Items are looked up two ways: by SKU for add/sell/restock, and by category for
+`list tool` and the report. Keeping two copies of each item would mean
+remembering to update both on every sale, so instead each item lives in one
+Rc<RefCell<Item>> (Chapter 15) and both HashMaps (Chapter 8) hold a clone of
+the Rc. Selling through the SKU map changes the quantity the category map
+sees, there's only one Item.
+
+RefCell moves the borrow checking to runtime. Every borrow_mut() here ends
+at the end of its statement or block, so two of them never overlap and
+nothing panics.
+*/
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::error::InventoryError;
+use crate::item::{self, Category, Item};
+
+type Shared = Rc<RefCell<Item>>;
+
+#[derive(Default)]
+pub struct Inventory {
+    by_sku: HashMap<String, Shared>,
+    by_category: HashMap<Category, Vec<Shared>>,
+    sales_cents: u64,
+}
+
+impl Inventory {
+    pub fn new() -> Inventory {
+        Inventory::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_sku.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_sku.is_empty()
+    }
+
+    pub fn add(&mut self, item: Item) -> Result<(), InventoryError> {
+        if self.by_sku.contains_key(&item.sku) {
+            return Err(InventoryError::DuplicateSku(item.sku));
+        }
+        let category = item.category;
+        let shared = Rc::new(RefCell::new(item));
+        self.by_category
+            .entry(category)
+            .or_default()
+            .push(Rc::clone(&shared));
+        let sku = shared.borrow().sku.clone();
+        self.by_sku.insert(sku, shared);
+        Ok(())
+    }
+
+    // the shared item itself, so changes made through it show up everywhere
+    pub fn get(&self, sku: &str) -> Option<Shared> {
+        self.by_sku.get(sku).map(Rc::clone)
+    }
+
+    fn find(&self, sku: &str) -> Result<&Shared, InventoryError> {
+        self.by_sku
+            .get(sku)
+            .ok_or_else(|| InventoryError::UnknownSku(String::from(sku)))
+    }
+
+    // returns the new quantity
+    pub fn restock(&mut self, sku: &str, quantity: u32) -> Result<u32, InventoryError> {
+        let mut item = self.find(sku)?.borrow_mut();
+        item.quantity = item.quantity.saturating_add(quantity);
+        Ok(item.quantity)
+    }
+
+    // returns what the sale made, in cents. Nothing changes if there isn't
+    // enough in stock
+    pub fn sell(&mut self, sku: &str, quantity: u32) -> Result<u64, InventoryError> {
+        let earned = {
+            let mut item = self.find(sku)?.borrow_mut();
+            if item.quantity < quantity {
+                return Err(InventoryError::NotEnoughStock {
+                    sku: String::from(sku),
+                    have: item.quantity,
+                    want: quantity,
+                });
+            }
+            item.quantity -= quantity;
+            item.price_cents as u64 * quantity as u64
+        };
+        self.sales_cents += earned;
+        Ok(earned)
+    }
+
+    pub fn remove(&mut self, sku: &str) -> Result<Item, InventoryError> {
+        let shared = self
+            .by_sku
+            .remove(sku)
+            .ok_or_else(|| InventoryError::UnknownSku(String::from(sku)))?;
+        let category = shared.borrow().category;
+        if let Some(items) = self.by_category.get_mut(&category) {
+            // ptr_eq: the same Rc, not just an equal looking item
+            items.retain(|other| !Rc::ptr_eq(other, &shared));
+        }
+        // with both maps' clones gone this is usually the last Rc and the Item
+        // can be moved out. If a caller is still holding one from get(), it
+        // gets a copy instead
+        Ok(Rc::try_unwrap(shared)
+            .map(RefCell::into_inner)
+            .unwrap_or_else(|shared| shared.borrow().clone()))
+    }
+
+    // copies of the items, sorted by SKU (a HashMap's order changes from run
+    // to run). None lists every category
+    pub fn items(&self, category: Option<Category>) -> Vec<Item> {
+        let mut items: Vec<Item> = match category {
+            Some(category) => self
+                .by_category
+                .get(&category)
+                .map(|items| items.iter().map(|item| item.borrow().clone()).collect())
+                .unwrap_or_default(),
+            None => self
+                .by_sku
+                .values()
+                .map(|item| item.borrow().clone())
+                .collect(),
+        };
+        items.sort_by(|a, b| a.sku.cmp(&b.sku));
+        items
+    }
+
+    // everything with `threshold` or fewer left, sorted by SKU
+    pub fn low_stock(&self, threshold: u32) -> Vec<Item> {
+        self.items(None)
+            .into_iter()
+            .filter(|item| item.quantity <= threshold)
+            .collect()
+    }
+
+    // what everything on the shelves is worth, in cents
+    pub fn stock_value(&self) -> u64 {
+        item::total_value(self.by_sku.values())
+    }
+
+    // the same, one total per category, in Category::ALL order
+    pub fn value_by_category(&self) -> Vec<(Category, u64)> {
+        Category::ALL
+            .into_iter()
+            .map(|category| {
+                let value = self.by_category.get(&category).map_or(0, item::total_value);
+                (category, value)
+            })
+            .collect()
+    }
+
+    pub fn sales_cents(&self) -> u64 {
+        self.sales_cents
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(sku: &str, category: Category, quantity: u32, price_cents: u32) -> Item {
+        Item {
+            sku: String::from(sku),
+            name: format!("{} item", sku),
+            category,
+            quantity,
+            price_cents,
+        }
+    }
+
+    fn stocked() -> Inventory {
+        let mut inventory = Inventory::new();
+        inventory.add(item("H1", Category::Tool, 4, 1999)).unwrap();
+        inventory.add(item("B7", Category::Part, 100, 5)).unwrap();
+        inventory
+            .add(item("T2", Category::Consumable, 2, 350))
+            .unwrap();
+        inventory.add(item("S3", Category::Tool, 1, 1200)).unwrap();
+        inventory
+    }
+
+    fn skus(items: &[Item]) -> Vec<&str> {
+        items.iter().map(|item| item.sku.as_str()).collect()
+    }
+
+    #[test]
+    fn add_rejects_duplicates() {
+        let mut inventory = stocked();
+        assert_eq!(
+            inventory.add(item("H1", Category::Part, 1, 1)),
+            Err(InventoryError::DuplicateSku(String::from("H1")))
+        );
+        assert_eq!(inventory.len(), 4);
+    }
+
+    #[test]
+    fn selling_is_seen_through_both_maps() {
+        let mut inventory = stocked();
+        assert_eq!(inventory.sell("H1", 3), Ok(3 * 1999));
+        let tools = inventory.items(Some(Category::Tool));
+        assert_eq!(tools[0].sku, "H1");
+        assert_eq!(tools[0].quantity, 1);
+        assert_eq!(inventory.sales_cents(), 3 * 1999);
+    }
+
+    #[test]
+    fn cant_sell_what_isnt_there() {
+        let mut inventory = stocked();
+        assert_eq!(
+            inventory.sell("T2", 3),
+            Err(InventoryError::NotEnoughStock {
+                sku: String::from("T2"),
+                have: 2,
+                want: 3
+            })
+        );
+        assert_eq!(inventory.get("T2").unwrap().borrow().quantity, 2);
+        assert_eq!(inventory.sales_cents(), 0);
+        assert_eq!(
+            inventory.sell("ZZ", 1),
+            Err(InventoryError::UnknownSku(String::from("ZZ")))
+        );
+    }
+
+    #[test]
+    fn restock_adds() {
+        let mut inventory = stocked();
+        assert_eq!(inventory.restock("T2", 10), Ok(12));
+        assert!(inventory.restock("ZZ", 1).is_err());
+    }
+
+    #[test]
+    fn the_maps_share_one_item() {
+        let inventory = stocked();
+        let hammer = inventory.get("H1").unwrap();
+        // by_sku, by_category, and ours
+        assert_eq!(Rc::strong_count(&hammer), 3);
+        hammer.borrow_mut().price_cents = 2499;
+        assert_eq!(inventory.items(Some(Category::Tool))[0].price_cents, 2499);
+    }
+
+    #[test]
+    fn remove_takes_it_out_of_both_maps() {
+        let mut inventory = stocked();
+        let removed = inventory.remove("S3").unwrap();
+        assert_eq!(removed, item("S3", Category::Tool, 1, 1200));
+        assert_eq!(skus(&inventory.items(Some(Category::Tool))), vec!["H1"]);
+        assert!(inventory.get("S3").is_none());
+        assert!(inventory.remove("S3").is_err());
+    }
+
+    #[test]
+    fn remove_while_someone_still_holds_it() {
+        let mut inventory = stocked();
+        let held = inventory.get("H1").unwrap();
+        let removed = inventory.remove("H1").unwrap();
+        assert_eq!(removed.sku, "H1");
+        // the holder keeps their copy, it's just not in the inventory any more
+        assert_eq!(Rc::strong_count(&held), 1);
+    }
+
+    #[test]
+    fn listing_and_low_stock() {
+        let inventory = stocked();
+        assert_eq!(skus(&inventory.items(None)), vec!["B7", "H1", "S3", "T2"]);
+        assert_eq!(
+            skus(&inventory.items(Some(Category::Tool))),
+            vec!["H1", "S3"]
+        );
+        assert_eq!(skus(&inventory.low_stock(2)), vec!["S3", "T2"]);
+        assert!(inventory.low_stock(0).is_empty());
+        assert!(Inventory::new().items(Some(Category::Part)).is_empty());
+    }
+
+    #[test]
+    fn values() {
+        let inventory = stocked();
+        // 4 * 19.99 + 100 * 0.05 + 2 * 3.50 + 12.00
+        assert_eq!(inventory.stock_value(), 7996 + 500 + 700 + 1200);
+        assert_eq!(
+            inventory.value_by_category(),
+            vec![
+                (Category::Tool, 7996 + 1200),
+                (Category::Part, 500),
+                (Category::Consumable, 700),
+            ]
+        );
+    }
+}
@@ -0,0 +1,221 @@
+// Items and What They're Worth
+
+/*
+An Item is a plain struct (Chapter 5). Prices are kept in cents as whole
+numbers, floats can't hold most decimal amounts exactly (0.1 + 0.2 != 0.3) and
+money shouldn't drift.
+
+Valued is a trait (Chapter 10) for anything with a unit price and a count. It
+gets value() for free from a default method, and total_value is generic over
+it, so the same function adds up a slice of Items or the Rc<RefCell<Item>>s
+the inventory keeps.
+*/
+
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Category {
+    Tool,
+    Part,
+    Consumable,
+}
+
+impl Category {
+    pub const ALL: [Category; 3] = [Category::Tool, Category::Part, Category::Consumable];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Category::Tool => "tool",
+            Category::Part => "part",
+            Category::Consumable => "consumable",
+        }
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Category {
+    type Err = String;
+
+    // "tool", "Tool" and "TOOL" all work
+    fn from_str(s: &str) -> Result<Category, String> {
+        Category::ALL
+            .into_iter()
+            .find(|category| category.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("unknown category '{}' (tool, part or consumable)", s))
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Item {
+    pub sku: String,
+    pub name: String,
+    pub category: Category,
+    pub quantity: u32,
+    pub price_cents: u32,
+}
+
+pub trait Valued {
+    fn unit_price(&self) -> u32;
+    fn count(&self) -> u32;
+
+    // u64 so a big stock of expensive things can't overflow
+    fn value(&self) -> u64 {
+        self.unit_price() as u64 * self.count() as u64
+    }
+}
+
+impl Valued for Item {
+    fn unit_price(&self) -> u32 {
+        self.price_cents
+    }
+
+    fn count(&self) -> u32 {
+        self.quantity
+    }
+}
+
+// a shared item is worth whatever the item inside it is worth. Each call
+// borrows the RefCell just long enough to read one field
+impl<T: Valued> Valued for Rc<RefCell<T>> {
+    fn unit_price(&self) -> u32 {
+        self.borrow().unit_price()
+    }
+
+    fn count(&self) -> u32 {
+        self.borrow().count()
+    }
+}
+
+pub fn total_value<'a, T: Valued + 'a>(items: impl IntoIterator<Item = &'a T>) -> u64 {
+    items.into_iter().map(|item| item.value()).sum()
+}
+
+// 1250 -> "$12.50"
+pub fn dollars(cents: u64) -> String {
+    format!("${}.{:02}", cents / 100, cents % 100)
+}
+
+// "12.50", "12.5" or "12" -> 1250. At most two decimal places, no sign
+pub fn parse_price(s: &str) -> Result<u32, String> {
+    let bad = || format!("'{}' isn't a price like 12.50", s);
+    let digits = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+    // parse() on its own would let a sign through ("+1", "1.+5"), so both
+    // sides are checked for plain digits first. A dot needs 1 or 2 after it
+    let (whole, fraction) = match s.split_once('.') {
+        Some((whole, fraction)) if digits(fraction) && fraction.len() <= 2 => (whole, fraction),
+        Some(_) => return Err(bad()),
+        None => (s, ""),
+    };
+    if !digits(whole) {
+        return Err(bad());
+    }
+    let whole: u32 = whole.parse().map_err(|_| bad())?;
+    let fraction: u32 = match fraction.len() {
+        0 => 0,
+        // "12.5" is 50 cents, not 5
+        1 => fraction.parse::<u32>().map_err(|_| bad())? * 10,
+        _ => fraction.parse().map_err(|_| bad())?,
+    };
+    whole
+        .checked_mul(100)
+        .and_then(|cents| cents.checked_add(fraction))
+        .ok_or_else(bad)
+}
+
+impl fmt::Display for Item {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} ({}) x{} @ {}",
+            self.sku,
+            self.name,
+            self.category,
+            self.quantity,
+            dollars(self.price_cents as u64)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(sku: &str, quantity: u32, price_cents: u32) -> Item {
+        Item {
+            sku: String::from(sku),
+            name: String::from("thing"),
+            category: Category::Part,
+            quantity,
+            price_cents,
+        }
+    }
+
+    #[test]
+    fn category_from_str() {
+        assert_eq!("tool".parse(), Ok(Category::Tool));
+        assert_eq!("CONSUMABLE".parse(), Ok(Category::Consumable));
+        assert!("snack".parse::<Category>().is_err());
+    }
+
+    #[test]
+    fn value_comes_from_the_default_method() {
+        assert_eq!(item("A", 3, 250).value(), 750);
+        assert_eq!(item("A", 0, 250).value(), 0);
+        // bigger than a u32 can hold
+        assert_eq!(item("A", u32::MAX, 2).value(), u32::MAX as u64 * 2);
+    }
+
+    #[test]
+    fn total_value_of_plain_and_shared_items() {
+        let items = vec![item("A", 2, 100), item("B", 1, 999)];
+        assert_eq!(total_value(&items), 1199);
+
+        let shared: Vec<Rc<RefCell<Item>>> = items
+            .into_iter()
+            .map(|item| Rc::new(RefCell::new(item)))
+            .collect();
+        assert_eq!(total_value(&shared), 1199);
+        shared[0].borrow_mut().quantity = 10;
+        assert_eq!(total_value(&shared), 1999);
+    }
+
+    #[test]
+    fn money_formatting() {
+        assert_eq!(dollars(1250), "$12.50");
+        assert_eq!(dollars(5), "$0.05");
+        assert_eq!(dollars(0), "$0.00");
+    }
+
+    #[test]
+    fn price_parsing() {
+        assert_eq!(parse_price("12.50"), Ok(1250));
+        assert_eq!(parse_price("12.5"), Ok(1250));
+        assert_eq!(parse_price("12"), Ok(1200));
+        assert_eq!(parse_price("0.05"), Ok(5));
+        for bad in [
+            "", ".5", "12.345", "-1", "+1", "1.x", "abc", "99999999", "1.+5", "1.-5", "12.", "1..5",
+        ] {
+            assert!(parse_price(bad).is_err(), "{:?} should be rejected", bad);
+        }
+    }
+
+    #[test]
+    fn display() {
+        let hammer = Item {
+            sku: String::from("H1"),
+            name: String::from("claw hammer"),
+            category: Category::Tool,
+            quantity: 4,
+            price_cents: 1999,
+        };
+        assert_eq!(hammer.to_string(), "H1 claw hammer (tool) x4 @ $19.99");
+    }
+}
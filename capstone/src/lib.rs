@@ -0,0 +1,26 @@
+// Capstone: An Inventory Manager (library)
+// main.rs hooks a session up to stdin and stdout, this lib holds everything
+// else so a whole session can be run (and tested) from a script
+
+/*
+A small program that uses a bit of most chapters at once. Commands come in one
+per line (`add`, `sell`, `restock`, `list`, ...) and each one prints a reply.
+
+Where each chapter shows up:
+  - item.rs       structs (5), an enum with FromStr (6), a trait with a
+                  default method and generic functions over it (10)
+  - command.rs    an enum with data in its variants and a match over words
+                  (6), parsing that returns Result instead of panicking (9)
+  - error.rs      one error enum with Display and Error (9)
+  - inventory.rs  HashMaps and Vecs (8), Rc<RefCell<Item>> so the SKU index
+                  and the category index share one copy of each item (15),
+                  iterator chains with closures for the reports (13)
+  - session.rs    the read-eval-print loop over any BufRead and Write, the
+                  same trick chapter_2 uses to play its game from a test
+*/
+
+pub mod command;
+pub mod error;
+pub mod inventory;
+pub mod item;
+pub mod session;
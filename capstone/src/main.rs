@@ -0,0 +1,18 @@
+// Capstone: An Inventory Manager
+
+/*
+Type commands, one per line, `help` lists them and `quit` (or end of input)
+stops. A session can also be piped in:
+
+    cargo run < restock_day.txt
+*/
+
+use std::io;
+
+fn main() {
+    let stdin = io::stdin();
+    if let Err(e) = capstone::session::run_session(stdin.lock(), &mut io::stdout()) {
+        eprintln!("Couldn't read or write: {e}");
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,165 @@
+// Running a Session
+
+/*
+The loop main.rs runs: read a line, parse it into a Command, carry it out,
+print the reply, repeat until `quit` or the input runs out. Reading from any
+BufRead and writing to any Write means a test can run a whole session from a
+string and check everything it printed.
+
+A command that fails prints "error: ..." and the session carries on, one typo
+shouldn't throw away the whole inventory. Only an I/O error (stdout closed,
+say) ends it early, and that comes back as the Err.
+*/
+
+use std::io::{self, BufRead, Write};
+
+use crate::command::{Command, HELP};
+use crate::error::InventoryError;
+use crate::inventory::Inventory;
+use crate::item::{dollars, Item};
+
+pub const GREETING: &str = "inventory manager, type 'help' for commands";
+
+fn lines(items: &[Item], empty: &str) -> String {
+    if items.is_empty() {
+        return String::from(empty);
+    }
+    items
+        .iter()
+        .map(|item| item.to_string())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+// carries out one command and returns what to print. Quit is handled by the
+// loop, here it just says goodbye
+pub fn execute(inventory: &mut Inventory, command: Command) -> Result<String, InventoryError> {
+    let reply = match command {
+        Command::Add {
+            sku,
+            name,
+            category,
+            quantity,
+            price_cents,
+        } => {
+            let reply = format!("added {} {}", sku, name);
+            inventory.add(Item {
+                sku,
+                name,
+                category,
+                quantity,
+                price_cents,
+            })?;
+            reply
+        }
+        Command::Restock { sku, quantity } => {
+            let now = inventory.restock(&sku, quantity)?;
+            format!("{} now has {}", sku, now)
+        }
+        Command::Sell { sku, quantity } => {
+            let earned = inventory.sell(&sku, quantity)?;
+            format!("sold {} x {} for {}", quantity, sku, dollars(earned))
+        }
+        Command::Remove { sku } => {
+            let item = inventory.remove(&sku)?;
+            format!("removed {} {}", item.sku, item.name)
+        }
+        Command::List(category) => lines(&inventory.items(category), "no items"),
+        Command::LowStock(threshold) => format!(
+            "{} or fewer left:\n{}",
+            threshold,
+            lines(&inventory.low_stock(threshold), "nothing")
+        ),
+        Command::Report => {
+            let mut report = format!(
+                "items: {}\nstock value: {}",
+                inventory.len(),
+                dollars(inventory.stock_value())
+            );
+            for (category, value) in inventory.value_by_category() {
+                report.push_str(&format!("\n  {}: {}", category, dollars(value)));
+            }
+            report.push_str(&format!("\nsales: {}", dollars(inventory.sales_cents())));
+            report
+        }
+        Command::Help => String::from(HELP),
+        Command::Quit => String::from("bye"),
+    };
+    Ok(reply)
+}
+
+pub fn run_session(input: impl BufRead, out: &mut dyn Write) -> io::Result<Inventory> {
+    let mut inventory = Inventory::new();
+    writeln!(out, "{}", GREETING)?;
+
+    for line in input.lines() {
+        let command = match Command::parse(&line?) {
+            Ok(Some(command)) => command,
+            Ok(None) => continue,
+            Err(e) => {
+                writeln!(out, "error: {}", e)?;
+                continue;
+            }
+        };
+        let quit = command == Command::Quit;
+        match execute(&mut inventory, command) {
+            Ok(reply) => writeln!(out, "{}", reply)?,
+            Err(e) => writeln!(out, "error: {}", e)?,
+        }
+        if quit {
+            break;
+        }
+    }
+    Ok(inventory)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(script: &str) -> (String, Inventory) {
+        let mut out = Vec::new();
+        let inventory = run_session(script.as_bytes(), &mut out).unwrap();
+        (String::from_utf8(out).unwrap(), inventory)
+    }
+
+    #[test]
+    fn errors_dont_end_the_session() {
+        let (output, inventory) = run("sell H1 1\nfly\nadd H1 tool 1 2.00 hammer\n");
+        assert_eq!(
+            output,
+            format!(
+                "{}\nerror: no item with SKU H1\nerror: unknown command 'fly', try 'help'\nadded H1 hammer\n",
+                GREETING
+            )
+        );
+        assert_eq!(inventory.len(), 1);
+    }
+
+    #[test]
+    fn quit_stops_reading() {
+        let (output, inventory) = run("quit\nadd H1 tool 1 2.00 hammer\n");
+        assert!(output.ends_with("bye\n"));
+        assert!(inventory.is_empty());
+    }
+
+    #[test]
+    fn empty_input_is_fine() {
+        let (output, inventory) = run("");
+        assert_eq!(output, format!("{}\n", GREETING));
+        assert!(inventory.is_empty());
+    }
+
+    #[test]
+    fn empty_lists() {
+        let mut inventory = Inventory::new();
+        assert_eq!(
+            execute(&mut inventory, Command::List(None)),
+            Ok(String::from("no items"))
+        );
+        assert_eq!(
+            execute(&mut inventory, Command::LowStock(5)),
+            Ok(String::from("5 or fewer left:\nnothing"))
+        );
+    }
+}
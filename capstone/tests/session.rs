@@ -0,0 +1,70 @@
+// A whole session, driven the way a user would: a script of commands in,
+// everything the program printed out. Like every file in tests/ this is its
+// own crate and only sees capstone's public API
+
+use capstone::item::Category;
+use capstone::session::{run_session, GREETING};
+
+const SCRIPT: &str = "\
+add H1 tool 4 19.99 claw hammer
+add B7 part 100 0.05 hex bolt
+add T2 consumable 2 3.50 duct tape
+add H1 tool 1 1.00 another hammer
+
+sell H1 3
+sell T2 5
+restock T2 10
+sell B7 40
+list
+list tool
+low 2
+remove B7
+report
+quit
+list
+";
+
+#[test]
+fn full_session() {
+    let mut out = Vec::new();
+    let inventory = run_session(SCRIPT.as_bytes(), &mut out).unwrap();
+    let output = String::from_utf8(out).unwrap();
+
+    let expected = [
+        GREETING,
+        "added H1 claw hammer",
+        "added B7 hex bolt",
+        "added T2 duct tape",
+        "error: SKU H1 is already in use",
+        "sold 3 x H1 for $59.97",
+        "error: only 2 of T2 in stock, can't take 5",
+        "T2 now has 12",
+        "sold 40 x B7 for $2.00",
+        // list
+        "B7 hex bolt (part) x60 @ $0.05",
+        "H1 claw hammer (tool) x1 @ $19.99",
+        "T2 duct tape (consumable) x12 @ $3.50",
+        // list tool
+        "H1 claw hammer (tool) x1 @ $19.99",
+        // low 2
+        "2 or fewer left:",
+        "H1 claw hammer (tool) x1 @ $19.99",
+        "removed B7 hex bolt",
+        // report: 19.99 + 12 * 3.50
+        "items: 2",
+        "stock value: $61.99",
+        "  tool: $19.99",
+        "  part: $0.00",
+        "  consumable: $42.00",
+        "sales: $61.97",
+        "bye",
+    ];
+    assert_eq!(output.lines().collect::<Vec<&str>>(), expected);
+
+    // the session hands back the inventory too, the `list` after quit never ran
+    assert_eq!(inventory.len(), 2);
+    let tape = inventory.get("T2").unwrap();
+    assert_eq!(tape.borrow().quantity, 12);
+    assert_eq!(tape.borrow().category, Category::Consumable);
+    assert_eq!(inventory.sales_cents(), 5997 + 200);
+}
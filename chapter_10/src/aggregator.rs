@@ -0,0 +1,212 @@
+// 10.2 (continued) The `aggregator` Library
+
+// In traits.rs NewsArticle, Tweet, and Summary only lived inside run(), so
+// nothing else could use them. Here they are as a real library: the final
+// version of the Summary trait (a required summarize_author plus a default
+// summarize), the two types that implement it, and a Feed that can hold both.
+
+pub trait Summary {
+    fn summarize_author(&self) -> String;
+
+    fn summarize(&self) -> String {
+        format!("(Read more from {}...)", self.summarize_author())
+    }
+
+    // seconds since the unix epoch, used to order a Feed
+    fn published_at(&self) -> u64;
+}
+
+pub struct NewsArticle {
+    pub headline: String,
+    pub location: String,
+    pub author: String,
+    pub content: String,
+    pub published_at: u64,
+}
+
+impl Summary for NewsArticle {
+    fn summarize_author(&self) -> String {
+        self.author.clone()
+    }
+
+    // overriding the default implementation
+    fn summarize(&self) -> String {
+        format!("{}, by {} ({})", self.headline, self.author, self.location)
+    }
+
+    fn published_at(&self) -> u64 {
+        self.published_at
+    }
+}
+
+pub struct Tweet {
+    pub username: String,
+    pub content: String,
+    pub reply: bool,
+    pub retweet: bool,
+    pub published_at: u64,
+}
+
+// Tweet keeps the default summarize
+impl Summary for Tweet {
+    fn summarize_author(&self) -> String {
+        format!("@{}", self.username)
+    }
+
+    fn published_at(&self) -> u64 {
+        self.published_at
+    }
+}
+
+/*
+A Vec can only hold one type, so Vec<NewsArticle> or Vec<Tweet> but not both.
+Generics don't help either: a Vec<T> with T: Summary still picks *one* T.
+What we want is "anything that implements Summary", which is what a trait
+object gives us. Box<dyn Summary> is a pointer to some value on the heap plus
+the table of Summary methods for its real type, so the calls get looked up at
+runtime instead of compile time. (Trait objects get a full section in Chapter 17.)
+*/
+
+#[derive(Default)]
+pub struct Feed {
+    items: Vec<Box<dyn Summary>>,
+}
+
+impl Feed {
+    pub fn new() -> Feed {
+        Feed { items: Vec::new() }
+    }
+
+    // 'static because the Feed owns the item, it can't hold any borrowed data
+    // that might go away before the Feed does
+    pub fn push(&mut self, item: impl Summary + 'static) {
+        self.items.push(Box::new(item));
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn summaries(&self) -> Vec<String> {
+        self.items.iter().map(|item| item.summarize()).collect()
+    }
+
+    pub fn sort_newest_first(&mut self) {
+        self.items
+            .sort_by_key(|item| std::cmp::Reverse(item.published_at()));
+    }
+}
+
+// Generics vs trait objects
+// The same search written both ways. A generic function gets a separate copy
+// compiled for each T it's used with (monomorphization), so every call is a
+// direct call the compiler can inline. A Box<dyn Summary> call goes through
+// the vtable at runtime instead. chapter_17's dispatch.rs adds a third way, an
+// enum, and uses these two to compare against. 0 for no items at all.
+
+pub fn newest_generic<T: Summary>(items: &[T]) -> u64 {
+    items
+        .iter()
+        .map(|item| item.published_at())
+        .max()
+        .unwrap_or(0)
+}
+
+pub fn newest_dyn(items: &[Box<dyn Summary>]) -> u64 {
+    items
+        .iter()
+        .map(|item| item.published_at())
+        .max()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn article(headline: &str, published_at: u64) -> NewsArticle {
+        NewsArticle {
+            headline: String::from(headline),
+            location: String::from("Pittsburgh, PA, USA"),
+            author: String::from("Iceburgh"),
+            content: String::new(),
+            published_at,
+        }
+    }
+
+    fn tweet(username: &str, published_at: u64) -> Tweet {
+        Tweet {
+            username: String::from(username),
+            content: String::from("of course, as you probably already know, people"),
+            reply: false,
+            retweet: false,
+            published_at,
+        }
+    }
+
+    #[test]
+    fn summaries_use_each_types_implementation() {
+        let mut feed = Feed::new();
+        feed.push(article("Penguins win the Stanley Cup Championship!", 10));
+        feed.push(tweet("horse_ebooks", 20));
+
+        assert_eq!(
+            feed.summaries(),
+            vec![
+                "Penguins win the Stanley Cup Championship!, by Iceburgh (Pittsburgh, PA, USA)",
+                "(Read more from @horse_ebooks...)",
+            ]
+        );
+    }
+
+    #[test]
+    fn sorts_mixed_items_newest_first() {
+        let mut feed = Feed::new();
+        feed.push(tweet("old", 1));
+        feed.push(article("Newest", 30));
+        feed.push(tweet("middle", 15));
+        feed.sort_newest_first();
+
+        assert_eq!(
+            feed.summaries(),
+            vec![
+                "Newest, by Iceburgh (Pittsburgh, PA, USA)",
+                "(Read more from @middle...)",
+                "(Read more from @old...)",
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_feed() {
+        let feed = Feed::default();
+        assert!(feed.is_empty());
+        assert_eq!(feed.len(), 0);
+        assert!(feed.summaries().is_empty());
+    }
+
+    // measures newest_generic against newest_dyn, run it with
+    //     cargo test --release -- --ignored --nocapture
+    #[test]
+    #[ignore]
+    fn bench_generic_vs_dyn_dispatch() {
+        use chapter_11::bench::time_it;
+
+        let tweets: Vec<Tweet> = (0..100_000).map(|i| tweet("bench", i)).collect();
+        let boxed: Vec<Box<dyn Summary>> = (0..100_000)
+            .map(|i| Box::new(tweet("bench", i)) as Box<dyn Summary>)
+            .collect();
+
+        let generic = time_it("generic", 200, || newest_generic(&tweets));
+        let dynamic = time_it("dyn", 200, || newest_dyn(&boxed));
+        println!("dyn / generic: {:.2}x", dynamic.ratio_to(&generic));
+
+        // timings depend on the machine, so the only thing asserted is that
+        // both versions agree
+        assert_eq!(newest_generic(&tweets), newest_dyn(&boxed));
+    }
+}
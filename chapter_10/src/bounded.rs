@@ -0,0 +1,122 @@
+// 10.2 (continued) Associated Constants: a Bounded Trait
+
+// Traits can require more than methods. An associated constant is a value that
+// every implementing type has to provide, and it's used through the type
+// rather than an instance: T::MAX instead of some_value.max().
+// The integer types already have inherent MIN and MAX constants (u8::MAX is
+// 255), but those aren't part of any trait, so generic code has no way to ask
+// a T for its bounds. A Bounded trait fixes that.
+
+pub trait Bounded {
+    const MIN: Self;
+    const MAX: Self;
+}
+
+impl Bounded for u8 {
+    const MIN: u8 = u8::MIN;
+    const MAX: u8 = u8::MAX;
+}
+
+impl Bounded for i8 {
+    const MIN: i8 = i8::MIN;
+    const MAX: i8 = i8::MAX;
+}
+
+impl Bounded for u32 {
+    const MIN: u32 = u32::MIN;
+    const MAX: u32 = u32::MAX;
+}
+
+impl Bounded for i32 {
+    const MIN: i32 = i32::MIN;
+    const MAX: i32 = i32::MAX;
+}
+
+impl Bounded for i64 {
+    const MIN: i64 = i64::MIN;
+    const MAX: i64 = i64::MAX;
+}
+
+// A guess between 1 and 100 again, but with the range described by the trait.
+// An i32 inside it *could* hold 500, so unlike the integers above, clamping
+// one actually does something.
+//
+// Not a replacement for domain::Guess, the one the guessing game and Chapter 9
+// use: that one refuses an out of range value (new() panics, try_new() gives
+// back a GuessError), this one quietly pulls it to the nearest bound (500
+// becomes 100). Handy where any answer beats no answer, wrong for a game that
+// should tell the player their guess didn't count. Hence the different name.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct ClampedGuess(i32);
+
+impl Bounded for ClampedGuess {
+    const MIN: ClampedGuess = ClampedGuess(1);
+    const MAX: ClampedGuess = ClampedGuess(100);
+}
+
+impl ClampedGuess {
+    // never fails, out of range values get pulled to the nearest bound
+    pub fn new(value: i32) -> ClampedGuess {
+        clamp_to_bounds(ClampedGuess(value))
+    }
+
+    pub fn value(&self) -> i32 {
+        self.0
+    }
+}
+
+pub fn clamp_to_bounds<T: Bounded + PartialOrd>(value: T) -> T {
+    if value < T::MIN {
+        T::MIN
+    } else if value > T::MAX {
+        T::MAX
+    } else {
+        value
+    }
+}
+
+pub fn bounds<T: Bounded>() -> (T, T) {
+    (T::MIN, T::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integer_bounds_through_the_trait() {
+        assert_eq!(bounds::<u8>(), (0, 255));
+        assert_eq!(bounds::<i8>(), (-128, 127));
+        assert_eq!(<i64 as Bounded>::MAX, i64::MAX);
+    }
+
+    #[test]
+    fn integers_are_always_within_their_own_bounds() {
+        assert_eq!(clamp_to_bounds(200u8), 200);
+        assert_eq!(clamp_to_bounds(i32::MIN), i32::MIN);
+    }
+
+    #[test]
+    fn clamped_guess_is_clamped() {
+        assert_eq!(
+            bounds::<ClampedGuess>(),
+            (ClampedGuess(1), ClampedGuess(100))
+        );
+        assert_eq!(clamp_to_bounds(ClampedGuess(150)), ClampedGuess::MAX);
+        assert_eq!(clamp_to_bounds(ClampedGuess(-3)), ClampedGuess::MIN);
+        assert_eq!(clamp_to_bounds(ClampedGuess(42)), ClampedGuess(42));
+    }
+
+    #[test]
+    fn guess_new_never_panics() {
+        assert_eq!(ClampedGuess::new(0).value(), 1);
+        assert_eq!(ClampedGuess::new(1000).value(), 100);
+        assert_eq!(ClampedGuess::new(50).value(), 50);
+    }
+
+    #[test]
+    fn domain_guess_refuses_what_clamped_guess_clamps() {
+        assert!(domain::Guess::try_new(1000).is_err());
+        assert_eq!(ClampedGuess::new(1000).value(), 100);
+    }
+}
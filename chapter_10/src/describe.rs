@@ -0,0 +1,105 @@
+// 10.2 (continued) Blanket Implementations: Describe
+
+// The book shows the standard library's `impl<T: Display> ToString for T`.
+// Here is our own version of that: one impl block gives describe() to every
+// type that implements Display, including types we haven't written yet.
+
+use std::fmt::Display;
+
+pub trait Describe {
+    fn describe(&self) -> String;
+}
+
+// the blanket implementation. T: ?Sized lets it cover str too, not just &str
+impl<T: Display + ?Sized> Describe for T {
+    fn describe(&self) -> String {
+        format!("This is {}", self)
+    }
+}
+
+// the domain crate's Rectangle, the same one chapter_13's sorting uses. It
+// implements Display ("a 30x50 rectangle"), which is all it takes to get
+// describe()
+pub use domain::Rectangle;
+
+/*
+What if we want Rectangle to describe itself differently? Writing
+`impl Describe for Rectangle` won't compile: the blanket impl already covers
+Rectangle (it implements Display), and a type can only implement a trait once.
+    error[E0119]: conflicting implementations of trait `Describe` for type `Rectangle`
+
+The way around it is a newtype, a tuple struct with one field that wraps the
+Rectangle. It's a brand new type that doesn't implement Display, so the blanket
+impl doesn't apply and we're free to write our own.
+*/
+pub struct Detailed(pub Rectangle);
+
+impl Describe for Detailed {
+    fn describe(&self) -> String {
+        let rect = &self.0;
+        format!(
+            "This is a rectangle {} wide and {} tall, with an area of {}",
+            rect.width,
+            rect.height,
+            rect.area()
+        )
+    }
+}
+
+// works for anything that can describe itself, blanket or hand written
+pub fn describe_all(items: &[&dyn Describe]) -> Vec<String> {
+    items.iter().map(|item| item.describe()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blanket_impl_covers_std_types() {
+        assert_eq!(5.describe(), "This is 5");
+        assert_eq!(String::from("hello").describe(), "This is hello");
+        assert_eq!("a str slice".describe(), "This is a str slice");
+    }
+
+    #[test]
+    fn blanket_impl_covers_our_types() {
+        let rect = Rectangle {
+            width: 30,
+            height: 50,
+        };
+        assert_eq!(rect.describe(), "This is a 30x50 rectangle");
+    }
+
+    #[test]
+    fn newtype_gets_its_own_impl() {
+        let rect = Rectangle {
+            width: 3,
+            height: 4,
+        };
+        assert_eq!(
+            Detailed(rect).describe(),
+            "This is a rectangle 3 wide and 4 tall, with an area of 12"
+        );
+        // the original is untouched and still uses the blanket impl
+        assert_eq!(rect.describe(), "This is a 3x4 rectangle");
+    }
+
+    #[test]
+    fn mixing_both_impls() {
+        let rect = Rectangle {
+            width: 1,
+            height: 2,
+        };
+        let detailed = Detailed(rect);
+        let number = -7;
+        assert_eq!(
+            describe_all(&[&number, &rect, &detailed]),
+            vec![
+                "This is -7",
+                "This is a 1x2 rectangle",
+                "This is a rectangle 1 wide and 2 tall, with an area of 2",
+            ]
+        );
+    }
+}
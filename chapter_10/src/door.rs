@@ -0,0 +1,112 @@
+// 10.1 (continued) Generic Marker Types: a Typestate Door
+
+// A generic parameter doesn't have to be stored as a value. Here the State in
+// Door<State> only exists to tell the compiler which state the door is in, so
+// a locked door simply doesn't *have* an open() method. Trying to open it is a
+// compile error, not a runtime check:
+//
+//     let door = Door::new(); // Door<Locked>
+//     door.open();
+//     error[E0599]: no method named `open` found for struct `Door<Locked>`
+//
+// Each state is a struct with no fields (a zero-sized type), so it takes up no
+// memory. But a struct that never uses its generic parameter won't compile
+// ("parameter `State` is never used"), which is what PhantomData is for: it
+// acts like it holds a State without actually storing anything.
+
+use std::marker::PhantomData;
+
+pub struct Locked;
+pub struct Unlocked;
+pub struct Open;
+
+pub struct Door<State> {
+    name: String,
+    state: PhantomData<State>,
+}
+
+// every transition consumes `self`, so the old Door<Locked> can't be used
+// after it's been unlocked. This is the same reason the book's blog post
+// example (Chapter 17) takes self instead of &self
+impl<State> Door<State> {
+    fn into_state<Next>(self) -> Door<Next> {
+        Door {
+            name: self.name,
+            state: PhantomData,
+        }
+    }
+
+    // available in every state
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Door<Locked> {
+    // every door starts out locked
+    pub fn new(name: &str) -> Door<Locked> {
+        Door {
+            name: String::from(name),
+            state: PhantomData,
+        }
+    }
+
+    pub fn unlock(self) -> Door<Unlocked> {
+        self.into_state()
+    }
+}
+
+impl Door<Unlocked> {
+    pub fn lock(self) -> Door<Locked> {
+        self.into_state()
+    }
+
+    pub fn open(self) -> Door<Open> {
+        self.into_state()
+    }
+}
+
+impl Door<Open> {
+    pub fn close(self) -> Door<Unlocked> {
+        self.into_state()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem::size_of;
+
+    #[test]
+    fn unlock_then_open() {
+        let door = Door::new("front");
+        let door: Door<Unlocked> = door.unlock();
+        let door: Door<Open> = door.open();
+        assert_eq!(door.name(), "front");
+    }
+
+    #[test]
+    fn close_and_lock_again() {
+        let door = Door::new("back").unlock().open().close().lock();
+        // only a Door<Locked> has unlock(), so this line compiling is the test
+        let door: Door<Unlocked> = door.unlock();
+        assert_eq!(door.name(), "back");
+    }
+
+    #[test]
+    fn markers_take_no_space() {
+        assert_eq!(size_of::<Locked>(), 0);
+        assert_eq!(size_of::<PhantomData<Open>>(), 0);
+        // so every Door is just its name, whatever state it's in
+        assert_eq!(size_of::<Door<Locked>>(), size_of::<String>());
+        assert_eq!(size_of::<Door<Open>>(), size_of::<Door<Unlocked>>());
+    }
+
+    /*
+    The transitions that aren't allowed can't be written as tests, because they
+    don't compile:
+        Door::new("x").open();                  // no open() on Door<Locked>
+        Door::new("x").unlock().unlock();       // no unlock() on Door<Unlocked>
+        Door::new("x").unlock().open().lock();  // no lock() on Door<Open>
+    */
+}
@@ -1,6 +1,8 @@
 // 10.1 Generic Data Types
 
-pub fn _run() {
+use std::ops::{Add, Mul, Neg, Sub};
+
+pub fn run() {
     // In function Definitions
     // we start with 2 functions tha find the largest value for different types
     fn largest_i32(list: &[i32]) -> &i32 {
@@ -31,29 +33,28 @@ pub fn _run() {
     println!("The largest char in the list is: {}", char_result);
 
     // both of these functions have the same logic, they just operate on
-    // different types. We can fix this using generics
-    fn largest<T: std::cmp::PartialOrd>(list: &[T]) -> &T {
-        let mut largest = &list[0];
-        for item in list {
-            if item > largest {
-                // this ordering expression doesn't compile if we don't ensure T implements PartialOrd
-                largest = item;
-            }
-        }
-        largest
-    }
+    // different types. We can fix this using generics (see `largest` at the
+    // bottom of this file, it's pulled out of run() so it can be tested)
 
-    let result2 = largest(&number_list);
-    println!("found the largest i32 using a generics func: {result2}");
-    let result3 = largest(&char_list);
-    println!("found the largest char using a generics func: {result3}");
+    if let Some(result2) = largest(&number_list) {
+        println!("found the largest i32 using a generics func: {result2}");
+    }
+    if let Some(result3) = largest(&char_list) {
+        println!("found the largest char using a generics func: {result3}");
+    }
+    let empty: Vec<i32> = Vec::new();
+    println!("the largest of an empty list is: {:?}", largest(&empty));
 
     // In Struct Definitions
     // we can also define structs to use generics as well
+    // (Point is defined at the bottom of this file, outside of run(), so we can
+    // keep building on it and test it)
+    /*
     struct Point<T> {
         x: T,
         y: T,
     }
+    */
 
     let integer = Point { x: 5, y: 10 };
     let float = Point { x: 1.1, y: 4.2 };
@@ -85,23 +86,30 @@ pub fn _run() {
     }
 
     // In Method Definitions
+    /*
     impl<T> Point<T> {
         fn x(&self) -> &T {
             &self.x
         }
     }
+    */
     let p = Point { x: 5, y: 10 };
     println!("p.x = {}", p.x());
     // we can also specify constraints on generic types when implementing methods
+    /*
     impl Point<f32> {
         fn distance_from_origin(&self) -> f32 {
             (self.x.powi(2) + self.y.powi(2)).sqrt()
         }
     }
+    */
 
     let fp = Point { x: 4.0, y: 10.5 };
 
     println!("distance from origin fp = {}", fp.distance_from_origin());
+    // and because Point implements the std::ops traits (bottom of the file)
+    // we can use operators on it just like on numbers
+    println!("fp + fp = {:?}, -fp = {:?}, fp * 2 = {:?}", fp + fp, -fp, fp * 2.0);
     // we can implement methods with generics that don't match the struct definition
     struct AnotherPoint<X1, Y1> {
         x: X1,
@@ -124,3 +132,220 @@ pub fn _run() {
 
     println!("p3.x = {}, p3.y = {}", p3.x, p3.y);
 }
+
+// Point, pulled out of run() so we can give it some real math. Operators like
+// + and - are just traits from std::ops, so implementing Add for Point is what
+// makes `a + b` work. Each impl says what T has to support for it to make
+// sense, e.g. adding two points needs T: Add.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point<T> {
+    x: T,
+    y: T,
+}
+
+impl<T> Point<T> {
+    pub fn new(x: T, y: T) -> Point<T> {
+        Point { x, y }
+    }
+
+    pub fn x(&self) -> &T {
+        &self.x
+    }
+
+    pub fn y(&self) -> &T {
+        &self.y
+    }
+}
+
+impl Point<f32> {
+    pub fn distance_from_origin(&self) -> f32 {
+        self.magnitude()
+    }
+
+    pub fn dot(&self, other: &Point<f32>) -> f32 {
+        self.x * other.x + self.y * other.y
+    }
+
+    pub fn magnitude(&self) -> f32 {
+        self.dot(self).sqrt()
+    }
+}
+
+impl Point<f64> {
+    pub fn dot(&self, other: &Point<f64>) -> f64 {
+        self.x * other.x + self.y * other.y
+    }
+
+    pub fn magnitude(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+}
+
+// `Output = T` means adding two Ts has to give back a T (true for numbers,
+// but not for everything, e.g. &str + &str doesn't even compile)
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Point<T>;
+
+    fn add(self, other: Point<T>) -> Point<T> {
+        Point {
+            x: self.x + other.x,
+            y: self.y + other.y,
+        }
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Point<T>;
+
+    fn sub(self, other: Point<T>) -> Point<T> {
+        Point {
+            x: self.x - other.x,
+            y: self.y - other.y,
+        }
+    }
+}
+
+// unary minus, -point. u32 doesn't implement Neg, so neither does Point<u32>
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Point<T>;
+
+    fn neg(self) -> Point<T> {
+        Point {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+// point * scalar. The scalar is used twice, so T also needs to be Copy
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point<T> {
+    type Output = Point<T>;
+
+    fn mul(self, scalar: T) -> Point<T> {
+        Point {
+            x: self.x * scalar,
+            y: self.y * scalar,
+        }
+    }
+}
+
+// The first version of largest started with `&list[0]`, which panics when the
+// list is empty. There *is* no largest item in an empty list, so instead of
+// crashing we return an Option and let the caller decide what to do.
+pub fn largest<T: PartialOrd>(list: &[T]) -> Option<&T> {
+    let mut largest = list.first()?;
+    for item in list {
+        if item > largest || incomparable(largest) {
+            largest = item;
+        }
+    }
+    Some(largest)
+}
+
+// Same idea, but comparing by some key pulled out of each item, like
+// largest_by_key(&rectangles, |r| r.area()). Ties keep the first one found.
+pub fn largest_by_key<T, K, F>(list: &[T], key: F) -> Option<&T>
+where
+    K: PartialOrd,
+    F: Fn(&T) -> K,
+{
+    let mut iter = list.iter();
+    let mut largest = iter.next()?;
+    let mut largest_key = key(largest);
+    for item in iter {
+        let item_key = key(item);
+        if item_key > largest_key || incomparable(&largest_key) {
+            largest = item;
+            largest_key = item_key;
+        }
+    }
+    Some(largest)
+}
+
+// PartialOrd (rather than Ord) means some values can't be compared at all,
+// like f64::NAN: every comparison with it is false, so once a NaN became our
+// "largest" nothing would ever replace it. A value that can't even be compared
+// with itself is one of those, so we let any other item take its place.
+fn incomparable<T: PartialOrd>(value: &T) -> bool {
+    value.partial_cmp(value).is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_slice_has_no_largest() {
+        let empty: [i32; 0] = [];
+        assert_eq!(largest(&empty), None);
+        assert_eq!(largest_by_key(&empty, |n| -n), None);
+    }
+
+    #[test]
+    fn numbers_and_chars() {
+        assert_eq!(largest(&[34, 50, 25, 100, 65]), Some(&100));
+        assert_eq!(largest(&['y', 'm', 'a', 'q']), Some(&'y'));
+        assert_eq!(largest(&[7]), Some(&7));
+    }
+
+    #[test]
+    fn floats_with_nan() {
+        assert_eq!(largest(&[1.5, f64::NAN, 2.5, 0.5]), Some(&2.5));
+        // a NaN at the front doesn't get stuck as the answer
+        assert_eq!(largest(&[f64::NAN, 1.0, 3.0]), Some(&3.0));
+        // but if NaN is all there is, that's what we get back
+        assert!(largest(&[f64::NAN]).unwrap().is_nan());
+    }
+
+    #[test]
+    fn point_add_sub_neg() {
+        let a = Point::new(1, 2);
+        let b = Point::new(10, 20);
+        assert_eq!(a + b, Point::new(11, 22));
+        assert_eq!(b - a, Point::new(9, 18));
+        assert_eq!(-a, Point::new(-1, -2));
+        assert_eq!(a + -a, Point::new(0, 0));
+    }
+
+    #[test]
+    fn point_scalar_mul() {
+        assert_eq!(Point::new(3, -4) * 2, Point::new(6, -8));
+        assert_eq!(Point::new(0.5, 1.5) * 2.0, Point::new(1.0, 3.0));
+    }
+
+    #[test]
+    fn point_dot_and_magnitude() {
+        let a = Point::new(3.0_f64, 4.0);
+        assert_eq!(a.magnitude(), 5.0);
+        assert_eq!(a.dot(&Point::new(1.0, 0.0)), 3.0);
+        // perpendicular vectors have a dot product of zero
+        assert_eq!(a.dot(&Point::new(-4.0, 3.0)), 0.0);
+
+        let b = Point::new(3.0_f32, 4.0);
+        assert_eq!(b.distance_from_origin(), 5.0);
+        assert_eq!(b.magnitude(), 5.0);
+    }
+
+    #[derive(Debug, PartialEq, PartialOrd)]
+    struct Version(u32, u32);
+
+    struct Player {
+        name: &'static str,
+        score: u32,
+    }
+
+    #[test]
+    fn custom_structs() {
+        // derived PartialOrd compares the fields in order
+        let versions = [Version(1, 4), Version(2, 0), Version(1, 9)];
+        assert_eq!(largest(&versions), Some(&Version(2, 0)));
+
+        let players = [
+            Player { name: "ann", score: 7 },
+            Player { name: "bob", score: 9 },
+            Player { name: "cat", score: 9 },
+        ];
+        let best = largest_by_key(&players, |p| p.score).unwrap();
+        assert_eq!(best.name, "bob");
+    }
+}
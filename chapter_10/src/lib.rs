@@ -0,0 +1,49 @@
+// Chapter 10: Generic Types, Traits, and Lifetimes (library)
+// A const generic Matrix, a memoizing cache, merge_sorted and its where
+// clause, traits with default methods and a blanket impl (Summary, Notify,
+// Describe), hand written ordering for version numbers, and a StrSplit whose
+// two lifetimes say which string its pieces borrow from. exercises() lists
+// the walkthroughs for the runner
+
+pub mod aggregator;
+pub mod bounded;
+pub mod describe;
+pub mod door;
+pub mod generic_types;
+pub mod lifetimes;
+pub mod matrix;
+pub mod memo;
+pub mod merge;
+pub mod notify;
+pub mod str_split;
+pub mod traits;
+pub mod version;
+
+use domain::Section;
+
+// every walkthrough, in the book's order, for the runner
+pub fn exercises() -> Vec<Section> {
+    vec![
+        Section {
+            name: "generic_types",
+            run: || {
+                generic_types::run();
+                Ok(())
+            },
+        },
+        Section {
+            name: "traits",
+            run: || {
+                traits::run();
+                Ok(())
+            },
+        },
+        Section {
+            name: "lifetimes",
+            run: || {
+                lifetimes::run();
+                Ok(())
+            },
+        },
+    ]
+}
@@ -184,9 +184,13 @@ pub fn run() {
     annotation on every reference in the struct's definition
     */
 
+    // (ImportantExcerpt is defined at the bottom of this file, outside of
+    // run(), so it can be reused and tested)
+    /*
     struct ImportantExcerpt<'a> {
         part: &'a str,
     }
+    */
 
     let novel = String::from("Call me Ishmael. Some year ago...");
     let first_sentence = novel.split(".").next().expect("Could not find a '.'");
@@ -219,19 +223,23 @@ pub fn run() {
     of the struct's type
     */
 
+    /*
     impl<'a> ImportantExcerpt<'a> {
         fn level(&self) -> i32 {
             3
         }
     }
+    */
     println!("{}", i.level());
 
+    /*
     impl<'a> ImportantExcerpt<'a> {
         fn announce_and_return_part(&self, announcement: &str) -> &str {
             println!("Attention please: {}", announcement);
             self.part
         }
     }
+    */
     i.announce_and_return_part("I'm hungry");
 
     // The Static Lifetime
@@ -251,6 +259,12 @@ pub fn run() {
     but most times this is due to some dangling reference errors which should be fixed
     */
 
+    // There are a few legitimate reasons to want 'static though, the `statics`
+    // module at the bottom of this file has three of them: a config that's set
+    // up once and shared for the rest of the program, leaking a String to get a
+    // &'static str, and handing values to a new thread
+    println!("running as {}", statics::config().app_name);
+
     // Generic Type Parameters, Trait Bounds, and Lifetimes Together
     // Here is what this might look like all together in a single function
 
@@ -266,3 +280,242 @@ pub fn run() {
         }
     }
 }
+
+pub struct ImportantExcerpt<'a> {
+    pub part: &'a str,
+}
+
+impl<'a> ImportantExcerpt<'a> {
+    pub fn level(&self) -> i32 {
+        3
+    }
+
+    pub fn announce_and_return_part(&self, announcement: &str) -> &str {
+        println!("Attention please: {}", announcement);
+        self.part
+    }
+
+    /*
+    The methods below all return slices of `part`, so they're really slices of
+    whatever text the excerpt was made from. Notice they return &'a str, not an
+    elided &str. With elision the result would be tied to &self (rule 3), and
+    we'd have to keep the ImportantExcerpt alive for as long as we use the
+    result. Naming 'a says "this borrows from the novel, not from the excerpt".
+    */
+
+    // sentences end at a '.', '!', or '?', empty pieces (like the ones between
+    // the dots of "...") are skipped
+    pub fn sentences(&self) -> impl Iterator<Item = &'a str> {
+        self.part
+            .split(['.', '!', '?'])
+            .map(|sentence| sentence.trim())
+            .filter(|sentence| !sentence.is_empty())
+    }
+
+    pub fn word_count(&self) -> usize {
+        self.part.split_whitespace().count()
+    }
+
+    // one slice covering the first n words (and the spaces between them), or
+    // the whole excerpt if it has fewer than n words
+    pub fn first_n_words(&self, n: usize) -> &'a str {
+        let text = self.part.trim_start();
+        if n == 0 {
+            return "";
+        }
+
+        let mut words = 0;
+        let mut in_word = false;
+        for (i, c) in text.char_indices() {
+            if c.is_whitespace() {
+                if in_word {
+                    words += 1;
+                    if words == n {
+                        return &text[..i];
+                    }
+                }
+                in_word = false;
+            } else {
+                in_word = true;
+            }
+        }
+        text.trim_end()
+    }
+}
+
+pub mod statics {
+    use std::fmt::Display;
+    use std::sync::OnceLock;
+    use std::thread::{self, JoinHandle};
+
+    // 1. Lazily initialized global configuration
+
+    /*
+    A `static` lives for the whole program, so references to it are &'static.
+    But a static has to be built at compile time, and a config usually isn't
+    known until the program is running. OnceLock fills that gap: it starts out
+    empty, the first call to get_or_init runs the closure, and every call after
+    that gets the same value back. It's also safe to use from several threads.
+    */
+    #[derive(Debug, PartialEq)]
+    pub struct Config {
+        pub app_name: String,
+        pub max_connections: u32,
+    }
+
+    static CONFIG: OnceLock<Config> = OnceLock::new();
+
+    pub fn config() -> &'static Config {
+        CONFIG.get_or_init(|| Config {
+            app_name: String::from("chapter_10"),
+            max_connections: 8,
+        })
+    }
+
+    // 2. Box::leak
+
+    /*
+    Box::leak gives up ownership of a heap value without ever freeing it, and
+    hands back a reference that's valid for the rest of the program. That's a
+    memory leak on purpose, so it's only a good idea for a value that's made
+    once (or a handful of times) and needed until the program exits, like a
+    name built from command line arguments that lots of structs want to hold
+    as a plain &'static str.
+    */
+    pub fn leak_str(s: String) -> &'static str {
+        Box::leak(s.into_boxed_str())
+    }
+
+    // 3. T: 'static for thread::spawn
+
+    /*
+    A spawned thread might outlive the function that spawned it, so
+    thread::spawn only accepts values that can't dangle: T: 'static. That
+    doesn't mean "lives forever", it means "doesn't borrow anything that could
+    go away". Owned values like String or Vec<i32> are 'static, a &String
+    pointing at a local variable isn't. (Threads are Chapter 16.)
+    */
+    pub fn describe_in_thread<T>(value: T) -> JoinHandle<String>
+    where
+        T: Display + Send + 'static,
+    {
+        thread::spawn(move || format!("thread got {}", value))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn config_is_initialized_once() {
+            let first = config();
+            let second = config();
+            assert!(std::ptr::eq(first, second));
+            assert_eq!(first.app_name, "chapter_10");
+            assert_eq!(first.max_connections, 8);
+        }
+
+        #[test]
+        fn config_is_shared_across_threads() {
+            let from_thread = thread::spawn(|| config() as *const Config as usize)
+                .join()
+                .unwrap();
+            assert_eq!(from_thread, config() as *const Config as usize);
+        }
+
+        #[test]
+        fn leaked_str_outlives_its_string() {
+            let leaked: &'static str;
+            {
+                let name = format!("{}-{}", "ferris", 42);
+                leaked = leak_str(name);
+                // `name` was moved into leak_str, nothing is dropped here
+            }
+            assert_eq!(leaked, "ferris-42");
+        }
+
+        #[test]
+        fn owned_values_can_go_to_a_thread() {
+            let text = String::from("an owned String");
+            assert_eq!(
+                describe_in_thread(text).join().unwrap(),
+                "thread got an owned String"
+            );
+            assert_eq!(describe_in_thread(7).join().unwrap(), "thread got 7");
+            // a leaked &'static str is fine too, since it can't dangle
+            let leaked = leak_str(String::from("leaked"));
+            assert_eq!(
+                describe_in_thread(leaked).join().unwrap(),
+                "thread got leaked"
+            );
+            // but a reference to a local isn't 'static:
+            // let local = String::from("local");
+            // describe_in_thread(&local);
+            // error[E0597]: `local` does not live long enough
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOVEL: &str = "Call me Ishmael. Some years ago... never mind how long precisely! Why?";
+
+    #[test]
+    fn sentences() {
+        let excerpt = ImportantExcerpt { part: NOVEL };
+        let sentences: Vec<&str> = excerpt.sentences().collect();
+        assert_eq!(
+            sentences,
+            vec![
+                "Call me Ishmael",
+                "Some years ago",
+                "never mind how long precisely",
+                "Why"
+            ]
+        );
+    }
+
+    #[test]
+    fn word_count() {
+        assert_eq!(ImportantExcerpt { part: NOVEL }.word_count(), 12);
+        assert_eq!(ImportantExcerpt { part: "   " }.word_count(), 0);
+    }
+
+    #[test]
+    fn first_n_words() {
+        let excerpt = ImportantExcerpt {
+            part: "  Call me   Ishmael.",
+        };
+        assert_eq!(excerpt.first_n_words(0), "");
+        assert_eq!(excerpt.first_n_words(1), "Call");
+        assert_eq!(excerpt.first_n_words(2), "Call me");
+        assert_eq!(excerpt.first_n_words(3), "Call me   Ishmael.");
+        assert_eq!(excerpt.first_n_words(10), "Call me   Ishmael.");
+    }
+
+    #[test]
+    fn results_outlive_the_excerpt() {
+        let novel = String::from("Call me Ishmael. Some years ago...");
+        let first_sentence;
+        let opening;
+        {
+            let excerpt = ImportantExcerpt { part: &novel };
+            first_sentence = excerpt.sentences().next();
+            opening = excerpt.first_n_words(2);
+        } // excerpt is dropped here, but the slices point into `novel`
+
+        assert_eq!(first_sentence, Some("Call me Ishmael"));
+        assert_eq!(opening, "Call me");
+    }
+
+    #[test]
+    fn slices_point_into_the_original_text() {
+        let novel = String::from("Call me Ishmael.");
+        let excerpt = ImportantExcerpt { part: &novel };
+        let first = excerpt.first_n_words(1);
+        // same memory, no copying happened
+        assert_eq!(first.as_ptr(), novel.as_ptr());
+    }
+}
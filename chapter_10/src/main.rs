@@ -1,8 +1,7 @@
 // Chapter 10 : Generic Types, Traits, and Lifetimes
 
-mod generic_types;
-mod traits;
-mod lifetimes;
+// the sections now live in the lib (lib.rs) so their types can be reused and
+// tested, main calls them as chapter_10::section::run()
 
 fn main() {
     // before we really dig into all this, lets first dig into removing duplication
@@ -40,7 +39,7 @@ fn main() {
 
     // In section 1 we'll use generics to reduce duplication even more
 
-    // generic_types::run();
-    // traits::run();
-    lifetimes::run();
+    // chapter_10::generic_types::run();
+    // chapter_10::traits::run();
+    chapter_10::lifetimes::run();
 }
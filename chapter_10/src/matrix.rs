@@ -0,0 +1,145 @@
+// 10.1 (continued) Const Generics: a Fixed-Size Matrix
+
+// Generics aren't limited to types. A const generic parameter is a value (like
+// a usize) that's part of the type, the same way the 3 in [i32; 3] is part of
+// the array's type. Matrix<T, 2, 3> and Matrix<T, 3, 2> are different types,
+// so adding a 2x3 to a 3x2 is a compile error instead of a runtime panic:
+//
+//     let a: Matrix<i32, 2, 3> = Matrix::zero();
+//     let b: Matrix<i32, 3, 2> = Matrix::zero();
+//     let c = a + b;
+//     error[E0308]: mismatched types
+//         expected `Matrix<i32, 2, 3>`, found `Matrix<i32, 3, 2>`
+
+use std::ops::{Add, Index, IndexMut};
+
+// R rows of C columns, stored as nested arrays so there's no Vec and no
+// heap allocation, the whole matrix lives wherever the struct does
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix<T, const R: usize, const C: usize> {
+    rows: [[T; C]; R],
+}
+
+impl<T, const R: usize, const C: usize> Matrix<T, R, C> {
+    pub fn from_rows(rows: [[T; C]; R]) -> Self {
+        Matrix { rows }
+    }
+
+    pub fn rows(&self) -> usize {
+        R
+    }
+
+    pub fn cols(&self) -> usize {
+        C
+    }
+}
+
+// zero() needs T: Default for the 0 and T: Copy to fill the arrays with it
+impl<T: Default + Copy, const R: usize, const C: usize> Matrix<T, R, C> {
+    pub fn zero() -> Self {
+        Matrix {
+            rows: [[T::default(); C]; R],
+        }
+    }
+
+    // the return type swaps R and C, a 2x3 always transposes into a 3x2
+    pub fn transpose(&self) -> Matrix<T, C, R> {
+        let mut result = Matrix::<T, C, R>::zero();
+        for (r, row) in self.rows.iter().enumerate() {
+            for (c, value) in row.iter().enumerate() {
+                result.rows[c][r] = *value;
+            }
+        }
+        result
+    }
+}
+
+// indexing with a (row, column) tuple: matrix[(1, 2)]
+// out of range indexes panic, just like indexing an array
+impl<T, const R: usize, const C: usize> Index<(usize, usize)> for Matrix<T, R, C> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        &self.rows[row][col]
+    }
+}
+
+impl<T, const R: usize, const C: usize> IndexMut<(usize, usize)> for Matrix<T, R, C> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        &mut self.rows[row][col]
+    }
+}
+
+// Self on both sides, so only a matrix with the same R and C can be added
+impl<T, const R: usize, const C: usize> Add for Matrix<T, R, C>
+where
+    T: Add<Output = T> + Copy,
+{
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        for (row, other_row) in self.rows.iter_mut().zip(other.rows.iter()) {
+            for (value, other_value) in row.iter_mut().zip(other_row.iter()) {
+                *value = *value + *other_value;
+            }
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_2x3() {
+        let m: Matrix<i32, 2, 3> = Matrix::zero();
+        assert_eq!(m, Matrix::from_rows([[0, 0, 0], [0, 0, 0]]));
+        assert_eq!((m.rows(), m.cols()), (2, 3));
+    }
+
+    #[test]
+    fn indexing_2x3() {
+        let mut m = Matrix::from_rows([[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(m[(0, 2)], 3);
+        assert_eq!(m[(1, 0)], 4);
+        m[(1, 0)] = 40;
+        assert_eq!(m[(1, 0)], 40);
+    }
+
+    #[test]
+    #[should_panic]
+    fn indexing_out_of_range() {
+        let m: Matrix<u8, 2, 3> = Matrix::zero();
+        let _ = m[(2, 0)];
+    }
+
+    #[test]
+    fn transpose_2x3_is_3x2() {
+        let m = Matrix::from_rows([[1, 2, 3], [4, 5, 6]]);
+        let t: Matrix<i32, 3, 2> = m.transpose();
+        assert_eq!(t, Matrix::from_rows([[1, 4], [2, 5], [3, 6]]));
+        assert_eq!(t.transpose(), m);
+    }
+
+    #[test]
+    fn add_2x3() {
+        let a = Matrix::from_rows([[1, 2, 3], [4, 5, 6]]);
+        let b = Matrix::from_rows([[10, 20, 30], [40, 50, 60]]);
+        assert_eq!(a + b, Matrix::from_rows([[11, 22, 33], [44, 55, 66]]));
+    }
+
+    #[test]
+    fn add_and_transpose_3x3() {
+        let identity = Matrix::from_rows([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]);
+        let m = Matrix::from_rows([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]);
+
+        // the identity is its own transpose
+        assert_eq!(identity.transpose(), identity);
+        assert_eq!(
+            m + identity,
+            Matrix::from_rows([[2.0, 2.0, 3.0], [4.0, 6.0, 6.0], [7.0, 8.0, 10.0]])
+        );
+        assert_eq!(m + Matrix::zero(), m);
+    }
+}
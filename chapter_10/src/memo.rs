@@ -0,0 +1,118 @@
+// 10.1 / 10.2 (continued) A Generic Memoizing Cache
+
+// Memo wraps a function and remembers every result it has computed, so calling
+// it again with the same argument just looks the answer up.
+// It's generic over three things:
+//   A: the argument, it's used as a HashMap key, so it must be Hash + Eq
+//   R: whatever the function returns
+//   F: the function itself. Every closure has its own unique type, so we can't
+//      name it, we can only say it implements Fn(&A) -> R (closures are Chapter 13)
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+pub struct Memo<A, R, F>
+where
+    A: Hash + Eq,
+    F: Fn(&A) -> R,
+{
+    func: F,
+    cache: HashMap<A, R>,
+    hits: usize,
+    misses: usize,
+}
+
+impl<A, R, F> Memo<A, R, F>
+where
+    A: Hash + Eq,
+    F: Fn(&A) -> R,
+{
+    pub fn new(func: F) -> Self {
+        Memo {
+            func,
+            cache: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    pub fn get_or_compute(&mut self, arg: A) -> &R {
+        // entry hands back either the slot that's already filled or the empty
+        // one we'd insert into, so we only hash the argument once
+        match self.cache.entry(arg) {
+            Entry::Occupied(entry) => {
+                self.hits += 1;
+                entry.into_mut()
+            }
+            Entry::Vacant(entry) => {
+                self.misses += 1;
+                let result = (self.func)(entry.key());
+                entry.insert(result)
+            }
+        }
+    }
+
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn calls_the_function_once_per_distinct_argument() {
+        // the closure is an Fn, so it can't mutate a plain counter it captures.
+        // A Cell allows changing the value through a shared reference (Chapter 15)
+        let calls = Cell::new(0);
+        let mut square = Memo::new(|n: &u64| {
+            calls.set(calls.get() + 1);
+            n * n
+        });
+
+        assert_eq!(*square.get_or_compute(4), 16);
+        assert_eq!(*square.get_or_compute(4), 16);
+        assert_eq!(*square.get_or_compute(5), 25);
+        assert_eq!(*square.get_or_compute(4), 16);
+
+        assert_eq!(calls.get(), 2);
+        assert_eq!(square.misses(), 2);
+        assert_eq!(square.hits(), 2);
+        assert_eq!(square.len(), 2);
+    }
+
+    #[test]
+    fn works_with_owned_keys_and_values() {
+        let mut shout = Memo::new(|s: &String| s.to_uppercase());
+        assert!(shout.is_empty());
+        assert_eq!(shout.get_or_compute(String::from("hi")), "HI");
+        assert_eq!(shout.get_or_compute(String::from("hi")), "HI");
+        assert_eq!((shout.hits(), shout.misses()), (1, 1));
+    }
+
+    #[test]
+    fn nothing_is_computed_until_asked() {
+        let calls = Cell::new(0);
+        let memo = Memo::new(|n: &i32| {
+            calls.set(calls.get() + 1);
+            *n
+        });
+        assert_eq!((memo.hits(), memo.misses()), (0, 0));
+        assert_eq!(calls.get(), 0);
+    }
+}
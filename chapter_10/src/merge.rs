@@ -0,0 +1,157 @@
+// 10.2 (continued) Clearer Trait Bounds with where Clauses: merge_sorted
+
+// Merging two already sorted sequences into one sorted Vec. With three generic
+// parameters and bounds on each of them, writing everything inside the <> gets
+// hard to read:
+//     fn merge_sorted<I: IntoIterator<Item = T>, J: IntoIterator<Item = T>, T: Ord>(a: I, b: J) -> Vec<T>
+// so the bounds move into a where clause after the return type instead.
+//
+// IntoIterator means the inputs can be anything a for loop accepts: a Vec, an
+// array, a range, a slice iterator, another iterator adapter...
+
+use std::iter::Peekable;
+
+pub fn merge_sorted<I, J, T>(a: I, b: J) -> Vec<T>
+where
+    I: IntoIterator<Item = T>,
+    J: IntoIterator<Item = T>,
+    T: Ord,
+{
+    MergeIter::new(a, b).collect()
+}
+
+// The lazy version: nothing is compared until someone asks for the next item,
+// so it also works on huge (or never ending) inputs as long as we only take
+// part of the result.
+//
+// Peekable lets us look at the next item of each side without taking it out,
+// so we can compare the two fronts and only advance the smaller one.
+pub struct MergeIter<I, J>
+where
+    I: Iterator,
+    J: Iterator<Item = I::Item>,
+{
+    a: Peekable<I>,
+    b: Peekable<J>,
+}
+
+impl<I, J> MergeIter<I, J>
+where
+    I: Iterator,
+    J: Iterator<Item = I::Item>,
+{
+    pub fn new<A, B>(a: A, b: B) -> Self
+    where
+        A: IntoIterator<IntoIter = I, Item = I::Item>,
+        B: IntoIterator<IntoIter = J, Item = I::Item>,
+    {
+        MergeIter {
+            a: a.into_iter().peekable(),
+            b: b.into_iter().peekable(),
+        }
+    }
+}
+
+impl<I, J> Iterator for MergeIter<I, J>
+where
+    I: Iterator,
+    J: Iterator<Item = I::Item>,
+    I::Item: Ord,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match (self.a.peek(), self.b.peek()) {
+            // on a tie we take from `a` first, so equal items keep the order
+            // they had (a before b), which makes the merge stable
+            (Some(x), Some(y)) if x <= y => self.a.next(),
+            (Some(_), Some(_)) => self.b.next(),
+            (Some(_), None) => self.a.next(),
+            (None, _) => self.b.next(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_equal_lengths() {
+        assert_eq!(merge_sorted([1, 4, 7], [2, 5, 8]), vec![1, 2, 4, 5, 7, 8]);
+    }
+
+    #[test]
+    fn merges_unequal_lengths() {
+        assert_eq!(merge_sorted(vec![1], vec![0, 2, 3, 4]), vec![0, 1, 2, 3, 4]);
+        assert_eq!(merge_sorted(1..=5, [3]), vec![1, 2, 3, 3, 4, 5]);
+    }
+
+    #[test]
+    fn one_or_both_empty() {
+        assert_eq!(merge_sorted(Vec::new(), vec![1, 2]), vec![1, 2]);
+        assert_eq!(merge_sorted(vec!['a'], Vec::new()), vec!['a']);
+        assert!(merge_sorted(Vec::<u8>::new(), Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn keeps_duplicates() {
+        assert_eq!(
+            merge_sorted([1, 1, 2, 2], [1, 2, 2, 3]),
+            vec![1, 1, 1, 2, 2, 2, 2, 3]
+        );
+    }
+
+    // only `key` takes part in comparisons, `side` shows where an item came from
+    #[derive(Debug)]
+    struct Item {
+        key: u8,
+        side: char,
+    }
+
+    impl PartialEq for Item {
+        fn eq(&self, other: &Self) -> bool {
+            self.key == other.key
+        }
+    }
+
+    impl Eq for Item {}
+
+    impl PartialOrd for Item {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Item {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            self.key.cmp(&other.key)
+        }
+    }
+
+    #[test]
+    fn ties_take_from_the_first_input_first() {
+        let item = |key, side| Item { key, side };
+        let merged = merge_sorted([item(1, 'a'), item(2, 'a')], [item(1, 'b'), item(2, 'b')]);
+        let sides: Vec<char> = merged.iter().map(|item| item.side).collect();
+        assert_eq!(sides, vec!['a', 'b', 'a', 'b']);
+    }
+
+    #[test]
+    fn works_with_borrowed_items() {
+        let a = vec![String::from("apple"), String::from("cherry")];
+        let b = vec![String::from("banana")];
+        let merged: Vec<&String> = merge_sorted(&a, &b);
+        assert_eq!(merged, vec!["apple", "banana", "cherry"]);
+    }
+
+    #[test]
+    fn merge_iter_is_lazy() {
+        // two never ending inputs: evens and odds. collect() would run forever,
+        // but take() only asks for as many items as it needs
+        let evens = (0..).step_by(2);
+        let odds = (1..).step_by(2);
+        let first: Vec<u32> = MergeIter::new(evens, odds).take(6).collect();
+        assert_eq!(first, vec![0, 1, 2, 3, 4, 5]);
+    }
+}
@@ -0,0 +1,212 @@
+// 10.2 (continued) Default Implementations: Notify
+
+// Same idea as Summary's summarize() calling summarize_author(): the default
+// notify() is built entirely out of the two required methods, so each channel
+// only has to say what the message looks like and where it goes. A channel that
+// needs something different can still override notify() itself.
+//
+// notify() writes to whatever `out` is instead of straight to stdout, so a
+// program can pass io::stdout() and the tests can pass a Vec<u8> and read it back.
+// It takes a `&mut dyn Write` rather than `impl Write`: a generic method would
+// stop us from using Notify as a trait object (&dyn Notify) in dispatch below.
+
+use std::io::{self, ErrorKind, Write};
+
+pub trait Notify {
+    fn render(&self) -> String;
+    fn destination(&self) -> String;
+
+    fn notify(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "to {}: {}", self.destination(), self.render())
+    }
+}
+
+pub struct Email {
+    pub address: String,
+    pub subject: String,
+    pub body: String,
+}
+
+// Email keeps the default notify
+impl Notify for Email {
+    fn render(&self) -> String {
+        format!("[{}] {}", self.subject, self.body)
+    }
+
+    fn destination(&self) -> String {
+        format!("<{}>", self.address)
+    }
+}
+
+pub struct Sms {
+    pub number: String,
+    pub text: String,
+}
+
+pub const SMS_LIMIT: usize = 160;
+
+impl Notify for Sms {
+    fn render(&self) -> String {
+        self.text.clone()
+    }
+
+    fn destination(&self) -> String {
+        self.number.clone()
+    }
+
+    // overriding the default: a text message gets split into 160 character
+    // parts, each one sent (written) separately. An empty text has no parts
+    // at all, so instead of quietly sending nothing it's an InvalidInput error
+    fn notify(&self, out: &mut dyn Write) -> io::Result<()> {
+        let chars: Vec<char> = self.render().chars().collect();
+        if chars.is_empty() {
+            return Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                format!("empty text message to {}", self.destination()),
+            ));
+        }
+        let parts: Vec<String> = chars
+            .chunks(SMS_LIMIT)
+            .map(|chunk| chunk.iter().collect())
+            .collect();
+        for (i, part) in parts.iter().enumerate() {
+            writeln!(
+                out,
+                "to {} ({}/{}): {}",
+                self.destination(),
+                i + 1,
+                parts.len(),
+                part
+            )?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Push {
+    pub device_id: String,
+    pub title: String,
+    pub silent: bool,
+}
+
+impl Notify for Push {
+    fn render(&self) -> String {
+        self.title.clone()
+    }
+
+    fn destination(&self) -> String {
+        format!("device {}", self.device_id)
+    }
+
+    // overriding the default: silent pushes don't show anything
+    fn notify(&self, out: &mut dyn Write) -> io::Result<()> {
+        if self.silent {
+            return Ok(());
+        }
+        writeln!(out, "to {}: {}", self.destination(), self.render())
+    }
+}
+
+// sends every notification in order, stops at the first one that fails
+pub fn dispatch(notifiers: &[&dyn Notify], out: &mut dyn Write) -> io::Result<()> {
+    for notifier in notifiers {
+        notifier.notify(out)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // runs dispatch against an in memory buffer and hands back what was written
+    fn capture(notifiers: &[&dyn Notify]) -> String {
+        let mut out = Vec::new();
+        dispatch(notifiers, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    fn email() -> Email {
+        Email {
+            address: String::from("ferris@example.com"),
+            subject: String::from("Hi"),
+            body: String::from("Chapter 10 is done"),
+        }
+    }
+
+    fn sms(text: &str) -> Sms {
+        Sms {
+            number: String::from("555-0100"),
+            text: String::from(text),
+        }
+    }
+
+    fn push(silent: bool) -> Push {
+        Push {
+            device_id: String::from("abc123"),
+            title: String::from("New message"),
+            silent,
+        }
+    }
+
+    #[test]
+    fn email_uses_the_default_notify() {
+        assert_eq!(
+            capture(&[&email()]),
+            "to <ferris@example.com>: [Hi] Chapter 10 is done\n"
+        );
+    }
+
+    #[test]
+    fn short_sms_is_one_part() {
+        assert_eq!(
+            capture(&[&sms("on my way")]),
+            "to 555-0100 (1/1): on my way\n"
+        );
+    }
+
+    #[test]
+    fn long_sms_is_split_into_parts() {
+        let text = "a".repeat(SMS_LIMIT + 5);
+        let output = capture(&[&sms(&text)]);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("to 555-0100 (1/2): "));
+        assert_eq!(lines[1], "to 555-0100 (2/2): aaaaa");
+    }
+
+    #[test]
+    fn empty_sms_is_an_error() {
+        let mut out = Vec::new();
+        let err = dispatch(&[&email(), &sms(""), &push(false)], &mut out).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert_eq!(err.to_string(), "empty text message to 555-0100");
+        // the email before it went out, the push after it didn't
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "to <ferris@example.com>: [Hi] Chapter 10 is done\n"
+        );
+    }
+
+    #[test]
+    fn silent_push_writes_nothing() {
+        assert_eq!(capture(&[&push(true)]), "");
+        assert_eq!(capture(&[&push(false)]), "to device abc123: New message\n");
+    }
+
+    #[test]
+    fn dispatch_keeps_the_order() {
+        let (email, sms, loud, silent) = (email(), sms("hey"), push(false), push(true));
+        assert_eq!(
+            capture(&[&sms, &silent, &email, &loud]),
+            "to 555-0100 (1/1): hey\n\
+             to <ferris@example.com>: [Hi] Chapter 10 is done\n\
+             to device abc123: New message\n"
+        );
+    }
+
+    #[test]
+    fn dispatching_nothing() {
+        assert_eq!(capture(&[]), "");
+    }
+}
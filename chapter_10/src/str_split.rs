@@ -0,0 +1,121 @@
+// 10.3 (continued) Multiple Lifetimes: Writing Our Own str::split
+
+// StrSplit walks through a string and hands back the pieces between each
+// delimiter, just like the standard library's split. It never allocates: every
+// piece it returns is a slice of the original string (the haystack).
+//
+// It holds two references, so it gets two lifetime parameters. The pieces we
+// return only come from the haystack, so Item is &'haystack str. If we used a
+// single 'a for both references, every piece would also be tied to the
+// delimiter, and a delimiter that only lives inside a function (like the one
+// in until_char below) would make it impossible to return the result.
+
+pub struct StrSplit<'haystack, 'delim> {
+    // None once we've handed out the last piece
+    remainder: Option<&'haystack str>,
+    delimiter: &'delim str,
+}
+
+impl<'haystack, 'delim> StrSplit<'haystack, 'delim> {
+    pub fn new(haystack: &'haystack str, delimiter: &'delim str) -> Self {
+        StrSplit {
+            remainder: Some(haystack),
+            delimiter,
+        }
+    }
+}
+
+impl<'haystack, 'delim> Iterator for StrSplit<'haystack, 'delim> {
+    type Item = &'haystack str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // `?` on the Option: if there's nothing left we're done
+        let remainder = self.remainder.as_mut()?;
+
+        // an empty delimiter would match everywhere without ever moving
+        // forward, so we treat it as "no delimiter" and return everything
+        if !self.delimiter.is_empty() {
+            if let Some(start) = remainder.find(self.delimiter) {
+                let piece = &remainder[..start];
+                *remainder = &remainder[start + self.delimiter.len()..];
+                return Some(piece);
+            }
+        }
+
+        // no more delimiters, whatever is left is the last piece
+        self.remainder.take()
+    }
+}
+
+// Everything in `s` before the first `c`. The delimiter is built inside this
+// function (in a small buffer on the stack), so it's dropped when we return.
+// That's only fine because the result's lifetime comes from 'haystack, not
+// from the delimiter.
+pub fn until_char(s: &str, c: char) -> &str {
+    let mut buffer = [0; 4];
+    let delimiter = c.encode_utf8(&mut buffer);
+    StrSplit::new(s, delimiter).next().unwrap_or(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split<'a>(haystack: &'a str, delimiter: &str) -> Vec<&'a str> {
+        StrSplit::new(haystack, delimiter).collect()
+    }
+
+    #[test]
+    fn splits_on_single_char() {
+        assert_eq!(split("a b c d e", " "), vec!["a", "b", "c", "d", "e"]);
+    }
+
+    #[test]
+    fn empty_haystack() {
+        // same as std: splitting "" gives back one empty piece
+        assert_eq!(split("", ","), vec![""]);
+        assert_eq!(split("", ","), "".split(',').collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn trailing_delimiter() {
+        assert_eq!(split("a,b,", ","), vec!["a", "b", ""]);
+        assert_eq!(split(",", ","), vec!["", ""]);
+    }
+
+    #[test]
+    fn multi_char_delimiter() {
+        assert_eq!(
+            split("one<>two<><>three", "<>"),
+            vec!["one", "two", "", "three"]
+        );
+        assert_eq!(split("🦀and🦀", "and"), vec!["🦀", "🦀"]);
+    }
+
+    #[test]
+    fn empty_delimiter_returns_everything() {
+        assert_eq!(split("abc", ""), vec!["abc"]);
+    }
+
+    #[test]
+    fn matches_std_split() {
+        let cases = [
+            ("a,b,c", ","),
+            (",,a,,", ","),
+            ("no delimiter here", "|"),
+            ("ab--cd--", "--"),
+        ];
+        for (haystack, delimiter) in cases {
+            let ours = split(haystack, delimiter);
+            let std: Vec<&str> = haystack.split(delimiter).collect();
+            assert_eq!(ours, std, "splitting {:?} on {:?}", haystack, delimiter);
+        }
+    }
+
+    #[test]
+    fn until_char_outlives_its_delimiter() {
+        assert_eq!(until_char("hello world", 'o'), "hell");
+        assert_eq!(until_char("hello world", 'z'), "hello world");
+        assert_eq!(until_char("naïve café", 'é'), "naïve caf");
+    }
+}
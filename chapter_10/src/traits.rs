@@ -3,7 +3,13 @@
 // A Trait defines functionality a particular type has, and can share with other types
 // we can use Traits to further constrain generic types to types that have certain behaviors
 
-pub fn _run() {
+use std::fmt::{self, Display};
+
+// the local Summary traits inside run() shadow this one in there, out here the
+// aggregator's Summary is always written out in full to avoid mixing them up
+use crate::aggregator::{self, NewsArticle, Tweet};
+
+pub fn run() {
     // Defining a Trait
     // A type's behavior consists of the methods we can call on that type.
     // Different types share the same behavior if we can call the same methods
@@ -36,12 +42,17 @@ pub fn _run() {
 
     // Now that we've defined the signatures of our Summary trait's methods,
     // we can implement it on the types in our media aggregator.
+    // NewsArticle and Tweet now live in the aggregator module of our lib
+    // (aggregator.rs) so other code can use them too. They look like:
+    /*
     pub struct NewsArticle {
         pub headline: String,
         pub location: String,
         pub author: String,
         pub content: String,
+        pub published_at: u64,
     }
+    */
 
     // impl Summary for NewsArticle {
     //     fn summarize(&self) -> String {
@@ -49,12 +60,15 @@ pub fn _run() {
     //     }
     // }
 
+    /*
     pub struct Tweet {
         pub username: String,
         pub content: String,
         pub reply: bool,
         pub retweet: bool,
+        pub published_at: u64,
     }
+    */
 
     // impl Summary for Tweet {
     //     fn summarize(&self) -> String {
@@ -67,6 +81,7 @@ pub fn _run() {
         content: String::from("of course, as you probably already know, people"),
         reply: false,
         retweet: false,
+        published_at: 1_700_000_000,
     };
 
     println!("1 new tweet: {}", tweet.summarize());
@@ -93,6 +108,7 @@ pub fn _run() {
         content: String::from(
             "The Pittsburgh Penguins once again are the best hockey team in the NHL.",
         ),
+        published_at: 1_700_000_100,
     };
 
     println!("New article available! {}", article.summarize());
@@ -184,6 +200,7 @@ pub fn _run() {
             content: String::from("of course, as you probably already know, people"),
             reply: false,
             retweet: false,
+            published_at: 1_700_000_000,
         }
     }
 
@@ -218,31 +235,31 @@ pub fn _run() {
         }
     }
     */
+    // `impl Trait` means "one specific type that I'm not naming", so both
+    // branches have to return the same type. There are two common ways around
+    // this, both at the bottom of this file: return a Box<dyn Summary> (a trait
+    // object, where the concrete type is only known at runtime), or wrap the
+    // possible types in an enum that implements Summary itself.
+    for kind in [SourceKind::News, SourceKind::Social] {
+        println!("boxed: {}", make_summarizable(kind).summarize());
+        let wrapped = make_summarizable_enum(kind);
+        println!("enum:  {}", aggregator::Summary::summarize(&wrapped));
+    }
 
     // Using Trait Bounds to Conditionally Implement Methods
     // By using a trait bound with an impl block that uses generic type parameters,
     // we can implement methods conditionally for types that implement the specified traits.
 
-    struct Pair<T> {
-        x: T,
-        y: T
-    }
-
-    impl<T> Pair<T> {
-        fn new(x: T, y: T) -> Self {
-            Self { x, y }
-        }
-    }
+    // Pair lives at the bottom of this file so it can be tested, it has a few
+    // impl blocks with different bounds:
+    //   impl<T> Pair<T>                       -> new, swap, map, into_tuple, as_refs
+    //   impl<T: Display + PartialOrd> Pair<T> -> cmp_display
+    //   impl<T: PartialOrd> Pair<T>           -> min_max
+    //   impl<T: Display> Display for Pair<T>  -> lets us print a Pair with {}
 
-    impl<T: Display + PartialOrd> Pair<T> {
-        fn cmp_display(&self) {
-            if self.x >= self.y {
-                println!("The largest member is x = {}", self.x);
-            } else {
-                println!("The largest member is y = {}", self.y);
-            }
-        }
-    }
+    let pair = Pair::new(3, 7);
+    pair.cmp_display();
+    println!("{} swapped is {}", pair, Pair::new(3, 7).swap());
 
     // We can also conditionally implement a trait for any type that implements
     // another trait. Implementations of a trait on any type that satisfies the
@@ -253,4 +270,241 @@ pub fn _run() {
 
     // impl<T: Display> ToString for T {}
     // let s = 3.to_string();
+
+    // describe.rs has a blanket implementation of our own: Describe for every
+    // Display type, plus a newtype for when one type needs something different
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SourceKind {
+    News,
+    Social,
+}
+
+fn penguins_article() -> NewsArticle {
+    NewsArticle {
+        headline: String::from("Penguins win the Stanley Cup Championship!"),
+        location: String::from("Pittsburgh, PA, USA"),
+        author: String::from("Iceburgh"),
+        content: String::from(
+            "The Pittsburgh Penguins once again are the best hockey team in the NHL.",
+        ),
+        published_at: 1_700_000_100,
+    }
+}
+
+fn horse_tweet() -> Tweet {
+    Tweet {
+        username: String::from("horse_ebooks"),
+        content: String::from("of course, as you probably already know, people"),
+        reply: false,
+        retweet: false,
+        published_at: 1_700_000_000,
+    }
+}
+
+// Solution 1: a trait object. Each branch boxes a different type, but both
+// boxes are the same type, Box<dyn Summary>. New kinds of summary can be added
+// anywhere without touching this code, at the cost of a heap allocation and a
+// method lookup at runtime.
+pub fn make_summarizable(kind: SourceKind) -> Box<dyn aggregator::Summary> {
+    match kind {
+        SourceKind::News => Box::new(penguins_article()),
+        SourceKind::Social => Box::new(horse_tweet()),
+    }
+}
+
+// Solution 2: an enum. No Box needed, and a match tells us exactly which
+// variant we have. The trade off: the list of types is closed, adding a new one
+// means adding a variant and updating every match on it.
+pub enum Summarizable {
+    Article(NewsArticle),
+    Tweet(Tweet),
+}
+
+impl aggregator::Summary for Summarizable {
+    fn summarize_author(&self) -> String {
+        match self {
+            Summarizable::Article(article) => aggregator::Summary::summarize_author(article),
+            Summarizable::Tweet(tweet) => aggregator::Summary::summarize_author(tweet),
+        }
+    }
+
+    fn summarize(&self) -> String {
+        match self {
+            Summarizable::Article(article) => aggregator::Summary::summarize(article),
+            Summarizable::Tweet(tweet) => aggregator::Summary::summarize(tweet),
+        }
+    }
+
+    fn published_at(&self) -> u64 {
+        match self {
+            Summarizable::Article(article) => aggregator::Summary::published_at(article),
+            Summarizable::Tweet(tweet) => aggregator::Summary::published_at(tweet),
+        }
+    }
+}
+
+pub fn make_summarizable_enum(kind: SourceKind) -> Summarizable {
+    match kind {
+        SourceKind::News => Summarizable::Article(penguins_article()),
+        SourceKind::Social => Summarizable::Tweet(horse_tweet()),
+    }
+}
+
+pub struct Pair<T> {
+    x: T,
+    y: T,
+}
+
+// available for every Pair<T>, no matter what T is
+impl<T> Pair<T> {
+    pub fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    pub fn swap(self) -> Pair<T> {
+        Pair {
+            x: self.y,
+            y: self.x,
+        }
+    }
+
+    // build a new Pair by running f on both members, the new Pair can hold a
+    // different type U. This is the same idea as Option::map
+    pub fn map<U, F>(self, f: F) -> Pair<U>
+    where
+        F: Fn(T) -> U,
+    {
+        Pair {
+            x: f(self.x),
+            y: f(self.y),
+        }
+    }
+
+    pub fn into_tuple(self) -> (T, T) {
+        (self.x, self.y)
+    }
+
+    // a Pair of references to our members, handy for calling the methods
+    // that take self (map, swap, ...) without giving up our Pair
+    pub fn as_refs(&self) -> Pair<&T> {
+        Pair {
+            x: &self.x,
+            y: &self.y,
+        }
+    }
+}
+
+// only available when T can be both compared and printed
+impl<T: Display + PartialOrd> Pair<T> {
+    pub fn cmp_display(&self) {
+        if self.x >= self.y {
+            println!("The largest member is x = {}", self.x);
+        } else {
+            println!("The largest member is y = {}", self.y);
+        }
+    }
+}
+
+// only available when T can be compared
+impl<T: PartialOrd> Pair<T> {
+    // (smaller, larger), when they're equal x comes first
+    pub fn min_max(&self) -> (&T, &T) {
+        if self.y < self.x {
+            (&self.y, &self.x)
+        } else {
+            (&self.x, &self.y)
+        }
+    }
+}
+
+// the trait version of the same idea: Pair<T> only implements Display when T does
+impl<T: Display> Display for Pair<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregator::Summary;
+
+    // implements neither Display nor PartialOrd
+    #[derive(Debug, PartialEq)]
+    struct Sock {
+        size: u32,
+    }
+
+    #[test]
+    fn both_factories_summarize_the_same_way() {
+        for kind in [SourceKind::News, SourceKind::Social] {
+            let boxed = make_summarizable(kind);
+            let wrapped = make_summarizable_enum(kind);
+            assert_eq!(boxed.summarize(), wrapped.summarize());
+            assert_eq!(boxed.published_at(), wrapped.published_at());
+        }
+    }
+
+    #[test]
+    fn factory_picks_the_type_from_the_kind() {
+        assert_eq!(
+            make_summarizable(SourceKind::Social).summarize(),
+            "(Read more from @horse_ebooks...)"
+        );
+        assert!(matches!(
+            make_summarizable_enum(SourceKind::News),
+            Summarizable::Article(_)
+        ));
+        // mixing both in one Vec is what the Box version is for
+        let feed: Vec<Box<dyn aggregator::Summary>> = vec![
+            make_summarizable(SourceKind::News),
+            make_summarizable(SourceKind::Social),
+        ];
+        assert_eq!(feed.len(), 2);
+    }
+
+    #[test]
+    fn swap_and_into_tuple() {
+        assert_eq!(Pair::new(1, 2).swap().into_tuple(), (2, 1));
+    }
+
+    #[test]
+    fn map_can_change_the_type() {
+        let lengths = Pair::new("hi", "there").map(|s| s.len());
+        assert_eq!(lengths.into_tuple(), (2, 5));
+    }
+
+    #[test]
+    fn as_refs_leaves_the_original_alone() {
+        let pair = Pair::new(String::from("a"), String::from("b"));
+        let upper = pair.as_refs().map(|s| s.to_uppercase());
+        assert_eq!(upper.into_tuple(), (String::from("A"), String::from("B")));
+        assert_eq!(pair.into_tuple(), (String::from("a"), String::from("b")));
+    }
+
+    #[test]
+    fn display_and_min_max_for_comparable_types() {
+        let pair = Pair::new(9, 4);
+        assert_eq!(pair.to_string(), "(9, 4)");
+        assert_eq!(pair.min_max(), (&4, &9));
+        assert_eq!(Pair::new('a', 'a').min_max(), (&'a', &'a'));
+    }
+
+    #[test]
+    fn unbounded_methods_work_for_any_type() {
+        // Sock has no Display or PartialOrd, so these don't compile:
+        //   socks.min_max();
+        //   socks.to_string();
+        // but everything in the plain impl<T> block is still there
+        let socks = Pair::new(Sock { size: 9 }, Sock { size: 11 });
+        let (a, b) = socks.swap().into_tuple();
+        assert_eq!(a, Sock { size: 11 });
+        assert_eq!(b, Sock { size: 9 });
+
+        // mapping to a type that *is* comparable unlocks min_max
+        let sizes = Pair::new(Sock { size: 9 }, Sock { size: 7 }).map(|s| s.size);
+        assert_eq!(sizes.min_max(), (&7, &9));
+    }
 }
@@ -0,0 +1,413 @@
+// 10.2 (continued) Implementing Ordering by Hand: Version
+
+// Version numbers like 1.10.0 and 2.0.0-rc.1, compared the way semver.org
+// says they should be. #[derive(PartialOrd, Ord)] would compare the fields in
+// order, which is right for major, minor and patch but wrong for `pre`:
+//   - a version *with* a pre-release (1.0.0-alpha) comes *before* the same
+//     version without one (1.0.0), but derive would put None first
+//   - pre-releases are compared one dot separated part at a time, numbers as
+//     numbers (beta.2 < beta.11) and words as text (alpha < beta), with numbers
+//     before words. Comparing the whole String would put beta.11 before beta.2
+// So Ord is written by hand, and PartialOrd just wraps it.
+//
+// Eq has to agree with Ord: a == b exactly when a.cmp(b) is Equal, or sort,
+// BTreeMap and friends get confused. The fields are pub, so someone could
+// build pre: Some("01") and Some("1"), which compare Equal as numbers but
+// aren't equal Strings. PartialEq is written in terms of cmp to keep the two
+// in step (and Hash is left out, since hashing the Strings wouldn't be).
+//
+// semver also allows build metadata after a +, like 1.0.0+20130313144700 or
+// 1.0.0-beta+exp.sha.5114f85. It never affects precedence (two versions that
+// only differ in it are equal), so parse checks it and then throws it away:
+// "1.0.0+build".parse() gives the same Version as "1.0.0", and displays as that.
+
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    pub pre: Option<String>,
+}
+
+impl Version {
+    pub fn new(major: u64, minor: u64, patch: u64) -> Version {
+        Version {
+            major,
+            minor,
+            patch,
+            pre: None,
+        }
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.major
+            .cmp(&other.major)
+            .then(self.minor.cmp(&other.minor))
+            .then(self.patch.cmp(&other.patch))
+            // then_with only compares the pre-releases if everything else tied
+            .then_with(|| compare_pre(&self.pre, &other.pre))
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+fn compare_pre(a: &Option<String>, b: &Option<String>) -> Ordering {
+    match (a, b) {
+        (None, None) => Ordering::Equal,
+        // 1.0.0 is the release that 1.0.0-anything was leading up to
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(a), Some(b)) => {
+            let mut a = a.split('.');
+            let mut b = b.split('.');
+            loop {
+                match (a.next(), b.next()) {
+                    (None, None) => return Ordering::Equal,
+                    // all the parts so far were equal, so more parts is later:
+                    // alpha < alpha.1
+                    (None, Some(_)) => return Ordering::Less,
+                    (Some(_), None) => return Ordering::Greater,
+                    (Some(x), Some(y)) => match compare_identifier(x, y) {
+                        Ordering::Equal => continue,
+                        different => return different,
+                    },
+                }
+            }
+        }
+    }
+}
+
+fn is_numeric(identifier: &str) -> bool {
+    !identifier.is_empty() && identifier.bytes().all(|b| b.is_ascii_digit())
+}
+
+// a dot separated part of a pre-release or build: letters, digits and -
+fn is_identifier(identifier: &str) -> bool {
+    !identifier.is_empty()
+        && identifier
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+fn compare_identifier(a: &str, b: &str) -> Ordering {
+    match (is_numeric(a), is_numeric(b)) {
+        // compared as numbers without parsing them, so a part too big for a
+        // u64 still works: with the leading zeros gone, a longer number is a
+        // bigger one, and numbers the same length compare like text
+        (true, true) => {
+            let (a, b) = (a.trim_start_matches('0'), b.trim_start_matches('0'));
+            a.len().cmp(&b.len()).then(a.cmp(b))
+        }
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        (false, false) => a.cmp(b),
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if let Some(pre) = &self.pre {
+            write!(f, "-{}", pre)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ParseVersionError {
+    // "1.2" has no patch
+    MissingPart(&'static str),
+    // "1.2.3.4"
+    TooManyParts,
+    // not a number, or a number with a leading zero like "01"
+    BadNumber { part: &'static str, got: String },
+    // a pre-release part that's empty, has something other than letters,
+    // digits and -, or is a number with a leading zero
+    BadPre(String),
+    // the same for build metadata, except leading zeros are fine there
+    BadBuild(String),
+}
+
+impl fmt::Display for ParseVersionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseVersionError::MissingPart(part) => write!(f, "missing the {} version", part),
+            ParseVersionError::TooManyParts => {
+                write!(f, "a version has three numbers, major.minor.patch")
+            }
+            ParseVersionError::BadNumber { part, got } => {
+                write!(f, "the {} version should be a number, got {:?}", part, got)
+            }
+            ParseVersionError::BadPre(pre) => write!(f, "not a valid pre-release: {:?}", pre),
+            ParseVersionError::BadBuild(build) => {
+                write!(f, "not valid build metadata: {:?}", build)
+            }
+        }
+    }
+}
+
+impl Error for ParseVersionError {}
+
+// FromStr is what str::parse calls, so this gives "1.2.3".parse::<Version>()
+impl FromStr for Version {
+    type Err = ParseVersionError;
+
+    fn from_str(s: &str) -> Result<Version, ParseVersionError> {
+        // build metadata goes first, it can have -s in it too (1.0.0+build-7)
+        let s = match s.split_once('+') {
+            Some((version, build)) => {
+                if !build.split('.').all(is_identifier) {
+                    return Err(ParseVersionError::BadBuild(build.to_string()));
+                }
+                version
+            }
+            None => s,
+        };
+
+        // everything after the first - is the pre-release, which can have
+        // more -s in it (1.0.0-x-y-z)
+        let (core, pre) = match s.split_once('-') {
+            Some((core, pre)) => (core, Some(pre)),
+            None => (s, None),
+        };
+
+        let mut parts = core.split('.');
+        let mut number = |part: &'static str| -> Result<u64, ParseVersionError> {
+            let got = parts.next().ok_or(ParseVersionError::MissingPart(part))?;
+            let bad = || ParseVersionError::BadNumber {
+                part,
+                got: got.to_string(),
+            };
+            if !is_numeric(got) || (got.len() > 1 && got.starts_with('0')) {
+                return Err(bad());
+            }
+            got.parse().map_err(|_| bad())
+        };
+        let (major, minor, patch) = (number("major")?, number("minor")?, number("patch")?);
+        if parts.next().is_some() {
+            return Err(ParseVersionError::TooManyParts);
+        }
+
+        if let Some(pre) = pre {
+            let valid = |identifier: &str| {
+                is_identifier(identifier)
+                    && !(is_numeric(identifier)
+                        && identifier.len() > 1
+                        && identifier.starts_with('0'))
+            };
+            if !pre.split('.').all(valid) {
+                return Err(ParseVersionError::BadPre(pre.to_string()));
+            }
+        }
+
+        Ok(Version {
+            major,
+            minor,
+            patch,
+            pre: pre.map(String::from),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(s: &str) -> Version {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parses() {
+        assert_eq!(v("1.2.3"), Version::new(1, 2, 3));
+        let rc = v("2.0.0-rc.1");
+        assert_eq!((rc.major, rc.minor, rc.patch), (2, 0, 0));
+        assert_eq!(rc.pre.as_deref(), Some("rc.1"));
+        assert_eq!(v("1.0.0-x-y-z.--").pre.as_deref(), Some("x-y-z.--"));
+        assert_eq!(v("0.0.0"), Version::new(0, 0, 0));
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for s in ["1.2.3", "0.10.0-alpha.1", "1.0.0-x-y-z"] {
+            assert_eq!(v(s).to_string(), s);
+        }
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = |s: &str| s.parse::<Version>().unwrap_err();
+        assert_eq!(err("1.2"), ParseVersionError::MissingPart("patch"));
+        assert_eq!(
+            err(""),
+            ParseVersionError::BadNumber {
+                part: "major",
+                got: String::new()
+            }
+        );
+        assert_eq!(err("1.2.3.4"), ParseVersionError::TooManyParts);
+        assert_eq!(
+            err("1.02.3"),
+            ParseVersionError::BadNumber {
+                part: "minor",
+                got: String::from("02")
+            }
+        );
+        assert_eq!(
+            err("1.2.x"),
+            ParseVersionError::BadNumber {
+                part: "patch",
+                got: String::from("x")
+            }
+        );
+        // bigger than a u64
+        assert!(matches!(
+            err("99999999999999999999.0.0"),
+            ParseVersionError::BadNumber { .. }
+        ));
+        assert_eq!(err("1.2.3-"), ParseVersionError::BadPre(String::new()));
+        assert_eq!(
+            err("1.2.3-alpha..1"),
+            ParseVersionError::BadPre(String::from("alpha..1"))
+        );
+        assert_eq!(
+            err("1.2.3-beta.01"),
+            ParseVersionError::BadPre(String::from("beta.01"))
+        );
+        assert_eq!(
+            err("1.2.3-beta_1"),
+            ParseVersionError::BadPre(String::from("beta_1"))
+        );
+        assert_eq!(err("1.2.3+"), ParseVersionError::BadBuild(String::new()));
+        assert_eq!(
+            err("1.2.3+exp..1"),
+            ParseVersionError::BadBuild(String::from("exp..1"))
+        );
+        assert_eq!(
+            err("1.2.3+a+b"),
+            ParseVersionError::BadBuild(String::from("a+b"))
+        );
+        // the + is split off first, so the pre-release is still checked
+        assert_eq!(
+            err("1.2.3-beta.01+exp"),
+            ParseVersionError::BadPre(String::from("beta.01"))
+        );
+        assert_eq!(err("1.2").to_string(), "missing the patch version");
+    }
+
+    #[test]
+    fn build_metadata_is_ignored() {
+        assert_eq!(v("1.0.0+20130313144700"), Version::new(1, 0, 0));
+        let beta = v("1.0.0-beta+exp.sha.5114f85");
+        assert_eq!(beta.pre.as_deref(), Some("beta"));
+        assert_eq!(beta.to_string(), "1.0.0-beta");
+        // leading zeros and -s are allowed in build metadata
+        assert_eq!(v("1.0.0+001-build"), v("1.0.0+002"));
+        assert!(v("1.0.0-rc.1+build.9") < v("1.0.0+build.1"));
+    }
+
+    #[test]
+    fn numbers_compare_as_numbers() {
+        assert!(v("1.9.0") < v("1.10.0"));
+        assert!(v("1.2.3") < v("1.2.10"));
+        assert!(v("2.0.0") > v("1.99.99"));
+        assert!(v("0.0.1") < v("0.1.0"));
+    }
+
+    #[test]
+    fn the_semver_spec_example() {
+        // from semver.org, item 11: each one comes before the next
+        let ordered = [
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+        ];
+        for pair in ordered.windows(2) {
+            assert!(v(pair[0]) < v(pair[1]), "{} < {}", pair[0], pair[1]);
+            assert_eq!(v(pair[1]).cmp(&v(pair[0])), Ordering::Greater);
+        }
+        // and every pair, not just neighbours
+        for (i, a) in ordered.iter().enumerate() {
+            for (j, b) in ordered.iter().enumerate() {
+                assert_eq!(v(a).cmp(&v(b)), i.cmp(&j), "{} vs {}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn sorting() {
+        let mut versions: Vec<Version> = [
+            "1.10.0",
+            "1.0.0",
+            "1.0.0-rc.1",
+            "0.9.12",
+            "1.2.0",
+            "1.0.0-beta.11",
+            "1.0.0-beta.2",
+        ]
+        .iter()
+        .map(|s| v(s))
+        .collect();
+        versions.sort();
+        let sorted: Vec<String> = versions.iter().map(|version| version.to_string()).collect();
+        assert_eq!(
+            sorted,
+            vec![
+                "0.9.12",
+                "1.0.0-beta.2",
+                "1.0.0-beta.11",
+                "1.0.0-rc.1",
+                "1.0.0",
+                "1.2.0",
+                "1.10.0"
+            ]
+        );
+        assert_eq!(versions.iter().max(), Some(&v("1.10.0")));
+        assert_eq!(versions.iter().min(), Some(&v("0.9.12")));
+    }
+
+    #[test]
+    fn numeric_parts_come_before_words() {
+        assert!(v("1.0.0-1") < v("1.0.0-alpha"));
+        assert!(v("1.0.0-rc.9") < v("1.0.0-rc.a"));
+        // numbers too big for a u64 are still compared as numbers
+        assert!(v("1.0.0-99999999999999999999") < v("1.0.0-100000000000000000000"));
+    }
+
+    #[test]
+    fn eq_agrees_with_cmp() {
+        assert_eq!(v("1.0.0-alpha"), v("1.0.0-alpha"));
+        assert_ne!(v("1.0.0-alpha"), v("1.0.0"));
+        // built by hand, skipping parse's leading zero check: 01 and 1 are the
+        // same number, so these are the same version
+        let mut leading_zero = v("1.0.0-beta.1");
+        leading_zero.pre = Some(String::from("beta.01"));
+        assert_eq!(leading_zero.cmp(&v("1.0.0-beta.1")), Ordering::Equal);
+        assert_eq!(leading_zero, v("1.0.0-beta.1"));
+    }
+}
@@ -0,0 +1,123 @@
+// Micro-Benchmarks
+
+/*
+`cargo bench` and #[bench] are still nightly only, so this is a small stand in
+built on Instant. It's good enough for "is A roughly faster than B", not for
+exact numbers.
+
+Benchmarks are slow and their numbers only mean something in an optimized
+build, so the tests that use this are marked #[ignore]. They're skipped by a
+plain `cargo test` and run with:
+
+    cargo test --release -- --ignored --nocapture
+
+(--nocapture so the results printed by time_it actually show up)
+*/
+
+use std::fmt;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone)]
+pub struct Stats {
+    pub label: String,
+    pub iterations: u32,
+    pub min: Duration,
+    pub max: Duration,
+    pub mean: Duration,
+    pub median: Duration,
+}
+
+impl Stats {
+    // how many times slower (> 1.0) or faster (< 1.0) self is than other,
+    // comparing medians since they aren't thrown off by one slow run
+    pub fn ratio_to(&self, other: &Stats) -> f64 {
+        self.median.as_secs_f64() / other.median.as_secs_f64()
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: median {:?}, mean {:?}, min {:?}, max {:?} ({} runs)",
+            self.label, self.median, self.mean, self.min, self.max, self.iterations
+        )
+    }
+}
+
+// Runs `f` a few times without timing it (the warmup, so caches and the CPU's
+// clock speed settle), then `iterations` timed runs, and prints and returns the
+// stats. black_box stops the optimizer from noticing a result is never used
+// and deleting the work we're trying to measure.
+pub fn time_it<R>(label: &str, iterations: u32, mut f: impl FnMut() -> R) -> Stats {
+    assert!(iterations > 0, "time_it needs at least one iteration");
+
+    for _ in 0..(iterations / 10).max(1) {
+        black_box(f());
+    }
+
+    let mut times: Vec<Duration> = (0..iterations)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+    times.sort();
+
+    let total: Duration = times.iter().sum();
+    let stats = Stats {
+        label: String::from(label),
+        iterations,
+        min: times[0],
+        max: times[times.len() - 1],
+        mean: total / iterations,
+        median: times[times.len() / 2],
+    };
+    println!("{}", stats);
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::thread;
+
+    #[test]
+    fn runs_warmup_plus_iterations() {
+        let calls = Cell::new(0);
+        time_it("count", 20, || calls.set(calls.get() + 1));
+        // 20 / 10 = 2 warmup runs
+        assert_eq!(calls.get(), 22);
+
+        calls.set(0);
+        time_it("count", 3, || calls.set(calls.get() + 1));
+        // always at least one warmup run
+        assert_eq!(calls.get(), 4);
+    }
+
+    #[test]
+    fn stats_are_ordered() {
+        let stats = time_it("sleep", 5, || thread::sleep(Duration::from_millis(1)));
+        assert_eq!(stats.iterations, 5);
+        assert!(stats.min >= Duration::from_millis(1));
+        assert!(stats.min <= stats.median && stats.median <= stats.max);
+        assert!(stats.min <= stats.mean && stats.mean <= stats.max);
+    }
+
+    #[test]
+    fn ratio_between_runs() {
+        let fast = time_it("fast", 5, || thread::sleep(Duration::from_millis(1)));
+        let slow = time_it("slow", 5, || thread::sleep(Duration::from_millis(5)));
+        assert!(slow.ratio_to(&fast) > 1.0);
+        assert!(fast.ratio_to(&slow) < 1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one iteration")]
+    fn zero_iterations() {
+        time_it("nothing", 0, || ());
+    }
+}
@@ -0,0 +1,76 @@
+// Capturing Output
+
+/*
+Most of the exercises in this repo only println!, and a test can't see what
+println! wrote: it goes straight to the terminal (or gets swallowed by cargo
+test). The fix is to write to "some writer" instead of always stdout:
+
+    fn print_total(total: i32, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "total: {}", total)
+    }
+
+run() passes `&mut io::stdout()` and gets the same output as before, and a test
+passes an in-memory sink instead. A Vec<u8> implements Write, every byte that's
+written gets pushed onto it, so afterwards we can turn it into a String and
+assert on it. capture() does that part for us.
+*/
+
+use std::io::{self, Write};
+
+// runs `f` with an in-memory sink and returns everything it wrote
+pub fn capture<F>(f: F) -> String
+where
+    F: FnOnce(&mut dyn Write) -> io::Result<()>,
+{
+    let mut sink: Vec<u8> = Vec::new();
+    f(&mut sink).expect("writing to an in-memory sink failed");
+    String::from_utf8(sink).expect("captured output was not valid UTF-8")
+}
+
+// the same, split into lines without their trailing newlines
+pub fn capture_lines<F>(f: F) -> Vec<String>
+where
+    F: FnOnce(&mut dyn Write) -> io::Result<()>,
+{
+    capture(f).lines().map(String::from).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn print_total(total: i32, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "total: {}", total)
+    }
+
+    #[test]
+    fn captures_everything_written() {
+        let output = capture(|out| {
+            write!(out, "a")?;
+            writeln!(out, "b")?;
+            print_total(3, out)
+        });
+        assert_eq!(output, "ab\ntotal: 3\n");
+    }
+
+    #[test]
+    fn captures_nothing() {
+        assert_eq!(capture(|_| Ok(())), "");
+        assert!(capture_lines(|_| Ok(())).is_empty());
+    }
+
+    #[test]
+    fn splits_into_lines() {
+        let lines = capture_lines(|out| {
+            print_total(1, out)?;
+            print_total(2, out)
+        });
+        assert_eq!(lines, vec!["total: 1", "total: 2"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "in-memory sink failed")]
+    fn errors_from_the_closure_fail_the_test() {
+        capture(|_| Err(io::Error::other("boom")));
+    }
+}
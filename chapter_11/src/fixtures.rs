@@ -0,0 +1,86 @@
+// Test Fixtures
+
+// Small helpers that build the values our tests need, so each test can say
+// what it's testing (`rect(8, 7)`) instead of repeating the same struct
+// literal over and over. This whole module is behind #[cfg(test)] in lib.rs, so
+// like the tests module it's never part of a normal build.
+//
+// Being a sibling of `tests` (instead of living inside it) means any test
+// module in the crate can `use crate::fixtures::*`. TempDir, which other
+// crates' tests use as well, is in temp.rs.
+
+use crate::{Guess, Rectangle, User};
+
+pub fn rect(width: u32, height: u32) -> Rectangle {
+    Rectangle { width, height }
+}
+
+pub fn guess(value: i32) -> Guess {
+    Guess::new(value)
+}
+
+// Builder style: start from a user that's valid for most tests and only
+// change the fields a test actually cares about
+//     let user = a_user().named("ferris").inactive().build();
+pub fn a_user() -> UserBuilder {
+    UserBuilder {
+        user: User {
+            active: true,
+            username: String::from("someone"),
+            email: String::from("someone@example.com"),
+            sign_in_count: 1,
+        },
+    }
+}
+
+pub struct UserBuilder {
+    user: User,
+}
+
+impl UserBuilder {
+    pub fn named(mut self, username: &str) -> UserBuilder {
+        self.user.username = String::from(username);
+        self.user.email = format!("{}@example.com", username);
+        self
+    }
+
+    pub fn email(mut self, email: &str) -> UserBuilder {
+        self.user.email = String::from(email);
+        self
+    }
+
+    pub fn sign_ins(mut self, count: u64) -> UserBuilder {
+        self.user.sign_in_count = count;
+        self
+    }
+
+    pub fn inactive(mut self) -> UserBuilder {
+        self.user.active = false;
+        self
+    }
+
+    pub fn build(self) -> User {
+        self.user
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn user_builder_defaults_and_overrides() {
+        let default = a_user().build();
+        assert!(default.active);
+        assert_eq!(default.sign_in_count, 1);
+
+        let ferris = a_user().named("ferris").sign_ins(3).inactive().build();
+        assert_eq!(ferris.username, "ferris");
+        assert_eq!(ferris.email, "ferris@example.com");
+        assert_eq!(ferris.sign_in_count, 3);
+        assert!(!ferris.active);
+
+        let custom = a_user().named("ferris").email("crab@rust.org").build();
+        assert_eq!(custom.email, "crab@rust.org");
+    }
+}
@@ -3,9 +3,30 @@
 // Tests are functions with the test attribute. We've defined attributes before
 // like: derive
 
+// helpers for building test values, shared by every test module in the crate
+#[cfg(test)]
+mod fixtures;
+
+// time_it() for the #[ignore]d benchmark tests in chapter_10 and chapter_13
+pub mod bench;
+
+// lets tests check what a function printed, see chapter_8 for it in use
+pub mod capture;
+
+// a tiny hand rolled version of property testing crates like proptest
+pub mod property;
+
+// TempDir for tests that need real files, see chapter_9's backup tests
+pub mod temp;
+
+// assert_between! and assert_contains!, exported for tests/ and other crates
+pub mod test_macros;
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fixtures::*;
+    use crate::{assert_between, assert_contains};
     #[test] // always use this to mark test functions (eg: not a setup function)
     fn exploration() {
         assert_eq!(2 + 2, 4);
@@ -31,27 +52,15 @@ mod tests {
 
     #[test]
     fn larger_can_hold_smaller() {
-        let larger = Rectangle {
-            width: 8,
-            height: 7,
-        };
-        let smaller = Rectangle {
-            width: 5,
-            height: 1,
-        };
+        let larger = rect(8, 7);
+        let smaller = rect(5, 1);
         assert!(larger.can_hold(&smaller));
     }
 
     #[test]
     fn smaller_cannot_hold_larger() {
-        let larger = Rectangle {
-            width: 8,
-            height: 7,
-        };
-        let smaller = Rectangle {
-            width: 5,
-            height: 1,
-        };
+        let larger = rect(8, 7);
+        let smaller = rect(5, 1);
         assert!(!smaller.can_hold(&larger));
     }
 
@@ -82,6 +91,44 @@ mod tests {
         );
     }
 
+    // Table-driven tests
+    // Checking one name at a time gets repetitive. test_cases! takes a function
+    // that checks a single input, and a list of `case_name: input` pairs, and
+    // writes a separate #[test] for each pair. Each case shows up on its own in
+    // the test output, so a failure tells us exactly which input broke.
+    macro_rules! test_cases {
+        ($check:ident { $($case:ident: $input:expr,)+ }) => {
+            mod $check {
+                use super::*;
+                $(
+                    #[test]
+                    fn $case() {
+                        $check($input);
+                    }
+                )+
+            }
+        };
+    }
+
+    fn greets_by_name(name: &str) {
+        let result = greeting(name);
+        assert_eq!(result, format!("Hello {}!", name));
+        // same check as greeting_contains_name, but the failure message always
+        // shows both strings (see test_macros.rs)
+        assert_contains!(result, name);
+    }
+
+    // runs as greets_by_name::plain, greets_by_name::accented, ...
+    test_cases!(greets_by_name {
+        plain: "Carol",
+        accented: "José",
+        umlaut: "Zoë",
+        greek: "Ελένη",
+        chinese: "李雷",
+        emoji: "🦀 Ferris",
+        empty: "",
+    });
+
     // Checking for panics with should_panic
     /*
     in addition to checking return values it is important to check that our code
@@ -95,7 +142,14 @@ mod tests {
     // # [should_panic] // this gets triggered for any panic, we can be more specific
     # [should_panic(expected = "greater than 100")] //notice it can be a substring
     fn greater_than_100() {
-        Guess::new(200);
+        guess(200);
+    }
+
+    #[test]
+    fn guess_keeps_its_value() {
+        for value in [1, 50, 100] {
+            assert_between!(guess(value).value(), 1, 100, "guess({})", value);
+        }
     }
 
     // Using Result<T, E> in Tests
@@ -104,6 +158,26 @@ mod tests {
     Result<T, E>
     */
 
+    // User compares (and hashes) by username only, see the impls after User
+    #[test]
+    fn users_are_the_same_user_after_signing_in_again() {
+        use std::collections::HashMap;
+
+        let before = a_user().named("ferris").sign_ins(1).build();
+        let mut after = before.clone();
+        after.sign_in_count += 1;
+        after.active = false;
+        assert_eq!(before, after);
+        assert_ne!(before, a_user().named("crab").build());
+
+        let mut last_seen: HashMap<User, &str> = HashMap::new();
+        last_seen.insert(before, "monday");
+        // an equal key replaces the value, it doesn't add a second entry
+        last_seen.insert(after, "tuesday");
+        assert_eq!(last_seen.len(), 1);
+        assert_eq!(last_seen[&a_user().named("ferris").build()], "tuesday");
+    }
+
     # [test]
     fn it_works_again() -> Result<(), String> {
         if 2 + 2 == 4 {
@@ -124,47 +198,62 @@ mod tests {
     */
 }
 
-#[derive(Debug)]
-struct Rectangle {
-    width: u32,
-    height: u32,
-}
+// Rectangle (Chapter 5) and Guess (Chapter 9) come from the domain crate, the
+// same ones the other chapters use. Re-exported with pub use so the fixtures
+// and the integration tests in tests/ can keep writing chapter_11::Rectangle.
+// Guess::new panics with "less than 1" or "greater than 100", which is what
+// the should_panic tests below check for
+pub use domain::{Guess, Rectangle};
 
-impl Rectangle {
-    fn can_hold(&self, other: &Rectangle) -> bool {
-        self.width > other.width && self.height > other.height
-    }
-}
+use std::hash::{Hash, Hasher};
 
 pub fn add_two(a: i32) -> i32 {
     a + 2
 }
 
+// The book breaks greeting on purpose to show what a failing custom message
+// looks like. The broken version is still here, behind the `buggy` feature:
+//     cargo test                    -> fixed, everything passes
+//     cargo test --features buggy   -> greeting drops the name and tests fail
+// cfg! is evaluated at compile time, so only one branch ends up in the binary
 pub fn greeting(name: &str) -> String {
-    // format!("Hello {}!", name) correct version
-    String::from("Hello") // buggy
+    if cfg!(feature = "buggy") {
+        String::from("Hello") // buggy
+    } else {
+        format!("Hello {}!", name) // correct version
+    }
 }
 
-pub struct Guess {
-    value: i32,
+// the User struct from Chapter 5, for tests that need some data to work with
+#[derive(Debug, Clone)]
+pub struct User {
+    pub active: bool,
+    pub username: String,
+    pub email: String,
+    pub sign_in_count: u64,
 }
 
-impl Guess {
-    pub fn new(value: i32) -> Guess {
-        // if value < 1 || value > 100 {
-        //     panic!("Guess value must be between 1 and 100, got: {}", value);
-        // } correct version
+/*
+PartialEq, Eq and Hash are written by hand instead of derived. Derived, two
+Users would only be equal if every field matched, so the same person before
+and after signing in again (sign_in_count 1, then 2) would be two different
+users, and two different HashMap keys. The username is what identifies a user,
+so that's all these compare.
 
-        // if value < 1 {
-        //     panic!("This is broken!!!")
-        // } buggy
+Hash has to agree with Eq: values that are equal must hash the same, so it
+hashes the username and nothing else too. Hashing a field Eq ignores would
+let two "equal" users land in different buckets.
+*/
+impl PartialEq for User {
+    fn eq(&self, other: &User) -> bool {
+        self.username == other.username
+    }
+}
 
-        if value < 1 {
-            panic!("The value provided was less than 1, got: {}", value);
-        } else if value > 100 {
-            panic!("The value provided was greater than 100, got: {}", value)
-        }
+impl Eq for User {}
 
-        Guess { value }
+impl Hash for User {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.username.hash(state);
     }
 }
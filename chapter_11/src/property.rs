@@ -0,0 +1,240 @@
+// Property-Based Testing (by hand)
+
+/*
+The tests so far check examples we picked: add_two(2) is 4. A property test
+states something that should be true for *every* input, like
+"add_two(a) - a is always 2", then throws lots of generated inputs at it.
+Crates like proptest and quickcheck do this properly, this module is a tiny
+version to show how they work:
+
+  1. a seeded random number generator, so a failing run can be repeated
+  2. a Generator trait that makes random values and knows how to shrink them
+  3. for_all, which runs the property and, when it fails, shrinks the input to
+     a smaller one that still fails before reporting it
+
+Shrinking matters because the first failing input is usually something
+unreadable like 738162. Shrinking keeps trying smaller values and reports the
+smallest one it can find, which is usually the interesting edge case.
+*/
+
+use domain::prelude::*;
+use std::fmt::Debug;
+
+// The random numbers come from domain's XorShift, the same one the games use
+// for dice and secret numbers. Generators only ask for "some Rng", so a test
+// could hand them a different one
+pub trait Generator {
+    type Value: Debug + Clone;
+
+    fn generate(&self, rng: &mut dyn Rng) -> Self::Value;
+
+    // smaller versions of `value` to try, most aggressive first
+    fn shrink(&self, value: &Self::Value) -> Vec<Self::Value>;
+}
+
+// any i32 in low..=high
+pub struct I32Range {
+    pub low: i32,
+    pub high: i32,
+}
+
+pub fn i32_in(low: i32, high: i32) -> I32Range {
+    assert!(low <= high, "empty range {}..={}", low, high);
+    I32Range { low, high }
+}
+
+impl Generator for I32Range {
+    type Value = i32;
+
+    fn generate(&self, rng: &mut dyn Rng) -> i32 {
+        rng.range_i32(self.low, self.high)
+    }
+
+    // Shrinking by halving: "small" means close to 0 (or the end of the range
+    // nearest 0). First try jumping all the way there, then half way, then a
+    // quarter of the way... down to a step of 1
+    fn shrink(&self, value: &i32) -> Vec<i32> {
+        let target = 0.clamp(self.low, self.high) as i64;
+        let value = *value as i64;
+        let mut candidates = Vec::new();
+        let mut step = value - target;
+        while step != 0 {
+            candidates.push((value - step) as i32);
+            step /= 2;
+        }
+        candidates
+    }
+}
+
+// two independent values, shrunk one side at a time
+pub struct Both<A, B>(pub A, pub B);
+
+impl<A: Generator, B: Generator> Generator for Both<A, B> {
+    type Value = (A::Value, B::Value);
+
+    fn generate(&self, rng: &mut dyn Rng) -> Self::Value {
+        (self.0.generate(rng), self.1.generate(rng))
+    }
+
+    fn shrink(&self, (a, b): &Self::Value) -> Vec<Self::Value> {
+        let mut candidates: Vec<Self::Value> = self
+            .0
+            .shrink(a)
+            .into_iter()
+            .map(|smaller| (smaller, b.clone()))
+            .collect();
+        candidates.extend(
+            self.1
+                .shrink(b)
+                .into_iter()
+                .map(|smaller| (a.clone(), smaller)),
+        );
+        candidates
+    }
+}
+
+pub struct Config {
+    pub cases: usize,
+    pub seed: u64,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            cases: 200,
+            seed: 0x5EED,
+        }
+    }
+}
+
+// checks `property` against Config::default().cases generated values, panics
+// with the smallest failing input it can find
+pub fn for_all<G: Generator>(generator: G, property: impl Fn(&G::Value) -> bool) {
+    for_all_with(Config::default(), generator, property)
+}
+
+pub fn for_all_with<G: Generator>(
+    config: Config,
+    generator: G,
+    property: impl Fn(&G::Value) -> bool,
+) {
+    let mut rng = XorShift::new(config.seed);
+    for case in 1..=config.cases {
+        let value = generator.generate(&mut rng);
+        if !property(&value) {
+            let smallest = shrink(&generator, value.clone(), &property);
+            panic!(
+                "property failed on case {} (seed {:#x})\n  input: {:?}\n  shrunk to: {:?}",
+                case, config.seed, value, smallest
+            );
+        }
+    }
+}
+
+// keep taking the first smaller candidate that still fails, until none do
+fn shrink<G: Generator>(
+    generator: &G,
+    mut failing: G::Value,
+    property: &impl Fn(&G::Value) -> bool,
+) -> G::Value {
+    while let Some(smaller) = generator
+        .shrink(&failing)
+        .into_iter()
+        .find(|candidate| !property(candidate))
+    {
+        failing = smaller;
+    }
+    failing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{add_two, Guess};
+    use std::panic;
+
+    // kept well inside i32 so a + b and add_two can't overflow
+    const LIMIT: i32 = 1_000_000;
+
+    #[test]
+    fn add_two_adds_exactly_two() {
+        for_all(i32_in(-LIMIT, LIMIT), |&a| add_two(a) - a == 2);
+    }
+
+    #[test]
+    fn add_two_twice_adds_four() {
+        for_all(i32_in(-LIMIT, LIMIT), |&a| add_two(add_two(a)) == a + 4);
+    }
+
+    #[test]
+    fn add_two_commutes_with_addition() {
+        // it doesn't matter which side of a + b the 2 gets added to
+        for_all(
+            Both(i32_in(-LIMIT, LIMIT), i32_in(-LIMIT, LIMIT)),
+            |&(a, b)| add_two(a) + b == a + add_two(b),
+        );
+    }
+
+    #[test]
+    fn guess_try_new_accepts_exactly_1_to_100() {
+        for_all(i32_in(-1000, 1000), |&value| {
+            Guess::try_new(value).is_ok() == (1..=100).contains(&value)
+        });
+        // random values might never land right on the edges, so check those too
+        for value in [0, 1, 100, 101] {
+            assert_eq!(Guess::try_new(value).is_ok(), (1..=100).contains(&value));
+        }
+    }
+
+    // the seed in a failure report is only useful if running with it again
+    // fails on the same case with the same input
+    #[test]
+    fn same_seed_same_failure() {
+        let run = |seed| {
+            let config = Config { cases: 200, seed };
+            let result =
+                panic::catch_unwind(|| for_all_with(config, i32_in(0, 1000), |&x| x % 7 != 3));
+            *result.unwrap_err().downcast::<String>().unwrap()
+        };
+        assert_eq!(run(42), run(42));
+    }
+
+    #[test]
+    fn generated_values_stay_in_range() {
+        let mut rng = XorShift::new(7);
+        let generator = i32_in(-3, 3);
+        for _ in 0..1000 {
+            let value = generator.generate(&mut rng);
+            assert!((-3..=3).contains(&value), "got {}", value);
+        }
+        assert_eq!(
+            i32_in(i32::MIN, i32::MAX).shrink(&i32::MIN).first(),
+            Some(&0)
+        );
+    }
+
+    #[test]
+    fn shrink_finds_the_smallest_failure() {
+        let generator = i32_in(0, 10_000);
+        // fails for everything from 50 up, so the smallest failure is 50
+        assert_eq!(shrink(&generator, 7_321, &|&x| x < 50), 50);
+
+        let negatives = i32_in(-10_000, -1);
+        assert_eq!(shrink(&negatives, -9_999, &|&x| x > -20), -20);
+    }
+
+    #[test]
+    fn pairs_shrink_one_side_at_a_time() {
+        let generator = Both(i32_in(0, 1000), i32_in(0, 1000));
+        let (a, b) = shrink(&generator, (812, 604), &|&(a, b)| a + b < 100);
+        // neither side can get any smaller without the sum dropping below 100
+        assert_eq!(a + b, 100);
+    }
+
+    #[test]
+    fn failing_property_reports_shrunk_input() {
+        let result = panic::catch_unwind(|| for_all(i32_in(0, 1000), |&x| x < 100));
+        let message = *result.unwrap_err().downcast::<String>().unwrap();
+        assert!(message.contains("shrunk to: 100"), "{}", message);
+    }
+}
@@ -0,0 +1,96 @@
+// Setup and Teardown: Temporary Directories
+
+/*
+Rust's test harness has no setup()/teardown() hooks like some other languages'
+test frameworks. The usual replacement is a value whose constructor does the
+setup and whose Drop does the teardown (Drop is Chapter 15). Because Drop runs
+even when a test panics, a failing assert! still cleans up after itself.
+
+TempDir is the one that tests touching real files need: a fresh directory
+under the system temp dir that no other test is using, removed again when the
+TempDir goes out of scope. Unlike fixtures.rs this module is pub and not behind
+#[cfg(test)], so other chapters can add chapter_11 as a dev-dependency and use
+it in their own tests:
+
+    let dir = TempDir::new("chapter_9_backup");
+    let src = dir.file("notes.txt", "remember the milk");
+*/
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+pub struct TempDir {
+    path: PathBuf,
+}
+
+// tests run on several threads at once, so every TempDir gets its own number
+static NEXT_DIR: AtomicUsize = AtomicUsize::new(0);
+
+impl TempDir {
+    // the label goes into the directory name, so a leftover dir can be traced
+    // back to the test that made it
+    pub fn new(label: &str) -> TempDir {
+        let n = NEXT_DIR.fetch_add(1, Ordering::SeqCst);
+        let path = env::temp_dir().join(format!("{}_{}_{}", label, std::process::id(), n));
+        fs::create_dir_all(&path).expect("could not create temp dir");
+        TempDir { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    // writes a file inside the dir and returns its full path
+    pub fn file(&self, name: &str, contents: &str) -> PathBuf {
+        let path = self.path.join(name);
+        fs::write(&path, contents).expect("could not write fixture file");
+        path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        // ignore errors, a leftover temp dir shouldn't fail a test
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::panic;
+
+    #[test]
+    fn temp_dir_is_removed_on_drop() {
+        let path;
+        {
+            let dir = TempDir::new("chapter_11_drop");
+            let file = dir.file("hello.txt", "hi");
+            assert_eq!(fs::read_to_string(file).unwrap(), "hi");
+            path = dir.path().to_path_buf();
+            assert!(path.exists());
+        }
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn temp_dir_is_removed_even_when_the_test_panics() {
+        let dir = TempDir::new("chapter_11_panic");
+        let path = dir.path().to_path_buf();
+        let result = panic::catch_unwind(move || {
+            let _dir = dir;
+            panic!("test body failed");
+        });
+        assert!(result.is_err());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn every_temp_dir_is_different() {
+        let a = TempDir::new("chapter_11_same");
+        let b = TempDir::new("chapter_11_same");
+        assert_ne!(a.path(), b.path());
+    }
+}
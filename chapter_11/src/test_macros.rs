@@ -0,0 +1,162 @@
+// Custom Assertion Macros
+
+/*
+assert! only knows the condition was false, so a failure like
+    assert!(result.contains("John"))
+just prints "assertion failed: result.contains(\"John\")" unless we remember to
+write a custom message every time. These macros always print the values
+involved, the same way assert_eq! prints left and right.
+
+#[macro_export] puts a macro at the root of the crate, so it's used as
+chapter_11::assert_between! from other crates (including the integration tests
+in tests/) and crate::assert_between! inside this one. Like assert!, any extra
+arguments are passed on to format! as a custom message.
+*/
+
+#[macro_export]
+macro_rules! assert_between {
+    ($value:expr, $low:expr, $high:expr $(,)?) => {
+        match (&$value, &$low, &$high) {
+            (value, low, high) => {
+                if !(*low <= *value && *value <= *high) {
+                    panic!(
+                        "assertion `low <= value <= high` failed\n value: {:?}\n range: {:?}..={:?}",
+                        value, low, high
+                    );
+                }
+            }
+        }
+    };
+    ($value:expr, $low:expr, $high:expr, $($arg:tt)+) => {
+        match (&$value, &$low, &$high) {
+            (value, low, high) => {
+                if !(*low <= *value && *value <= *high) {
+                    panic!(
+                        "assertion `low <= value <= high` failed: {}\n value: {:?}\n range: {:?}..={:?}",
+                        format_args!($($arg)+),
+                        value,
+                        low,
+                        high
+                    );
+                }
+            }
+        }
+    };
+}
+
+// works with anything that can be viewed as a &str, like &str and String
+#[macro_export]
+macro_rules! assert_contains {
+    ($haystack:expr, $needle:expr $(,)?) => {
+        match (&$haystack, &$needle) {
+            (haystack, needle) => {
+                let haystack: &str = ::std::convert::AsRef::<str>::as_ref(haystack);
+                let needle: &str = ::std::convert::AsRef::<str>::as_ref(needle);
+                if !haystack.contains(needle) {
+                    panic!(
+                        "assertion `haystack.contains(needle)` failed\n haystack: {:?}\n   needle: {:?}",
+                        haystack, needle
+                    );
+                }
+            }
+        }
+    };
+    ($haystack:expr, $needle:expr, $($arg:tt)+) => {
+        match (&$haystack, &$needle) {
+            (haystack, needle) => {
+                let haystack: &str = ::std::convert::AsRef::<str>::as_ref(haystack);
+                let needle: &str = ::std::convert::AsRef::<str>::as_ref(needle);
+                if !haystack.contains(needle) {
+                    panic!(
+                        "assertion `haystack.contains(needle)` failed: {}\n haystack: {:?}\n   needle: {:?}",
+                        format_args!($($arg)+),
+                        haystack,
+                        needle
+                    );
+                }
+            }
+        }
+    };
+}
+
+// Testing the tests: each of these runs a macro that's supposed to fail, catches
+// the panic, and checks the message is the one we promised above
+#[cfg(test)]
+mod tests {
+    use std::panic;
+
+    // runs `f`, expecting it to panic, and returns the panic message
+    fn panic_message(f: impl FnOnce() + panic::UnwindSafe) -> String {
+        let payload = panic::catch_unwind(f).expect_err("expected a panic");
+        // panic! with format arguments gives a String, a plain literal a &str
+        match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => payload
+                .downcast::<&str>()
+                .map(|message| message.to_string())
+                .unwrap_or_default(),
+        }
+    }
+
+    #[test]
+    fn between_passes_inclusive_bounds() {
+        assert_between!(1, 1, 100);
+        assert_between!(100, 1, 100);
+        assert_between!(0.5, 0.0, 1.0);
+        assert_between!('m', 'a', 'z', "letters work too");
+    }
+
+    #[test]
+    fn between_failure_message() {
+        let message = panic_message(|| assert_between!(101, 1, 100));
+        assert_eq!(
+            message,
+            "assertion `low <= value <= high` failed\n value: 101\n range: 1..=100"
+        );
+    }
+
+    #[test]
+    fn between_failure_with_custom_message() {
+        let message = panic_message(|| assert_between!(-5, 1, 100, "guess #{} was off", 3));
+        assert_eq!(
+            message,
+            "assertion `low <= value <= high` failed: guess #3 was off\n value: -5\n range: 1..=100"
+        );
+    }
+
+    #[test]
+    fn between_evaluates_each_argument_once() {
+        let mut calls = 0;
+        let mut next = || {
+            calls += 1;
+            calls
+        };
+        assert_between!(next(), 0, 10);
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn contains_passes() {
+        assert_contains!("Hello Carol!", "Carol");
+        assert_contains!(String::from("Hello Carol!"), String::from("Hello"));
+        assert_contains!("anything", "");
+    }
+
+    #[test]
+    fn contains_failure_message() {
+        let message = panic_message(|| assert_contains!(String::from("Hello"), "Carol"));
+        assert_eq!(
+            message,
+            "assertion `haystack.contains(needle)` failed\n haystack: \"Hello\"\n   needle: \"Carol\""
+        );
+    }
+
+    #[test]
+    fn contains_failure_with_custom_message() {
+        let message = panic_message(|| {
+            assert_contains!("Hello", "Carol", "greeting for {}", "Carol");
+        });
+        assert!(message
+            .starts_with("assertion `haystack.contains(needle)` failed: greeting for Carol\n"));
+    }
+}
@@ -0,0 +1,18 @@
+// Shared helpers for the integration tests (see "Submodules in Integration
+// Tests" in src/test_organization.md). Living in common/mod.rs instead of
+// common.rs means Cargo doesn't treat this file as a test crate of its own.
+
+use chapter_11::Rectangle;
+
+pub fn setup() {
+    // setup code specific to the library's tests would go here. Nothing in
+    // chapter_11 needs any yet, but every test calls it so there's one place
+    // to add it later
+}
+
+// the integration tests can't see src/fixtures.rs (it's #[cfg(test)] inside
+// the library, and the library isn't built in test mode for tests/), so they
+// get their own, built only from the public API
+pub fn rect(width: u32, height: u32) -> Rectangle {
+    Rectangle { width, height }
+}
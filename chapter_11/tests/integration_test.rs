@@ -0,0 +1,60 @@
+// 11.3 Integration Tests
+// Each file in tests/ is compiled as its own crate, so just like any other
+// user of chapter_11 these tests can only reach the public API
+
+use chapter_11::{add_two, assert_between, Guess};
+
+mod common;
+
+#[test]
+fn it_adds_two() {
+    common::setup();
+    assert_eq!(4, add_two(2));
+}
+
+#[test]
+fn add_two_handles_negatives() {
+    common::setup();
+    assert_eq!(0, add_two(-2));
+    assert_eq!(-98, add_two(-100));
+}
+
+#[test]
+fn larger_can_hold_smaller() {
+    common::setup();
+    let larger = common::rect(8, 7);
+    let smaller = common::rect(5, 1);
+    assert!(larger.can_hold(&smaller));
+    assert!(!smaller.can_hold(&larger));
+}
+
+#[test]
+fn rectangle_cannot_hold_itself() {
+    common::setup();
+    let square = common::rect(4, 4);
+    assert!(!square.can_hold(&common::rect(4, 4)));
+}
+
+#[test]
+fn guess_keeps_values_in_range() {
+    common::setup();
+    assert_eq!(Guess::new(1).value(), 1);
+    assert_eq!(Guess::new(100).value(), 100);
+    for value in [2, 42, 99] {
+        assert_between!(Guess::new(value).value(), 1, 100);
+    }
+}
+
+#[test]
+#[should_panic(expected = "less than 1")]
+fn guess_below_range_panics() {
+    common::setup();
+    Guess::new(0);
+}
+
+#[test]
+#[should_panic(expected = "greater than 100")]
+fn guess_above_range_panics() {
+    common::setup();
+    Guess::new(101);
+}
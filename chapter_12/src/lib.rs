@@ -0,0 +1,329 @@
+// Chapter 12: An I/O Project: Building a Command Line Program (library)
+
+/*
+minigrep: a (very) small grep. Given a query and a file path it prints every
+line of the file that contains the query:
+
+    cargo run -- frog poem.txt
+
+12.3 moves everything except the argument collecting and error printing out
+of main.rs and into this lib, for two reasons:
+  - main can't be tested, lib functions can
+  - main was doing too many jobs at once (parsing args, reading the file,
+    searching, printing), each of those is its own piece now
+*/
+
+pub mod matcher;
+
+use matcher::{Matcher, Substring};
+use std::env;
+use std::error::Error;
+use std::fs;
+
+pub const USAGE: &str = "\
+Usage: minigrep [--regex] <query> <file_path>
+
+Prints every line of <file_path> that contains <query>.
+
+Options:
+  -h, --help    show this message
+  --regex       treat <query> as a regular expression
+                (only in builds with `--features regex`)
+  --            stop reading options, so a query can start with -:
+                minigrep -- -h notes.txt
+
+Environment:
+  IGNORE_CASE   if set (to anything), matching ignores upper/lower case
+";
+
+// Splits the arguments (without the program name) at the first `--`, the
+// usual way for a command line program to say "no more options after this".
+// Only what's before it is checked for flags, so `minigrep -- -h notes.txt`
+// searches for "-h" instead of printing the help
+fn split_options(args: &[String]) -> (&[String], &[String]) {
+    let args = args.get(1..).unwrap_or_default();
+    match args.iter().position(|arg| arg == "--") {
+        Some(at) => (&args[..at], &args[at + 1..]),
+        None => (args, &[]),
+    }
+}
+
+// true if --help or -h is anywhere before a `--`, checked before build so
+// `minigrep --help` works without a query and file
+pub fn wants_help(args: &[String]) -> bool {
+    split_options(args)
+        .0
+        .iter()
+        .any(|arg| arg == "--help" || arg == "-h")
+}
+
+// 12.3 Grouping Configuration Values
+//
+// query and file_path belong together, so they're one struct rather than a
+// tuple or two loose variables. They're owned Strings (cloned from args) so
+// Config doesn't have to borrow from the args Vec and carry a lifetime around
+pub struct Config {
+    pub query: String,
+    pub file_path: String,
+    pub ignore_case: bool,
+    pub use_regex: bool,
+}
+
+impl Config {
+    // "build" rather than "new": people expect new to never fail, and this can.
+    // args[0] is the program's own name, the real arguments start at 1.
+    // --regex can go anywhere before a `--`, query and file_path are the first
+    // two of the rest
+    pub fn build(args: &[String]) -> Result<Config, &'static str> {
+        let (options, after) = split_options(args);
+        let use_regex = options.iter().any(|arg| arg == "--regex");
+        if use_regex && !cfg!(feature = "regex") {
+            return Err("--regex needs minigrep built with `--features regex`");
+        }
+
+        let positional: Vec<&String> = options
+            .iter()
+            .filter(|arg| *arg != "--regex")
+            .chain(after)
+            .collect();
+        if positional.len() < 2 {
+            return Err("not enough arguments");
+        }
+
+        let query = positional[0].clone();
+        let file_path = positional[1].clone();
+
+        // 12.5: only whether IGNORE_CASE is set matters, not its value, so
+        // is_ok() (set) is all we need from the Result
+        let ignore_case = env::var("IGNORE_CASE").is_ok();
+
+        Ok(Config {
+            query,
+            file_path,
+            ignore_case,
+            use_regex,
+        })
+    }
+
+    // the Matcher for this search. Building a regex can fail (the pattern is
+    // user input), so this returns a Result too
+    pub fn matcher(&self) -> Result<Box<dyn Matcher>, Box<dyn Error>> {
+        #[cfg(feature = "regex")]
+        if self.use_regex {
+            let regex = matcher::RegexMatcher::new(&self.query, self.ignore_case)?;
+            return Ok(Box::new(regex));
+        }
+        Ok(Box::new(Substring::new(&self.query, self.ignore_case)))
+    }
+}
+
+// 12.3 Returning Errors from the run Function
+//
+// Box<dyn Error> means "any kind of error". The ? on read_to_string passes an
+// io::Error back up instead of panicking, and main decides what to do with it
+//
+// Which lines match is up to the Matcher (matcher.rs), run just reads the
+// file and prints. The matcher is built first so a bad regex is reported
+// before we bother reading the file
+pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
+    let matcher = config.matcher()?;
+    let contents = fs::read_to_string(config.file_path)?;
+
+    let results = matcher::search_with(&*matcher, &contents);
+
+    for line in results {
+        println!("{line}");
+    }
+
+    Ok(())
+}
+
+// 12.4 Developing the Library's Functionality with Test-Driven Development
+//
+// The lifetime says the returned lines borrow from `contents` (they're slices
+// of it), not from `query`
+//
+// The book writes both searches with a for loop pushing onto a mutable Vec,
+// then comes back in 13.3 and turns them into iterator chains. This is the
+// 13.3 version, chapter_13/src/search.rs shows the loop it replaced
+pub fn search<'a>(query: &str, contents: &'a str) -> Vec<&'a str> {
+    contents
+        .lines()
+        .filter(|line| line.contains(query))
+        .collect()
+}
+
+// 12.5 Working with Environment Variables
+//
+// the same search, lowercasing both sides before comparing. to_lowercase makes
+// a new String, so query is a String now and gets passed on as &query. It's
+// lowercased once up front instead of once per line, and the results are
+// still the original lines, only the comparison ignores case
+pub fn search_case_insensitive<'a>(query: &str, contents: &'a str) -> Vec<&'a str> {
+    let query = query.to_lowercase();
+    contents
+        .lines()
+        .filter(|line| line.to_lowercase().contains(&query))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn build_takes_query_and_path() {
+        let config = Config::build(&args(&["minigrep", "frog", "poem.txt"])).unwrap();
+        assert_eq!(config.query, "frog");
+        assert_eq!(config.file_path, "poem.txt");
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn regex_flag_can_go_anywhere() {
+        for list in [
+            ["minigrep", "--regex", "fr.g", "poem.txt"],
+            ["minigrep", "fr.g", "--regex", "poem.txt"],
+            ["minigrep", "fr.g", "poem.txt", "--regex"],
+        ] {
+            let config = Config::build(&args(&list)).unwrap();
+            assert!(config.use_regex);
+            assert_eq!(config.query, "fr.g");
+            assert_eq!(config.file_path, "poem.txt");
+        }
+        let plain = Config::build(&args(&["minigrep", "frog", "poem.txt"])).unwrap();
+        assert!(!plain.use_regex);
+    }
+
+    #[cfg(not(feature = "regex"))]
+    #[test]
+    fn regex_flag_needs_the_feature() {
+        let result = Config::build(&args(&["minigrep", "--regex", "fr.g", "poem.txt"]));
+        match result {
+            Ok(_) => panic!("--regex should be refused without the feature"),
+            Err(e) => assert!(e.contains("--features regex")),
+        }
+    }
+
+    #[test]
+    fn flag_is_not_a_positional_argument() {
+        let result = Config::build(&args(&["minigrep", "--regex", "frog"]));
+        assert!(result.is_err());
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn bad_regex_is_reported_by_run() {
+        let config = Config::build(&args(&["minigrep", "--regex", "(", "poem.txt"])).unwrap();
+        assert!(run(config).is_err());
+    }
+
+    #[test]
+    fn build_needs_two_arguments() {
+        assert_eq!(
+            Config::build(&args(&["minigrep", "frog"])).err(),
+            Some("not enough arguments")
+        );
+        assert!(Config::build(&args(&["minigrep"])).is_err());
+    }
+
+    #[test]
+    fn help_flag_before_any_double_dash() {
+        assert!(wants_help(&args(&["minigrep", "--help"])));
+        assert!(wants_help(&args(&["minigrep", "frog", "-h"])));
+        assert!(!wants_help(&args(&["minigrep", "frog", "poem.txt"])));
+        // the program's own name doesn't count
+        assert!(!wants_help(&args(&["--help"])));
+        // after a -- it's a query, not a flag
+        assert!(!wants_help(&args(&["minigrep", "--", "-h", "poem.txt"])));
+        assert!(wants_help(&args(&["minigrep", "-h", "--", "poem.txt"])));
+    }
+
+    #[test]
+    fn double_dash_ends_the_options() {
+        let config = Config::build(&args(&["minigrep", "--", "-h", "poem.txt"])).unwrap();
+        assert_eq!(
+            (config.query.as_str(), config.file_path.as_str()),
+            ("-h", "poem.txt")
+        );
+        // only the first -- is special, and --regex after it is a query
+        let config = Config::build(&args(&["minigrep", "--", "--regex", "--"])).unwrap();
+        assert_eq!(
+            (config.query.as_str(), config.file_path.as_str()),
+            ("--regex", "--")
+        );
+        assert!(!config.use_regex);
+        assert!(Config::build(&args(&["minigrep", "--"])).is_err());
+    }
+
+    #[test]
+    fn run_reports_a_missing_file() {
+        let config = Config::build(&args(&["minigrep", "x", "no/such/file.txt"])).unwrap();
+        assert!(run(config).is_err());
+    }
+
+    #[test]
+    fn one_result() {
+        let query = "duct";
+        let contents = "\
+Rust:
+safe, fast, productive.
+Pick three.";
+
+        assert_eq!(vec!["safe, fast, productive."], search(query, contents));
+    }
+
+    #[test]
+    fn case_sensitive() {
+        let query = "duct";
+        let contents = "\
+Rust:
+safe, fast, productive.
+Pick three.
+Duct tape.";
+
+        assert_eq!(vec!["safe, fast, productive."], search(query, contents));
+    }
+
+    #[test]
+    fn case_insensitive() {
+        let query = "rUsT";
+        let contents = "\
+Rust:
+safe, fast, productive.
+Pick three.
+Trust me.";
+
+        assert_eq!(
+            vec!["Rust:", "Trust me."],
+            search_case_insensitive(query, contents)
+        );
+    }
+
+    #[test]
+    fn no_results() {
+        assert!(search("frog", "nothing\nhere").is_empty());
+        assert!(search_case_insensitive("FROG", "").is_empty());
+    }
+
+    #[test]
+    fn empty_query_matches_every_line() {
+        assert_eq!(search("", "a\nb\n"), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn results_outlive_the_query() {
+        let contents = String::from("one fish\ntwo fish\nred fish");
+        let results;
+        {
+            // query only lives in this block, but the results only borrow contents
+            let query = String::from("fish");
+            results = search_case_insensitive(&query, &contents);
+        }
+        assert_eq!(results.len(), 3);
+    }
+}
@@ -0,0 +1,51 @@
+// Chapter 12: An I/O Project: Building a Command Line Program
+
+/*
+main is kept small on purpose (12.3 Separation of Concerns for Binary
+Projects): it collects the arguments, hands them to Config::build, calls run,
+and turns any error into a message and an exit code. Everything else is in
+lib.rs where it can be tested.
+
+    cargo run -- to poem.txt
+*/
+
+use chapter_12::Config;
+use std::env;
+use std::process;
+
+fn main() {
+    // 12.1 Accepting Command Line Arguments
+    //
+    // env::args() is an iterator over the arguments, collect turns it into a
+    // Vec. The first one is the path of the program itself
+    let args: Vec<String> = env::args().collect();
+
+    // asking for help isn't an error, so it goes to stdout and exits with 0
+    if chapter_12::wants_help(&args) {
+        print!("{}", chapter_12::USAGE);
+        return;
+    }
+
+    // unwrap_or_else: the Config if build worked, otherwise run the closure
+    // with the error. process::exit stops right away with that exit code,
+    // nonzero tells whoever ran us that something went wrong
+    //
+    // 12.6 Writing Error Messages to Standard Error Instead of Standard Output
+    //
+    // eprintln! writes to stderr, so `minigrep to poem.txt > output.txt` puts
+    // only the matching lines in the file and errors still show up on screen.
+    // (The "Searching for ..." lines from 12.1 are gone for the same reason,
+    // stdout is just the results now)
+    let config = Config::build(&args).unwrap_or_else(|err| {
+        eprintln!("Problem parsing arguments: {err}");
+        eprintln!("Try `minigrep --help` for usage.");
+        process::exit(1);
+    });
+
+    // run only returns () on success, so there's nothing to unwrap, we only
+    // care about the error
+    if let Err(e) = chapter_12::run(config) {
+        eprintln!("Application error: {e}");
+        process::exit(1);
+    }
+}
@@ -0,0 +1,151 @@
+// Matching Strategies
+
+/*
+search and search_case_insensitive in lib.rs each have the "does this line
+match" test baked in. To add regular expressions without a third copy of the
+loop, the test becomes a trait: a Matcher says whether one line matches, and
+search_with keeps the lines any Matcher accepts. run() picks the Matcher from
+the Config and doesn't care which one it got.
+
+The regex version needs the regex crate, which is an optional dependency
+behind the `regex` feature. Without the feature RegexMatcher doesn't exist at
+all (#[cfg] removes it before compiling), so a plain build doesn't pay for a
+dependency it isn't using.
+*/
+
+pub trait Matcher {
+    fn is_match(&self, line: &str) -> bool;
+}
+
+// the book's two searches as one Matcher
+pub struct Substring {
+    query: String,
+    ignore_case: bool,
+}
+
+impl Substring {
+    pub fn new(query: &str, ignore_case: bool) -> Substring {
+        Substring {
+            // lowercased once here instead of once per line
+            query: if ignore_case {
+                query.to_lowercase()
+            } else {
+                query.to_string()
+            },
+            ignore_case,
+        }
+    }
+}
+
+impl Matcher for Substring {
+    fn is_match(&self, line: &str) -> bool {
+        if self.ignore_case {
+            line.to_lowercase().contains(&self.query)
+        } else {
+            line.contains(&self.query)
+        }
+    }
+}
+
+#[cfg(feature = "regex")]
+pub struct RegexMatcher {
+    regex: regex::Regex,
+}
+
+#[cfg(feature = "regex")]
+impl RegexMatcher {
+    // an invalid pattern like "(" is an error, not a panic, it's user input
+    pub fn new(pattern: &str, ignore_case: bool) -> Result<RegexMatcher, regex::Error> {
+        let regex = regex::RegexBuilder::new(pattern)
+            .case_insensitive(ignore_case)
+            .build()?;
+        Ok(RegexMatcher { regex })
+    }
+}
+
+#[cfg(feature = "regex")]
+impl Matcher for RegexMatcher {
+    fn is_match(&self, line: &str) -> bool {
+        self.regex.is_match(line)
+    }
+}
+
+pub fn search_with<'a>(matcher: &dyn Matcher, contents: &'a str) -> Vec<&'a str> {
+    contents
+        .lines()
+        .filter(|line| matcher.is_match(line))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{search, search_case_insensitive};
+
+    const CONTENTS: &str = "\
+Rust:
+safe, fast, productive.
+Pick three.
+Duct tape.
+Trust me.";
+
+    #[test]
+    fn substring_matches_the_books_searches() {
+        for query in ["duct", "rUsT", "t", "", "frog"] {
+            assert_eq!(
+                search_with(&Substring::new(query, false), CONTENTS),
+                search(query, CONTENTS)
+            );
+            assert_eq!(
+                search_with(&Substring::new(query, true), CONTENTS),
+                search_case_insensitive(query, CONTENTS)
+            );
+        }
+    }
+
+    #[test]
+    fn any_matcher_works() {
+        // search_with only needs the trait, so a test can make up its own
+        struct LongerThan(usize);
+
+        impl Matcher for LongerThan {
+            fn is_match(&self, line: &str) -> bool {
+                line.len() > self.0
+            }
+        }
+
+        assert_eq!(
+            search_with(&LongerThan(11), CONTENTS),
+            vec!["safe, fast, productive."]
+        );
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn regex_patterns() {
+        let starts_with_capital_ends_with_dot = RegexMatcher::new(r"^[A-Z].*\.$", false).unwrap();
+        assert_eq!(
+            search_with(&starts_with_capital_ends_with_dot, CONTENTS),
+            vec!["Pick three.", "Duct tape.", "Trust me."]
+        );
+
+        let word = RegexMatcher::new(r"\brust\b", false).unwrap();
+        assert!(search_with(&word, CONTENTS).is_empty());
+        let word = RegexMatcher::new(r"\brust\b", true).unwrap();
+        // "Trust" doesn't count, \b needs a word boundary before the r
+        assert_eq!(search_with(&word, CONTENTS), vec!["Rust:"]);
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn plain_text_is_a_regex_too() {
+        let regex = RegexMatcher::new("duct", false).unwrap();
+        assert_eq!(search_with(&regex, CONTENTS), search("duct", CONTENTS));
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn invalid_pattern_is_an_error() {
+        assert!(RegexMatcher::new("(unclosed", false).is_err());
+    }
+}
@@ -0,0 +1,111 @@
+// Integration tests for the minigrep binary
+// These run the real program (cargo builds it before running tests and tells
+// us where with CARGO_BIN_EXE_<name>) and look at what it wrote to stdout and
+// stderr and the exit code, the same things a user or a shell script would see
+
+use std::process::{Command, Output};
+
+// runs minigrep from the crate root (where poem.txt is) with IGNORE_CASE unset
+// unless `ignore_case` says otherwise
+fn minigrep(args: &[&str], ignore_case: bool) -> Output {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_chapter_12"));
+    command.args(args).current_dir(env!("CARGO_MANIFEST_DIR"));
+    if ignore_case {
+        command.env("IGNORE_CASE", "1");
+    } else {
+        command.env_remove("IGNORE_CASE");
+    }
+    command.output().expect("failed to run minigrep")
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+#[test]
+fn results_go_to_stdout_only() {
+    let output = minigrep(&["to", "poem.txt"], false);
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        "Are you nobody, too?\nHow dreary to be somebody!\n"
+    );
+    assert_eq!(stderr(&output), "");
+}
+
+#[test]
+fn ignore_case_env_var() {
+    let output = minigrep(&["to", "poem.txt"], true);
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        "Are you nobody, too?\nHow dreary to be somebody!\nTo tell your name the livelong day\nTo an admiring bog!\n"
+    );
+}
+
+#[test]
+fn no_matches_is_not_an_error() {
+    let output = minigrep(&["monomorphization", "poem.txt"], false);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "");
+    assert_eq!(stderr(&output), "");
+}
+
+#[test]
+fn missing_arguments_go_to_stderr() {
+    let output = minigrep(&["to"], false);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "");
+    assert!(stderr(&output).starts_with("Problem parsing arguments: not enough arguments\n"));
+    assert!(stderr(&output).contains("--help"));
+}
+
+#[test]
+fn missing_file_goes_to_stderr() {
+    let output = minigrep(&["to", "no_such_poem.txt"], false);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "");
+    assert!(stderr(&output).starts_with("Application error: "));
+}
+
+#[test]
+fn help_goes_to_stdout() {
+    for flag in ["--help", "-h"] {
+        let output = minigrep(&[flag], false);
+        assert!(output.status.success());
+        assert_eq!(stdout(&output), chapter_12::USAGE);
+        assert_eq!(stderr(&output), "");
+    }
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn regex_search() {
+    // lines ending in ! or ?
+    let output = minigrep(&["--regex", "[!?]$", "poem.txt"], false);
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        "I'm nobody! Who are you?\nAre you nobody, too?\nThen there's a pair of us - don't tell!\nHow dreary to be somebody!\nTo an admiring bog!\n"
+    );
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn bad_regex_goes_to_stderr() {
+    let output = minigrep(&["--regex", "(", "poem.txt"], false);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).starts_with("Application error: regex parse error"));
+}
+
+#[cfg(not(feature = "regex"))]
+#[test]
+fn regex_flag_needs_the_feature() {
+    let output = minigrep(&["--regex", "to", "poem.txt"], false);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("--features regex"));
+}
@@ -0,0 +1,156 @@
+// 13.2 (continued) Writing Our Own Iterator Adaptors
+
+/*
+map and filter aren't magic, they're ordinary structs that wrap another
+iterator. Each one stores the iterator it came from plus the closure, and its
+next() asks the inner iterator for items and applies the closure. Nothing
+happens until someone calls next(), which is why iterator adaptors are lazy.
+
+MyMap and MyFilter below work the same way as std's Map and Filter.
+*/
+
+pub struct MyMap<I, F> {
+    iter: I,
+    f: F,
+}
+
+impl<I, F, B> Iterator for MyMap<I, F>
+where
+    I: Iterator,
+    F: FnMut(I::Item) -> B,
+{
+    type Item = B;
+
+    fn next(&mut self) -> Option<B> {
+        // Option's own map: None stays None, Some(x) becomes Some(f(x))
+        self.iter.next().map(&mut self.f)
+    }
+}
+
+pub struct MyFilter<I, P> {
+    iter: I,
+    predicate: P,
+}
+
+impl<I, P> Iterator for MyFilter<I, P>
+where
+    I: Iterator,
+    P: FnMut(&I::Item) -> bool,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        // keep pulling until an item passes, or the inner iterator runs out.
+        // find does exactly that (and is itself built on next())
+        self.iter.find(|item| (self.predicate)(item))
+    }
+}
+
+/*
+To call .my_map() on any iterator we need a trait with a default method, and a
+blanket impl (10.2) that gives it to every type implementing Iterator. This is
+called an extension trait: it adds methods to types we didn't write. It has to
+be brought into scope (use chapter_13::adaptors::IteratorExt) before the
+methods show up, the same as any other trait.
+*/
+pub trait IteratorExt: Iterator + Sized {
+    fn my_map<B, F>(self, f: F) -> MyMap<Self, F>
+    where
+        F: FnMut(Self::Item) -> B,
+    {
+        MyMap { iter: self, f }
+    }
+
+    fn my_filter<P>(self, predicate: P) -> MyFilter<Self, P>
+    where
+        P: FnMut(&Self::Item) -> bool,
+    {
+        MyFilter {
+            iter: self,
+            predicate,
+        }
+    }
+}
+
+impl<I: Iterator> IteratorExt for I {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn my_map_matches_map() {
+        let v = [1, 2, 3];
+        let ours: Vec<i32> = v.iter().my_map(|x| x + 1).collect();
+        let std: Vec<i32> = v.iter().map(|x| x + 1).collect();
+        assert_eq!(ours, std);
+        assert_eq!(ours, vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn my_filter_matches_filter() {
+        let words = ["apple", "kiwi", "banana", "fig"];
+        let ours: Vec<&&str> = words.iter().my_filter(|w| w.len() > 3).collect();
+        let std: Vec<&&str> = words.iter().filter(|w| w.len() > 3).collect();
+        assert_eq!(ours, std);
+    }
+
+    #[test]
+    fn chained_like_std() {
+        let ours: Vec<u32> = (1..=20)
+            .my_filter(|n| n % 3 == 0)
+            .my_map(|n| n * n)
+            .my_filter(|n| n % 2 == 0)
+            .collect();
+        let std: Vec<u32> = (1..=20)
+            .filter(|n| n % 3 == 0)
+            .map(|n| n * n)
+            .filter(|n| n % 2 == 0)
+            .collect();
+        assert_eq!(ours, std);
+        assert_eq!(ours, vec![36, 144, 324]);
+    }
+
+    #[test]
+    fn mixes_with_std_adaptors() {
+        let total: i32 = (1..=5).my_map(|x| x * 10).take(3).sum();
+        assert_eq!(total, 60);
+        let last = (1..=5).map(|x| x - 1).my_filter(|x| x % 2 == 1).last();
+        assert_eq!(last, Some(3));
+    }
+
+    #[test]
+    fn empty_and_nothing_passes() {
+        assert_eq!(Vec::<i32>::new().into_iter().my_map(|x| x * 2).count(), 0);
+        assert_eq!((1..10).my_filter(|_| false).next(), None);
+    }
+
+    #[test]
+    fn adaptors_are_lazy() {
+        let calls = Cell::new(0);
+        let mapped = (1..=3).my_map(|x| {
+            calls.set(calls.get() + 1);
+            x
+        });
+        // building the adaptor calls nothing
+        assert_eq!(calls.get(), 0);
+        let first_two: Vec<i32> = mapped.take(2).collect();
+        assert_eq!(first_two, vec![1, 2]);
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn closures_can_mutate_their_state() {
+        // FnMut, so a closure can keep a running total between calls
+        let mut running = 0;
+        let totals: Vec<i32> = [1, 2, 3, 4]
+            .into_iter()
+            .my_map(|x| {
+                running += x;
+                running
+            })
+            .collect();
+        assert_eq!(totals, vec![1, 3, 6, 10]);
+    }
+}
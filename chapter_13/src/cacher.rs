@@ -0,0 +1,125 @@
+// 13.1 (continued) Storing Closures in a Struct: the Cacher
+
+/*
+Older editions of the book built a Cacher: a struct that holds an expensive
+closure and only calls it the first time its result is needed. The book's
+version stores a single Option<u32>:
+
+    struct Cacher<T>
+    where
+        T: Fn(u32) -> u32,
+    {
+        calculation: T,
+        value: Option<u32>,
+    }
+
+which has a known limitation the book points out itself: once value is Some,
+every call gets that same answer back, whatever argument it's called with.
+
+    let mut c = Cacher::new(|a| a);
+    let v1 = c.value(1);
+    let v2 = c.value(2); // still 1!
+
+This version fixes it by keeping a HashMap from argument to result, and it's
+generic over the argument and result types instead of only working for u32.
+*/
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+pub struct Cacher<F, A, R>
+where
+    F: Fn(A) -> R,
+{
+    calculation: F,
+    values: HashMap<A, R>,
+}
+
+impl<F, A, R> Cacher<F, A, R>
+where
+    F: Fn(A) -> R,
+    A: Eq + Hash + Clone,
+    R: Clone,
+{
+    pub fn new(calculation: F) -> Cacher<F, A, R> {
+        Cacher {
+            calculation,
+            values: HashMap::new(),
+        }
+    }
+
+    // the argument is cloned because it's used twice: once as the closure's
+    // input and once as the key in the map. The result is cloned so the cache
+    // keeps its own copy
+    pub fn value(&mut self, arg: A) -> R {
+        if let Some(v) = self.values.get(&arg) {
+            return v.clone();
+        }
+
+        let v = (self.calculation)(arg.clone());
+        self.values.insert(arg, v.clone());
+        v
+    }
+
+    // how many different arguments have been calculated so far
+    pub fn cached(&self) -> usize {
+        self.values.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn call_with_different_values() {
+        // the test from the book that fails with the single Option<u32> version
+        let mut c = Cacher::new(|a| a);
+
+        let _v1 = c.value(1);
+        let v2 = c.value(2);
+
+        assert_eq!(v2, 2);
+    }
+
+    #[test]
+    fn calculates_once_per_argument() {
+        let calls = Cell::new(0);
+        let mut c = Cacher::new(|num: u32| {
+            calls.set(calls.get() + 1);
+            num * 10
+        });
+
+        assert_eq!(c.value(1), 10);
+        assert_eq!(c.value(1), 10);
+        assert_eq!(c.value(2), 20);
+        assert_eq!(c.value(1), 10);
+        assert_eq!(c.value(2), 20);
+
+        assert_eq!(calls.get(), 2);
+        assert_eq!(c.cached(), 2);
+    }
+
+    #[test]
+    fn works_with_other_types() {
+        let mut lengths = Cacher::new(|s: &str| s.len());
+        assert_eq!(lengths.value("hello"), 5);
+        assert_eq!(lengths.value("hi"), 2);
+
+        let mut shout = Cacher::new(|s: String| s.to_uppercase());
+        assert_eq!(shout.value(String::from("hey")), "HEY");
+        assert_eq!(shout.cached(), 1);
+    }
+
+    #[test]
+    fn nothing_is_calculated_up_front() {
+        let calls = Cell::new(0);
+        let c = Cacher::new(|num: i32| {
+            calls.set(calls.get() + 1);
+            num
+        });
+        assert_eq!(c.cached(), 0);
+        assert_eq!(calls.get(), 0);
+    }
+}
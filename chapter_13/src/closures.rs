@@ -1,6 +1,6 @@
 // 13.1: Closures: Anonymous Functions that Capture Their Environment
 
-pub fn _run() {
+pub fn run() {
     /*
     Rust's closures are anonymous functions you can save in a variable or pass
     as arguments to other functions. You can create the closure in one place and
@@ -29,57 +29,65 @@ pub fn _run() {
     of shirt the person will get
     */
 
-    #[derive(Debug, PartialEq, Copy, Clone)]
-    enum ShirtColor {
-        Red,
-        Blue,
-    }
-
-    struct Inventory {
-        shirts: Vec<ShirtColor>,
-    }
-
-    impl Inventory {
-        fn giveaway(&self, user_preference: Option<ShirtColor>) -> ShirtColor {
-            user_preference.unwrap_or_else(|| self.most_stocked())
-        }
-        /*
-        unwrap_or_else takes a closure, the closure is `|| self.most_stocked()`. If there is Some(user_preference), unwrap_or_else returns
-        that value, otherwise it returns the closure computed value (the most stocked shirt color in this case). If the closure took an argument
-        it would go between the pipe characters.
-        */
-
-        fn most_stocked(&self) -> ShirtColor {
-            let mut num_red = 0;
-            let mut num_blue = 0;
-
-            for color in &self.shirts {
-                match color {
-                    ShirtColor::Red => num_red += 1,
-                    ShirtColor::Blue => num_blue += 1,
-                }
-            }
-            if num_red > num_blue {
-                ShirtColor::Red
-            } else {
-                ShirtColor::Blue
-            }
-        }
-    }
-
-    let store = Inventory {
-        shirts: vec![ShirtColor::Blue, ShirtColor::Red, ShirtColor::Blue],
-    };
+    // The book's version of the store, kept here for reference. The one that's
+    // actually used lives in inventory.rs, it knows more colors, takes shirts
+    // out of stock, and errors once they're gone:
+    // #[derive(Debug, PartialEq, Copy, Clone)]
+    // enum ShirtColor {
+    //     Red,
+    //     Blue,
+    // }
+
+    // struct Inventory {
+    //     shirts: Vec<ShirtColor>,
+    // }
+
+    // impl Inventory {
+    //     fn giveaway(&self, user_preference: Option<ShirtColor>) -> ShirtColor {
+    //         user_preference.unwrap_or_else(|| self.most_stocked())
+    //     }
+    //     /*
+    //     unwrap_or_else takes a closure, the closure is `|| self.most_stocked()`. If there is Some(user_preference), unwrap_or_else returns
+    //     that value, otherwise it returns the closure computed value (the most stocked shirt color in this case). If the closure took an argument
+    //     it would go between the pipe characters.
+    //     */
+
+    //     fn most_stocked(&self) -> ShirtColor {
+    //         let mut num_red = 0;
+    //         let mut num_blue = 0;
+
+    //         for color in &self.shirts {
+    //             match color {
+    //                 ShirtColor::Red => num_red += 1,
+    //                 ShirtColor::Blue => num_blue += 1,
+    //             }
+    //         }
+    //         if num_red > num_blue {
+    //             ShirtColor::Red
+    //         } else {
+    //             ShirtColor::Blue
+    //         }
+    //     }
+    // }
+
+    // let store = Inventory {
+    //     shirts: vec![ShirtColor::Blue, ShirtColor::Red, ShirtColor::Blue],
+    // };
+
+    use crate::inventory::{Inventory, ShirtColor};
+
+    let mut store =
+        Inventory::from_shirts(vec![ShirtColor::Blue, ShirtColor::Red, ShirtColor::Blue]);
 
     let user_pref1 = Some(ShirtColor::Red);
-    let giveaway1 = store.giveaway(user_pref1);
+    let giveaway1 = store.giveaway("winner 1", user_pref1).unwrap();
     println!(
         "The user with preference {:?} gets {:?}",
         user_pref1, giveaway1
     );
 
     let user_pref2 = None;
-    let giveaway2 = store.giveaway(user_pref2);
+    let giveaway2 = store.giveaway("winner 2", user_pref2).unwrap();
     println!(
         "The user with preference {:?} gets {:?}",
         user_pref2, giveaway2
@@ -111,6 +119,9 @@ pub fn _run() {
     let add_one_v4 = |x|               x + 1  ;  <-- remove optional brackets
     */
 
+    // cacher.rs stores an expensive closure like the one above in a struct, so
+    // it only runs once for each different argument
+
     // Capturing References of Moving Ownership
 
     /*
@@ -221,11 +232,8 @@ pub fn _run() {
     In the following example we sort a list of Rectangle instances by their width attribute; low -> high
     */
 
-    # [derive(Debug)]
-    struct Rectangle {
-        width: u32,
-        height: u32,
-    }
+    // (the Rectangle from Chapter 5, now shared through the domain crate)
+    use domain::prelude::*;
 
     let mut list = [
         Rectangle { width: 10, height: 1},
@@ -235,4 +243,7 @@ pub fn _run() {
 
     list.sort_by_key(|r| r.width); // this closure takes an FnMut trait function because it is called once for each item
     println!("{:#?}", list);
+
+    // sorting.rs keeps closures like this one in a HashMap, so the order can be
+    // picked by name while the program is running
 }
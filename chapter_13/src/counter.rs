@@ -0,0 +1,126 @@
+// 13.2 (continued) Creating Our Own Iterators with the Iterator Trait
+
+/*
+The book's Counter counts from 1 to 5. All we have to write is next(), and
+every other Iterator method (map, filter, zip, sum, ...) comes for free from
+their default implementations, because they're all built on next().
+*/
+
+pub struct Counter {
+    count: u32,
+}
+
+impl Counter {
+    pub fn new() -> Counter {
+        Counter { count: 0 }
+    }
+}
+
+impl Default for Counter {
+    fn default() -> Counter {
+        Counter::new()
+    }
+}
+
+impl Iterator for Counter {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.count < 5 {
+            self.count += 1;
+            Some(self.count)
+        } else {
+            None
+        }
+    }
+}
+
+/*
+Using Other Iterator Trait Methods
+Pair each value with the value after it (1,2), (2,3), (3,4), (4,5), multiply
+each pair, keep the products divisible by 3 and add them up. zip stops as soon
+as either side runs out, so the skipped Counter's 4 items decide the length:
+    1*2=2, 2*3=6, 3*4=12, 4*5=20  ->  6 + 12 = 18
+*/
+pub fn sum_of_multiplied_pairs() -> u32 {
+    Counter::new()
+        .zip(Counter::new().skip(1))
+        .map(|(a, b)| a * b)
+        .filter(|x| x % 3 == 0)
+        .sum()
+}
+
+// every other value: 1, 3, 5
+pub fn odd_counts() -> Vec<u32> {
+    Counter::new().step_by(2).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calling_next_directly() {
+        let mut counter = Counter::new();
+
+        assert_eq!(counter.next(), Some(1));
+        assert_eq!(counter.next(), Some(2));
+        assert_eq!(counter.next(), Some(3));
+        assert_eq!(counter.next(), Some(4));
+        assert_eq!(counter.next(), Some(5));
+        assert_eq!(counter.next(), None);
+        // once it's done it stays done
+        assert_eq!(counter.next(), None);
+    }
+
+    #[test]
+    fn using_other_iterator_trait_methods() {
+        assert_eq!(sum_of_multiplied_pairs(), 18);
+    }
+
+    #[test]
+    fn zip_pairs_stop_at_the_shorter_side() {
+        let pairs: Vec<(u32, u32)> = Counter::new().zip(Counter::new().skip(1)).collect();
+        assert_eq!(pairs, vec![(1, 2), (2, 3), (3, 4), (4, 5)]);
+    }
+
+    #[test]
+    fn step_by() {
+        assert_eq!(odd_counts(), vec![1, 3, 5]);
+        // step_by always yields the first item, then every nth after it
+        let every_third: Vec<u32> = Counter::new().step_by(3).collect();
+        assert_eq!(every_third, vec![1, 4]);
+    }
+
+    #[test]
+    fn more_adapters() {
+        let doubled: Vec<u32> = Counter::new().map(|x| x * 2).collect();
+        assert_eq!(doubled, vec![2, 4, 6, 8, 10]);
+
+        let small: Vec<u32> = Counter::new().take_while(|x| *x < 3).collect();
+        assert_eq!(small, vec![1, 2]);
+
+        let chained: Vec<u32> = Counter::new()
+            .take(2)
+            .chain(Counter::new().skip(3))
+            .collect();
+        assert_eq!(chained, vec![1, 2, 4, 5]);
+
+        let indexed: Vec<(usize, u32)> = Counter::new().enumerate().skip(3).collect();
+        assert_eq!(indexed, vec![(3, 4), (4, 5)]);
+    }
+
+    #[test]
+    fn consuming_adapters() {
+        assert_eq!(Counter::new().sum::<u32>(), 15);
+        assert_eq!(Counter::new().product::<u32>(), 120);
+        assert_eq!(Counter::new().count(), 5);
+        assert_eq!(Counter::new().max(), Some(5));
+        assert_eq!(
+            Counter::new().fold(String::new(), |s, x| s + &x.to_string()),
+            "12345"
+        );
+        assert!(Counter::new().any(|x| x == 4));
+        assert_eq!(Counter::new().position(|x| x == 4), Some(3));
+    }
+}
@@ -0,0 +1,164 @@
+// 13.1 (continued) Storing Closures: an Event Bus
+
+/*
+An event bus keeps a list of callbacks (subscribers) and calls each of them
+whenever an event is published. Which Fn trait the stored closures need
+depends on how they'll be called:
+
+  Fn      can be called many times, but can't change anything it captured.
+          A subscriber that counts clicks couldn't do `count += 1`.
+  FnMut   can be called many times and may change what it captured. That's
+          what regular subscribers are: called once per event, keeping
+          whatever state they like between calls.
+  FnOnce  can only be called once, because it might move a captured value out
+          (like sending a value it owns somewhere else). That's a perfect fit
+          for one-shot subscribers: the bus takes them out of the list when it
+          calls them, so calling twice can't even be written.
+
+Every closure has a different type, so they're stored as Box<dyn FnMut(&Event)>.
+The 'a lets subscribers borrow from their surroundings, as long as whatever
+they borrow outlives the bus.
+*/
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    Click { x: i32, y: i32 },
+    KeyPress(char),
+    Quit,
+}
+
+type Subscriber<'a> = Box<dyn FnMut(&Event) + 'a>;
+type OneShot<'a> = Box<dyn FnOnce(&Event) + 'a>;
+
+#[derive(Default)]
+pub struct EventBus<'a> {
+    subscribers: Vec<Subscriber<'a>>,
+    one_shots: Vec<OneShot<'a>>,
+}
+
+impl<'a> EventBus<'a> {
+    pub fn new() -> EventBus<'a> {
+        EventBus::default()
+    }
+
+    // called for every event from now on
+    pub fn subscribe(&mut self, subscriber: impl FnMut(&Event) + 'a) {
+        self.subscribers.push(Box::new(subscriber));
+    }
+
+    // called for the next event only, then dropped
+    pub fn subscribe_once(&mut self, subscriber: impl FnOnce(&Event) + 'a) {
+        self.one_shots.push(Box::new(subscriber));
+    }
+
+    // &mut self because calling an FnMut needs mutable access to it
+    pub fn publish(&mut self, event: &Event) {
+        for subscriber in self.subscribers.iter_mut() {
+            subscriber(event);
+        }
+        // mem::take swaps an empty Vec in and hands us the old one. Calling a
+        // Box<dyn FnOnce> moves it, so we need to own them to call them
+        for one_shot in std::mem::take(&mut self.one_shots) {
+            one_shot(event);
+        }
+    }
+
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.len() + self.one_shots.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+
+    #[test]
+    fn every_subscriber_sees_every_event() {
+        let mut clicks = 0;
+        let mut keys = String::new();
+        {
+            let mut bus = EventBus::new();
+            bus.subscribe(|event| {
+                if let Event::Click { .. } = event {
+                    clicks += 1;
+                }
+            });
+            bus.subscribe(|event| {
+                if let Event::KeyPress(c) = event {
+                    keys.push(*c);
+                }
+            });
+
+            bus.publish(&Event::Click { x: 1, y: 2 });
+            bus.publish(&Event::KeyPress('h'));
+            bus.publish(&Event::KeyPress('i'));
+            bus.publish(&Event::Click { x: 3, y: 4 });
+            // the bus is dropped here, ending its mutable borrows of
+            // clicks and keys so we can read them below
+        }
+        assert_eq!(clicks, 2);
+        assert_eq!(keys, "hi");
+    }
+
+    #[test]
+    fn one_shot_subscribers_run_once() {
+        let calls = Cell::new(0);
+        let mut bus = EventBus::new();
+        bus.subscribe_once(|_| calls.set(calls.get() + 1));
+        assert_eq!(bus.subscriber_count(), 1);
+
+        bus.publish(&Event::Quit);
+        bus.publish(&Event::Quit);
+        bus.publish(&Event::Quit);
+
+        assert_eq!(calls.get(), 1);
+        assert_eq!(bus.subscriber_count(), 0);
+    }
+
+    #[test]
+    fn one_shot_can_move_out_what_it_captured() {
+        // this closure gives away `report` (a String it owns) when it runs, so
+        // it's FnOnce only. subscribe() wouldn't accept it
+        let inbox = RefCell::new(Vec::new());
+        let report = String::from("first event seen");
+        let mut bus = EventBus::new();
+        bus.subscribe_once(|event| {
+            inbox.borrow_mut().push(report);
+            inbox.borrow_mut().push(format!("{:?}", event));
+        });
+
+        bus.publish(&Event::KeyPress('q'));
+        bus.publish(&Event::Quit);
+        assert_eq!(*inbox.borrow(), vec!["first event seen", "KeyPress('q')"]);
+    }
+
+    #[test]
+    fn counts_each_kind_of_subscriber() {
+        let regular = Cell::new(0);
+        let once = Cell::new(0);
+        let mut bus = EventBus::new();
+        bus.subscribe(|_| regular.set(regular.get() + 1));
+        bus.subscribe(|_| regular.set(regular.get() + 1));
+        bus.subscribe_once(|_| once.set(once.get() + 1));
+        bus.subscribe_once(|_| once.set(once.get() + 1));
+
+        for _ in 0..5 {
+            bus.publish(&Event::Click { x: 0, y: 0 });
+        }
+        // 2 regular subscribers x 5 events, the one-shots only the first time
+        assert_eq!(regular.get(), 10);
+        assert_eq!(once.get(), 2);
+        assert_eq!(bus.subscriber_count(), 2);
+    }
+
+    #[test]
+    fn subscriber_added_later_misses_earlier_events() {
+        let seen = RefCell::new(Vec::new());
+        let mut bus = EventBus::new();
+        bus.publish(&Event::KeyPress('a'));
+        bus.subscribe(|event| seen.borrow_mut().push(event.clone()));
+        bus.publish(&Event::KeyPress('b'));
+        assert_eq!(*seen.borrow(), vec![Event::KeyPress('b')]);
+    }
+}
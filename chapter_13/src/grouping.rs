@@ -0,0 +1,146 @@
+// 13.2 (continued) Chunking and Grouping
+
+/*
+Two more adaptors in the style of adaptors.rs, both added to every iterator by
+an extension trait:
+
+chunks_of(n) is a lazy adaptor like MyMap. It wraps the inner iterator and each
+call to next() pulls up to n items from it and hands them back together as a
+Vec. Slices already have .chunks(n), this one works on any iterator. When the
+item count doesn't divide evenly the last chunk is just shorter.
+
+group_by_key(f) is a consumer instead, like collect or sum. Every item has to be
+looked at before any group is complete, so it runs the whole iterator straight
+away and returns a HashMap from key to the items with that key (in the order
+they came in).
+*/
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+pub struct ChunksOfIter<I> {
+    iter: I,
+    size: usize,
+}
+
+impl<I: Iterator> Iterator for ChunksOfIter<I> {
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Vec<I::Item>> {
+        // by_ref so take() borrows the inner iterator instead of consuming it
+        let chunk: Vec<I::Item> = self.iter.by_ref().take(self.size).collect();
+        if chunk.is_empty() {
+            None
+        } else {
+            Some(chunk)
+        }
+    }
+}
+
+pub trait GroupingExt: Iterator + Sized {
+    // panics on a size of 0, the same as slice::chunks, since a chunk of
+    // nothing would never make progress
+    fn chunks_of(self, size: usize) -> ChunksOfIter<Self> {
+        assert!(size > 0, "chunk size must be greater than 0");
+        ChunksOfIter { iter: self, size }
+    }
+
+    fn group_by_key<K, F>(self, mut key: F) -> HashMap<K, Vec<Self::Item>>
+    where
+        K: Eq + Hash,
+        F: FnMut(&Self::Item) -> K,
+    {
+        let mut groups: HashMap<K, Vec<Self::Item>> = HashMap::new();
+        for item in self {
+            groups.entry(key(&item)).or_default().push(item);
+        }
+        groups
+    }
+}
+
+impl<I: Iterator> GroupingExt for I {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn even_chunks() {
+        let chunks: Vec<Vec<i32>> = (1..=6).chunks_of(2).collect();
+        assert_eq!(chunks, vec![vec![1, 2], vec![3, 4], vec![5, 6]]);
+    }
+
+    #[test]
+    fn uneven_final_chunk() {
+        let chunks: Vec<Vec<i32>> = (1..=7).chunks_of(3).collect();
+        assert_eq!(chunks, vec![vec![1, 2, 3], vec![4, 5, 6], vec![7]]);
+
+        // bigger than the whole input: one short chunk
+        let chunks: Vec<Vec<char>> = "ab".chars().chunks_of(5).collect();
+        assert_eq!(chunks, vec![vec!['a', 'b']]);
+    }
+
+    #[test]
+    fn matches_slice_chunks() {
+        let v: Vec<u32> = (0..23).collect();
+        let ours: Vec<Vec<u32>> = v.iter().copied().chunks_of(4).collect();
+        let std: Vec<Vec<u32>> = v.chunks(4).map(|c| c.to_vec()).collect();
+        assert_eq!(ours, std);
+    }
+
+    #[test]
+    fn empty_input_no_chunks() {
+        assert_eq!(Vec::<i32>::new().into_iter().chunks_of(3).next(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "greater than 0")]
+    fn zero_size_chunks() {
+        (1..3).chunks_of(0);
+    }
+
+    #[test]
+    fn chunks_are_lazy() {
+        let pulled = Cell::new(0);
+        let mut chunks = (1..=10)
+            .inspect(|_| pulled.set(pulled.get() + 1))
+            .chunks_of(3);
+        assert_eq!(pulled.get(), 0);
+        assert_eq!(chunks.next(), Some(vec![1, 2, 3]));
+        // only the first chunk's worth has been read
+        assert_eq!(pulled.get(), 3);
+    }
+
+    #[test]
+    fn group_words_by_length() {
+        let words = ["fig", "kiwi", "apple", "pear", "yam", "lemon"];
+        let groups = words.into_iter().group_by_key(|w| w.len());
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups[&3], vec!["fig", "yam"]);
+        assert_eq!(groups[&4], vec!["kiwi", "pear"]);
+        assert_eq!(groups[&5], vec!["apple", "lemon"]);
+    }
+
+    #[test]
+    fn group_keeps_input_order() {
+        let groups = (1..=10).group_by_key(|n| n % 3);
+        assert_eq!(groups[&0], vec![3, 6, 9]);
+        assert_eq!(groups[&1], vec![1, 4, 7, 10]);
+        assert_eq!(groups[&2], vec![2, 5, 8]);
+    }
+
+    #[test]
+    fn group_empty() {
+        let groups = Vec::<i32>::new().into_iter().group_by_key(|n| *n);
+        assert!(groups.is_empty());
+    }
+
+    #[test]
+    fn chunk_then_group() {
+        // the two compose: group each batch by how many items it holds
+        let groups = (1..=7).chunks_of(3).group_by_key(|chunk| chunk.len());
+        assert_eq!(groups[&3], vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        assert_eq!(groups[&1], vec![vec![7]]);
+    }
+}
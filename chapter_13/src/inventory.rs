@@ -0,0 +1,331 @@
+// 13.1 (continued) The T-Shirt Inventory
+
+/*
+closures.rs introduces closures with a t-shirt giveaway: the winner gets their
+favorite color, or whatever color there's the most of. That version only
+knows about Red and Blue and never actually hands a shirt out, so the same
+store could give away its last shirt forever.
+
+This one keeps a count per color, removes a shirt for every giveaway, can be
+restocked, and remembers who won what. The closures are still the heart of
+giveaway(): `filter` only keeps the preference if it's in stock, and
+`or_else` only works out the most stocked color if it has to.
+
+raffle() picks the winner for us: one entrant is drawn with an Rng from the
+domain crate, so a seeded one always draws the same person.
+*/
+
+use domain::Rng;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum ShirtColor {
+    Red,
+    Blue,
+    Green,
+    Black,
+    White,
+}
+
+impl ShirtColor {
+    // in tie breaking order: when two colors have the same stock the one that
+    // comes first wins (Blue before Red, like the original version)
+    pub const ALL: [ShirtColor; 5] = [
+        ShirtColor::Blue,
+        ShirtColor::Red,
+        ShirtColor::Green,
+        ShirtColor::Black,
+        ShirtColor::White,
+    ];
+}
+
+#[derive(Debug, PartialEq)]
+pub enum GiveawayError {
+    OutOfStock,
+    NoEntrants,
+}
+
+impl fmt::Display for GiveawayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GiveawayError::OutOfStock => write!(f, "there are no shirts left to give away"),
+            GiveawayError::NoEntrants => write!(f, "nobody entered the raffle"),
+        }
+    }
+}
+
+impl Error for GiveawayError {}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Winner {
+    pub name: String,
+    pub preference: Option<ShirtColor>,
+    pub color: ShirtColor,
+}
+
+#[derive(Debug, Default)]
+pub struct Inventory {
+    stock: HashMap<ShirtColor, u32>,
+    history: Vec<Winner>,
+}
+
+impl Inventory {
+    pub fn new() -> Inventory {
+        Inventory::default()
+    }
+
+    // one shirt per item, the same shape as the original `shirts: Vec<ShirtColor>`
+    pub fn from_shirts(shirts: Vec<ShirtColor>) -> Inventory {
+        let mut inventory = Inventory::new();
+        for color in shirts {
+            inventory.restock(color, 1);
+        }
+        inventory
+    }
+
+    pub fn restock(&mut self, color: ShirtColor, count: u32) {
+        *self.stock.entry(color).or_insert(0) += count;
+    }
+
+    pub fn stock(&self, color: ShirtColor) -> u32 {
+        self.stock.get(&color).copied().unwrap_or(0)
+    }
+
+    pub fn total(&self) -> u32 {
+        self.stock.values().sum()
+    }
+
+    // None when there's nothing left at all
+    pub fn most_stocked(&self) -> Option<ShirtColor> {
+        let mut most: Option<(ShirtColor, u32)> = None;
+        for color in ShirtColor::ALL {
+            let count = self.stock(color);
+            // strictly greater, so earlier colors win ties
+            if count > 0 && most.is_none_or(|(_, best)| count > best) {
+                most = Some((color, count));
+            }
+        }
+        most.map(|(color, _)| color)
+    }
+
+    pub fn giveaway(
+        &mut self,
+        name: &str,
+        preference: Option<ShirtColor>,
+    ) -> Result<ShirtColor, GiveawayError> {
+        let color = preference
+            .filter(|color| self.stock(*color) > 0)
+            .or_else(|| self.most_stocked())
+            .ok_or(GiveawayError::OutOfStock)?;
+
+        // only colors with stock > 0 get picked above, so this can't underflow
+        if let Some(count) = self.stock.get_mut(&color) {
+            *count -= 1;
+        }
+        self.history.push(Winner {
+            name: String::from(name),
+            preference,
+            color,
+        });
+        Ok(color)
+    }
+
+    // draws one of `entrants` (name and preference) and gives them a shirt
+    pub fn raffle(
+        &mut self,
+        entrants: &[(&str, Option<ShirtColor>)],
+        rng: &mut dyn Rng,
+    ) -> Result<Winner, GiveawayError> {
+        if entrants.is_empty() {
+            return Err(GiveawayError::NoEntrants);
+        }
+        let (name, preference) = entrants[rng.below(entrants.len() as u64) as usize];
+        let color = self.giveaway(name, preference)?;
+        Ok(Winner {
+            name: String::from(name),
+            preference,
+            color,
+        })
+    }
+
+    // every winner so far, oldest first
+    pub fn history(&self) -> &[Winner] {
+        &self.history
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use domain::XorShift;
+
+    #[test]
+    fn original_behavior() {
+        let mut store =
+            Inventory::from_shirts(vec![ShirtColor::Blue, ShirtColor::Red, ShirtColor::Blue]);
+        assert_eq!(
+            store.giveaway("a", Some(ShirtColor::Red)),
+            Ok(ShirtColor::Red)
+        );
+        assert_eq!(store.giveaway("b", None), Ok(ShirtColor::Blue));
+    }
+
+    #[test]
+    fn giveaway_takes_a_shirt_out_of_stock() {
+        let mut store = Inventory::new();
+        store.restock(ShirtColor::Green, 2);
+        store.giveaway("a", Some(ShirtColor::Green)).unwrap();
+        assert_eq!(store.stock(ShirtColor::Green), 1);
+        assert_eq!(store.total(), 1);
+    }
+
+    #[test]
+    fn preference_out_of_stock_falls_back() {
+        let mut store = Inventory::new();
+        store.restock(ShirtColor::Black, 1);
+        store.restock(ShirtColor::White, 3);
+        assert_eq!(
+            store.giveaway("a", Some(ShirtColor::Red)),
+            Ok(ShirtColor::White)
+        );
+        // Black is still there, so a preference for it is honored
+        assert_eq!(
+            store.giveaway("b", Some(ShirtColor::Black)),
+            Ok(ShirtColor::Black)
+        );
+        assert_eq!(
+            store.giveaway("c", Some(ShirtColor::Black)),
+            Ok(ShirtColor::White)
+        );
+    }
+
+    #[test]
+    fn empty_store_is_an_error() {
+        let mut store = Inventory::from_shirts(vec![ShirtColor::Red]);
+        assert!(store.giveaway("a", None).is_ok());
+        let err = store.giveaway("b", Some(ShirtColor::Red)).unwrap_err();
+        assert_eq!(err, GiveawayError::OutOfStock);
+        assert_eq!(err.to_string(), "there are no shirts left to give away");
+        // a failed giveaway doesn't get a history entry
+        assert_eq!(store.history().len(), 1);
+    }
+
+    #[test]
+    fn restock_after_running_out() {
+        let mut store = Inventory::new();
+        assert_eq!(store.giveaway("a", None), Err(GiveawayError::OutOfStock));
+        store.restock(ShirtColor::Red, 1);
+        store.restock(ShirtColor::Red, 1);
+        assert_eq!(store.stock(ShirtColor::Red), 2);
+        assert_eq!(store.giveaway("a", None), Ok(ShirtColor::Red));
+    }
+
+    #[test]
+    fn ties_go_to_the_earlier_color() {
+        let mut store = Inventory::new();
+        store.restock(ShirtColor::White, 2);
+        store.restock(ShirtColor::Red, 2);
+        assert_eq!(store.most_stocked(), Some(ShirtColor::Red));
+        store.restock(ShirtColor::Blue, 2);
+        assert_eq!(store.most_stocked(), Some(ShirtColor::Blue));
+    }
+
+    #[test]
+    fn history_records_winners_in_order() {
+        let mut store = Inventory::from_shirts(vec![ShirtColor::Red, ShirtColor::Blue]);
+        store.giveaway("ann", Some(ShirtColor::Red)).unwrap();
+        store.giveaway("bob", None).unwrap();
+        assert_eq!(
+            store.history(),
+            &[
+                Winner {
+                    name: String::from("ann"),
+                    preference: Some(ShirtColor::Red),
+                    color: ShirtColor::Red,
+                },
+                Winner {
+                    name: String::from("bob"),
+                    preference: None,
+                    color: ShirtColor::Blue,
+                },
+            ]
+        );
+    }
+
+    const ENTRANTS: [(&str, Option<ShirtColor>); 3] = [
+        ("ann", Some(ShirtColor::Red)),
+        ("bob", None),
+        ("cat", Some(ShirtColor::Green)),
+    ];
+
+    #[test]
+    fn raffle_with_the_same_seed_draws_the_same_winner() {
+        let draw = |seed| {
+            let mut store = Inventory::from_shirts(vec![ShirtColor::Red, ShirtColor::Blue]);
+            store.raffle(&ENTRANTS, &mut XorShift::new(seed)).unwrap()
+        };
+        assert_eq!(draw(42), draw(42));
+
+        // over enough draws everyone wins at least once
+        let mut store = Inventory::new();
+        store.restock(ShirtColor::Red, 100);
+        let mut rng = XorShift::new(1);
+        let winners: Vec<String> = (0..100)
+            .map(|_| store.raffle(&ENTRANTS, &mut rng).unwrap().name)
+            .collect();
+        for (name, _) in ENTRANTS {
+            assert!(
+                winners.iter().any(|winner| winner == name),
+                "{} never won",
+                name
+            );
+        }
+        assert_eq!(store.history().len(), 100);
+    }
+
+    #[test]
+    fn raffle_needs_entrants_and_shirts() {
+        let mut rng = XorShift::new(1);
+        let mut store = Inventory::from_shirts(vec![ShirtColor::Red]);
+        assert_eq!(store.raffle(&[], &mut rng), Err(GiveawayError::NoEntrants));
+        assert!(store.raffle(&ENTRANTS, &mut rng).is_ok());
+        assert_eq!(
+            store.raffle(&ENTRANTS, &mut rng),
+            Err(GiveawayError::OutOfStock)
+        );
+        assert_eq!(
+            GiveawayError::NoEntrants.to_string(),
+            "nobody entered the raffle"
+        );
+    }
+
+    #[test]
+    fn winners_grouped_by_color() {
+        use std::collections::{HashMap, HashSet};
+
+        let mut store = Inventory::new();
+        store.restock(ShirtColor::Red, 2);
+        store.restock(ShirtColor::Blue, 1);
+        store.giveaway("ann", Some(ShirtColor::Red)).unwrap();
+        store.giveaway("bob", Some(ShirtColor::Blue)).unwrap();
+        store.giveaway("cat", None).unwrap();
+
+        // ShirtColor as the key, Winners in the values
+        let mut by_color: HashMap<ShirtColor, Vec<&str>> = HashMap::new();
+        for winner in store.history() {
+            by_color
+                .entry(winner.color)
+                .or_default()
+                .push(winner.name.as_str());
+        }
+        assert_eq!(by_color[&ShirtColor::Red], vec!["ann", "cat"]);
+        assert_eq!(by_color[&ShirtColor::Blue], vec!["bob"]);
+
+        // a Winner is Hash too, so the same win recorded twice is one entry
+        let mut wins: HashSet<Winner> = store.history().iter().cloned().collect();
+        wins.insert(store.history()[0].clone());
+        assert_eq!(wins.len(), 3);
+    }
+}
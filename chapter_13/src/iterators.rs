@@ -1,5 +1,7 @@
 // 13.2 Processing a Series of Items with Iterators
 
+use crate::shoes::{Shoe, ShoeQuery};
+
 pub fn run() {
     /*
     The iterator pattern allows us to perform some task on a sequence of items in turn.
@@ -41,7 +43,7 @@ pub fn run() {
     the Iterator trait means you also need to define an Item type, which must be used
     in the return of the next method. The Item type is what gets returned from the iterator.
 
-    We can even call the next method directly (see the test block below)
+    We can even call the next method directly (see next_until_none below)
 
     There are a couple flavors of iterators:
     1. iter -> values we get are immutable references
@@ -59,8 +61,9 @@ pub fn run() {
     Methods that call next() are called consuming adaptors, because calling them uses
     up the iterator. One example is the sum method, which takes ownership of the iterator
     and iterates through the items, repeatedly calling next, using up the iterator.
-    (example in test block below)
+    (example in sum_of below)
     */
+    println!("{:?} sums to {}", v1, sum_of(&v1));
 
     // Methods that Produce Other Iterators
 
@@ -70,12 +73,11 @@ pub fn run() {
     An example of this is the map() method
     */
 
-    let v3 = vec![1, 2, 3];
     // v1.iter().map(|x| x + 1); this results in an error, we aren't doing anything with the new iterator
     // we can fix the error above by collecting the iterator into a new variable
-    let v4: Vec<_> = v3.iter().map(|x| x + 1).collect();
-
-    assert_eq!(v4, vec![2, 3, 4]);
+    // (see add_one_to_each below)
+    let v3 = [1, 2, 3];
+    println!("{:?} plus one is {:?}", v3, add_one_to_each(&v3));
 
     // Using Closures that Capture Their Environment
     /*
@@ -91,69 +93,104 @@ pub fn run() {
     of the specified size.
     */
 
-    #[derive(Debug, PartialEq)]
-    pub struct Shoe {
-        size: u32,
-        style: String,
-    }
-
-    pub fn shoes_in_size(shoes: Vec<Shoe>, shoe_size: u32) -> Vec<Shoe> {
-        shoes.into_iter().filter(|s| s.size == shoe_size).collect()
-    }
+    // the book's shoes_in_size filtered the Vec directly:
+    //     shoes.into_iter().filter(|s| s.size == shoe_size).collect()
+    // the one below hands the same closure to the ShoeQuery in shoes.rs, which
+    // takes any closures we give it
 
     let shoes = vec![
-        Shoe {
-            size: 10,
-            style: String::from("sneaker"),
-        },
-        Shoe {
-            size: 13,
-            style: String::from("sandal"),
-        },
-        Shoe {
-            size: 10,
-            style: String::from("boot"),
-        },
+        Shoe::new(10, "sneaker"),
+        Shoe::new(13, "sandal"),
+        Shoe::new(10, "boot"),
     ];
+    println!("in size 10: {:?}", shoes_in_size(shoes, 10));
 
-    let in_my_size = shoes_in_size(shoes, 10);
-
-    assert_eq!(
-        in_my_size,
-        vec![
-            Shoe {
-                size: 10,
-                style: String::from("sneaker")
-            },
-            Shoe {
-                size: 10,
-                style: String::from("boot")
-            },
-        ]
-    );
+    // and calling next() ourselves, see next_until_none below
+    println!("next() gave {:?}", next_until_none(&[1, 2, 3]));
 }
-// Calling next() ourselves
-#[test]
-fn iterator_demonstration() {
-    let v1 = vec![1, 2, 3];
 
-    let mut v1_iter = v1.iter();
-    // We make this mut, because calling next() changes the internal state of the iterator.
-    // We don't do this explicitly with a for loop, but the loop does it behind the scenes
+/*
+The demonstrations as functions that hand back what they made, so the checks
+can live in #[test]s below and a mistake shows up in `cargo test` instead of
+as a panic halfway through run().
+*/
 
-    assert_eq!(v1_iter.next(), Some(&1));
-    assert_eq!(v1_iter.next(), Some(&2));
-    assert_eq!(v1_iter.next(), Some(&3));
-    assert_eq!(v1_iter.next(), None);
+// the map pipeline: a new Vec with every number one bigger
+pub fn add_one_to_each(numbers: &[i32]) -> Vec<i32> {
+    numbers.iter().map(|x| x + 1).collect()
 }
 
-// Sum consumes the iterator
-#[test]
-fn iterator_sum() {
-    let v1 = vec![1, 2, 3];
-    let v1_iter = v1.iter();
+// the closure captures shoe_size from the function's arguments
+pub fn shoes_in_size(shoes: Vec<Shoe>, shoe_size: u32) -> Vec<Shoe> {
+    ShoeQuery::new()
+        .filter(|s| s.size == shoe_size)
+        .run(shoes)
+        .collect()
+}
+
+// Calling next() ourselves: everything next() returned, up to and including
+// the first None. The iterator has to be mut, because calling next() changes
+// the internal state of the iterator. A for loop does it behind the scenes
+pub fn next_until_none<T>(items: &[T]) -> Vec<Option<&T>> {
+    let mut iter = items.iter();
+    let mut calls = Vec::new();
+    loop {
+        let item = iter.next();
+        calls.push(item);
+        if item.is_none() {
+            return calls;
+        }
+    }
+}
 
-    let total: i32 = v1_iter.sum();
+// Sum consumes the iterator, it takes ownership and calls next() until None
+pub fn sum_of(numbers: &[i32]) -> i32 {
+    let iter = numbers.iter();
+    iter.sum()
+}
+
+// Creating Our Own Iterators with the Iterator Trait: see counter.rs
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    assert_eq!(total, 6);
+    #[test]
+    fn map_pipeline() {
+        assert_eq!(add_one_to_each(&[1, 2, 3]), vec![2, 3, 4]);
+        assert!(add_one_to_each(&[]).is_empty());
+    }
+
+    #[test]
+    fn filter_shoes_by_size() {
+        let shoes = vec![
+            Shoe::new(10, "sneaker"),
+            Shoe::new(13, "sandal"),
+            Shoe::new(10, "boot"),
+        ];
+        assert_eq!(
+            shoes_in_size(shoes, 10),
+            vec![Shoe::new(10, "sneaker"), Shoe::new(10, "boot")]
+        );
+        assert!(shoes_in_size(vec![Shoe::new(9, "boot")], 10).is_empty());
+    }
+
+    #[test]
+    fn iterator_demonstration() {
+        assert_eq!(
+            next_until_none(&[1, 2, 3]),
+            vec![Some(&1), Some(&2), Some(&3), None]
+        );
+        assert_eq!(next_until_none::<i32>(&[]), vec![None]);
+    }
+
+    #[test]
+    fn iterator_sum() {
+        assert_eq!(sum_of(&[1, 2, 3]), 6);
+        assert_eq!(sum_of(&[]), 0);
+    }
 }
+
+// 13.4 Comparing Performance: Loops vs. Iterators
+// see performance.rs, it has the same pipeline written both ways and an
+// #[ignore]d benchmark that times them
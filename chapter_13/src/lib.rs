@@ -0,0 +1,44 @@
+// Chapter 13: Functional Language Features: Iterators and Closures (library)
+// Closures stored and called later (a cacher, undo/redo, event handlers, a
+// transform pipeline) and iterators built up from adaptors (grouping, word
+// counts, a custom Counter), with the loop vs iterator timing comparison
+// The book's closure and iterator walkthroughs are here as well, exercises()
+// lists them for the runner
+
+pub mod adaptors;
+pub mod cacher;
+pub mod closures;
+pub mod counter;
+pub mod events;
+pub mod grouping;
+pub mod inventory;
+pub mod iterators;
+pub mod performance;
+pub mod pipeline;
+pub mod search;
+pub mod shoes;
+pub mod sorting;
+pub mod undo;
+pub mod word_counts;
+
+use domain::Section;
+
+// every walkthrough, in the book's order, for the runner
+pub fn exercises() -> Vec<Section> {
+    vec![
+        Section {
+            name: "closures",
+            run: || {
+                closures::run();
+                Ok(())
+            },
+        },
+        Section {
+            name: "iterators",
+            run: || {
+                iterators::run();
+                Ok(())
+            },
+        },
+    ]
+}
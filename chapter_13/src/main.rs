@@ -1,9 +1,6 @@
 // Chapter 13: Functional Language Features: Iterators and Closures
 
-mod closures;
-mod iterators;
-
 fn main() {
-    // closures::run();
-    iterators::run();
+    // chapter_13::closures::run();
+    chapter_13::iterators::run();
 }
@@ -0,0 +1,129 @@
+// 13.4 Comparing Performance: Loops vs. Iterators
+
+/*
+The book says iterators are one of Rust's zero-cost abstractions: a chain of
+filter/map/fold compiles down to roughly the same machine code as the loop we'd
+write by hand, so picking the more readable version costs nothing at runtime.
+
+Here's the same pipeline written both ways, over a list of sensor readings:
+  1. filter: drop readings that are out of range (the sensor glitched)
+  2. map:    convert each reading from tenths of a degree to a squared
+             deviation from the target temperature
+  3. fold:   collect the count, total, and largest deviation in one pass
+
+The tests check that both give identical answers. The #[ignore]d benchmark
+at the bottom times them over a million readings, run it with
+    cargo test --release -- --ignored --nocapture
+*/
+
+pub const MIN_READING: i32 = -400; // -40.0 degrees
+pub const MAX_READING: i32 = 1250; // 125.0 degrees
+pub const TARGET: i32 = 215; // 21.5 degrees
+
+#[derive(Debug, PartialEq, Default, Clone, Copy)]
+pub struct Deviation {
+    pub count: u64,
+    pub total: u64,
+    pub largest: u64,
+}
+
+fn in_range(reading: i32) -> bool {
+    (MIN_READING..=MAX_READING).contains(&reading)
+}
+
+fn squared_deviation(reading: i32) -> u64 {
+    let diff = reading.abs_diff(TARGET) as u64;
+    diff * diff
+}
+
+// The loop indexes on purpose, that's the bounds checked style the book
+// compares iterators against
+#[allow(clippy::needless_range_loop)]
+pub fn deviation_loop(readings: &[i32]) -> Deviation {
+    let mut result = Deviation::default();
+    for i in 0..readings.len() {
+        let reading = readings[i];
+        if !in_range(reading) {
+            continue;
+        }
+        let deviation = squared_deviation(reading);
+        result.count += 1;
+        result.total += deviation;
+        if deviation > result.largest {
+            result.largest = deviation;
+        }
+    }
+    result
+}
+
+pub fn deviation_iter(readings: &[i32]) -> Deviation {
+    readings
+        .iter()
+        .copied()
+        .filter(|reading| in_range(*reading))
+        .map(squared_deviation)
+        .fold(Deviation::default(), |acc, deviation| Deviation {
+            count: acc.count + 1,
+            total: acc.total + deviation,
+            largest: acc.largest.max(deviation),
+        })
+}
+
+// a repeatable spread of readings, a few of them out of range
+pub fn sample_readings(n: usize) -> Vec<i32> {
+    (0..n)
+        .map(|i| ((i as i64 * 7919) % 2000) as i32 - 500)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chapter_11::bench::time_it;
+
+    #[test]
+    fn both_versions_agree() {
+        for n in [0, 1, 10, 1000, 50_000] {
+            let readings = sample_readings(n);
+            assert_eq!(
+                deviation_loop(&readings),
+                deviation_iter(&readings),
+                "n = {}",
+                n
+            );
+        }
+    }
+
+    #[test]
+    fn small_example_by_hand() {
+        // 21.5 is right on target, -50.0 and 200.0 are out of range
+        let readings = [215, 225, -500, 195, 2000];
+        let expected = Deviation {
+            count: 3,
+            // 0 for the on-target reading, then 10 squared and 20 squared
+            total: 100 + 400,
+            largest: 400,
+        };
+        assert_eq!(deviation_loop(&readings), expected);
+        assert_eq!(deviation_iter(&readings), expected);
+    }
+
+    #[test]
+    fn range_edges_are_kept() {
+        let readings = [MIN_READING, MAX_READING, MIN_READING - 1, MAX_READING + 1];
+        assert_eq!(deviation_iter(&readings).count, 2);
+        assert_eq!(deviation_loop(&readings).count, 2);
+    }
+
+    #[test]
+    #[ignore]
+    fn bench_iterator_vs_loop() {
+        let readings = sample_readings(1_000_000);
+
+        let looped = time_it("loop", 100, || deviation_loop(&readings));
+        let iterated = time_it("iterators", 100, || deviation_iter(&readings));
+        println!("iterators / loop: {:.2}x", iterated.ratio_to(&looped));
+
+        assert_eq!(deviation_loop(&readings), deviation_iter(&readings));
+    }
+}
@@ -0,0 +1,164 @@
+// 13.1 (continued) Building Functions Out of Closures: a Pipeline
+
+/*
+A pipeline is a list of steps, each one taking a value and handing back a new
+one for the next step. The steps are closures, and like sorting.rs's
+comparators every closure has its own type, so they're stored as
+Box<dyn Fn(T) -> T>. Fn because apply can run the pipeline any number of
+times, and each run calls every stage again.
+
+The stages are 'static: they can own what they capture (with `move`) but not
+borrow from the code that built the pipeline, so a Pipeline can be built in
+one function and used long after it returns.
+
+then and when take self and hand it back, so a pipeline is built in one go:
+
+    let shout = Pipeline::new()
+        .then(|s: String| s.trim().to_string())
+        .when(|s| !s.ends_with('!'), |s| s + "!")
+        .then(|s| s.to_uppercase());
+    shout.apply(String::from(" hi "))   // "HI!"
+
+when is a branch: its stage only runs if the predicate says yes for the value
+at that point, otherwise the value goes through unchanged. It doesn't need any
+support from apply, it's just a then with an if inside the closure.
+*/
+
+type Stage<T> = Box<dyn Fn(T) -> T>;
+
+pub struct Pipeline<T> {
+    stages: Vec<Stage<T>>,
+}
+
+// written out instead of derived: #[derive(Default)] would only implement it
+// when T: Default, which a Pipeline doesn't need
+impl<T> Default for Pipeline<T> {
+    fn default() -> Self {
+        Pipeline { stages: Vec::new() }
+    }
+}
+
+impl<T: 'static> Pipeline<T> {
+    pub fn new() -> Pipeline<T> {
+        Pipeline::default()
+    }
+
+    pub fn then(mut self, stage: impl Fn(T) -> T + 'static) -> Pipeline<T> {
+        self.stages.push(Box::new(stage));
+        self
+    }
+
+    pub fn when(
+        self,
+        predicate: impl Fn(&T) -> bool + 'static,
+        stage: impl Fn(T) -> T + 'static,
+    ) -> Pipeline<T> {
+        self.then(move |value| {
+            if predicate(&value) {
+                stage(value)
+            } else {
+                value
+            }
+        })
+    }
+
+    // runs every stage in order, each one getting the last one's output
+    pub fn apply(&self, input: T) -> T {
+        self.stages.iter().fold(input, |value, stage| stage(value))
+    }
+
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the cleanup a comment box might do before saving what was typed in
+    fn cleanup(max_len: usize) -> Pipeline<String> {
+        Pipeline::new()
+            .then(|s: String| s.trim().to_string())
+            // runs of spaces, tabs and newlines become one space
+            .then(|s| s.split_whitespace().collect::<Vec<_>>().join(" "))
+            .then(|s| s.chars().filter(|c| !c.is_control()).collect())
+            .when(
+                move |s| s.chars().count() > max_len,
+                move |s| s.chars().take(max_len).collect::<String>() + "...",
+            )
+            .when(|s| s.is_empty(), |_| String::from("(no comment)"))
+    }
+
+    #[test]
+    fn cleans_up_text() {
+        let clean = cleanup(20);
+        assert_eq!(clean.len(), 5);
+        assert_eq!(
+            clean.apply(String::from("  hello \t\n  world  ")),
+            "hello world"
+        );
+        assert_eq!(
+            clean.apply(String::from("this one is far too long to keep")),
+            "this one is far too ..."
+        );
+        assert_eq!(clean.apply(String::from(" \n\t ")), "(no comment)");
+        // counted in chars, so it never cuts a multi-byte char in half
+        assert_eq!(cleanup(3).apply(String::from("Здравствуйте")), "Здр...");
+    }
+
+    #[test]
+    fn stages_run_in_order() {
+        let add_then_double = Pipeline::new().then(|n: i32| n + 1).then(|n| n * 2);
+        let double_then_add = Pipeline::new().then(|n: i32| n * 2).then(|n| n + 1);
+        assert_eq!(add_then_double.apply(5), 12);
+        assert_eq!(double_then_add.apply(5), 11);
+    }
+
+    #[test]
+    fn when_only_runs_if_the_predicate_holds() {
+        let collatz = Pipeline::new()
+            .when(|n: &u64| n.is_multiple_of(2), |n| n / 2)
+            // the predicate sees the value as it is *now*, after the stage
+            // above: 6 halves to 3, which is odd, so this runs too
+            .when(|n| !n.is_multiple_of(2), |n| 3 * n + 1);
+        assert_eq!(collatz.apply(6), 10);
+        assert_eq!(collatz.apply(7), 22);
+        assert_eq!(collatz.apply(8), 4);
+    }
+
+    #[test]
+    fn an_empty_pipeline_changes_nothing() {
+        let nothing: Pipeline<String> = Pipeline::new();
+        assert!(nothing.is_empty());
+        assert_eq!(nothing.apply(String::from("as is")), "as is");
+    }
+
+    #[test]
+    fn pipelines_can_be_stages() {
+        // a pipeline is just a way of calling its stages, so a whole one can be
+        // moved into a closure and used as a single stage (or branch) of another
+        let shout = Pipeline::new()
+            .when(|s: &String| !s.ends_with('!'), |s| s + "!")
+            .then(|s| s.to_uppercase());
+        let reply = cleanup(50).when(|s| s.starts_with("hey"), move |s| shout.apply(s));
+        assert_eq!(reply.apply(String::from("  hey   you ")), "HEY YOU!");
+        assert_eq!(reply.apply(String::from("hello   you")), "hello you");
+    }
+
+    #[test]
+    fn can_be_built_in_one_place_and_used_in_another() {
+        // the stages own what they captured, so the pipeline can outlive the
+        // function that built it
+        fn make(prefix: String) -> Pipeline<String> {
+            Pipeline::new().then(move |s| format!("{}{}", prefix, s))
+        }
+        let tagged = make(String::from("[note] "));
+        assert_eq!(tagged.apply(String::from("hi")), "[note] hi");
+        assert_eq!(tagged.apply(String::from("again")), "[note] again");
+    }
+}
@@ -0,0 +1,27 @@
+// 13.3 (continued) minigrep's search Functions, Built on Iterators
+
+/*
+Chapter 12's minigrep project (chapter_12/) searches a file's contents for
+lines containing a query. The book first writes search with a for loop and
+a mutable Vec, then in 13.3 rewrites it as an iterator chain:
+
+    let mut results = Vec::new();
+    for line in contents.lines() {
+        if line.contains(query) {
+            results.push(line);
+        }
+    }
+    results
+
+becomes lines() -> filter() -> collect(), with no mutable state to keep track of.
+
+The lifetime annotation says the returned slices borrow from `contents`, not
+from `query`: they're lines of the text being searched, so they can live as
+long as it does, even after the query is gone.
+
+13.3 is improving minigrep itself rather than writing a second one, so the
+rewritten functions are the ones in chapter_12's lib.rs (tested there too).
+They're re-exported here so this chapter's code can use them by name.
+*/
+
+pub use chapter_12::{search, search_case_insensitive};
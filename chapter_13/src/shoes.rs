@@ -0,0 +1,208 @@
+// 13.2 (continued) A Closure-Powered Query for the Shoe Catalog
+
+/*
+iterators.rs has shoes_in_size, which can answer exactly one question: which
+shoes are this size? ShoeQuery takes the closure out of that function and lets
+the caller pass in their own, as many as they like, plus a sort key and a
+limit:
+
+    ShoeQuery::new()
+        .filter(|s| s.size == 10)
+        .filter(|s| s.style.contains("boot"))
+        .sort_by_key(|s| s.style.clone())
+        .limit(2)
+        .run(shoes)
+
+Building the query doesn't touch any shoes. The closures are stored as boxed
+trait objects (every closure has its own type, so a Vec of them needs
+Box<dyn Fn>), and only run() feeds the shoes through them, one at a time.
+*/
+
+use std::cmp::Ordering;
+
+// Shoe itself is in the domain crate, re-exported here next to the query
+// that works on it
+pub use domain::Shoe;
+
+// 'a lets the closures borrow from their surroundings (like a shoe_size
+// variable) instead of having to own everything they use
+type Predicate<'a> = Box<dyn Fn(&Shoe) -> bool + 'a>;
+type Comparator<'a> = Box<dyn Fn(&Shoe, &Shoe) -> Ordering + 'a>;
+
+#[derive(Default)]
+pub struct ShoeQuery<'a> {
+    filters: Vec<Predicate<'a>>,
+    sort: Option<Comparator<'a>>,
+    limit: Option<usize>,
+}
+
+impl<'a> ShoeQuery<'a> {
+    pub fn new() -> ShoeQuery<'a> {
+        ShoeQuery::default()
+    }
+
+    pub fn filter(mut self, predicate: impl Fn(&Shoe) -> bool + 'a) -> ShoeQuery<'a> {
+        self.filters.push(Box::new(predicate));
+        self
+    }
+
+    // shortcuts for the two most common filters
+    pub fn size(self, size: u32) -> ShoeQuery<'a> {
+        self.filter(move |shoe| shoe.size == size)
+    }
+
+    pub fn style(self, style: &'a str) -> ShoeQuery<'a> {
+        self.filter(move |shoe| shoe.style == style)
+    }
+
+    // a later sort_by_key replaces an earlier one
+    pub fn sort_by_key<K: Ord>(mut self, key: impl Fn(&Shoe) -> K + 'a) -> ShoeQuery<'a> {
+        self.sort = Some(Box::new(move |a, b| key(a).cmp(&key(b))));
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> ShoeQuery<'a> {
+        self.limit = Some(limit);
+        self
+    }
+
+    /*
+    Without a sort, this stays lazy: filter and take pull one shoe at a time, and
+    with a limit it stops reading as soon as it has enough. Sorting has to see
+    every match before it knows which comes first, so only then are the matches
+    collected into a Vec.
+    The two branches are different iterator types, so both get boxed into the
+    same Box<dyn Iterator> to give run() a single return type.
+    */
+    pub fn run<I>(self, shoes: I) -> Box<dyn Iterator<Item = Shoe> + 'a>
+    where
+        I: IntoIterator<Item = Shoe>,
+        I::IntoIter: 'a,
+    {
+        let ShoeQuery {
+            filters,
+            sort,
+            limit,
+        } = self;
+        let limit = limit.unwrap_or(usize::MAX);
+        // a shoe has to pass every filter to make it into the results
+        let matches = shoes
+            .into_iter()
+            .filter(move |shoe| filters.iter().all(|predicate| predicate(shoe)));
+
+        match sort {
+            None => Box::new(matches.take(limit)),
+            Some(compare) => {
+                let mut sorted: Vec<Shoe> = matches.collect();
+                // a stable sort, so equal keys keep the catalog order
+                sorted.sort_by(|a, b| compare(a, b));
+                Box::new(sorted.into_iter().take(limit))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    fn catalog() -> Vec<Shoe> {
+        vec![
+            Shoe::new(10, "sneaker"),
+            Shoe::new(13, "sandal"),
+            Shoe::new(10, "boot"),
+            Shoe::new(9, "boot"),
+            Shoe::new(10, "hiking boot"),
+        ]
+    }
+
+    #[test]
+    fn filters_by_size() {
+        // the book's shoes_in_size(shoes, 10)
+        let in_my_size: Vec<Shoe> = ShoeQuery::new().size(10).run(catalog()).collect();
+        assert_eq!(
+            in_my_size,
+            vec![
+                Shoe::new(10, "sneaker"),
+                Shoe::new(10, "boot"),
+                Shoe::new(10, "hiking boot"),
+            ]
+        );
+    }
+
+    #[test]
+    fn combines_size_and_style() {
+        let boots: Vec<Shoe> = ShoeQuery::new()
+            .size(10)
+            .style("boot")
+            .run(catalog())
+            .collect();
+        assert_eq!(boots, vec![Shoe::new(10, "boot")]);
+
+        let any_boot: Vec<Shoe> = ShoeQuery::new()
+            .size(10)
+            .filter(|shoe| shoe.style.contains("boot"))
+            .run(catalog())
+            .collect();
+        assert_eq!(
+            any_boot,
+            vec![Shoe::new(10, "boot"), Shoe::new(10, "hiking boot")]
+        );
+    }
+
+    #[test]
+    fn filters_can_borrow_their_environment() {
+        let wanted = String::from("sandal");
+        let max_size = 13;
+        let found: Vec<Shoe> = ShoeQuery::new()
+            .filter(|shoe| shoe.style == wanted)
+            .filter(|shoe| shoe.size <= max_size)
+            .run(catalog())
+            .collect();
+        assert_eq!(found, vec![Shoe::new(13, "sandal")]);
+    }
+
+    #[test]
+    fn sorts_and_limits() {
+        let smallest_two: Vec<Shoe> = ShoeQuery::new()
+            .sort_by_key(|shoe| shoe.size)
+            .limit(2)
+            .run(catalog())
+            .collect();
+        assert_eq!(
+            smallest_two,
+            vec![Shoe::new(9, "boot"), Shoe::new(10, "sneaker")]
+        );
+
+        let by_style: Vec<String> = ShoeQuery::new()
+            .size(10)
+            .sort_by_key(|shoe| shoe.style.clone())
+            .run(catalog())
+            .map(|shoe| shoe.style)
+            .collect();
+        assert_eq!(by_style, vec!["boot", "hiking boot", "sneaker"]);
+    }
+
+    #[test]
+    fn no_filters_returns_everything() {
+        assert_eq!(ShoeQuery::new().run(catalog()).count(), 5);
+        assert_eq!(ShoeQuery::new().limit(0).run(catalog()).count(), 0);
+    }
+
+    #[test]
+    fn unsorted_queries_are_lazy() {
+        // count how many shoes the query actually pulls from the catalog
+        let pulled = Cell::new(0);
+        let shoes = catalog()
+            .into_iter()
+            .inspect(|_| pulled.set(pulled.get() + 1));
+
+        let mut results = ShoeQuery::new().size(10).limit(1).run(shoes);
+        assert_eq!(pulled.get(), 0, "nothing is read until we ask");
+        assert_eq!(results.next(), Some(Shoe::new(10, "sneaker")));
+        assert_eq!(results.next(), None);
+        // the limit was reached after the first shoe, so the rest were never read
+        assert_eq!(pulled.get(), 1);
+    }
+}
@@ -0,0 +1,176 @@
+// 13.1 (continued) A Registry of Sorting Closures
+
+/*
+closures.rs sorts Rectangles with sort_by_key(|r| r.width), the closure is
+written right where it's used. Sometimes the order isn't known until the
+program runs, say it comes from a command line flag like --sort by_area. For
+that we can store closures in a HashMap under a name and look them up later.
+
+Every closure has its own type, so to keep several of them in one map they're
+boxed as trait objects: Box<dyn Fn(&Rectangle, &Rectangle) -> Ordering>. Fn
+(not FnMut or FnOnce) because sort_by calls the comparator many times and the
+registry only hands out shared references to it.
+*/
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+// the domain crate's Rectangle, re-exported so callers can keep using
+// chapter_13::sorting::Rectangle
+pub use domain::Rectangle;
+
+type Comparator = Box<dyn Fn(&Rectangle, &Rectangle) -> Ordering>;
+
+#[derive(Debug, PartialEq)]
+pub struct UnknownComparator(pub String);
+
+impl fmt::Display for UnknownComparator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "no comparator named '{}'", self.0)
+    }
+}
+
+impl Error for UnknownComparator {}
+
+#[derive(Default)]
+pub struct ComparatorRegistry {
+    comparators: HashMap<String, Comparator>,
+}
+
+impl ComparatorRegistry {
+    // an empty registry
+    pub fn new() -> ComparatorRegistry {
+        ComparatorRegistry::default()
+    }
+
+    // by_width, by_area, and by_height_desc already registered
+    pub fn with_defaults() -> ComparatorRegistry {
+        let mut registry = ComparatorRegistry::new();
+        registry.register("by_width", |a, b| a.width.cmp(&b.width));
+        registry.register("by_area", |a, b| a.area().cmp(&b.area()));
+        // b compared to a instead of a to b flips the order: tallest first
+        registry.register("by_height_desc", |a, b| b.height.cmp(&a.height));
+        registry
+    }
+
+    // registering a name that's already taken replaces the old comparator
+    pub fn register<F>(&mut self, name: &str, compare: F)
+    where
+        F: Fn(&Rectangle, &Rectangle) -> Ordering + 'static,
+    {
+        self.comparators
+            .insert(String::from(name), Box::new(compare));
+    }
+
+    // sorted, since a HashMap's order changes from run to run
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.comparators.keys().map(|name| name.as_str()).collect();
+        names.sort();
+        names
+    }
+
+    // the rectangles are left untouched when the name isn't registered
+    pub fn sort_with(
+        &self,
+        name: &str,
+        rectangles: &mut [Rectangle],
+    ) -> Result<(), UnknownComparator> {
+        let compare = self
+            .comparators
+            .get(name)
+            .ok_or_else(|| UnknownComparator(String::from(name)))?;
+        rectangles.sort_by(|a, b| compare(a, b));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(width: u32, height: u32) -> Rectangle {
+        Rectangle { width, height }
+    }
+
+    // the list from closures.rs
+    fn list() -> [Rectangle; 3] {
+        [rect(10, 1), rect(3, 5), rect(7, 12)]
+    }
+
+    #[test]
+    fn by_width() {
+        let mut list = list();
+        ComparatorRegistry::with_defaults()
+            .sort_with("by_width", &mut list)
+            .unwrap();
+        assert_eq!(list, [rect(3, 5), rect(7, 12), rect(10, 1)]);
+    }
+
+    #[test]
+    fn by_area() {
+        let mut list = list();
+        ComparatorRegistry::with_defaults()
+            .sort_with("by_area", &mut list)
+            .unwrap();
+        // areas 10, 15, 84
+        assert_eq!(list, [rect(10, 1), rect(3, 5), rect(7, 12)]);
+    }
+
+    #[test]
+    fn by_height_desc() {
+        let mut list = list();
+        ComparatorRegistry::with_defaults()
+            .sort_with("by_height_desc", &mut list)
+            .unwrap();
+        assert_eq!(list, [rect(7, 12), rect(3, 5), rect(10, 1)]);
+    }
+
+    #[test]
+    fn unknown_comparator_leaves_the_list_alone() {
+        let mut list = list();
+        let err = ComparatorRegistry::with_defaults()
+            .sort_with("by_color", &mut list)
+            .unwrap_err();
+        assert_eq!(err, UnknownComparator(String::from("by_color")));
+        assert_eq!(err.to_string(), "no comparator named 'by_color'");
+        assert_eq!(list, self::list());
+    }
+
+    #[test]
+    fn empty_registry_knows_nothing() {
+        let registry = ComparatorRegistry::new();
+        assert!(registry.names().is_empty());
+        assert!(registry.sort_with("by_width", &mut []).is_err());
+    }
+
+    #[test]
+    fn custom_comparators_can_capture() {
+        // sort by how far each width is from a target chosen at runtime. The
+        // closure owns `target` (move) since the registry may outlive it
+        let target = 8;
+        let mut registry = ComparatorRegistry::with_defaults();
+        registry.register("closest_width", move |a, b| {
+            a.width.abs_diff(target).cmp(&b.width.abs_diff(target))
+        });
+
+        let mut list = list();
+        registry.sort_with("closest_width", &mut list).unwrap();
+        assert_eq!(list, [rect(7, 12), rect(10, 1), rect(3, 5)]);
+        assert_eq!(
+            registry.names(),
+            vec!["by_area", "by_height_desc", "by_width", "closest_width"]
+        );
+    }
+
+    #[test]
+    fn registering_again_replaces() {
+        let mut registry = ComparatorRegistry::with_defaults();
+        registry.register("by_width", |a, b| b.width.cmp(&a.width));
+        let mut list = list();
+        registry.sort_with("by_width", &mut list).unwrap();
+        assert_eq!(list, [rect(10, 1), rect(7, 12), rect(3, 5)]);
+        assert_eq!(registry.names().len(), 3);
+    }
+}
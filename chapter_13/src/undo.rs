@@ -0,0 +1,330 @@
+// 13.1 (continued) Undo and Redo with Closures
+
+/*
+Every edit to a document is stored as a pair of closures: one that makes the
+change and one that reverses it. Undo runs the most recent "undo" closure,
+redo runs the matching "do" closure again. The closures capture whatever they
+need (the text to insert, where to put it) so the stack itself never has to
+know what kind of edit it's holding.
+
+They're FnMut rather than FnOnce because each one may run many times (do, undo,
+redo, undo, ...), and they're boxed since every closure has its own type.
+
+String::insert_str and replace_range panic on an index past the end or one in
+the middle of a multi-byte character, so insert() and delete() check their
+indexes first and refuse the edit with an EditError. Nothing is run or pushed
+onto the stack when they do.
+*/
+
+use std::error::Error;
+use std::fmt;
+use std::ops::Range;
+
+#[derive(Debug, PartialEq)]
+pub enum EditError {
+    OutOfRange { index: usize, len: usize },
+    NotCharBoundary(usize),
+    Backwards(Range<usize>),
+}
+
+impl fmt::Display for EditError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EditError::OutOfRange { index, len } => {
+                write!(
+                    f,
+                    "index {} is past the end of the text ({} bytes)",
+                    index, len
+                )
+            }
+            EditError::NotCharBoundary(index) => {
+                write!(f, "index {} is in the middle of a character", index)
+            }
+            EditError::Backwards(range) => {
+                write!(
+                    f,
+                    "range {}..{} ends before it starts",
+                    range.start, range.end
+                )
+            }
+        }
+    }
+}
+
+impl Error for EditError {}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Doc {
+    pub text: String,
+}
+
+impl Doc {
+    pub fn new(text: &str) -> Doc {
+        Doc {
+            text: String::from(text),
+        }
+    }
+
+    // Ok if `index` is somewhere an edit can start or end: at most the length
+    // of the text and not inside a character
+    fn check(&self, index: usize) -> Result<(), EditError> {
+        if index > self.text.len() {
+            Err(EditError::OutOfRange {
+                index,
+                len: self.text.len(),
+            })
+        } else if !self.text.is_char_boundary(index) {
+            Err(EditError::NotCharBoundary(index))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+type Edit = Box<dyn FnMut(&mut Doc)>;
+
+struct Command {
+    apply: Edit,
+    revert: Edit,
+}
+
+#[derive(Default)]
+pub struct CommandStack {
+    done: Vec<Command>,
+    undone: Vec<Command>,
+}
+
+impl CommandStack {
+    pub fn new() -> CommandStack {
+        CommandStack::default()
+    }
+
+    // runs `apply` now and remembers `revert` for undo. A new edit clears the
+    // redo history, the same as in a text editor
+    pub fn execute<A, R>(&mut self, doc: &mut Doc, apply: A, revert: R)
+    where
+        A: FnMut(&mut Doc) + 'static,
+        R: FnMut(&mut Doc) + 'static,
+    {
+        let mut command = Command {
+            apply: Box::new(apply),
+            revert: Box::new(revert),
+        };
+        (command.apply)(doc);
+        self.done.push(command);
+        self.undone.clear();
+    }
+
+    // false when there's nothing to undo
+    pub fn undo(&mut self, doc: &mut Doc) -> bool {
+        match self.done.pop() {
+            Some(mut command) => {
+                (command.revert)(doc);
+                self.undone.push(command);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // false when there's nothing to redo
+    pub fn redo(&mut self, doc: &mut Doc) -> bool {
+        match self.undone.pop() {
+            Some(mut command) => {
+                (command.apply)(doc);
+                self.done.push(command);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.done.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.undone.is_empty()
+    }
+
+    // Ready made edits. Each one builds its do/undo closures and moves in
+    // copies of the values they need
+
+    pub fn insert(&mut self, doc: &mut Doc, at: usize, text: &str) -> Result<(), EditError> {
+        doc.check(at)?;
+        let inserted = String::from(text);
+        let len = inserted.len();
+        self.execute(
+            doc,
+            move |doc| doc.text.insert_str(at, &inserted),
+            move |doc| {
+                doc.text.replace_range(at..at + len, "");
+            },
+        );
+        Ok(())
+    }
+
+    // the end of the text is always a valid place to insert
+    pub fn append(&mut self, doc: &mut Doc, text: &str) {
+        let at = doc.text.len();
+        self.insert(doc, at, text)
+            .expect("the end of the text is a char boundary");
+    }
+
+    // the removed text is read now, while it's still in the document, so the
+    // undo closure can put it back
+    pub fn delete(&mut self, doc: &mut Doc, range: Range<usize>) -> Result<(), EditError> {
+        if range.start > range.end {
+            return Err(EditError::Backwards(range));
+        }
+        doc.check(range.start)?;
+        doc.check(range.end)?;
+        let removed = doc.text[range.clone()].to_string();
+        let start = range.start;
+        self.execute(
+            doc,
+            move |doc| doc.text.replace_range(range.clone(), ""),
+            move |doc| doc.text.insert_str(start, &removed),
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undo_and_redo_an_append() {
+        let mut doc = Doc::new("Hello");
+        let mut stack = CommandStack::new();
+
+        stack.append(&mut doc, ", world");
+        assert_eq!(doc.text, "Hello, world");
+
+        assert!(stack.undo(&mut doc));
+        assert_eq!(doc.text, "Hello");
+
+        assert!(stack.redo(&mut doc));
+        assert_eq!(doc.text, "Hello, world");
+    }
+
+    #[test]
+    fn undo_walks_back_through_history() {
+        let mut doc = Doc::default();
+        let mut stack = CommandStack::new();
+        stack.append(&mut doc, "one");
+        stack.append(&mut doc, " two");
+        stack.insert(&mut doc, 0, "zero ").unwrap();
+        stack.delete(&mut doc, 4..5).unwrap();
+        assert_eq!(doc.text, "zeroone two");
+
+        stack.undo(&mut doc);
+        assert_eq!(doc.text, "zero one two");
+        stack.undo(&mut doc);
+        assert_eq!(doc.text, "one two");
+        stack.undo(&mut doc);
+        assert_eq!(doc.text, "one");
+        stack.undo(&mut doc);
+        assert_eq!(doc.text, "");
+        assert!(!stack.undo(&mut doc));
+        assert!(!stack.can_undo());
+    }
+
+    #[test]
+    fn redo_replays_in_order() {
+        let mut doc = Doc::default();
+        let mut stack = CommandStack::new();
+        stack.append(&mut doc, "a");
+        stack.append(&mut doc, "b");
+        stack.append(&mut doc, "c");
+        while stack.undo(&mut doc) {}
+        assert_eq!(doc.text, "");
+
+        stack.redo(&mut doc);
+        stack.redo(&mut doc);
+        assert_eq!(doc.text, "ab");
+        assert!(stack.can_redo());
+    }
+
+    #[test]
+    fn a_new_edit_clears_redo() {
+        let mut doc = Doc::new("abc");
+        let mut stack = CommandStack::new();
+        stack.delete(&mut doc, 0..1).unwrap();
+        stack.undo(&mut doc);
+        assert!(stack.can_redo());
+
+        stack.append(&mut doc, "d");
+        assert!(!stack.can_redo());
+        assert!(!stack.redo(&mut doc));
+        assert_eq!(doc.text, "abcd");
+    }
+
+    #[test]
+    fn bad_indexes_are_refused() {
+        // "é" is two bytes, so 2 is inside it
+        let mut doc = Doc::new("héllo");
+        let mut stack = CommandStack::new();
+        assert_eq!(
+            stack.insert(&mut doc, 7, "!"),
+            Err(EditError::OutOfRange { index: 7, len: 6 })
+        );
+        assert_eq!(
+            stack.insert(&mut doc, 2, "!"),
+            Err(EditError::NotCharBoundary(2))
+        );
+        assert_eq!(
+            stack.delete(&mut doc, 1..2),
+            Err(EditError::NotCharBoundary(2))
+        );
+        assert_eq!(
+            stack.delete(&mut doc, 4..10),
+            Err(EditError::OutOfRange { index: 10, len: 6 })
+        );
+        // built as a struct, clippy flags a literal 3..1 as an empty range
+        let backwards = Range { start: 3, end: 1 };
+        assert_eq!(
+            stack.delete(&mut doc, backwards.clone()),
+            Err(EditError::Backwards(backwards))
+        );
+        // none of them touched the document or the history
+        assert_eq!(doc.text, "héllo");
+        assert!(!stack.can_undo());
+
+        // the whole character is fine, and so is the very end
+        stack.delete(&mut doc, 1..3).unwrap();
+        stack.insert(&mut doc, 4, "!").unwrap();
+        assert_eq!(doc.text, "hllo!");
+        while stack.undo(&mut doc) {}
+        assert_eq!(doc.text, "héllo");
+    }
+
+    #[test]
+    fn custom_commands() {
+        // any pair of closures works, here's an uppercase command that has to
+        // remember the text from before it ran to be able to undo
+        let mut doc = Doc::new("quiet");
+        let mut stack = CommandStack::new();
+        let before = doc.text.clone();
+        stack.execute(
+            &mut doc,
+            |doc| doc.text = doc.text.to_uppercase(),
+            move |doc| doc.text = before.clone(),
+        );
+        assert_eq!(doc.text, "QUIET");
+        stack.undo(&mut doc);
+        assert_eq!(doc.text, "quiet");
+        stack.redo(&mut doc);
+        assert_eq!(doc.text, "QUIET");
+    }
+
+    #[test]
+    fn nothing_to_do_on_a_fresh_stack() {
+        let mut doc = Doc::new("x");
+        let mut stack = CommandStack::new();
+        assert!(!stack.undo(&mut doc));
+        assert!(!stack.redo(&mut doc));
+        assert_eq!(doc, Doc::new("x"));
+    }
+}
@@ -0,0 +1,170 @@
+// 13.2 (continued) A Streaming Word Count Iterator
+
+/*
+Counting words the simple way reads the whole file into a String first. For a
+huge file that's a lot of memory for text we only look at once. WordCounts
+reads one line at a time from anything that implements BufRead (a file wrapped
+in a BufReader, stdin, or an in-memory Cursor in the tests), and every call to
+next() hands back the next word along with how many times it's been seen so
+far. Only the current line and the counts table are ever held in memory.
+
+Reading can fail (the file disappears, the bytes aren't UTF-8...), so each item
+is an io::Result, the same way BufRead::lines() does it.
+*/
+
+use std::collections::{HashMap, VecDeque};
+use std::io::{self, BufRead, Lines};
+
+pub struct WordCounts<R> {
+    lines: Lines<R>,
+    // the words of the current line that haven't been handed out yet
+    pending: VecDeque<String>,
+    counts: HashMap<String, usize>,
+}
+
+impl<R: BufRead> WordCounts<R> {
+    pub fn new(reader: R) -> WordCounts<R> {
+        WordCounts {
+            lines: reader.lines(),
+            pending: VecDeque::new(),
+            counts: HashMap::new(),
+        }
+    }
+
+    // the totals so far, complete once the iterator has returned None
+    pub fn counts(&self) -> &HashMap<String, usize> {
+        &self.counts
+    }
+}
+
+// "Hello," and "hello" are the same word
+fn normalize(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase()
+}
+
+impl<R: BufRead> Iterator for WordCounts<R> {
+    type Item = io::Result<(String, usize)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(word) = self.pending.pop_front() {
+                let count = self.counts.entry(word.clone()).or_insert(0);
+                *count += 1;
+                return Some(Ok((word, *count)));
+            }
+
+            // out of words, read another line. `?` passes on the None at the
+            // end of the input, errors are handed to the caller as Some(Err)
+            match self.lines.next()? {
+                Ok(line) => self.pending.extend(
+                    line.split_whitespace()
+                        .map(normalize)
+                        .filter(|word| !word.is_empty()),
+                ),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::io::{BufReader, Cursor, Read};
+
+    fn updates(text: &str) -> Vec<(String, usize)> {
+        WordCounts::new(Cursor::new(text))
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap()
+    }
+
+    fn pair(word: &str, count: usize) -> (String, usize) {
+        (String::from(word), count)
+    }
+
+    #[test]
+    fn running_counts() {
+        assert_eq!(
+            updates("the cat\nthe hat\n"),
+            vec![
+                pair("the", 1),
+                pair("cat", 1),
+                pair("the", 2),
+                pair("hat", 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_case_and_punctuation() {
+        assert_eq!(
+            updates("Hello, world! HELLO... -- hello?"),
+            vec![
+                pair("hello", 1),
+                pair("world", 1),
+                pair("hello", 2),
+                pair("hello", 3)
+            ]
+        );
+    }
+
+    #[test]
+    fn blank_lines_and_empty_input() {
+        assert!(updates("").is_empty());
+        assert!(updates("\n\n   \n").is_empty());
+        assert_eq!(updates("\n\none\n\n"), vec![pair("one", 1)]);
+    }
+
+    #[test]
+    fn final_counts() {
+        let mut words = WordCounts::new(Cursor::new("a b a c a b"));
+        let last = words.by_ref().last().unwrap().unwrap();
+        assert_eq!(last, pair("b", 2));
+        assert_eq!(words.counts()["a"], 3);
+        assert_eq!(words.counts()["c"], 1);
+        assert_eq!(words.counts().len(), 3);
+    }
+
+    // a reader that keeps track of how many bytes have been read from it
+    struct Counting<'a> {
+        inner: Cursor<&'a [u8]>,
+        bytes_read: &'a Cell<usize>,
+    }
+
+    impl Read for Counting<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = self.inner.read(buf)?;
+            self.bytes_read.set(self.bytes_read.get() + n);
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn reads_lazily() {
+        let text = "first line\n".repeat(1000);
+        let bytes_read = Cell::new(0);
+        let reader = Counting {
+            inner: Cursor::new(text.as_bytes()),
+            bytes_read: &bytes_read,
+        };
+        let mut words = WordCounts::new(BufReader::with_capacity(16, reader));
+
+        assert_eq!(words.next().unwrap().unwrap(), pair("first", 1));
+        // one 16 byte buffer was enough for the first line, the other
+        // 10,984 bytes haven't been touched
+        assert_eq!(bytes_read.get(), 16);
+    }
+
+    #[test]
+    fn read_errors_are_passed_on() {
+        // invalid UTF-8 can't become a String line
+        let bytes: &[u8] = b"ok line\n\xff\xfe\nafter\n";
+        let results: Vec<io::Result<(String, usize)>> =
+            WordCounts::new(Cursor::new(bytes)).collect();
+        assert_eq!(results.len(), 4);
+        assert!(results[2].is_err());
+        assert_eq!(results[3].as_ref().unwrap(), &pair("after", 1));
+    }
+}
@@ -0,0 +1,34 @@
+// add_two is built on top of add_one, a library depending on another library
+// in the same workspace. The dependency is a path one (see Cargo.toml) just
+// like adder's
+
+// pub use re-exports add_one's function as part of add_two's own API, so
+// anyone depending on add_two can call add_two::add_one without adding add_one
+// to their Cargo.toml too
+pub use add_one::add_one;
+
+pub fn add_two(x: i32) -> i32 {
+    add_one(add_one(x))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let result = add_two(2);
+        assert_eq!(result, 4);
+    }
+
+    #[test]
+    fn reexported_add_one_is_the_same_function() {
+        assert_eq!(crate::add_one(2), add_one::add_one(2));
+    }
+
+    #[test]
+    fn negatives() {
+        assert_eq!(add_two(-2), 0);
+        assert_eq!(add_two(-10), -8);
+    }
+}
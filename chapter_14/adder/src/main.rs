@@ -1,6 +1,30 @@
-use add_one;
+// add_one comes from add_one directly, add_two (and its re-export of add_one)
+// from add_two
+fn describe(num: i32) -> String {
+    format!(
+        "{num} plus one is {}, plus two is {}",
+        add_one::add_one(num),
+        add_two::add_two(num)
+    )
+}
 
 fn main() {
     let num = 10;
-    println!("Hello, world! {num} plus one is {}", add_one::add_one(num));
+    println!("Hello, world! {}", describe(num));
+    println!("add_two re-exports add_one too: {}", add_two::add_one(num));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describes_both_sums() {
+        assert_eq!(describe(10), "10 plus one is 11, plus two is 12");
+    }
+
+    #[test]
+    fn both_paths_to_add_one_agree() {
+        assert_eq!(add_one::add_one(5), add_two::add_one(5));
+    }
 }
@@ -0,0 +1,303 @@
+// 15.6 (continued) Graphs Without Rc: an Arena
+
+/*
+tree.rs and cycles.rs show how much care a graph of Rc<RefCell<...>> nodes
+needs: Weak for back edges, borrow() and upgrade() everywhere, and a leak if a
+cycle slips through. The common alternative is an arena. All the nodes live in
+one Vec, and instead of pointers an edge is just the index of the node it
+points at (wrapped in a NodeId so it can't be mixed up with other numbers).
+
+The Vec owns every node, so there's only one owner and the normal borrow rules
+work again: &arena to read, &mut arena to change. Cycles are just indexes, and
+dropping the arena drops everything at once. The cost is that nodes can't be
+removed without leaving a hole, and a NodeId from one arena means nothing in
+another.
+
+The #[ignore]d benchmarks at the bottom time building and walking the same
+graph both ways, run them with
+    cargo test --release -- --ignored --nocapture
+*/
+
+use std::collections::VecDeque;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId(usize);
+
+#[derive(Debug)]
+struct Node<T> {
+    value: T,
+    edges: Vec<NodeId>,
+}
+
+#[derive(Debug)]
+pub struct Arena<T> {
+    nodes: Vec<Node<T>>,
+}
+
+impl<T> Arena<T> {
+    pub fn new() -> Arena<T> {
+        Arena { nodes: Vec::new() }
+    }
+
+    pub fn add(&mut self, value: T) -> NodeId {
+        self.nodes.push(Node {
+            value,
+            edges: Vec::new(),
+        });
+        NodeId(self.nodes.len() - 1)
+    }
+
+    // a one way edge, call it twice (swapped) for both directions
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId) {
+        assert!(to.0 < self.nodes.len(), "no node {:?} in this arena", to);
+        self.nodes[from.0].edges.push(to);
+    }
+
+    // a chain of nodes, each with an edge to the next, like a cons list.
+    // Returns the head, or None if there were no values
+    pub fn add_list(&mut self, values: impl IntoIterator<Item = T>) -> Option<NodeId> {
+        let ids: Vec<NodeId> = values.into_iter().map(|value| self.add(value)).collect();
+        for pair in ids.windows(2) {
+            self.add_edge(pair[0], pair[1]);
+        }
+        ids.first().copied()
+    }
+
+    pub fn get(&self, id: NodeId) -> &T {
+        &self.nodes[id.0].value
+    }
+
+    pub fn get_mut(&mut self, id: NodeId) -> &mut T {
+        &mut self.nodes[id.0].value
+    }
+
+    pub fn neighbors(&self, id: NodeId) -> &[NodeId] {
+        &self.nodes[id.0].edges
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    // breadth first: start, then everything one edge away, then two... Each
+    // node is visited once, so cycles don't loop forever
+    pub fn bfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut seen = vec![false; self.nodes.len()];
+        let mut order = Vec::new();
+        let mut queue = VecDeque::from([start]);
+        seen[start.0] = true;
+
+        while let Some(id) = queue.pop_front() {
+            order.push(id);
+            for &next in self.neighbors(id) {
+                if !seen[next.0] {
+                    seen[next.0] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+        order
+    }
+
+    pub fn path_exists(&self, from: NodeId, to: NodeId) -> bool {
+        self.bfs(from).contains(&to)
+    }
+}
+
+impl<T> Default for Arena<T> {
+    fn default() -> Arena<T> {
+        Arena::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chapter_11::bench::time_it;
+    use std::cell::RefCell;
+    use std::mem;
+    use std::rc::Rc;
+
+    fn values<T: Copy>(arena: &Arena<T>, ids: &[NodeId]) -> Vec<T> {
+        ids.iter().map(|&id| *arena.get(id)).collect()
+    }
+
+    #[test]
+    fn add_and_get() {
+        let mut arena = Arena::new();
+        assert!(arena.is_empty());
+        let a = arena.add("a");
+        let b = arena.add("b");
+        assert_ne!(a, b);
+        assert_eq!(arena.len(), 2);
+        assert_eq!(*arena.get(b), "b");
+
+        *arena.get_mut(a) = "A";
+        assert_eq!(*arena.get(a), "A");
+    }
+
+    #[test]
+    fn list_is_a_chain() {
+        let mut arena = Arena::new();
+        let head = arena.add_list([1, 2, 3]).unwrap();
+        assert_eq!(values(&arena, &arena.bfs(head)), vec![1, 2, 3]);
+        assert_eq!(arena.add_list(Vec::<i32>::new()), None);
+    }
+
+    #[test]
+    fn shared_tail_without_rc() {
+        // rc_list.rs's b and c sharing a, with indexes instead of Rc
+        let mut arena = Arena::new();
+        let a = arena.add_list([5, 10]).unwrap();
+        let b = arena.add(3);
+        let c = arena.add(4);
+        arena.add_edge(b, a);
+        arena.add_edge(c, a);
+
+        assert_eq!(values(&arena, &arena.bfs(b)), vec![3, 5, 10]);
+        assert_eq!(values(&arena, &arena.bfs(c)), vec![4, 5, 10]);
+        // changing the shared node shows up from both
+        *arena.get_mut(a) += 10;
+        assert_eq!(values(&arena, &arena.bfs(c)), vec![4, 15, 10]);
+    }
+
+    #[test]
+    fn cycles_are_fine() {
+        // the cycle from cycles.rs: no leak, no infinite loop
+        let mut arena = Arena::new();
+        let a = arena.add(5);
+        let b = arena.add(10);
+        arena.add_edge(a, b);
+        arena.add_edge(b, a);
+        assert_eq!(arena.bfs(a), vec![a, b]);
+        assert!(arena.path_exists(b, a));
+    }
+
+    #[test]
+    fn bfs_goes_level_by_level() {
+        //     0
+        //    / \
+        //   1   2
+        //   |   |
+        //   3   4
+        let mut arena = Arena::new();
+        let ids: Vec<NodeId> = (0..5).map(|n| arena.add(n)).collect();
+        arena.add_edge(ids[0], ids[1]);
+        arena.add_edge(ids[0], ids[2]);
+        arena.add_edge(ids[1], ids[3]);
+        arena.add_edge(ids[2], ids[4]);
+
+        assert_eq!(values(&arena, &arena.bfs(ids[0])), vec![0, 1, 2, 3, 4]);
+        assert_eq!(values(&arena, &arena.bfs(ids[2])), vec![2, 4]);
+        assert!(!arena.path_exists(ids[3], ids[0]));
+    }
+
+    #[test]
+    #[should_panic(expected = "no node")]
+    fn edge_to_a_missing_node() {
+        let mut arena = Arena::new();
+        let a = arena.add(1);
+        let mut other = Arena::new();
+        other.add(1);
+        let stranger = other.add(2);
+        arena.add_edge(a, stranger);
+    }
+
+    // the same graph with Rc<RefCell<...>> nodes, for the benchmarks
+    struct RcNode {
+        value: u64,
+        edges: Vec<Rc<RefCell<RcNode>>>,
+    }
+
+    // Dropping a long chain of Rcs is recursive (each node's drop drops the
+    // next), and 100_000 deep overflows the stack. Unhooking the edges into a
+    // Vec and dropping them one at a time avoids that. The arena never needs
+    // this, its nodes are all dropped by the one Vec
+    impl Drop for RcNode {
+        fn drop(&mut self) {
+            let mut pending = mem::take(&mut self.edges);
+            while let Some(node) = pending.pop() {
+                // only unhook nodes nobody else still owns
+                if let Ok(cell) = Rc::try_unwrap(node) {
+                    pending.append(&mut cell.into_inner().edges);
+                }
+            }
+        }
+    }
+
+    fn rc_chain(n: u64) -> Rc<RefCell<RcNode>> {
+        let mut head = Rc::new(RefCell::new(RcNode {
+            value: n - 1,
+            edges: vec![],
+        }));
+        for value in (0..n - 1).rev() {
+            head = Rc::new(RefCell::new(RcNode {
+                value,
+                edges: vec![head],
+            }));
+        }
+        head
+    }
+
+    fn rc_sum(head: &Rc<RefCell<RcNode>>) -> u64 {
+        let mut total = 0;
+        let mut current = Rc::clone(head);
+        loop {
+            let next = {
+                let node = current.borrow();
+                total += node.value;
+                node.edges.first().map(Rc::clone)
+            };
+            match next {
+                Some(next) => current = next,
+                None => return total,
+            }
+        }
+    }
+
+    fn arena_chain(n: u64) -> (Arena<u64>, NodeId) {
+        let mut arena = Arena::new();
+        let head = arena.add_list(0..n).unwrap();
+        (arena, head)
+    }
+
+    fn arena_sum(arena: &Arena<u64>, head: NodeId) -> u64 {
+        let mut total = 0;
+        let mut current = Some(head);
+        while let Some(id) = current {
+            total += arena.get(id);
+            current = arena.neighbors(id).first().copied();
+        }
+        total
+    }
+
+    const N: u64 = 100_000;
+
+    #[test]
+    fn both_chains_agree() {
+        let (arena, head) = arena_chain(1_000);
+        assert_eq!(arena_sum(&arena, head), rc_sum(&rc_chain(1_000)));
+    }
+
+    #[test]
+    #[ignore]
+    fn bench_build() {
+        let rc = time_it("build Rc<RefCell>", 20, || rc_chain(N));
+        let arena = time_it("build arena", 20, || arena_chain(N));
+        println!("arena / Rc: {:.2}x", arena.ratio_to(&rc));
+    }
+
+    #[test]
+    #[ignore]
+    fn bench_walk() {
+        let rc_head = rc_chain(N);
+        let (arena, head) = arena_chain(N);
+        let rc = time_it("walk Rc<RefCell>", 100, || rc_sum(&rc_head));
+        let arena_stats = time_it("walk arena", 100, || arena_sum(&arena, head));
+        println!("arena / Rc: {:.2}x", arena_stats.ratio_to(&rc));
+    }
+}
@@ -13,7 +13,7 @@ situations:
  - When you want to own a value and you care only that it's a type that implements
     a particular trait rather than being a specific type
 */
-pub fn _run() {
+pub fn run() {
     // Using a Box<T> to Store Data on the Heap
 
     // but first, Box syntax and interaction
@@ -81,12 +81,14 @@ pub fn _run() {
     We can now change the code above to look like this:
     */
     
-    enum List {
-        Cons(i32,  Box<List>),
-        Nil,
-    }
-    
-    use List::{Cons, Nil};
+    // enum List {
+    //     Cons(i32,  Box<List>),
+    //     Nil,
+    // }
+
+    // List now lives in cons_list.rs, along with len, append, iter and Display
+    use crate::cons_list::List::{Cons, Nil};
 
-    let _list = Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Nil))))));
+    let list = Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Nil))))));
+    println!("list = {} ({} items)", list, list.len());
 }
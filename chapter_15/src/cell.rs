@@ -0,0 +1,162 @@
+// 15.5 (continued) Cell<T>: Interior Mutability for Copy Values
+
+/*
+RefCell hands out borrows of the value inside it (Ref and RefMut) and keeps
+count of them at runtime, panicking if the rules are broken. For small Copy
+values like a u32 there's a simpler option, Cell<T>. A Cell never lends out a
+reference to what's inside, it only copies values in and out:
+
+  get()          a copy of the current value (needs T: Copy)
+  set(v)         replace it, dropping the old one
+  replace(v)     replace it and hand back the old one
+  update(f)      set it to f(current value)
+
+Since nobody can ever be holding a reference into a Cell, there's nothing to
+track: no borrow flag, no runtime checks, and no way to panic. The trade-off is
+that we can't borrow the contents, so for a Vec or a String (where copying the
+whole thing out to change it would be silly) RefCell is the right tool.
+
+HitCounter is the kind of thing Cell is for. It counts how often something was
+looked at, and looking only needs &self.
+*/
+
+use std::cell::Cell;
+
+#[derive(Debug, Default)]
+pub struct HitCounter {
+    hits: Cell<u32>,
+    // the most hits seen before the last reset
+    best: Cell<u32>,
+}
+
+impl HitCounter {
+    pub fn new() -> HitCounter {
+        HitCounter::default()
+    }
+
+    // &self, not &mut self: code holding a shared reference can still count
+    pub fn hit(&self) -> u32 {
+        self.hits.update(|hits| hits + 1);
+        self.hits.get()
+    }
+
+    pub fn hits(&self) -> u32 {
+        self.hits.get()
+    }
+
+    // back to 0, returning the count it had
+    pub fn reset(&self) -> u32 {
+        let old = self.hits.replace(0);
+        if old > self.best.get() {
+            self.best.set(old);
+        }
+        old
+    }
+
+    pub fn best(&self) -> u32 {
+        self.best.get().max(self.hits.get())
+    }
+}
+
+// something with a counter inside that's only ever handed out as &Page
+#[derive(Debug)]
+pub struct Page {
+    pub path: String,
+    pub views: HitCounter,
+}
+
+impl Page {
+    pub fn new(path: &str) -> Page {
+        Page {
+            path: String::from(path),
+            views: HitCounter::new(),
+        }
+    }
+
+    pub fn render(&self) -> String {
+        let views = self.views.hit();
+        format!("{} (viewed {} times)", self.path, views)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[test]
+    fn counts_through_shared_references() {
+        let counter = HitCounter::new();
+        let a = &counter;
+        let b = &counter;
+        a.hit();
+        b.hit();
+        assert_eq!(a.hit(), 3);
+        assert_eq!(counter.hits(), 3);
+    }
+
+    #[test]
+    fn reset_hands_back_the_old_count() {
+        let counter = HitCounter::new();
+        for _ in 0..5 {
+            counter.hit();
+        }
+        assert_eq!(counter.reset(), 5);
+        assert_eq!(counter.hits(), 0);
+        counter.hit();
+        assert_eq!(counter.best(), 5);
+        for _ in 0..9 {
+            counter.hit();
+        }
+        // 10 now, more than any count before a reset
+        assert_eq!(counter.best(), 10);
+    }
+
+    #[test]
+    fn pages_count_their_own_views() {
+        let pages = vec![Page::new("/"), Page::new("/about")];
+        // only ever borrowed immutably, the Vec isn't even mut
+        for page in &pages {
+            page.render();
+        }
+        assert_eq!(pages[0].render(), "/ (viewed 2 times)");
+        assert_eq!(pages[0].views.hits(), 2);
+        assert_eq!(pages[1].views.hits(), 1);
+    }
+
+    #[test]
+    fn cell_basics() {
+        let cell = Cell::new(1u32);
+        assert_eq!(cell.get(), 1);
+        cell.set(2);
+        assert_eq!(cell.replace(3), 2);
+        cell.update(|n| n * 10);
+        assert_eq!(cell.get(), 30);
+        assert_eq!(cell.into_inner(), 30);
+    }
+
+    #[test]
+    fn cell_never_panics_where_ref_cell_does() {
+        // with RefCell, holding a borrow while asking to change the value is a
+        // runtime panic...
+        let ref_cell = RefCell::new(1u32);
+        let held = ref_cell.borrow();
+        assert!(ref_cell.try_borrow_mut().is_err());
+        drop(held);
+
+        // ...a Cell can't be borrowed into, so every get and set just works
+        let cell = Cell::new(1u32);
+        let copy = cell.get();
+        cell.set(copy + 1);
+        assert_eq!((copy, cell.get()), (1, 2));
+    }
+
+    #[test]
+    fn get_is_a_copy() {
+        let cell = Cell::new(5u32);
+        let mut copy = cell.get();
+        copy += 1;
+        // changing the copy doesn't touch the Cell
+        assert_eq!((copy, cell.get()), (6, 5));
+    }
+}
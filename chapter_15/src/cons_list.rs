@@ -0,0 +1,186 @@
+// 15.1 (continued) A Cons List We Can Actually Use
+
+/*
+box_pointer.rs got the Box-based cons list to compile, but all it could do was
+exist. This is the same enum with enough methods to work with it: counting,
+adding to the front, joining two lists, iterating, and printing it the way the
+book writes it, (1, (2, (3, Nil))).
+
+Everything here walks the list one Cons at a time, following the Box to the
+next node until it reaches Nil.
+*/
+
+use std::fmt;
+use std::mem;
+
+#[derive(Debug, PartialEq)]
+pub enum List {
+    Cons(i32, Box<List>),
+    Nil,
+}
+
+use List::{Cons, Nil};
+
+impl List {
+    pub fn new() -> List {
+        Nil
+    }
+
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Nil
+    }
+
+    // mem::replace swaps Nil in for a moment so we can take ownership of the
+    // old list and box it up as the new tail
+    pub fn push_front(&mut self, value: i32) {
+        let rest = mem::replace(self, Nil);
+        *self = Cons(value, Box::new(rest));
+    }
+
+    // walk to the Nil at the end and put `other` there instead
+    pub fn append(&mut self, other: List) {
+        let mut tail = self;
+        while let Cons(_, next) = tail {
+            tail = next;
+        }
+        *tail = other;
+    }
+
+    pub fn iter(&self) -> Iter<'_> {
+        Iter { next: self }
+    }
+}
+
+impl Default for List {
+    fn default() -> List {
+        List::new()
+    }
+}
+
+// builds the list in the same order as the items, so [1, 2, 3] is
+// (1, (2, (3, Nil))). Collect into a Vec first and push_front from the back,
+// since pushing onto the front as items arrive would reverse them
+impl FromIterator<i32> for List {
+    fn from_iter<I: IntoIterator<Item = i32>>(iter: I) -> List {
+        let items: Vec<i32> = iter.into_iter().collect();
+        let mut list = Nil;
+        for value in items.into_iter().rev() {
+            list.push_front(value);
+        }
+        list
+    }
+}
+
+// borrows the list, so it yields &i32 and the list is still usable afterwards
+pub struct Iter<'a> {
+    next: &'a List,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a i32;
+
+    fn next(&mut self) -> Option<&'a i32> {
+        match self.next {
+            Cons(value, rest) => {
+                self.next = rest;
+                Some(value)
+            }
+            Nil => None,
+        }
+    }
+}
+
+// lets `for x in &list` work, the same way it does for &Vec
+impl<'a> IntoIterator for &'a List {
+    type Item = &'a i32;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+impl fmt::Display for List {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Cons(value, rest) => write!(f, "({}, {})", value, rest),
+            Nil => write!(f, "Nil"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn one_two_three() -> List {
+        Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Nil))))))
+    }
+
+    #[test]
+    fn len_counts_the_cons() {
+        assert_eq!(one_two_three().len(), 3);
+        assert_eq!(Nil.len(), 0);
+        assert!(Nil.is_empty());
+        assert!(!one_two_three().is_empty());
+    }
+
+    #[test]
+    fn push_front_adds_to_the_head() {
+        let mut list = List::new();
+        list.push_front(3);
+        list.push_front(2);
+        list.push_front(1);
+        assert_eq!(list, one_two_three());
+    }
+
+    #[test]
+    fn append_joins_lists() {
+        let mut list: List = [1, 2].into_iter().collect();
+        list.append([3].into_iter().collect());
+        assert_eq!(list, one_two_three());
+
+        // appending onto, or appending, an empty list
+        let mut empty = List::new();
+        empty.append(one_two_three());
+        assert_eq!(empty, one_two_three());
+        let mut list = one_two_three();
+        list.append(Nil);
+        assert_eq!(list, one_two_three());
+    }
+
+    #[test]
+    fn iter_borrows_in_order() {
+        let list = one_two_three();
+        let values: Vec<&i32> = list.iter().collect();
+        assert_eq!(values, vec![&1, &2, &3]);
+        assert_eq!(list.iter().sum::<i32>(), 6);
+
+        let mut doubled = Vec::new();
+        for value in &list {
+            doubled.push(value * 2);
+        }
+        assert_eq!(doubled, vec![2, 4, 6]);
+        // still ours after all that borrowing
+        assert_eq!(list.len(), 3);
+        assert_eq!(Nil.iter().next(), None);
+    }
+
+    #[test]
+    fn collect_keeps_order() {
+        let list: List = (1..=3).collect();
+        assert_eq!(list, one_two_three());
+        assert_eq!(std::iter::empty().collect::<List>(), Nil);
+    }
+
+    #[test]
+    fn display_like_the_book() {
+        assert_eq!(one_two_three().to_string(), "(1, (2, (3, Nil)))");
+        assert_eq!(Nil.to_string(), "Nil");
+        assert_eq!(Cons(-4, Box::new(Nil)).to_string(), "(-4, Nil)");
+    }
+}
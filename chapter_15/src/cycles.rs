@@ -0,0 +1,189 @@
+// 15.6 Reference Cycles Can Leak Memory
+
+/*
+Rust makes memory leaks hard, but not impossible. With Rc and RefCell we can
+make two values that own each other: a's tail points at b and b's tail points
+at a. When the variables a and b go out of scope each strong_count only drops
+from 2 to 1, since the other list still owns it, so neither is ever cleaned up.
+Nothing crashes and no error is reported, the memory just never comes back.
+
+The book's List is the cons list again, but with the *tail* in a RefCell so it
+can be changed after the list is built (which is what lets us close the loop).
+It's generic here so the tests can put CycleProbe values in it.
+
+Careful with {:?} on a list that's part of a cycle: Debug follows the tails
+round and round until the stack overflows.
+*/
+
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::ops::Deref;
+use std::rc::Rc;
+
+#[derive(Debug)]
+pub enum List<T> {
+    Cons(T, RefCell<Rc<List<T>>>),
+    Nil,
+}
+
+use List::Cons;
+
+impl<T> List<T> {
+    pub fn tail(&self) -> Option<&RefCell<Rc<List<T>>>> {
+        match self {
+            Cons(_, item) => Some(item),
+            List::Nil => None,
+        }
+    }
+}
+
+/*
+To prove the cycle leaks we need to see what *didn't* get dropped. A CycleProbe
+hands out Tracked values that count themselves: `track` adds one to `created`
+and a Tracked's Drop adds one to `dropped`. Whatever is left over, alive(), has
+leaked (or is still in use). Clones of a probe share the same counts.
+*/
+#[derive(Debug, Default, Clone)]
+pub struct CycleProbe {
+    created: Rc<Cell<usize>>,
+    dropped: Rc<Cell<usize>>,
+}
+
+impl CycleProbe {
+    pub fn new() -> CycleProbe {
+        CycleProbe::default()
+    }
+
+    pub fn track<T>(&self, value: T) -> Tracked<T> {
+        self.created.set(self.created.get() + 1);
+        Tracked {
+            value,
+            dropped: Rc::clone(&self.dropped),
+        }
+    }
+
+    pub fn created(&self) -> usize {
+        self.created.get()
+    }
+
+    pub fn dropped(&self) -> usize {
+        self.dropped.get()
+    }
+
+    pub fn alive(&self) -> usize {
+        self.created() - self.dropped()
+    }
+}
+
+pub struct Tracked<T> {
+    value: T,
+    dropped: Rc<Cell<usize>>,
+}
+
+impl<T> Deref for Tracked<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> Drop for Tracked<T> {
+    fn drop(&mut self) {
+        self.dropped.set(self.dropped.get() + 1);
+    }
+}
+
+// just the value, the counter isn't interesting to print
+impl<T: fmt::Debug> fmt::Debug for Tracked<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use List::Nil;
+
+    type TrackedList = Rc<List<Tracked<i32>>>;
+
+    // the book's setup: a is (5), b is (10, a), then a's tail is pointed at b
+    fn make_cycle(probe: &CycleProbe) -> (TrackedList, TrackedList) {
+        let a = Rc::new(Cons(probe.track(5), RefCell::new(Rc::new(Nil))));
+        let b = Rc::new(Cons(probe.track(10), RefCell::new(Rc::clone(&a))));
+        if let Some(link) = a.tail() {
+            *link.borrow_mut() = Rc::clone(&b);
+        }
+        (a, b)
+    }
+
+    #[test]
+    fn counts_like_the_book() {
+        let probe = CycleProbe::new();
+        let a = Rc::new(Cons(probe.track(5), RefCell::new(Rc::new(Nil))));
+        assert_eq!(Rc::strong_count(&a), 1);
+        assert!(matches!(**a.tail().unwrap().borrow(), Nil));
+
+        let b = Rc::new(Cons(probe.track(10), RefCell::new(Rc::clone(&a))));
+        assert_eq!(Rc::strong_count(&a), 2);
+        assert_eq!(Rc::strong_count(&b), 1);
+
+        if let Some(link) = a.tail() {
+            *link.borrow_mut() = Rc::clone(&b);
+        }
+        assert_eq!(Rc::strong_count(&a), 2);
+        assert_eq!(Rc::strong_count(&b), 2);
+    }
+
+    #[test]
+    fn probe_counts_drops() {
+        let probe = CycleProbe::new();
+        let first = probe.track("first");
+        let second = probe.track("second");
+        assert_eq!(*first, "first");
+        assert_eq!((probe.created(), probe.dropped()), (2, 0));
+        drop(first);
+        assert_eq!(probe.alive(), 1);
+        drop(second);
+        assert_eq!(probe.alive(), 0);
+    }
+
+    #[test]
+    fn lists_without_a_cycle_are_freed() {
+        let probe = CycleProbe::new();
+        {
+            let a = Rc::new(Cons(probe.track(5), RefCell::new(Rc::new(Nil))));
+            let _b = Rc::new(Cons(probe.track(10), RefCell::new(Rc::clone(&a))));
+            assert_eq!(probe.alive(), 2);
+        }
+        assert_eq!(probe.alive(), 0);
+    }
+
+    // This documents the leak: it passes *because* the cycle's values are
+    // never dropped. (The memory stays leaked until the test process exits.)
+    #[test]
+    fn a_cycle_is_never_dropped() {
+        let probe = CycleProbe::new();
+        {
+            let (a, b) = make_cycle(&probe);
+            assert_eq!(Rc::strong_count(&a), 2);
+            assert_eq!(Rc::strong_count(&b), 2);
+        }
+        // a and b are out of scope, but each list still owns the other
+        assert_eq!(probe.created(), 2);
+        assert_eq!(probe.dropped(), 0);
+        assert_eq!(probe.alive(), 2);
+    }
+
+    #[test]
+    fn breaking_the_cycle_frees_everything() {
+        let probe = CycleProbe::new();
+        {
+            let (a, _b) = make_cycle(&probe);
+            // point a back at Nil before letting go, and the loop is gone
+            *a.tail().unwrap().borrow_mut() = Rc::new(Nil);
+        }
+        assert_eq!(probe.alive(), 0);
+    }
+}
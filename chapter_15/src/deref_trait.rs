@@ -14,7 +14,7 @@ to work in ways similar to references. Then we'll look at Rust's deref coercion
 feature and how it lets us work with ref or smart pointers.
 */
 
-pub fn _run() {
+pub fn run() {
     // Following the Pointer to the Value
 
     /*
@@ -60,13 +60,17 @@ pub fn _run() {
     the Rust version.
     */
 
-    struct MyBox<T>(T);
+    // struct MyBox<T>(T);
 
-    impl<T> MyBox<T> {
-        fn new(x: T) -> MyBox<T> {
-            MyBox(x)
-        }
-    }
+    // impl<T> MyBox<T> {
+    //     fn new(x: T) -> MyBox<T> {
+    //         MyBox(x)
+    //     }
+    // }
+
+    // the full MyBox (with DerefMut and Drop too) now lives in my_box.rs, the
+    // snippets here are kept as comments to follow along with
+    use crate::my_box::MyBox;
 
     /*
     At this point if we try to substitute MyBox for Box in the example above we
@@ -82,15 +86,15 @@ pub fn _run() {
     method that borrows self and returns a reference to the inner data.
     */
 
-    use std::ops::Deref;
+    // use std::ops::Deref;
 
-    impl<T> Deref for MyBox<T> {
-        type Target = T;
+    // impl<T> Deref for MyBox<T> {
+    //     type Target = T;
 
-        fn deref(&self) -> &Self::Target {
-            &self.0
-        }
-    }
+    //     fn deref(&self) -> &Self::Target {
+    //         &self.0
+    //     }
+    // }
 
     /*
     The 'type Target = T' syntax defines an associated type for the Deref trait to
@@ -166,4 +170,13 @@ pub fn _run() {
     data. Converting one mutable reference to one immutable reference will never
     break that rule, but going the other way very well could. 
     */
+
+    // MyBox implements DerefMut too, so the second case works with it
+    fn exclaim(s: &mut String) {
+        s.push('!');
+    }
+
+    let mut m2 = MyBox::new(String::from("Rust"));
+    exclaim(&mut m2); // &mut MyBox<String> -> &mut String
+    hello(&m2); // and &MyBox<String> -> &str still works
 }
\ No newline at end of file
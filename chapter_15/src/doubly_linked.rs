@@ -0,0 +1,318 @@
+// 15.6 (continued) A Doubly Linked List: Rc Forward, Weak Back
+
+/*
+Each node points at both its neighbours. If both directions were Rcs, every
+pair of neighbours would own each other, which is the reference cycle from
+cycles.rs and nothing would ever be freed. So the same rule as the tree in
+tree.rs: one direction owns, the other direction only looks.
+
+  - next is an Rc: the list owns the head, the head owns the second node, ...
+  - prev is a Weak back to the node before
+  - the list's tail is a Weak too, the last node is already owned by the one
+    before it (or by head, if it's the only node)
+
+That leaves every node with exactly one strong owner, so when the list goes
+away the nodes go with it. The nodes are in RefCells because adding to the
+list means changing a node's next or prev after it's already shared.
+
+iter() hands out clones of the values. A plain &T can't be returned out of a
+RefCell once the borrow() guard is gone, and the guard can't outlive the step
+of the loop that made it.
+
+The iterators hold an Rc to the node they'll visit next, which is a second
+strong owner for as long as they're around. So they borrow the list as well:
+the PhantomData<&'a DoublyLinkedList<T>> field gives them the lifetime of
+&self without storing the reference (PhantomData takes up no space, it only
+tells the compiler what the struct acts like it holds). With that, popping
+from the list while an iterator is still alive is a borrow error at compile
+time, instead of a node with two owners that pop_front can't take back.
+*/
+
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::rc::{Rc, Weak};
+
+type Link<T> = Rc<RefCell<Node<T>>>;
+
+struct Node<T> {
+    value: T,
+    next: Option<Link<T>>,
+    prev: Option<Weak<RefCell<Node<T>>>>,
+}
+
+pub struct DoublyLinkedList<T> {
+    head: Option<Link<T>>,
+    tail: Option<Weak<RefCell<Node<T>>>>,
+    len: usize,
+}
+
+impl<T> DoublyLinkedList<T> {
+    pub fn new() -> DoublyLinkedList<T> {
+        DoublyLinkedList {
+            head: None,
+            tail: None,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn push_front(&mut self, value: T) {
+        let node = Rc::new(RefCell::new(Node {
+            value,
+            next: self.head.take(),
+            prev: None,
+        }));
+        match &node.borrow().next {
+            Some(old_head) => old_head.borrow_mut().prev = Some(Rc::downgrade(&node)),
+            None => self.tail = Some(Rc::downgrade(&node)),
+        }
+        self.head = Some(node);
+        self.len += 1;
+    }
+
+    pub fn push_back(&mut self, value: T) {
+        let old_tail = self.tail.as_ref().and_then(Weak::upgrade);
+        let node = Rc::new(RefCell::new(Node {
+            value,
+            next: None,
+            prev: old_tail.as_ref().map(Rc::downgrade),
+        }));
+        self.tail = Some(Rc::downgrade(&node));
+        // the node before owns the new one, or head does if the list was empty
+        match old_tail {
+            Some(old_tail) => old_tail.borrow_mut().next = Some(node),
+            None => self.head = Some(node),
+        }
+        self.len += 1;
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        let old_head = self.head.take()?;
+        match old_head.borrow_mut().next.take() {
+            Some(next) => {
+                next.borrow_mut().prev = None;
+                self.head = Some(next);
+            }
+            None => self.tail = None,
+        }
+        self.len -= 1;
+        // head was its only strong owner (everything else points at it with a
+        // Weak, and an iterator's Rc can't be alive while we have &mut self),
+        // so try_unwrap always gets the node back out
+        let node = Rc::try_unwrap(old_head)
+            .ok()
+            .expect("the list is the only owner of a node");
+        Some(node.into_inner().value)
+    }
+
+    /// The iterator borrows the list, so the list can't change under it:
+    ///
+    /// ```compile_fail
+    /// use chapter_15::doubly_linked::DoublyLinkedList;
+    ///
+    /// let mut list = DoublyLinkedList::new();
+    /// list.push_back(1);
+    /// let mut values = list.iter();
+    /// list.pop_front(); // error[E0502]: `list` is already borrowed
+    /// values.next();
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head.clone(),
+            list: PhantomData,
+        }
+    }
+
+    // the same thing backwards, following the prev Weaks from the tail
+    pub fn iter_rev(&self) -> IterRev<'_, T> {
+        IterRev {
+            next: self.tail.as_ref().and_then(Weak::upgrade),
+            list: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for DoublyLinkedList<T> {
+    fn default() -> DoublyLinkedList<T> {
+        DoublyLinkedList::new()
+    }
+}
+
+// Dropping head would drop the second node, which drops the third... one
+// nested drop per node, enough to overflow the stack on a long list. Popping
+// them one at a time keeps it flat
+impl<T> Drop for DoublyLinkedList<T> {
+    fn drop(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+}
+
+pub struct Iter<'a, T> {
+    next: Option<Link<T>>,
+    list: PhantomData<&'a DoublyLinkedList<T>>,
+}
+
+impl<T: Clone> Iterator for Iter<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let current = self.next.take()?;
+        let node = current.borrow();
+        self.next = node.next.clone();
+        Some(node.value.clone())
+    }
+}
+
+pub struct IterRev<'a, T> {
+    next: Option<Link<T>>,
+    list: PhantomData<&'a DoublyLinkedList<T>>,
+}
+
+impl<T: Clone> Iterator for IterRev<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let current = self.next.take()?;
+        let node = current.borrow();
+        self.next = node.prev.as_ref().and_then(Weak::upgrade);
+        Some(node.value.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cycles::CycleProbe;
+
+    fn list_of(values: &[i32]) -> DoublyLinkedList<i32> {
+        let mut list = DoublyLinkedList::new();
+        for &value in values {
+            list.push_back(value);
+        }
+        list
+    }
+
+    #[test]
+    fn push_back_keeps_order() {
+        let list = list_of(&[1, 2, 3]);
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.iter().collect::<Vec<i32>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn push_front_reverses() {
+        let mut list = DoublyLinkedList::new();
+        list.push_front(1);
+        list.push_front(2);
+        list.push_front(3);
+        assert_eq!(list.iter().collect::<Vec<i32>>(), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn mixed_pushes_and_both_directions() {
+        let mut list = DoublyLinkedList::new();
+        list.push_back(2);
+        list.push_front(1);
+        list.push_back(3);
+        list.push_front(0);
+        assert_eq!(list.iter().collect::<Vec<i32>>(), vec![0, 1, 2, 3]);
+        assert_eq!(list.iter_rev().collect::<Vec<i32>>(), vec![3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn pop_front_until_empty() {
+        let mut list = list_of(&[1, 2]);
+        assert_eq!(list.pop_front(), Some(1));
+        assert_eq!(list.len(), 1);
+        assert_eq!(list.pop_front(), Some(2));
+        assert_eq!(list.pop_front(), None);
+        assert!(list.is_empty());
+        assert_eq!(list.iter_rev().next(), None);
+
+        // and it still works after being emptied
+        list.push_back(7);
+        assert_eq!(list.iter_rev().collect::<Vec<i32>>(), vec![7]);
+    }
+
+    #[test]
+    fn prev_pointers_are_fixed_after_pop() {
+        let mut list = list_of(&[1, 2, 3]);
+        list.pop_front();
+        assert_eq!(list.iter_rev().collect::<Vec<i32>>(), vec![3, 2]);
+    }
+
+    #[test]
+    fn drains_after_iterating() {
+        let mut list = list_of(&[1, 2, 3]);
+        assert_eq!(list.iter().collect::<Vec<i32>>(), vec![1, 2, 3]);
+        // stopped partway, so each iterator still held an Rc to a node until
+        // it was dropped at the end of its statement
+        assert_eq!(list.iter().next(), Some(1));
+        assert_eq!(list.iter_rev().nth(1), Some(2));
+
+        let mut drained = Vec::new();
+        while let Some(value) = list.pop_front() {
+            drained.push(value);
+        }
+        assert_eq!(drained, vec![1, 2, 3]);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn each_node_has_one_owner() {
+        let list = list_of(&[1, 2, 3]);
+        let mut current = list.head.clone();
+        while let Some(node) = current {
+            // our clone, plus the one real owner
+            assert_eq!(Rc::strong_count(&node), 2);
+            current = node.borrow().next.clone();
+        }
+    }
+
+    #[test]
+    fn dropping_the_list_drops_every_value() {
+        // no cycle, thanks to the Weak prev pointers
+        let probe = CycleProbe::new();
+        {
+            let mut list = DoublyLinkedList::new();
+            for n in 0..5 {
+                list.push_back(probe.track(n));
+            }
+            assert_eq!(probe.alive(), 5);
+        }
+        assert_eq!(probe.alive(), 0);
+    }
+
+    #[test]
+    fn popped_values_belong_to_the_caller() {
+        let probe = CycleProbe::new();
+        let mut list = DoublyLinkedList::new();
+        list.push_back(probe.track("a"));
+        list.push_back(probe.track("b"));
+
+        let a = list.pop_front().unwrap();
+        assert_eq!(*a, "a");
+        drop(list);
+        // "b" went with the list, "a" is ours until we drop it
+        assert_eq!(probe.alive(), 1);
+        drop(a);
+        assert_eq!(probe.alive(), 0);
+    }
+
+    #[test]
+    fn long_lists_drop_without_overflowing() {
+        let mut list = DoublyLinkedList::new();
+        for n in 0..200_000 {
+            list.push_front(n);
+        }
+        assert_eq!(list.len(), 200_000);
+        drop(list);
+    }
+}
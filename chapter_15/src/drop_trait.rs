@@ -18,7 +18,7 @@ a value goes out of scope, and the compiler will insert this code automatically.
 As a result, we don't need to be as careful about placing cleanup code everywhere.
 */
 
-pub fn _run() {
+pub fn run() {
     /*
     We specify the code to run when a value goes out of scope by implementing the
     Drop trait. The Drop trait requires that we implement one method named drop
@@ -66,4 +66,56 @@ pub fn _run() {
     println!("CustomSmartPointer created... again.");
     drop(e);
     println!("CustomSmartPointer dropped before the end of run().");
+
+    // Scope Guards
+
+    /*
+    Because drop always runs at the end of a scope, a value whose only job is
+    to run some code in its drop works like `defer` in other languages. The
+    ScopeGuard in scope_guard.rs holds a closure and calls it when dropped, and
+    defer! is shorthand for making one.
+    */
+
+    use crate::defer;
+    use crate::scope_guard::ScopeGuard;
+
+    {
+        defer! { println!("deferred: runs last, when the block ends"); }
+        let _f = CustomSmartPointer {
+            data: String::from("made after the defer"),
+        };
+        println!("inside the block");
+        // _f is dropped first (made last), then the defer runs
+    }
+
+    let rollback = ScopeGuard::new(|| println!("rolling back!"));
+    println!("the work went fine, so no rollback needed");
+    rollback.dismiss();
+
+    // Not Dropping at All: mem::forget and ManuallyDrop
+
+    /*
+    The opposite problem: sometimes a value must *not* be dropped, usually
+    because something else (often C code, Chapter 19) has taken over cleaning
+    it up. mem::forget gives the value away without ever dropping it, and
+    ManuallyDrop wraps a value so leaving the scope skips its drop. These are
+    in manual_drop.rs with tests counting exactly which drops ran.
+    */
+
+    use crate::manual_drop::{self, DropNow};
+
+    manual_drop::forget(CustomSmartPointer {
+        data: String::from("forgotten, you won't see this one dropped"),
+    });
+
+    let kept = manual_drop::defuse(CustomSmartPointer {
+        data: String::from("defused"),
+    });
+    println!("defused value still readable: {}", kept.data);
+
+    let mut early = DropNow::new(CustomSmartPointer {
+        data: String::from("dropped on request"),
+    });
+    early.drop_now();
+    println!("DropNow already dropped its value: {}", early.is_dropped());
 }
\ No newline at end of file
@@ -0,0 +1,341 @@
+// 15.1 (continued) A Recursive Type That Earns Its Box: Expression Trees
+
+/*
+The cons list is the book's example of a recursive type, but nobody really
+writes one in Rust. Expression trees are where recursive enums show up for
+real (compilers, calculators, query planners). An expression is either a
+number, or an operation on smaller expressions:
+
+    (1 + 2) * -(3)   is   Mul(Add(Num(1), Num(2)), Neg(Num(3)))
+
+Just like List, Expr can't hold an Expr directly (it'd be infinitely big), so
+every sub-expression is behind a Box. And everything we do with a tree is
+recursive too: eval evaluates the children and combines them, Display prints
+the children and joins them.
+
+Display only adds the parentheses that are needed. Each kind of expression has
+a precedence (* binds tighter than +, and unary - tighter than both), and a
+child gets parentheses when it binds more loosely than its parent. The parser
+reads a + b + c as (a + b) + c, so a right hand child also gets them when it's
+equal, which keeps parse(expr.to_string()) == expr.
+
+Negative numbers need one more rule for that. A `-` written right against a
+number is part of it, so "-3" is the literal Num(-3) (that's how Num(-3) is
+displayed, so it has to read back the same way). Negating a number is written
+with parentheses, -(3), or with a space, - 3.
+*/
+
+use std::error::Error;
+use std::fmt;
+use std::iter::Peekable;
+use std::str::Chars;
+
+#[derive(Debug, PartialEq)]
+pub enum Expr {
+    Num(f64),
+    Add(Box<Expr>, Box<Expr>),
+    Mul(Box<Expr>, Box<Expr>),
+    Neg(Box<Expr>),
+}
+
+use Expr::{Add, Mul, Neg, Num};
+
+impl Expr {
+    pub fn eval(&self) -> f64 {
+        match self {
+            Num(n) => *n,
+            Add(a, b) => a.eval() + b.eval(),
+            Mul(a, b) => a.eval() * b.eval(),
+            Neg(a) => -a.eval(),
+        }
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            Add(..) => 1,
+            Mul(..) => 2,
+            Neg(_) => 3,
+            Num(_) => 4,
+        }
+    }
+
+    // a few shorthands so building trees by hand isn't all Box::new
+    pub fn plus(a: Expr, b: Expr) -> Expr {
+        Add(Box::new(a), Box::new(b))
+    }
+
+    pub fn times(a: Expr, b: Expr) -> Expr {
+        Mul(Box::new(a), Box::new(b))
+    }
+
+    pub fn negate(a: Expr) -> Expr {
+        Neg(Box::new(a))
+    }
+}
+
+// writes `child`, in parentheses if it binds more loosely than `min`
+fn write_child(f: &mut fmt::Formatter, child: &Expr, min: u8) -> fmt::Result {
+    if child.precedence() < min {
+        write!(f, "({})", child)
+    } else {
+        write!(f, "{}", child)
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let own = self.precedence();
+        match self {
+            Num(n) => write!(f, "{}", n),
+            Add(a, b) => {
+                write_child(f, a, own)?;
+                write!(f, " + ")?;
+                write_child(f, b, own + 1)
+            }
+            Mul(a, b) => {
+                write_child(f, a, own)?;
+                write!(f, " * ")?;
+                write_child(f, b, own + 1)
+            }
+            // -3 would read back as the literal Num(-3)
+            Neg(a) if matches!(**a, Num(_)) => write!(f, "-({})", a),
+            Neg(a) => {
+                write!(f, "-")?;
+                write_child(f, a, own)
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    UnexpectedEnd,
+    UnexpectedChar(char),
+    BadNumber(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::UnexpectedEnd => write!(f, "expression ended too soon"),
+            ParseError::UnexpectedChar(c) => write!(f, "didn't expect {:?}", c),
+            ParseError::BadNumber(text) => write!(f, "{:?} isn't a number", text),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/*
+The parser is recursive descent: one function per precedence level, each
+calling the next tighter one for its operands.
+
+    expr   = term ("+" term)*
+    term   = unary ("*" unary)*
+    unary  = "-" unary | atom
+    atom   = number | "(" expr ")"
+    number = ["-"] digits      (no space after the "-")
+
+There's no subtraction or division, Expr doesn't have them (a - b can be
+written a + -b).
+*/
+pub fn parse(input: &str) -> Result<Expr, ParseError> {
+    let mut parser = Parser {
+        chars: input.chars().peekable(),
+    };
+    let expr = parser.expr()?;
+    match parser.peek() {
+        None => Ok(expr),
+        Some(c) => Err(ParseError::UnexpectedChar(c)),
+    }
+}
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl Parser<'_> {
+    // the next non-space character, without taking it
+    fn peek(&mut self) -> Option<char> {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+        self.chars.peek().copied()
+    }
+
+    fn expr(&mut self) -> Result<Expr, ParseError> {
+        let mut left = self.term()?;
+        while self.peek() == Some('+') {
+            self.chars.next();
+            left = Expr::plus(left, self.term()?);
+        }
+        Ok(left)
+    }
+
+    fn term(&mut self) -> Result<Expr, ParseError> {
+        let mut left = self.unary()?;
+        while self.peek() == Some('*') {
+            self.chars.next();
+            left = Expr::times(left, self.unary()?);
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, ParseError> {
+        if self.peek() == Some('-') {
+            self.chars.next();
+            // peeking at chars directly, a space in between means negation
+            if self
+                .chars
+                .peek()
+                .is_some_and(|c| c.is_ascii_digit() || *c == '.')
+            {
+                return self.number("-");
+            }
+            return Ok(Expr::negate(self.unary()?));
+        }
+        self.atom()
+    }
+
+    fn atom(&mut self) -> Result<Expr, ParseError> {
+        match self.peek() {
+            None => Err(ParseError::UnexpectedEnd),
+            Some('(') => {
+                self.chars.next();
+                let inner = self.expr()?;
+                match self.peek() {
+                    Some(')') => {
+                        self.chars.next();
+                        Ok(inner)
+                    }
+                    Some(c) => Err(ParseError::UnexpectedChar(c)),
+                    None => Err(ParseError::UnexpectedEnd),
+                }
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => self.number(""),
+            Some(c) => Err(ParseError::UnexpectedChar(c)),
+        }
+    }
+
+    // reads the digits and dots that come next, after `sign`
+    fn number(&mut self, sign: &str) -> Result<Expr, ParseError> {
+        let mut text = String::from(sign);
+        while let Some(c) = self.chars.next_if(|c| c.is_ascii_digit() || *c == '.') {
+            text.push(c);
+        }
+        text.parse()
+            .map(Num)
+            .map_err(|_| ParseError::BadNumber(text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn n(value: f64) -> Expr {
+        Num(value)
+    }
+
+    #[test]
+    fn eval_by_hand_built_tree() {
+        // (1 + 2) * -3
+        let expr = Expr::times(Expr::plus(n(1.0), n(2.0)), Expr::negate(n(3.0)));
+        assert_eq!(expr.eval(), -9.0);
+    }
+
+    #[test]
+    fn display_adds_only_needed_parens() {
+        let expr = Expr::times(Expr::plus(n(1.0), n(2.0)), Expr::negate(n(3.0)));
+        assert_eq!(expr.to_string(), "(1 + 2) * -(3)");
+        assert_eq!(Expr::times(n(2.0), n(-3.0)).to_string(), "2 * -3");
+
+        let expr = Expr::plus(n(1.0), Expr::times(n(2.0), n(3.0)));
+        assert_eq!(expr.to_string(), "1 + 2 * 3");
+
+        let expr = Expr::negate(Expr::plus(n(1.0), n(2.5)));
+        assert_eq!(expr.to_string(), "-(1 + 2.5)");
+    }
+
+    #[test]
+    fn display_keeps_grouping_on_the_right() {
+        let left = Expr::plus(Expr::plus(n(1.0), n(2.0)), n(3.0));
+        let right = Expr::plus(n(1.0), Expr::plus(n(2.0), n(3.0)));
+        assert_eq!(left.to_string(), "1 + 2 + 3");
+        assert_eq!(right.to_string(), "1 + (2 + 3)");
+    }
+
+    #[test]
+    fn parse_respects_precedence() {
+        assert_eq!(
+            parse("1 + 2 * 3").unwrap(),
+            Expr::plus(n(1.0), Expr::times(n(2.0), n(3.0)))
+        );
+        assert_eq!(parse("1 + 2 * 3").unwrap().eval(), 7.0);
+        assert_eq!(parse("(1 + 2) * 3").unwrap().eval(), 9.0);
+        assert_eq!(parse("--4").unwrap(), Expr::negate(n(-4.0)));
+        assert_eq!(parse("- -4").unwrap(), Expr::negate(n(-4.0)));
+        assert_eq!(parse("- - 4").unwrap(), Expr::negate(Expr::negate(n(4.0))));
+        assert_eq!(parse("2 * -0.5 + 10").unwrap().eval(), 9.0);
+    }
+
+    #[test]
+    fn parse_ignores_spaces() {
+        assert_eq!(
+            parse("  ( 1+2 )*3 ").unwrap(),
+            parse("(1 + 2) * 3").unwrap()
+        );
+    }
+
+    #[test]
+    fn to_string_round_trips() {
+        for input in [
+            "1",
+            "1 + 2 + 3",
+            "1 + (2 + 3)",
+            "(1 + 2) * (3 + 4)",
+            "-(1 + 2) * 3",
+            "2 * 3 * 4 + -5",
+            "((((7))))",
+            "-(3) * -3",
+        ] {
+            let expr = parse(input).unwrap();
+            assert_eq!(parse(&expr.to_string()).unwrap(), expr, "{}", input);
+        }
+    }
+
+    #[test]
+    fn negative_literals_round_trip() {
+        for expr in [
+            n(-3.0),
+            n(-0.5),
+            Expr::negate(n(3.0)),
+            Expr::negate(n(-3.0)),
+            Expr::negate(Expr::negate(n(3.0))),
+            Expr::times(n(-2.0), Expr::plus(n(1.0), n(-1.5))),
+        ] {
+            assert_eq!(parse(&expr.to_string()).unwrap(), expr, "{}", expr);
+        }
+        assert_eq!(parse("-3").unwrap(), n(-3.0));
+        assert_eq!(parse("- 3").unwrap(), Expr::negate(n(3.0)));
+        assert_eq!(parse("-(3)").unwrap(), Expr::negate(n(3.0)));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(parse(""), Err(ParseError::UnexpectedEnd));
+        assert_eq!(parse("1 +"), Err(ParseError::UnexpectedEnd));
+        assert_eq!(parse("(1 + 2"), Err(ParseError::UnexpectedEnd));
+        assert_eq!(parse("1 + x"), Err(ParseError::UnexpectedChar('x')));
+        assert_eq!(parse("1 2"), Err(ParseError::UnexpectedChar('2')));
+        assert_eq!(parse("1 - 2"), Err(ParseError::UnexpectedChar('-')));
+        assert_eq!(parse("1 -2"), Err(ParseError::UnexpectedChar('-')));
+        assert_eq!(
+            parse("-1.2.3"),
+            Err(ParseError::BadNumber(String::from("-1.2.3")))
+        );
+        assert_eq!(
+            parse("1.2.3"),
+            Err(ParseError::BadNumber(String::from("1.2.3")))
+        );
+        assert_eq!(parse("1 + x").unwrap_err().to_string(), "didn't expect 'x'");
+    }
+}
@@ -0,0 +1,350 @@
+// 15.1 (continued) A Recursive Type That Doesn't Need a Box: JSON Values
+
+/*
+A JSON value is null, true or false, a number, a string, or a list or an
+object made of more JSON values:
+
+    {"name": "Ferris", "tags": ["crab", "rust"], "age": 8}
+
+Like Expr, Value is recursive: an Array holds Values. But where Expr needed
+Box<Expr> for its children, Value gets away with plain Vec<Value>. The Box was
+there to give Expr a known size, a pointer instead of a whole Expr inside an
+Expr inside an Expr... A Vec is already that: a pointer to the items on the
+heap plus a length and a capacity, the same size no matter what's in it. So
+Vec (and String) already do Box's job, and adding a Box on top would just be
+one more pointer to follow.
+
+Object is a Vec of (key, value) pairs instead of a HashMap, so the keys come
+back out in the order they went in, which is what a pretty printer wants.
+Looking a key up is a linear search, which is fine for the few keys a JSON
+object usually has.
+*/
+
+use std::fmt::{self, Write};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    // the value under `key`, if self is an Object and has one. With the same
+    // key twice, the first one wins
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(fields) => fields
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    // the item at `index`, if self is an Array that long
+    pub fn at(&self, index: usize) -> Option<&Value> {
+        match self {
+            Value::Array(items) => items.get(index),
+            _ => None,
+        }
+    }
+
+    // Follows a path of keys and indexes down into nested values:
+    //     "a.b[2]"   is   value.get("a")?.get("b")?.at(2)
+    //     "[0].name" starts with an index, for when the top is an Array
+    //     ""         is the value itself
+    // None if anything along the way is missing, the wrong kind of value, or
+    // the path itself doesn't make sense ("a..b", "a[x]", "a[1"). A key with a
+    // . or a [ in it can't be reached this way, use get for those
+    pub fn get_path(&self, path: &str) -> Option<&Value> {
+        if path.is_empty() {
+            return Some(self);
+        }
+        let mut current = self;
+        for segment in path.split('.') {
+            let (key, mut indexes) = match segment.find('[') {
+                Some(at) => segment.split_at(at),
+                None => (segment, ""),
+            };
+            match (key.is_empty(), indexes.is_empty()) {
+                // an empty segment, from "a..b" or a . at either end
+                (true, true) => return None,
+                // just indexes, like the "[0]" in "[0].name"
+                (true, false) => (),
+                (false, _) => current = current.get(key)?,
+            }
+            while !indexes.is_empty() {
+                let (index, after) = indexes.strip_prefix('[')?.split_once(']')?;
+                current = current.at(index.parse().ok()?)?;
+                indexes = after;
+            }
+        }
+        Some(current)
+    }
+
+    // every Array and Object on its own lines, indented two spaces a level.
+    // Empty ones and everything else look the same as in Display
+    pub fn pretty(&self) -> String {
+        let mut out = String::new();
+        self.write_pretty(&mut out, 0);
+        out
+    }
+
+    fn write_pretty(&self, out: &mut String, depth: usize) {
+        let indent = |out: &mut String, depth: usize| out.push_str(&"  ".repeat(depth));
+        match self {
+            Value::Array(items) if !items.is_empty() => {
+                out.push_str("[\n");
+                for (i, item) in items.iter().enumerate() {
+                    indent(out, depth + 1);
+                    item.write_pretty(out, depth + 1);
+                    if i + 1 < items.len() {
+                        out.push(',');
+                    }
+                    out.push('\n');
+                }
+                indent(out, depth);
+                out.push(']');
+            }
+            Value::Object(fields) if !fields.is_empty() => {
+                out.push_str("{\n");
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    indent(out, depth + 1);
+                    write_string(out, key).unwrap();
+                    out.push_str(": ");
+                    value.write_pretty(out, depth + 1);
+                    if i + 1 < fields.len() {
+                        out.push(',');
+                    }
+                    out.push('\n');
+                }
+                indent(out, depth);
+                out.push('}');
+            }
+            // writing to a String can't fail, so unwrap is safe here
+            other => write!(out, "{}", other).unwrap(),
+        }
+    }
+}
+
+// a JSON string: in double quotes, with quotes, backslashes and control
+// characters escaped. Generic over fmt::Write so it works for both a
+// Formatter and a String
+fn write_string(out: &mut impl Write, s: &str) -> fmt::Result {
+    out.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => out.write_str("\\\"")?,
+            '\\' => out.write_str("\\\\")?,
+            '\n' => out.write_str("\\n")?,
+            '\t' => out.write_str("\\t")?,
+            '\r' => out.write_str("\\r")?,
+            c if c.is_control() => write!(out, "\\u{:04x}", c as u32)?,
+            c => out.write_char(c)?,
+        }
+    }
+    out.write_char('"')
+}
+
+// the compact form, all on one line: {"a":[1,2]}
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Null => write!(f, "null"),
+            Value::Bool(b) => write!(f, "{}", b),
+            // f64's Display already leaves off a trailing .0. JSON has no NaN
+            // or infinity, so those become null (like JavaScript's
+            // JSON.stringify)
+            Value::Number(n) if n.is_finite() => write!(f, "{}", n),
+            Value::Number(_) => write!(f, "null"),
+            Value::String(s) => write_string(f, s),
+            Value::Array(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
+            Value::Object(fields) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object(fields: Vec<(&str, Value)>) -> Value {
+        Value::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+        )
+    }
+
+    fn string(s: &str) -> Value {
+        Value::String(s.to_string())
+    }
+
+    // {"a": {"b": [10, 20, {"c": true}]}, "list": [[1, 2], [3]], "name": "x"}
+    fn nested() -> Value {
+        object(vec![
+            (
+                "a",
+                object(vec![(
+                    "b",
+                    Value::Array(vec![
+                        Value::Number(10.0),
+                        Value::Number(20.0),
+                        object(vec![("c", Value::Bool(true))]),
+                    ]),
+                )]),
+            ),
+            (
+                "list",
+                Value::Array(vec![
+                    Value::Array(vec![Value::Number(1.0), Value::Number(2.0)]),
+                    Value::Array(vec![Value::Number(3.0)]),
+                ]),
+            ),
+            ("name", string("x")),
+        ])
+    }
+
+    #[test]
+    fn get_path_walks_down() {
+        let value = nested();
+        assert_eq!(value.get_path("a.b[1]"), Some(&Value::Number(20.0)));
+        assert_eq!(value.get_path("a.b[2].c"), Some(&Value::Bool(true)));
+        assert_eq!(value.get_path("list[1][0]"), Some(&Value::Number(3.0)));
+        assert_eq!(value.get_path("name"), Some(&string("x")));
+        assert_eq!(value.get_path(""), Some(&value));
+        assert_eq!(
+            value.get_path("a.b").and_then(|b| b.get_path("[0]")),
+            Some(&Value::Number(10.0))
+        );
+    }
+
+    #[test]
+    fn get_path_missing_or_wrong_kind() {
+        let value = nested();
+        // no such key, index past the end
+        assert_eq!(value.get_path("a.z"), None);
+        assert_eq!(value.get_path("a.b[3]"), None);
+        // indexing an object, or looking up a key in an array or a number
+        assert_eq!(value.get_path("a[0]"), None);
+        assert_eq!(value.get_path("list.b"), None);
+        assert_eq!(value.get_path("a.b[0].c"), None);
+        // paths that don't make sense
+        for path in [
+            "a..b", ".a", "a.", "a.b[x]", "a.b[1", "a.b1]", "a.b[]", "a.b[-1]",
+        ] {
+            assert_eq!(value.get_path(path), None, "{}", path);
+        }
+    }
+
+    #[test]
+    fn arrays_at_the_top() {
+        let value = Value::Array(vec![object(vec![("name", string("ann"))])]);
+        assert_eq!(value.get_path("[0].name"), Some(&string("ann")));
+        assert_eq!(value.get_path("[1].name"), None);
+    }
+
+    #[test]
+    fn first_key_wins() {
+        let value = object(vec![("k", Value::Null), ("k", Value::Bool(false))]);
+        assert_eq!(value.get("k"), Some(&Value::Null));
+    }
+
+    #[test]
+    fn compact_display() {
+        assert_eq!(
+            nested().to_string(),
+            r#"{"a":{"b":[10,20,{"c":true}]},"list":[[1,2],[3]],"name":"x"}"#
+        );
+        assert_eq!(Value::Number(1.5).to_string(), "1.5");
+        assert_eq!(Value::Number(-0.25).to_string(), "-0.25");
+        assert_eq!(Value::Number(f64::NAN).to_string(), "null");
+        assert_eq!(Value::Array(vec![]).to_string(), "[]");
+        assert_eq!(Value::Object(vec![]).to_string(), "{}");
+    }
+
+    #[test]
+    fn strings_are_escaped() {
+        assert_eq!(
+            string("say \"hi\"\\\n\t\u{1}é🦀").to_string(),
+            r#""say \"hi\"\\\n\t\u0001é🦀""#
+        );
+        // keys too
+        assert_eq!(
+            object(vec![("a\"b", Value::Null)]).to_string(),
+            r#"{"a\"b":null}"#
+        );
+    }
+
+    #[test]
+    fn pretty_prints_nested_values() {
+        let expected = r#"{
+  "a": {
+    "b": [
+      10,
+      20,
+      {
+        "c": true
+      }
+    ]
+  },
+  "list": [
+    [
+      1,
+      2
+    ],
+    [
+      3
+    ]
+  ],
+  "name": "x"
+}"#;
+        assert_eq!(nested().pretty(), expected);
+    }
+
+    #[test]
+    fn pretty_leaves_empty_and_simple_values_alone() {
+        assert_eq!(Value::Null.pretty(), "null");
+        assert_eq!(string("hi").pretty(), "\"hi\"");
+        let value = object(vec![
+            ("empty", Value::Array(vec![])),
+            ("none", Value::Object(vec![])),
+        ]);
+        assert_eq!(value.pretty(), "{\n  \"empty\": [],\n  \"none\": {}\n}");
+    }
+
+    #[test]
+    fn no_box_needed() {
+        // the Values inside an Array are on the heap, a Vec<Value> itself is
+        // only a pointer, a length and a capacity whatever it holds. So a
+        // Value is the size of its biggest variant (a Vec or a String) plus
+        // the tag saying which variant it is
+        let word = std::mem::size_of::<usize>();
+        assert_eq!(std::mem::size_of::<Vec<Value>>(), 3 * word);
+        assert!(std::mem::size_of::<Value>() <= 4 * word);
+    }
+}
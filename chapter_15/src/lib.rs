@@ -0,0 +1,76 @@
+// Chapter 15: Smart Pointers (library)
+// Box, Rc and RefCell in the data structures that need them: cons lists,
+// trees, a doubly linked list, an arena and an LRU cache, plus home made
+// MyBox, MyRc and MyRefCell to show what Deref, Drop and borrow checking at
+// run time are doing underneath
+// The walkthroughs for each section of the chapter are here as well,
+// exercises() lists them for the runner
+
+pub mod arena;
+pub mod box_pointer;
+pub mod cell;
+pub mod cons_list;
+pub mod cycles;
+pub mod deref_trait;
+pub mod doubly_linked;
+pub mod drop_trait;
+pub mod expr;
+pub mod json;
+pub mod limit_tracker;
+pub mod lru;
+pub mod manual_drop;
+pub mod messengers;
+pub mod my_box;
+pub mod my_rc;
+pub mod my_ref_cell;
+pub mod pointer_costs;
+pub mod rc_list;
+pub mod ref_cell;
+pub mod reference_counted;
+pub mod scope_guard;
+pub mod shapes;
+pub mod shared_list;
+pub mod tree;
+
+use domain::Section;
+
+// every walkthrough, in the book's order, for the runner
+pub fn exercises() -> Vec<Section> {
+    vec![
+        Section {
+            name: "box_pointer",
+            run: || {
+                box_pointer::run();
+                Ok(())
+            },
+        },
+        Section {
+            name: "deref_trait",
+            run: || {
+                deref_trait::run();
+                Ok(())
+            },
+        },
+        Section {
+            name: "drop_trait",
+            run: || {
+                drop_trait::run();
+                Ok(())
+            },
+        },
+        Section {
+            name: "reference_counted",
+            run: || {
+                reference_counted::run();
+                Ok(())
+            },
+        },
+        Section {
+            name: "ref_cell",
+            run: || {
+                ref_cell::run();
+                Ok(())
+            },
+        },
+    ]
+}
@@ -0,0 +1,778 @@
+// 15.5 (continued) LimitTracker with Configurable Thresholds
+
+/*
+The book's LimitTracker (still in ref_cell.rs as comments) has its three
+warnings hard-coded at 75%, 90% and 100%, and it sends a warning on *every*
+set_value that's over one, so a user sitting at 80% gets the same message
+again and again.
+
+This version takes its thresholds as a list of (fraction of max, message)
+pairs. Each threshold fires once when the value crosses it and stays quiet
+until the value drops back below it, which re-arms it. If one set_value jumps
+past several thresholds at once only the highest one is sent, the same as the
+book's if / else if chain.
+
+Messages are templates, these placeholders get filled in:
+    {value}      the value that was just set
+    {max}        the maximum
+    {threshold}  the threshold as a percentage, like 75
+
+For use as a real quota tracker there's also a builder (further down), which
+checks its settings instead of quietly accepting a max of 0, plus increment
+for "used some more" and remaining/percentage to ask where things stand.
+*/
+
+use std::error::Error;
+use std::fmt;
+
+pub trait Messenger {
+    fn send(&self, msg: &str);
+}
+
+struct Threshold {
+    fraction: f64,
+    template: String,
+    fired: bool,
+}
+
+pub struct LimitTracker<'a, T: Messenger> {
+    messenger: &'a T,
+    value: usize,
+    max: usize,
+    // kept sorted from lowest to highest fraction
+    thresholds: Vec<Threshold>,
+}
+
+impl<'a, T> LimitTracker<'a, T>
+where
+    T: Messenger,
+{
+    // the book's three warnings
+    pub fn new(messenger: &'a T, max: usize) -> LimitTracker<'a, T> {
+        LimitTracker::with_thresholds(
+            messenger,
+            max,
+            vec![
+                (0.75, "Warning: You've used up over 75% of your quota!"),
+                (
+                    0.9,
+                    "Urgent warning: You've used up over 90% of your quota!",
+                ),
+                (1.0, "Error: You are over your quota!"),
+            ],
+        )
+    }
+
+    pub fn with_thresholds(
+        messenger: &'a T,
+        max: usize,
+        thresholds: Vec<(f64, &str)>,
+    ) -> LimitTracker<'a, T> {
+        let mut thresholds: Vec<Threshold> = thresholds
+            .into_iter()
+            .map(|(fraction, template)| Threshold {
+                fraction,
+                template: String::from(template),
+                fired: false,
+            })
+            .collect();
+        // f64 isn't Ord (NaN), total_cmp gives it an order anyway
+        thresholds.sort_by(|a, b| a.fraction.total_cmp(&b.fraction));
+
+        LimitTracker {
+            messenger,
+            value: 0,
+            max,
+            thresholds,
+        }
+    }
+
+    /// Starts a [`LimitTrackerBuilder`]. Without any `threshold` calls the
+    /// tracker gets the book's 75%, 90% and 100% warnings.
+    ///
+    /// ```
+    /// use chapter_15::limit_tracker::LimitTracker;
+    /// use chapter_15::messengers::BufferMessenger;
+    ///
+    /// let messenger = BufferMessenger::new();
+    /// let mut tracker = LimitTracker::builder(&messenger)
+    ///     .max(200)
+    ///     .threshold(0.5, "half of {max} used")
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// tracker.set_value(120);
+    /// assert_eq!(messenger.messages(), vec!["half of 200 used"]);
+    /// ```
+    pub fn builder(messenger: &'a T) -> LimitTrackerBuilder<'a, T> {
+        LimitTrackerBuilder {
+            messenger,
+            max: None,
+            thresholds: None,
+            start: 0,
+        }
+    }
+
+    pub fn set_value(&mut self, value: usize) {
+        self.value = value;
+
+        let percentage_of_max = self.value as f64 / self.max as f64;
+
+        // re-arm everything we're now below, and find the highest threshold
+        // that's crossed but hasn't fired yet
+        let mut to_send = None;
+        for (i, threshold) in self.thresholds.iter_mut().enumerate() {
+            if percentage_of_max >= threshold.fraction {
+                if !threshold.fired {
+                    to_send = Some(i);
+                }
+                // lower ones are marked too, they've been passed even if we
+                // only send the highest
+                threshold.fired = true;
+            } else {
+                threshold.fired = false;
+            }
+        }
+
+        if let Some(i) = to_send {
+            let message = self.render(&self.thresholds[i]);
+            self.messenger.send(&message);
+        }
+    }
+
+    // back to 0 with every threshold ready to fire again
+    pub fn reset(&mut self) {
+        self.value = 0;
+        for threshold in self.thresholds.iter_mut() {
+            threshold.fired = false;
+        }
+    }
+
+    pub fn value(&self) -> usize {
+        self.value
+    }
+
+    pub fn max(&self) -> usize {
+        self.max
+    }
+
+    /// Adds `by` to the current value and sends a warning if that crosses a
+    /// threshold, exactly as `set_value` would. Saturates instead of
+    /// overflowing.
+    ///
+    /// ```
+    /// use chapter_15::limit_tracker::LimitTracker;
+    /// use chapter_15::messengers::BufferMessenger;
+    ///
+    /// let messenger = BufferMessenger::new();
+    /// let mut tracker = LimitTracker::builder(&messenger).max(10).build().unwrap();
+    ///
+    /// tracker.increment(5);
+    /// tracker.increment(3);
+    /// assert_eq!(tracker.value(), 8);
+    /// assert_eq!(
+    ///     messenger.messages(),
+    ///     vec!["Warning: You've used up over 75% of your quota!"]
+    /// );
+    /// ```
+    pub fn increment(&mut self, by: usize) {
+        self.set_value(self.value.saturating_add(by));
+    }
+
+    /// How much is left before the max, 0 once it's reached or passed.
+    ///
+    /// ```
+    /// use chapter_15::limit_tracker::LimitTracker;
+    /// use chapter_15::messengers::BufferMessenger;
+    ///
+    /// let messenger = BufferMessenger::new();
+    /// let mut tracker = LimitTracker::builder(&messenger).max(10).build().unwrap();
+    ///
+    /// tracker.set_value(4);
+    /// assert_eq!(tracker.remaining(), 6);
+    /// tracker.set_value(12);
+    /// assert_eq!(tracker.remaining(), 0);
+    /// ```
+    pub fn remaining(&self) -> usize {
+        self.max.saturating_sub(self.value)
+    }
+
+    /// The value as a percentage of the max. Can go over 100.
+    ///
+    /// ```
+    /// use chapter_15::limit_tracker::LimitTracker;
+    /// use chapter_15::messengers::BufferMessenger;
+    ///
+    /// let messenger = BufferMessenger::new();
+    /// let mut tracker = LimitTracker::builder(&messenger).max(200).build().unwrap();
+    ///
+    /// tracker.set_value(50);
+    /// assert_eq!(tracker.percentage(), 25.0);
+    /// tracker.set_value(300);
+    /// assert_eq!(tracker.percentage(), 150.0);
+    /// ```
+    pub fn percentage(&self) -> f64 {
+        self.value as f64 / self.max as f64 * 100.0
+    }
+
+    fn render(&self, threshold: &Threshold) -> String {
+        threshold
+            .template
+            .replace("{value}", &self.value.to_string())
+            .replace("{max}", &self.max.to_string())
+            .replace(
+                "{threshold}",
+                &format!("{}", (threshold.fraction * 100.0).round()),
+            )
+    }
+}
+
+// The Builder
+
+/*
+new and with_thresholds take everything at once and check nothing: a max of 0
+divides by zero on every set_value, and a NaN threshold can never be crossed.
+The builder collects the settings one call at a time and build() checks them,
+returning a BuildError rather than a tracker that can't work. Each setter
+takes and returns self, so a tracker can be set up in one expression.
+*/
+
+#[derive(Debug, PartialEq)]
+pub enum BuildError {
+    MissingMax,
+    ZeroMax,
+    // not a number, or not above 0
+    BadThreshold(f64),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuildError::MissingMax => write!(f, "a LimitTracker needs a max"),
+            BuildError::ZeroMax => write!(f, "the max has to be more than 0"),
+            BuildError::BadThreshold(fraction) => write!(
+                f,
+                "threshold {} has to be a fraction above 0, like 0.75",
+                fraction
+            ),
+        }
+    }
+}
+
+impl Error for BuildError {}
+
+pub struct LimitTrackerBuilder<'a, T: Messenger> {
+    messenger: &'a T,
+    max: Option<usize>,
+    // None until the first threshold() call, so we know whether to use the
+    // book's defaults
+    thresholds: Option<Vec<(f64, String)>>,
+    start: usize,
+}
+
+impl<'a, T> LimitTrackerBuilder<'a, T>
+where
+    T: Messenger,
+{
+    /// The limit the thresholds are fractions of. Required, and it has to be
+    /// more than 0.
+    ///
+    /// ```
+    /// use chapter_15::limit_tracker::LimitTracker;
+    /// use chapter_15::messengers::BufferMessenger;
+    ///
+    /// let messenger = BufferMessenger::new();
+    /// let tracker = LimitTracker::builder(&messenger).max(50).build().unwrap();
+    /// assert_eq!(tracker.max(), 50);
+    /// assert_eq!(tracker.remaining(), 50);
+    /// ```
+    pub fn max(mut self, max: usize) -> LimitTrackerBuilder<'a, T> {
+        self.max = Some(max);
+        self
+    }
+
+    /// Adds one threshold, a fraction of the max (0.75 is 75%) and the
+    /// message template to send when it's crossed. Fractions above 1.0 are
+    /// allowed, for warnings once the limit's been overshot.
+    ///
+    /// ```
+    /// use chapter_15::limit_tracker::{BuildError, LimitTracker};
+    /// use chapter_15::messengers::BufferMessenger;
+    ///
+    /// let messenger = BufferMessenger::new();
+    /// let result = LimitTracker::builder(&messenger)
+    ///     .max(10)
+    ///     .threshold(f64::NAN, "never")
+    ///     .build();
+    /// assert!(matches!(result, Err(BuildError::BadThreshold(_))));
+    /// ```
+    pub fn threshold(mut self, fraction: f64, template: &str) -> LimitTrackerBuilder<'a, T> {
+        self.thresholds
+            .get_or_insert_with(Vec::new)
+            .push((fraction, String::from(template)));
+        self
+    }
+
+    /// A tracker that never sends anything, it only counts.
+    ///
+    /// ```
+    /// use chapter_15::limit_tracker::LimitTracker;
+    /// use chapter_15::messengers::BufferMessenger;
+    ///
+    /// let messenger = BufferMessenger::new();
+    /// let mut tracker = LimitTracker::builder(&messenger)
+    ///     .max(10)
+    ///     .no_thresholds()
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// tracker.set_value(25);
+    /// assert_eq!(tracker.percentage(), 250.0);
+    /// assert!(messenger.messages().is_empty());
+    /// ```
+    pub fn no_thresholds(mut self) -> LimitTrackerBuilder<'a, T> {
+        self.thresholds = Some(Vec::new());
+        self
+    }
+
+    /// Where the value starts. Thresholds it's already past count as fired,
+    /// so build() doesn't send anything.
+    ///
+    /// ```
+    /// use chapter_15::limit_tracker::LimitTracker;
+    /// use chapter_15::messengers::BufferMessenger;
+    ///
+    /// let messenger = BufferMessenger::new();
+    /// let mut tracker = LimitTracker::builder(&messenger)
+    ///     .max(100)
+    ///     .starting_at(80)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(tracker.value(), 80);
+    /// assert!(messenger.messages().is_empty());
+    ///
+    /// // 75% already counted as fired, so only 90% is new
+    /// tracker.set_value(95);
+    /// assert_eq!(
+    ///     messenger.messages(),
+    ///     vec!["Urgent warning: You've used up over 90% of your quota!"]
+    /// );
+    /// ```
+    pub fn starting_at(mut self, value: usize) -> LimitTrackerBuilder<'a, T> {
+        self.start = value;
+        self
+    }
+
+    /// Checks the settings and makes the tracker.
+    ///
+    /// ```
+    /// use chapter_15::limit_tracker::{BuildError, LimitTracker};
+    /// use chapter_15::messengers::BufferMessenger;
+    ///
+    /// let messenger = BufferMessenger::new();
+    /// assert_eq!(
+    ///     LimitTracker::builder(&messenger).build().err(),
+    ///     Some(BuildError::MissingMax)
+    /// );
+    /// assert_eq!(
+    ///     LimitTracker::builder(&messenger).max(0).build().err(),
+    ///     Some(BuildError::ZeroMax)
+    /// );
+    /// ```
+    pub fn build(self) -> Result<LimitTracker<'a, T>, BuildError> {
+        let max = match self.max {
+            None => return Err(BuildError::MissingMax),
+            Some(0) => return Err(BuildError::ZeroMax),
+            Some(max) => max,
+        };
+
+        let mut tracker = match self.thresholds {
+            None => LimitTracker::new(self.messenger, max),
+            Some(thresholds) => {
+                if let Some(&(bad, _)) = thresholds
+                    .iter()
+                    .find(|(fraction, _)| fraction.is_nan() || *fraction <= 0.0)
+                {
+                    return Err(BuildError::BadThreshold(bad));
+                }
+                let thresholds: Vec<(f64, &str)> = thresholds
+                    .iter()
+                    .map(|(fraction, template)| (*fraction, template.as_str()))
+                    .collect();
+                LimitTracker::with_thresholds(self.messenger, max, thresholds)
+            }
+        };
+
+        // mark what's already passed without telling anyone
+        tracker.value = self.start;
+        let percentage_of_max = self.start as f64 / max as f64;
+        for threshold in tracker.thresholds.iter_mut() {
+            threshold.fired = percentage_of_max >= threshold.fraction;
+        }
+        Ok(tracker)
+    }
+}
+
+/*
+The mock from ref_cell.rs, grown a bit. Each message is recorded along with a
+sequence number (the order it arrived in) and a timestamp. The mock can't know
+the real time in a test, so the test sets the "current time" itself with
+set_time and every message sent after that is stamped with it.
+
+The helpers at the bottom let the tests say what they mean
+(assert_sent_containing("75%")) instead of digging through the RefCell.
+They all borrow the RefCell briefly and hand back owned values, so a test never
+ends up holding a borrow while the tracker tries to send.
+*/
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Sent {
+        seq: usize,
+        at: u64,
+        text: String,
+    }
+
+    struct MockMessenger {
+        sent_messages: RefCell<Vec<Sent>>,
+        now: Cell<u64>,
+    }
+
+    impl MockMessenger {
+        fn new() -> MockMessenger {
+            MockMessenger {
+                sent_messages: RefCell::new(vec![]),
+                now: Cell::new(0),
+            }
+        }
+
+        fn set_time(&self, at: u64) {
+            self.now.set(at);
+        }
+
+        fn sent(&self) -> Vec<Sent> {
+            self.sent_messages.borrow().clone()
+        }
+
+        fn texts(&self) -> Vec<String> {
+            self.sent_messages
+                .borrow()
+                .iter()
+                .map(|sent| sent.text.clone())
+                .collect()
+        }
+
+        fn sent_count(&self) -> usize {
+            self.sent_messages.borrow().len()
+        }
+
+        fn last_message(&self) -> Option<String> {
+            self.sent_messages
+                .borrow()
+                .last()
+                .map(|sent| sent.text.clone())
+        }
+
+        fn assert_sent_containing(&self, needle: &str) {
+            let texts = self.texts();
+            assert!(
+                texts.iter().any(|text| text.contains(needle)),
+                "no message containing {:?}, sent: {:?}",
+                needle,
+                texts
+            );
+        }
+    }
+
+    impl Messenger for MockMessenger {
+        fn send(&self, message: &str) {
+            let mut sent_messages = self.sent_messages.borrow_mut();
+            let seq = sent_messages.len() + 1;
+            sent_messages.push(Sent {
+                seq,
+                at: self.now.get(),
+                text: String::from(message),
+            })
+        }
+    }
+
+    #[test]
+    fn it_sends_an_over_75_percent_warning_message() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 100);
+
+        limit_tracker.set_value(80);
+
+        assert_eq!(mock_messenger.sent_count(), 1);
+        mock_messenger.assert_sent_containing("over 75%");
+    }
+
+    #[test]
+    fn default_thresholds_match_the_book() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 100);
+
+        limit_tracker.set_value(50);
+        limit_tracker.set_value(75);
+        limit_tracker.set_value(95);
+        limit_tracker.set_value(100);
+
+        assert_eq!(
+            mock_messenger.texts(),
+            vec![
+                "Warning: You've used up over 75% of your quota!",
+                "Urgent warning: You've used up over 90% of your quota!",
+                "Error: You are over your quota!",
+            ]
+        );
+    }
+
+    #[test]
+    fn fires_once_per_crossing() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 100);
+
+        limit_tracker.set_value(80);
+        limit_tracker.set_value(85);
+        limit_tracker.set_value(89);
+        assert_eq!(mock_messenger.sent_count(), 1);
+
+        // dropping below 75% re-arms it
+        limit_tracker.set_value(10);
+        limit_tracker.set_value(76);
+        assert_eq!(mock_messenger.sent_count(), 2);
+    }
+
+    #[test]
+    fn jumping_past_several_sends_the_highest() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 100);
+
+        limit_tracker.set_value(120);
+        // and coming back down past 90 doesn't fire the already passed 75 again
+        limit_tracker.set_value(80);
+
+        assert_eq!(mock_messenger.sent_count(), 1);
+        assert_eq!(
+            mock_messenger.last_message().as_deref(),
+            Some("Error: You are over your quota!")
+        );
+    }
+
+    #[test]
+    fn custom_thresholds_and_templates() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::with_thresholds(
+            &mock_messenger,
+            200,
+            // out of order on purpose, they get sorted
+            vec![
+                (1.0, "{value}/{max}: limit reached"),
+                (0.5, "half way ({threshold}%) at {value}"),
+            ],
+        );
+
+        limit_tracker.set_value(100);
+        limit_tracker.set_value(150);
+        limit_tracker.set_value(200);
+
+        assert_eq!(
+            mock_messenger.texts(),
+            vec!["half way (50%) at 100", "200/200: limit reached"]
+        );
+    }
+
+    #[test]
+    fn reset_rearms_everything() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 100);
+
+        limit_tracker.set_value(100);
+        limit_tracker.reset();
+        assert_eq!(limit_tracker.value(), 0);
+
+        limit_tracker.set_value(100);
+        assert_eq!(
+            mock_messenger.texts(),
+            vec![
+                "Error: You are over your quota!",
+                "Error: You are over your quota!",
+            ]
+        );
+    }
+
+    #[test]
+    fn no_thresholds_no_messages() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::with_thresholds(&mock_messenger, 10, vec![]);
+        limit_tracker.set_value(1000);
+        assert_eq!(mock_messenger.sent_count(), 0);
+        assert_eq!(mock_messenger.last_message(), None);
+    }
+
+    #[test]
+    fn messages_are_numbered_and_stamped() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 10);
+
+        mock_messenger.set_time(100);
+        limit_tracker.set_value(8);
+        mock_messenger.set_time(250);
+        limit_tracker.set_value(9);
+        limit_tracker.set_value(10);
+
+        let sent = mock_messenger.sent();
+        let seq_and_time: Vec<(usize, u64)> = sent.iter().map(|s| (s.seq, s.at)).collect();
+        assert_eq!(seq_and_time, vec![(1, 100), (2, 250), (3, 250)]);
+        assert_eq!(sent[2].text, "Error: You are over your quota!");
+    }
+
+    #[test]
+    #[should_panic(expected = "no message containing \"90%\"")]
+    fn assert_sent_containing_reports_what_was_sent() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 100);
+        limit_tracker.set_value(80);
+        mock_messenger.assert_sent_containing("90%");
+    }
+
+    #[test]
+    fn builder_defaults_to_the_books_thresholds() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::builder(&mock_messenger)
+            .max(100)
+            .build()
+            .unwrap();
+
+        limit_tracker.set_value(80);
+        limit_tracker.set_value(100);
+        assert_eq!(
+            mock_messenger.texts(),
+            vec![
+                "Warning: You've used up over 75% of your quota!",
+                "Error: You are over your quota!",
+            ]
+        );
+    }
+
+    #[test]
+    fn builder_thresholds_replace_the_defaults() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::builder(&mock_messenger)
+            .max(10)
+            .threshold(1.5, "way over: {value}/{max}")
+            .threshold(0.5, "{threshold}% gone")
+            .build()
+            .unwrap();
+
+        limit_tracker.increment(5);
+        limit_tracker.increment(4);
+        limit_tracker.increment(6);
+        assert_eq!(mock_messenger.texts(), vec!["50% gone", "way over: 15/10"]);
+    }
+
+    #[test]
+    fn builder_rejects_bad_settings() {
+        let mock_messenger = MockMessenger::new();
+        let build = |fraction: f64| {
+            LimitTracker::builder(&mock_messenger)
+                .max(10)
+                .threshold(0.5, "fine")
+                .threshold(fraction, "bad")
+                .build()
+                .err()
+        };
+        assert_eq!(build(0.0), Some(BuildError::BadThreshold(0.0)));
+        assert_eq!(build(-0.5), Some(BuildError::BadThreshold(-0.5)));
+        assert!(matches!(build(f64::NAN), Some(BuildError::BadThreshold(f)) if f.is_nan()));
+        assert_eq!(build(2.0), None);
+        assert_eq!(
+            BuildError::ZeroMax.to_string(),
+            "the max has to be more than 0"
+        );
+    }
+
+    #[test]
+    fn no_thresholds_only_counts() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::builder(&mock_messenger)
+            .max(10)
+            .no_thresholds()
+            .build()
+            .unwrap();
+        limit_tracker.increment(100);
+        assert_eq!(mock_messenger.sent_count(), 0);
+        assert_eq!(limit_tracker.value(), 100);
+    }
+
+    #[test]
+    fn starting_value_counts_as_already_warned() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::builder(&mock_messenger)
+            .max(100)
+            .starting_at(80)
+            .build()
+            .unwrap();
+        assert_eq!(mock_messenger.sent_count(), 0);
+        assert_eq!(limit_tracker.remaining(), 20);
+
+        // still past 75, so only the 90% warning is new
+        limit_tracker.increment(12);
+        assert_eq!(
+            mock_messenger.texts(),
+            vec!["Urgent warning: You've used up over 90% of your quota!"]
+        );
+    }
+
+    #[test]
+    fn increment_saturates() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 100);
+        limit_tracker.set_value(usize::MAX - 1);
+        limit_tracker.increment(5);
+        assert_eq!(limit_tracker.value(), usize::MAX);
+        assert_eq!(limit_tracker.remaining(), 0);
+    }
+
+    #[test]
+    fn remaining_and_percentage() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 8);
+        assert_eq!(limit_tracker.remaining(), 8);
+        assert_eq!(limit_tracker.percentage(), 0.0);
+        limit_tracker.increment(2);
+        assert_eq!(limit_tracker.remaining(), 6);
+        assert_eq!(limit_tracker.percentage(), 25.0);
+        assert_eq!(limit_tracker.max(), 8);
+    }
+
+    // Regression test: the helpers return owned copies, so looking at what was
+    // sent while more messages are being sent doesn't trip the RefCell
+    #[test]
+    fn reading_while_sending_does_not_conflict() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 100);
+        limit_tracker.set_value(80);
+
+        for sent in mock_messenger.sent() {
+            mock_messenger.send(&format!("echo {}", sent.text));
+        }
+        assert_eq!(mock_messenger.sent_count(), 2);
+        mock_messenger.assert_sent_containing("echo Warning");
+    }
+
+    // what the above guards against: holding a borrow() of the RefCell (as
+    // `for sent in mock_messenger.sent_messages.borrow().iter()` would) while
+    // send asks for a borrow_mut() panics at runtime
+    #[test]
+    #[should_panic(expected = "already borrowed")]
+    fn holding_a_borrow_while_sending_panics() {
+        let mock_messenger = MockMessenger::new();
+        mock_messenger.send("first");
+        let _held = mock_messenger.sent_messages.borrow();
+        mock_messenger.send("second");
+    }
+}
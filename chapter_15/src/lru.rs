@@ -0,0 +1,265 @@
+// 15 (wrap up) An LRU Cache Out of the Chapter's Pieces
+
+/*
+A least recently used cache keeps at most `capacity` entries, and when it's
+full and something new comes in it throws out whichever entry was used longest
+ago. It needs two things to be fast:
+
+  - find an entry by key: a HashMap
+  - know the usage order, and move an entry to the front when it's used:
+    a doubly linked list, most recently used at the head
+
+Moving a node means reaching it straight from the map, so the map and the list
+share the nodes. That's Rc (two owners) with RefCell inside (so relinking a
+node only needs a shared reference), and the back pointers are Weak so
+neighbouring nodes don't own each other (doubly_linked.rs, cycles.rs).
+
+get returns a clone of the value: a plain &V can't be handed out from inside
+a RefCell once the borrow() guard is gone (same reason as doubly_linked.rs).
+*/
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::rc::{Rc, Weak};
+
+type Link<K, V> = Rc<RefCell<Node<K, V>>>;
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    prev: Option<Weak<RefCell<Node<K, V>>>>,
+    next: Option<Link<K, V>>,
+}
+
+pub struct LruCache<K, V> {
+    capacity: usize,
+    map: HashMap<K, Link<K, V>>,
+    head: Option<Link<K, V>>,
+    tail: Option<Weak<RefCell<Node<K, V>>>>,
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> LruCache<K, V> {
+    pub fn new(capacity: usize) -> LruCache<K, V> {
+        assert!(capacity > 0, "an LRU cache needs a capacity of at least 1");
+        LruCache {
+            capacity,
+            map: HashMap::new(),
+            head: None,
+            tail: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    // looking something up counts as using it, so it moves to the front
+    pub fn get(&mut self, key: &K) -> Option<V> {
+        let node = Rc::clone(self.map.get(key)?);
+        self.detach(&node);
+        self.push_front(&node);
+        let value = node.borrow().value.clone();
+        Some(value)
+    }
+
+    // Adds or updates `key`, making it the most recently used. If that takes
+    // the cache over capacity the least recently used entry is thrown out and
+    // returned
+    pub fn put(&mut self, key: K, value: V) -> Option<(K, V)> {
+        if let Some(node) = self.map.get(&key).map(Rc::clone) {
+            node.borrow_mut().value = value;
+            self.detach(&node);
+            self.push_front(&node);
+            return None;
+        }
+
+        let node = Rc::new(RefCell::new(Node {
+            key: key.clone(),
+            value,
+            prev: None,
+            next: None,
+        }));
+        self.push_front(&node);
+        self.map.insert(key, node);
+
+        if self.map.len() > self.capacity {
+            self.evict()
+        } else {
+            None
+        }
+    }
+
+    // keys from most to least recently used
+    pub fn keys(&self) -> Vec<K> {
+        let mut keys = Vec::new();
+        let mut current = self.head.clone();
+        while let Some(node) = current {
+            let node = node.borrow();
+            keys.push(node.key.clone());
+            current = node.next.clone();
+        }
+        keys
+    }
+
+    fn evict(&mut self) -> Option<(K, V)> {
+        let last = self.tail.as_ref().and_then(Weak::upgrade)?;
+        self.detach(&last);
+        let key = last.borrow().key.clone();
+        self.map.remove(&key);
+        // detached and out of the map, `last` is the only owner left
+        let node = Rc::try_unwrap(last)
+            .ok()
+            .expect("evicted node still shared")
+            .into_inner();
+        Some((node.key, node.value))
+    }
+
+    // unhook `node` from its neighbours (and head/tail), leaving it unlinked
+    fn detach(&mut self, node: &Link<K, V>) {
+        let mut n = node.borrow_mut();
+        let prev = n.prev.take().and_then(|weak| weak.upgrade());
+        let next = n.next.take();
+
+        match &next {
+            Some(next) => next.borrow_mut().prev = prev.as_ref().map(Rc::downgrade),
+            None => self.tail = prev.as_ref().map(Rc::downgrade),
+        }
+        match prev {
+            Some(prev) => prev.borrow_mut().next = next,
+            None => self.head = next,
+        }
+    }
+
+    fn push_front(&mut self, node: &Link<K, V>) {
+        match self.head.take() {
+            Some(old_head) => {
+                old_head.borrow_mut().prev = Some(Rc::downgrade(node));
+                node.borrow_mut().next = Some(old_head);
+            }
+            None => self.tail = Some(Rc::downgrade(node)),
+        }
+        self.head = Some(Rc::clone(node));
+    }
+}
+
+// unlink one node at a time so a big cache doesn't drop recursively (see
+// doubly_linked.rs)
+impl<K, V> Drop for LruCache<K, V> {
+    fn drop(&mut self) {
+        let mut current = self.head.take();
+        while let Some(node) = current {
+            current = node.borrow_mut().next.take();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cycles::CycleProbe;
+
+    #[test]
+    fn get_and_put() {
+        let mut cache = LruCache::new(2);
+        assert!(cache.is_empty());
+        cache.put("a", 1);
+        cache.put("b", 2);
+        assert_eq!(cache.get(&"a"), Some(1));
+        assert_eq!(cache.get(&"b"), Some(2));
+        assert_eq!(cache.get(&"c"), None);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.capacity(), 2);
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut cache = LruCache::new(2);
+        cache.put("a", 1);
+        cache.put("b", 2);
+        assert_eq!(cache.put("c", 3), Some(("a", 1)));
+        assert_eq!(cache.get(&"a"), None);
+        assert_eq!(cache.keys(), vec!["c", "b"]);
+    }
+
+    #[test]
+    fn get_promotes_to_front() {
+        let mut cache = LruCache::new(3);
+        cache.put(1, "one");
+        cache.put(2, "two");
+        cache.put(3, "three");
+        assert_eq!(cache.keys(), vec![3, 2, 1]);
+
+        // using 1 saves it, so 2 is the oldest now
+        cache.get(&1);
+        assert_eq!(cache.keys(), vec![1, 3, 2]);
+        assert_eq!(cache.put(4, "four"), Some((2, "two")));
+        assert_eq!(cache.keys(), vec![4, 1, 3]);
+    }
+
+    #[test]
+    fn eviction_order_over_many_puts() {
+        let mut cache = LruCache::new(3);
+        let mut evicted = Vec::new();
+        for n in 0..6 {
+            if let Some((key, _)) = cache.put(n, n * 10) {
+                evicted.push(key);
+            }
+            // keep touching 0 so it never becomes the oldest
+            cache.get(&0);
+        }
+        assert_eq!(evicted, vec![1, 2, 3]);
+        assert_eq!(cache.keys(), vec![0, 5, 4]);
+    }
+
+    #[test]
+    fn put_existing_key_updates_and_promotes() {
+        let mut cache = LruCache::new(2);
+        cache.put("a", 1);
+        cache.put("b", 2);
+        assert_eq!(cache.put("a", 10), None);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.keys(), vec!["a", "b"]);
+        assert_eq!(cache.put("c", 3), Some(("b", 2)));
+        assert_eq!(cache.get(&"a"), Some(10));
+    }
+
+    #[test]
+    fn capacity_of_one() {
+        let mut cache = LruCache::new(1);
+        cache.put('x', 1);
+        assert_eq!(cache.put('y', 2), Some(('x', 1)));
+        assert_eq!(cache.get(&'y'), Some(2));
+        assert_eq!(cache.keys(), vec!['y']);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least 1")]
+    fn zero_capacity() {
+        LruCache::<i32, i32>::new(0);
+    }
+
+    #[test]
+    fn nothing_leaks() {
+        // the evicted value is handed back, and the rest go with the cache
+        let probe = CycleProbe::new();
+        {
+            let mut cache = LruCache::new(2);
+            cache.put(1, Rc::new(probe.track(1)));
+            cache.put(2, Rc::new(probe.track(2)));
+            let (_, evicted) = cache.put(3, Rc::new(probe.track(3))).unwrap();
+            assert_eq!(**evicted, 1);
+            drop(evicted);
+            assert_eq!(probe.alive(), 2);
+        }
+        assert_eq!(probe.alive(), 0);
+    }
+}
@@ -27,16 +27,10 @@ We'll be covering the most common smart pointers:
     borrowing rules at runtime instead of compile time.
 */
 
-mod box_pointer;
-mod deref_trait;
-mod drop_trait;
-mod reference_counted;
-mod ref_cell;
-
 fn main() {
-    // box_pointer::run();
-    // deref_trait::run();
-    // drop_trait::run();
-    // reference_counted::run();
-    ref_cell::run();
+    // chapter_15::box_pointer::run();
+    // chapter_15::deref_trait::run();
+    // chapter_15::drop_trait::run();
+    // chapter_15::reference_counted::run();
+    chapter_15::ref_cell::run();
 }
@@ -0,0 +1,161 @@
+// 15.3 (continued) Turning Drop Off: mem::forget and ManuallyDrop
+
+/*
+drop_trait.rs showed the two normal ways a value gets dropped: at the end of
+its scope, or early with std::mem::drop. There are also two ways to make sure
+drop *doesn't* run:
+
+  mem::forget(value)      takes ownership and just never drops it. Whatever
+                          the value owned (heap memory, a file...) is leaked.
+  ManuallyDrop::new(value) wraps it so going out of scope doesn't drop it.
+                          Unlike forget the value is still there to use, and we
+                          can drop it ourselves later with ManuallyDrop::drop,
+                          or unwrap it with ManuallyDrop::into_inner so it's
+                          back to being dropped normally.
+
+Both forget and ManuallyDrop::new are safe: never running a destructor can leak
+but can't cause undefined behaviour. ManuallyDrop::drop is unsafe, since after
+calling it the value is gone but the ManuallyDrop is still there, and using it
+(or dropping it again) would be a use after free. DropNow below wraps it with a
+flag so that can't happen.
+*/
+
+use std::mem::{self, ManuallyDrop};
+
+// never runs `value`'s drop, and whatever it owned is never freed
+pub fn forget<T>(value: T) {
+    mem::forget(value);
+}
+
+// the value stays usable (ManuallyDrop derefs to it) but won't be dropped when
+// the ManuallyDrop goes out of scope, unless it's unwrapped with into_inner
+pub fn defuse<T>(value: T) -> ManuallyDrop<T> {
+    ManuallyDrop::new(value)
+}
+
+// A value we can drop on purpose before its owner goes away, with
+// ManuallyDrop::drop doing the actual dropping. `dropped` is what makes that
+// safe: once it's set nothing reads the value or drops it again
+pub struct DropNow<T> {
+    value: ManuallyDrop<T>,
+    dropped: bool,
+}
+
+impl<T> DropNow<T> {
+    pub fn new(value: T) -> DropNow<T> {
+        DropNow {
+            value: ManuallyDrop::new(value),
+            dropped: false,
+        }
+    }
+
+    pub fn get(&self) -> Option<&T> {
+        if self.dropped {
+            None
+        } else {
+            Some(&self.value)
+        }
+    }
+
+    // true if this call dropped it, false if it was already gone
+    pub fn drop_now(&mut self) -> bool {
+        if self.dropped {
+            return false;
+        }
+        self.dropped = true;
+        // SAFETY: the flag was false, so the value hasn't been dropped yet, and
+        // now that it's true get and Drop won't touch it again
+        unsafe { ManuallyDrop::drop(&mut self.value) };
+        true
+    }
+
+    pub fn is_dropped(&self) -> bool {
+        self.dropped
+    }
+}
+
+// if nobody called drop_now, drop it normally along with its owner
+impl<T> Drop for DropNow<T> {
+    fn drop(&mut self) {
+        self.drop_now();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cycles::CycleProbe;
+
+    #[test]
+    fn normal_drop_for_comparison() {
+        let probe = CycleProbe::new();
+        {
+            let _value = probe.track("normal");
+        }
+        assert_eq!(probe.dropped(), 1);
+    }
+
+    #[test]
+    fn forget_never_drops() {
+        let probe = CycleProbe::new();
+        forget(probe.track("forgotten"));
+        assert_eq!(probe.created(), 1);
+        assert_eq!(probe.dropped(), 0);
+    }
+
+    #[test]
+    fn defused_value_is_usable_but_not_dropped() {
+        let probe = CycleProbe::new();
+        {
+            let value = defuse(probe.track(String::from("still here")));
+            // through ManuallyDrop's Deref, then Tracked's
+            assert_eq!(value.len(), 10);
+        }
+        assert_eq!(probe.alive(), 1);
+    }
+
+    #[test]
+    fn into_inner_turns_dropping_back_on() {
+        let probe = CycleProbe::new();
+        let defused = defuse(probe.track(1));
+        let value = ManuallyDrop::into_inner(defused);
+        assert_eq!(probe.alive(), 1);
+        drop(value);
+        assert_eq!(probe.alive(), 0);
+    }
+
+    #[test]
+    fn drop_now_drops_exactly_once() {
+        let probe = CycleProbe::new();
+        let mut value = DropNow::new(probe.track(5));
+        assert_eq!(value.get().map(|v| **v), Some(5));
+
+        assert!(value.drop_now());
+        assert_eq!(probe.dropped(), 1);
+        assert!(value.is_dropped());
+        assert!(value.get().is_none());
+
+        // again, and when `value` itself goes away: nothing more happens
+        assert!(!value.drop_now());
+        drop(value);
+        assert_eq!(probe.dropped(), 1);
+    }
+
+    #[test]
+    fn drop_now_falls_back_to_normal_drop() {
+        let probe = CycleProbe::new();
+        {
+            let value = DropNow::new(probe.track("untouched"));
+            assert!(!value.is_dropped());
+        }
+        assert_eq!(probe.dropped(), 1);
+    }
+
+    #[test]
+    fn forgetting_a_drop_now_forgets_its_value() {
+        // forget skips DropNow's own drop, so the value inside never goes either
+        let probe = CycleProbe::new();
+        forget(DropNow::new(probe.track(())));
+        assert_eq!(probe.alive(), 1);
+    }
+}
@@ -0,0 +1,229 @@
+// 15.5 (continued) Real Messengers, and Sending to Several at Once
+
+/*
+LimitTracker only knows about the Messenger trait, so anything that can send a
+&str can be plugged in. Three of them:
+
+  ConsoleMessenger  prints each message, with an optional prefix
+  BufferMessenger   keeps them in a RefCell<Vec<String>>, the MockMessenger
+                    from the tests made into a real type (handy for logs)
+  MultiMessenger    holds a list of other messengers and passes every message
+                    on to each of them, in the order they were added
+
+MultiMessenger is itself a Messenger, so a LimitTracker can notify several
+channels without LimitTracker changing at all. It stores &dyn Messenger rather
+than a generic T, since the point is to mix different kinds of messenger in one
+list (17.2 trait objects).
+*/
+
+use crate::limit_tracker::Messenger;
+use std::cell::RefCell;
+
+#[derive(Default)]
+pub struct ConsoleMessenger {
+    prefix: String,
+}
+
+impl ConsoleMessenger {
+    pub fn new() -> ConsoleMessenger {
+        ConsoleMessenger::default()
+    }
+
+    pub fn with_prefix(prefix: &str) -> ConsoleMessenger {
+        ConsoleMessenger {
+            prefix: String::from(prefix),
+        }
+    }
+
+    // what send prints, split out so it can be tested without capturing stdout
+    pub fn format(&self, msg: &str) -> String {
+        format!("{}{}", self.prefix, msg)
+    }
+}
+
+impl Messenger for ConsoleMessenger {
+    fn send(&self, msg: &str) {
+        println!("{}", self.format(msg));
+    }
+}
+
+#[derive(Default)]
+pub struct BufferMessenger {
+    buffer: RefCell<Vec<String>>,
+}
+
+impl BufferMessenger {
+    pub fn new() -> BufferMessenger {
+        BufferMessenger::default()
+    }
+
+    // a copy, so the caller isn't holding a borrow of the RefCell
+    pub fn messages(&self) -> Vec<String> {
+        self.buffer.borrow().clone()
+    }
+
+    // hands over everything sent so far and empties the buffer
+    pub fn take(&self) -> Vec<String> {
+        self.buffer.take()
+    }
+}
+
+impl Messenger for BufferMessenger {
+    fn send(&self, msg: &str) {
+        self.buffer.borrow_mut().push(String::from(msg));
+    }
+}
+
+#[derive(Default)]
+pub struct MultiMessenger<'a> {
+    targets: Vec<&'a dyn Messenger>,
+}
+
+impl<'a> MultiMessenger<'a> {
+    pub fn new() -> MultiMessenger<'a> {
+        MultiMessenger::default()
+    }
+
+    // builder style, so a MultiMessenger can be made in one expression
+    pub fn with(mut self, target: &'a dyn Messenger) -> MultiMessenger<'a> {
+        self.add(target);
+        self
+    }
+
+    pub fn add(&mut self, target: &'a dyn Messenger) {
+        self.targets.push(target);
+    }
+
+    pub fn len(&self) -> usize {
+        self.targets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+}
+
+impl Messenger for MultiMessenger<'_> {
+    fn send(&self, msg: &str) {
+        for target in &self.targets {
+            target.send(msg);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::limit_tracker::LimitTracker;
+
+    // writes "name: msg" into a log shared with other messengers, so a test
+    // can see the order the messengers were called in
+    struct Labelled<'a> {
+        name: &'a str,
+        log: &'a RefCell<Vec<String>>,
+    }
+
+    impl Messenger for Labelled<'_> {
+        fn send(&self, msg: &str) {
+            self.log
+                .borrow_mut()
+                .push(format!("{}: {}", self.name, msg));
+        }
+    }
+
+    #[test]
+    fn console_formats_with_prefix() {
+        assert_eq!(ConsoleMessenger::new().format("hi"), "hi");
+        let console = ConsoleMessenger::with_prefix("[quota] ");
+        assert_eq!(console.format("hi"), "[quota] hi");
+        // and sending just prints, nothing to check but that it doesn't panic
+        console.send("hi");
+    }
+
+    #[test]
+    fn buffer_keeps_messages_in_order() {
+        let buffer = BufferMessenger::new();
+        buffer.send("one");
+        buffer.send("two");
+        assert_eq!(buffer.messages(), vec!["one", "two"]);
+        assert_eq!(buffer.take(), vec!["one", "two"]);
+        assert!(buffer.messages().is_empty());
+    }
+
+    #[test]
+    fn multi_sends_to_every_target() {
+        let first = BufferMessenger::new();
+        let second = BufferMessenger::new();
+        let multi = MultiMessenger::new().with(&first).with(&second);
+        assert_eq!(multi.len(), 2);
+
+        multi.send("hello");
+        assert_eq!(first.messages(), vec!["hello"]);
+        assert_eq!(second.messages(), vec!["hello"]);
+    }
+
+    #[test]
+    fn multi_keeps_target_order() {
+        let log = RefCell::new(Vec::new());
+        let a = Labelled {
+            name: "a",
+            log: &log,
+        };
+        let b = Labelled {
+            name: "b",
+            log: &log,
+        };
+        let c = Labelled {
+            name: "c",
+            log: &log,
+        };
+
+        let mut multi = MultiMessenger::new();
+        multi.add(&b);
+        multi.add(&a);
+        multi.add(&c);
+        multi.send("1");
+        multi.send("2");
+
+        assert_eq!(
+            *log.borrow(),
+            vec!["b: 1", "a: 1", "c: 1", "b: 2", "a: 2", "c: 2"]
+        );
+    }
+
+    #[test]
+    fn multi_can_mix_kinds_and_nest() {
+        let buffer = BufferMessenger::new();
+        let console = ConsoleMessenger::with_prefix("test: ");
+        let inner = MultiMessenger::new().with(&buffer);
+        let outer = MultiMessenger::new().with(&console).with(&inner);
+
+        outer.send("nested");
+        assert_eq!(buffer.messages(), vec!["nested"]);
+    }
+
+    #[test]
+    fn empty_multi_sends_nowhere() {
+        let multi = MultiMessenger::new();
+        assert!(multi.is_empty());
+        multi.send("into the void");
+    }
+
+    #[test]
+    fn limit_tracker_notifies_every_channel() {
+        let audit = BufferMessenger::new();
+        let alerts = BufferMessenger::new();
+        let multi = MultiMessenger::new().with(&audit).with(&alerts);
+        let mut tracker = LimitTracker::new(&multi, 100);
+
+        tracker.set_value(80);
+        tracker.set_value(100);
+
+        let expected = vec![
+            "Warning: You've used up over 75% of your quota!",
+            "Error: You are over your quota!",
+        ];
+        assert_eq!(audit.messages(), expected);
+        assert_eq!(alerts.messages(), expected);
+    }
+}
@@ -0,0 +1,173 @@
+// 15.2 / 15.3 MyBox: Deref, DerefMut, and Drop Together
+
+/*
+deref_trait.rs built MyBox inside run() with just enough to make * work. This is
+the full version: Deref for reading through the box, DerefMut for changing the
+value through it, and Drop so we can see exactly when a box gets cleaned up.
+
+Rather than always println! in drop (like CustomSmartPointer in drop_trait.rs)
+a box can be given a hook, a closure that's handed the value just before it
+goes away. A test can use that to record the order things were dropped in.
+*/
+
+use std::ops::{Deref, DerefMut};
+
+// boxed since every closure is its own type, FnOnce since drop only ever
+// happens once
+type DropHook<T> = Box<dyn FnOnce(&T)>;
+
+pub struct MyBox<T> {
+    value: T,
+    on_drop: Option<DropHook<T>>,
+}
+
+impl<T> MyBox<T> {
+    pub fn new(x: T) -> MyBox<T> {
+        MyBox {
+            value: x,
+            on_drop: None,
+        }
+    }
+
+    // same as new, but `hook` runs with the value when the box is dropped
+    pub fn with_drop_hook(x: T, hook: impl FnOnce(&T) + 'static) -> MyBox<T> {
+        MyBox {
+            value: x,
+            on_drop: Some(Box::new(hook)),
+        }
+    }
+}
+
+impl<T> Deref for MyBox<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+// DerefMut builds on Deref (it reuses its Target), so *b = ... and calling
+// &mut self methods on the value both work through the box
+impl<T> DerefMut for MyBox<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T> Drop for MyBox<T> {
+    fn drop(&mut self) {
+        // take() moves the hook out of the Option, we can't call an FnOnce
+        // through the &mut self that drop gives us
+        if let Some(hook) = self.on_drop.take() {
+            hook(&self.value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    // a shared log the drop hooks push onto (Rc and RefCell are coming up in
+    // 15.4 and 15.5, here they just let several hooks write to one Vec)
+    fn logging_box(name: &'static str, log: &Rc<RefCell<Vec<String>>>) -> MyBox<&'static str> {
+        let log = Rc::clone(log);
+        MyBox::with_drop_hook(name, move |value| log.borrow_mut().push(value.to_string()))
+    }
+
+    #[test]
+    fn deref_reads_the_value() {
+        let b = MyBox::new(5);
+        assert_eq!(5, *b);
+    }
+
+    #[test]
+    fn deref_coercion_to_str() {
+        fn hello(name: &str) -> String {
+            format!("Hello, {name}")
+        }
+        let m = MyBox::new(String::from("Rust"));
+        // &MyBox<String> -> &String -> &str
+        assert_eq!(hello(&m), "Hello, Rust");
+        assert_eq!(m.len(), 4);
+    }
+
+    #[test]
+    fn mutate_through_deref_mut() {
+        let mut b = MyBox::new(5);
+        *b += 1;
+        assert_eq!(*b, 6);
+
+        let mut s = MyBox::new(String::from("Hello"));
+        s.push_str(", world");
+        assert_eq!(*s, "Hello, world");
+    }
+
+    #[test]
+    fn deref_mut_coercion() {
+        fn exclaim(s: &mut String) {
+            s.push('!');
+        }
+        let mut b = MyBox::new(String::from("Rust"));
+        // &mut MyBox<String> coerces to &mut String
+        exclaim(&mut b);
+        assert_eq!(*b, "Rust!");
+    }
+
+    #[test]
+    fn hook_sees_the_final_value() {
+        let seen = Rc::new(RefCell::new(None));
+        let slot = Rc::clone(&seen);
+        let mut b = MyBox::with_drop_hook(1, move |value| *slot.borrow_mut() = Some(*value));
+        *b = 42;
+        assert_eq!(*seen.borrow(), None);
+        drop(b);
+        assert_eq!(*seen.borrow(), Some(42));
+    }
+
+    #[test]
+    fn dropped_in_reverse_order_of_creation() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        {
+            let _first = logging_box("first", &log);
+            let _second = logging_box("second", &log);
+            let _third = logging_box("third", &log);
+        }
+        assert_eq!(*log.borrow(), vec!["third", "second", "first"]);
+    }
+
+    #[test]
+    fn early_drop_comes_first() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        {
+            let first = logging_box("first", &log);
+            let _second = logging_box("second", &log);
+            drop(first);
+            log.borrow_mut().push(String::from("end of scope"));
+        }
+        assert_eq!(*log.borrow(), vec!["first", "end of scope", "second"]);
+    }
+
+    #[test]
+    fn nested_boxes_drop_outside_in() {
+        // the outer box's drop runs first, then its fields (including the inner
+        // box) are dropped
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let inner_log = Rc::clone(&log);
+        let outer_log = Rc::clone(&log);
+        let inner = MyBox::with_drop_hook(1, move |_| inner_log.borrow_mut().push("inner"));
+        let outer = MyBox::with_drop_hook(inner, move |_| outer_log.borrow_mut().push("outer"));
+        assert_eq!(**outer, 1);
+        drop(outer);
+        assert_eq!(*log.borrow(), vec!["outer", "inner"]);
+    }
+
+    #[test]
+    fn no_hook_no_problem() {
+        let b = MyBox::new(vec![1, 2, 3]);
+        assert_eq!(b.iter().sum::<i32>(), 6);
+        drop(b);
+    }
+}
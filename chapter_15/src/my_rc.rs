@@ -0,0 +1,194 @@
+// 15.4 (continued) Building Our Own Rc<T>
+
+/*
+Rc<T> isn't magic either. Underneath it's a pointer to one heap allocation
+that holds two things: the value, and a count of how many Rcs point at it.
+
+  - new puts the count (starting at 1) and the value on the heap
+  - clone copies the pointer and adds 1 to the count, the value isn't copied
+  - drop subtracts 1, and whoever brings it to 0 frees the allocation
+
+The tricky part is that the Rust ownership rules can't describe "owned by
+whoever is last", so the pointer has to be a raw one and the freeing has to be
+done by hand in unsafe code (Chapter 19). The unsafe is kept inside this file:
+code using MyRc only sees new, clone, deref and drop, and can't misuse it.
+
+This is simplified from the real Rc: no weak counts, no overflow checks, and
+the count is a Cell (interior mutability again) because clone only gets &self.
+Like Rc it's single threaded. NonNull isn't Send or Sync, so neither is MyRc,
+and the compiler stops it being shared between threads for us.
+
+The tests stick to plain safe calls so they can also be run under Miri
+(`cargo +nightly miri test my_rc`), which checks the unsafe code for use after
+free, double frees and leaks.
+*/
+
+use std::cell::Cell;
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::ptr::NonNull;
+
+// the heap allocation every clone points at
+struct RcBox<T> {
+    count: Cell<usize>,
+    value: T,
+}
+
+pub struct MyRc<T> {
+    ptr: NonNull<RcBox<T>>,
+    // tells the compiler we own an RcBox<T> (so dropping a MyRc may drop a
+    // T), which a raw pointer alone doesn't say
+    _owns: PhantomData<RcBox<T>>,
+}
+
+impl<T> MyRc<T> {
+    pub fn new(value: T) -> MyRc<T> {
+        let boxed = Box::new(RcBox {
+            count: Cell::new(1),
+            value,
+        });
+        MyRc {
+            // leak gives up the Box's ownership, freeing it is now our job (in
+            // drop). A Box is never null, so NonNull is fine
+            ptr: NonNull::from(Box::leak(boxed)),
+            _owns: PhantomData,
+        }
+    }
+
+    // an associated function, like Rc::strong_count, so it can't clash with a
+    // method on T that we reach through Deref
+    pub fn strong_count(this: &MyRc<T>) -> usize {
+        this.inner().count.get()
+    }
+
+    pub fn ptr_eq(this: &MyRc<T>, other: &MyRc<T>) -> bool {
+        this.ptr == other.ptr
+    }
+
+    fn inner(&self) -> &RcBox<T> {
+        // SAFETY: while any MyRc exists the count is at least 1, so the
+        // allocation hasn't been freed yet and the pointer is valid
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T> Clone for MyRc<T> {
+    fn clone(&self) -> MyRc<T> {
+        let count = &self.inner().count;
+        count.set(count.get() + 1);
+        MyRc {
+            ptr: self.ptr,
+            _owns: PhantomData,
+        }
+    }
+}
+
+impl<T> Deref for MyRc<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.inner().value
+    }
+}
+
+impl<T> Drop for MyRc<T> {
+    fn drop(&mut self) {
+        let count = &self.inner().count;
+        count.set(count.get() - 1);
+        if count.get() == 0 {
+            // SAFETY: we were the last MyRc, nobody else can use the pointer
+            // now. It came from a Box in new, so turning it back into one and
+            // dropping that frees the memory and drops the value exactly once
+            unsafe {
+                drop(Box::from_raw(self.ptr.as_ptr()));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // counts how many times it's been dropped, to check the value is dropped
+    // exactly once and only when the last MyRc goes
+    struct DropCounter<'a>(&'a Cell<usize>);
+
+    impl Drop for DropCounter<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn strong_count_like_reference_counted() {
+        // the same steps as reference_counted.rs and rc_list.rs
+        let a = MyRc::new(5);
+        assert_eq!(MyRc::strong_count(&a), 1);
+        let _b = MyRc::clone(&a);
+        assert_eq!(MyRc::strong_count(&a), 2);
+        let _c = MyRc::clone(&a);
+        assert_eq!(MyRc::strong_count(&a), 3);
+        {
+            let _d = MyRc::clone(&a);
+            assert_eq!(MyRc::strong_count(&a), 4);
+        }
+        assert_eq!(MyRc::strong_count(&a), 3);
+    }
+
+    #[test]
+    fn clones_share_one_value() {
+        let a = MyRc::new(String::from("shared"));
+        let b = a.clone();
+        assert!(MyRc::ptr_eq(&a, &b));
+        // through Deref
+        assert_eq!(*b, "shared");
+        assert_eq!(a.len(), 6);
+
+        let other = MyRc::new(String::from("shared"));
+        assert!(!MyRc::ptr_eq(&a, &other));
+    }
+
+    #[test]
+    fn value_dropped_once_by_the_last_owner() {
+        let drops = Cell::new(0);
+        let a = MyRc::new(DropCounter(&drops));
+        let b = a.clone();
+        let c = b.clone();
+
+        drop(a);
+        drop(c);
+        assert_eq!(drops.get(), 0);
+        assert_eq!(MyRc::strong_count(&b), 1);
+
+        drop(b);
+        assert_eq!(drops.get(), 1);
+    }
+
+    #[test]
+    fn works_as_a_shared_list_tail() {
+        // a little cons list, like rc_list.rs but on MyRc
+        enum List {
+            Cons(i32, MyRc<List>),
+            Nil,
+        }
+        use List::{Cons, Nil};
+
+        fn sum(list: &List) -> i32 {
+            match list {
+                Cons(value, rest) => value + sum(rest),
+                Nil => 0,
+            }
+        }
+
+        let a = MyRc::new(Cons(5, MyRc::new(Cons(10, MyRc::new(Nil)))));
+        let b = Cons(3, a.clone());
+        let c = Cons(4, a.clone());
+        assert_eq!(MyRc::strong_count(&a), 3);
+        assert_eq!(sum(&b), 18);
+        assert_eq!(sum(&c), 19);
+        drop(b);
+        drop(c);
+        assert_eq!(MyRc::strong_count(&a), 1);
+    }
+}
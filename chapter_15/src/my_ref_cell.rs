@@ -0,0 +1,206 @@
+// 15.5 (continued) Building Our Own RefCell<T>
+
+/*
+RefCell's "borrowing rules checked at runtime" comes down to one number kept
+next to the value:
+
+     0   nobody is borrowing it
+    >0   that many borrow()s are active
+    -1   one borrow_mut() is active
+
+borrow() checks the number isn't -1 and adds 1, borrow_mut() checks it's 0 and
+sets -1, and if the check fails it panics. The trick that makes it work is that
+they don't hand out plain references, they hand out guard types (RefCell's are
+called Ref and RefMut). A guard derefs to the value, and when it's dropped it
+puts the number back. So the borrow ends exactly when the guard goes out of
+scope, the same place a normal reference's borrow would end.
+
+The flag is a Cell (so it can change through &self), and the value is in an
+UnsafeCell, the one type Rust allows to be mutated through a shared reference.
+Every Cell and RefCell in std is built on it. Handing out &mut T from &self is
+only sound because the flag guarantees no other guard exists at the time.
+*/
+
+use std::cell::{Cell, UnsafeCell};
+use std::ops::{Deref, DerefMut};
+
+const UNUSED: isize = 0;
+const WRITING: isize = -1;
+
+pub struct MyRefCell<T> {
+    borrow: Cell<isize>,
+    value: UnsafeCell<T>,
+}
+
+impl<T> MyRefCell<T> {
+    pub fn new(value: T) -> MyRefCell<T> {
+        MyRefCell {
+            borrow: Cell::new(UNUSED),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    pub fn borrow(&self) -> MyRef<'_, T> {
+        let flag = self.borrow.get();
+        if flag == WRITING {
+            panic!("already mutably borrowed");
+        }
+        self.borrow.set(flag + 1);
+        MyRef { cell: self }
+    }
+
+    pub fn borrow_mut(&self) -> MyRefMut<'_, T> {
+        if self.borrow.get() != UNUSED {
+            panic!("already borrowed");
+        }
+        self.borrow.set(WRITING);
+        MyRefMut { cell: self }
+    }
+
+    // how many borrow()s are active, or -1 for a borrow_mut(). Only here so
+    // the tests can watch the flag change
+    pub fn borrow_state(&self) -> isize {
+        self.borrow.get()
+    }
+
+    // taking self means nothing can be borrowing it, no check needed
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+}
+
+pub struct MyRef<'a, T> {
+    cell: &'a MyRefCell<T>,
+}
+
+impl<T> Deref for MyRef<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: while this guard exists the flag is > 0, so there's no
+        // MyRefMut handing out a &mut T at the same time
+        unsafe { &*self.cell.value.get() }
+    }
+}
+
+impl<T> Drop for MyRef<'_, T> {
+    fn drop(&mut self) {
+        self.cell.borrow.set(self.cell.borrow.get() - 1);
+    }
+}
+
+pub struct MyRefMut<'a, T> {
+    cell: &'a MyRefCell<T>,
+}
+
+impl<T> Deref for MyRefMut<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: the flag is WRITING, this guard is the only way in
+        unsafe { &*self.cell.value.get() }
+    }
+}
+
+impl<T> DerefMut for MyRefMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: as above, and &mut self means this guard's own &T from
+        // deref can't be alive at the same time either
+        unsafe { &mut *self.cell.value.get() }
+    }
+}
+
+impl<T> Drop for MyRefMut<'_, T> {
+    fn drop(&mut self) {
+        self.cell.borrow.set(UNUSED);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn many_readers_at_once() {
+        let cell = MyRefCell::new(5);
+        let first = cell.borrow();
+        let second = cell.borrow();
+        assert_eq!(*first + *second, 10);
+        assert_eq!(cell.borrow_state(), 2);
+        drop(first);
+        assert_eq!(cell.borrow_state(), 1);
+        drop(second);
+        assert_eq!(cell.borrow_state(), 0);
+    }
+
+    #[test]
+    fn mutate_through_shared_reference() {
+        let cell = MyRefCell::new(vec![1, 2]);
+        // `cell` isn't mut, borrow_mut only needs &self
+        cell.borrow_mut().push(3);
+        {
+            let mut writer = cell.borrow_mut();
+            assert_eq!(cell.borrow_state(), -1);
+            writer[0] = 10;
+        }
+        assert_eq!(cell.borrow_state(), 0);
+        assert_eq!(*cell.borrow(), vec![10, 2, 3]);
+        assert_eq!(cell.into_inner(), vec![10, 2, 3]);
+    }
+
+    #[test]
+    fn borrow_again_after_guards_drop() {
+        let cell = MyRefCell::new(String::from("a"));
+        {
+            let _reader = cell.borrow();
+        }
+        cell.borrow_mut().push('b');
+        let reader = cell.borrow();
+        assert_eq!(*reader, "ab");
+    }
+
+    #[test]
+    #[should_panic(expected = "already borrowed")]
+    fn borrow_mut_while_reading_panics() {
+        let cell = MyRefCell::new(5);
+        let _reader = cell.borrow();
+        cell.borrow_mut();
+    }
+
+    #[test]
+    #[should_panic(expected = "already borrowed")]
+    fn two_borrow_muts_panic() {
+        // the book's example of breaking the rules with RefCell
+        let cell = MyRefCell::new(vec![String::new()]);
+        let _one_borrow = cell.borrow_mut();
+        let _two_borrow = cell.borrow_mut();
+    }
+
+    #[test]
+    #[should_panic(expected = "already mutably borrowed")]
+    fn borrow_while_writing_panics() {
+        let cell = MyRefCell::new(5);
+        let _writer = cell.borrow_mut();
+        cell.borrow();
+    }
+
+    #[test]
+    fn works_as_a_mock_messenger_store() {
+        // the reason RefCell came up in the first place: record from &self
+        struct Mock {
+            sent: MyRefCell<Vec<String>>,
+        }
+        impl Mock {
+            fn send(&self, msg: &str) {
+                self.sent.borrow_mut().push(String::from(msg));
+            }
+        }
+
+        let mock = Mock {
+            sent: MyRefCell::new(vec![]),
+        };
+        mock.send("one");
+        mock.send("two");
+        assert_eq!(mock.sent.borrow().len(), 2);
+    }
+}
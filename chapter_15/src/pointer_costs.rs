@@ -0,0 +1,174 @@
+// 15 (wrap up) What Each Pointer Costs: &T vs Box vs Rc vs Arc
+
+/*
+The chapter makes a few performance claims in passing: Box has no overhead
+beyond the heap allocation, an Rc clone is cheap because only a count changes,
+and Arc (Chapter 16) costs a bit more than Rc because its count is atomic so
+threads can share it. The #[ignore]d benchmarks here put numbers on those
+claims for the three things we do with a pointer:
+
+  clone   &T copies an address, Box copies the whole payload into a new
+          allocation, Rc and Arc add 1 to a count
+  deref   following the pointer to read the payload, which should cost the
+          same for all four
+  drop    &T does nothing, Box frees its allocation, Rc and Arc subtract 1
+          (and free when it hits 0)
+
+Run them with
+    cargo test --release -- --ignored --nocapture
+
+The helpers are generic over Deref<Target = Payload> and Clone, so every
+benchmark runs the exact same code and only the pointer type changes.
+*/
+
+use std::ops::Deref;
+
+// big enough that copying it (what Box::clone does) is noticeable
+#[derive(Debug, Clone, PartialEq)]
+pub struct Payload {
+    pub id: u64,
+    pub data: [u64; 32],
+}
+
+impl Payload {
+    pub fn new(id: u64) -> Payload {
+        let mut data = [0; 32];
+        for (i, slot) in data.iter_mut().enumerate() {
+            *slot = id * 31 + i as u64;
+        }
+        Payload { id, data }
+    }
+
+    pub fn checksum(&self) -> u64 {
+        self.data
+            .iter()
+            .fold(self.id, |acc, x| acc.wrapping_add(*x))
+    }
+}
+
+pub fn payloads(n: u64) -> Vec<Payload> {
+    (0..n).map(Payload::new).collect()
+}
+
+// reads every payload through whatever pointer type it's behind
+pub fn total_checksum<P: Deref<Target = Payload>>(pointers: &[P]) -> u64 {
+    pointers
+        .iter()
+        .fold(0, |acc, p| acc.wrapping_add(p.checksum()))
+}
+
+pub fn clone_all<P: Clone>(pointers: &[P]) -> Vec<P> {
+    pointers.to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chapter_11::bench::time_it;
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    const N: u64 = 10_000;
+
+    #[test]
+    fn every_pointer_reads_the_same_data() {
+        let owned = payloads(100);
+        let refs: Vec<&Payload> = owned.iter().collect();
+        let boxes: Vec<Box<Payload>> = owned.iter().cloned().map(Box::new).collect();
+        let rcs: Vec<Rc<Payload>> = owned.iter().cloned().map(Rc::new).collect();
+        let arcs: Vec<Arc<Payload>> = owned.iter().cloned().map(Arc::new).collect();
+
+        let expected = total_checksum(&refs);
+        assert_eq!(total_checksum(&boxes), expected);
+        assert_eq!(total_checksum(&rcs), expected);
+        assert_eq!(total_checksum(&arcs), expected);
+    }
+
+    #[test]
+    fn box_clone_copies_the_payload() {
+        let boxes = vec![Box::new(Payload::new(1))];
+        let cloned = clone_all(&boxes);
+        assert_eq!(cloned[0], boxes[0]);
+        // equal, but a second allocation
+        assert!(!std::ptr::eq(&*cloned[0], &*boxes[0]));
+    }
+
+    #[test]
+    fn rc_and_arc_clones_share_the_payload() {
+        let rcs = vec![Rc::new(Payload::new(1))];
+        let cloned = clone_all(&rcs);
+        assert!(Rc::ptr_eq(&cloned[0], &rcs[0]));
+        assert_eq!(Rc::strong_count(&rcs[0]), 2);
+        drop(cloned);
+        assert_eq!(Rc::strong_count(&rcs[0]), 1);
+
+        let arcs = vec![Arc::new(Payload::new(1))];
+        let cloned = clone_all(&arcs);
+        assert!(Arc::ptr_eq(&cloned[0], &arcs[0]));
+        assert_eq!(Arc::strong_count(&arcs[0]), 2);
+    }
+
+    #[test]
+    fn ref_clone_is_the_same_address() {
+        let owned = payloads(3);
+        let refs: Vec<&Payload> = owned.iter().collect();
+        let cloned = clone_all(&refs);
+        assert!(cloned.iter().zip(&refs).all(|(a, b)| std::ptr::eq(*a, *b)));
+    }
+
+    #[test]
+    #[ignore]
+    fn bench_clone() {
+        let owned = payloads(N);
+        let refs: Vec<&Payload> = owned.iter().collect();
+        let boxes: Vec<Box<Payload>> = owned.iter().cloned().map(Box::new).collect();
+        let rcs: Vec<Rc<Payload>> = owned.iter().cloned().map(Rc::new).collect();
+        let arcs: Vec<Arc<Payload>> = owned.iter().cloned().map(Arc::new).collect();
+
+        let r = time_it("clone &T", 100, || clone_all(&refs));
+        let b = time_it("clone Box", 100, || clone_all(&boxes));
+        let rc = time_it("clone Rc", 100, || clone_all(&rcs));
+        let arc = time_it("clone Arc", 100, || clone_all(&arcs));
+        println!("Box / &T: {:.2}x", b.ratio_to(&r));
+        println!("Rc / &T: {:.2}x", rc.ratio_to(&r));
+        println!("Arc / Rc: {:.2}x", arc.ratio_to(&rc));
+    }
+
+    #[test]
+    #[ignore]
+    fn bench_deref() {
+        let owned = payloads(N);
+        let refs: Vec<&Payload> = owned.iter().collect();
+        let boxes: Vec<Box<Payload>> = owned.iter().cloned().map(Box::new).collect();
+        let rcs: Vec<Rc<Payload>> = owned.iter().cloned().map(Rc::new).collect();
+        let arcs: Vec<Arc<Payload>> = owned.iter().cloned().map(Arc::new).collect();
+
+        let r = time_it("deref &T", 200, || total_checksum(&refs));
+        let b = time_it("deref Box", 200, || total_checksum(&boxes));
+        let rc = time_it("deref Rc", 200, || total_checksum(&rcs));
+        let arc = time_it("deref Arc", 200, || total_checksum(&arcs));
+        println!("Box / &T: {:.2}x", b.ratio_to(&r));
+        println!("Rc / &T: {:.2}x", rc.ratio_to(&r));
+        println!("Arc / &T: {:.2}x", arc.ratio_to(&r));
+    }
+
+    // each run makes a fresh set of clones and drops them, so the numbers
+    // include the clone too. Compare them with bench_clone to see the drop
+    #[test]
+    #[ignore]
+    fn bench_clone_and_drop() {
+        let owned = payloads(N);
+        let refs: Vec<&Payload> = owned.iter().collect();
+        let boxes: Vec<Box<Payload>> = owned.iter().cloned().map(Box::new).collect();
+        let rcs: Vec<Rc<Payload>> = owned.iter().cloned().map(Rc::new).collect();
+        let arcs: Vec<Arc<Payload>> = owned.iter().cloned().map(Arc::new).collect();
+
+        let r = time_it("clone+drop &T", 100, || drop(clone_all(&refs)));
+        let b = time_it("clone+drop Box", 100, || drop(clone_all(&boxes)));
+        let rc = time_it("clone+drop Rc", 100, || drop(clone_all(&rcs)));
+        let arc = time_it("clone+drop Arc", 100, || drop(clone_all(&arcs)));
+        println!("Box / &T: {:.2}x", b.ratio_to(&r));
+        println!("Rc / &T: {:.2}x", rc.ratio_to(&r));
+        println!("Arc / Rc: {:.2}x", arc.ratio_to(&rc));
+    }
+}
@@ -0,0 +1,138 @@
+// 15.4 (continued) Sharing a Tail with Rc<T>
+
+/*
+reference_counted.rs builds two lists, b and c, that both point at the same
+list a. Here's that List as a module-level type so the reference counts can be
+checked in tests instead of only printed.
+
+With Rc the lists really do share nodes, so we can ask a question that doesn't
+make sense for the Box list: where do two lists join? Two Rcs are the same node
+when they point at the same allocation, which Rc::ptr_eq checks. Comparing with
+== would instead compare the values, and two separate lists that happen to hold
+the same numbers aren't sharing anything.
+*/
+
+use std::rc::Rc;
+
+#[derive(Debug, PartialEq)]
+pub enum List {
+    Cons(i32, Rc<List>),
+    Nil,
+}
+
+use List::Cons;
+
+// every Cons node of `list` in order, each as the Rc that points at it
+fn nodes(list: &Rc<List>) -> Vec<&Rc<List>> {
+    let mut nodes = Vec::new();
+    let mut current = list;
+    while let Cons(_, next) = &**current {
+        nodes.push(current);
+        current = next;
+    }
+    nodes
+}
+
+// The first node that both lists lead to, or None if they never meet. Once two
+// lists join they stay joined all the way to the end, so the first node of b
+// that's also in a is where the shared tail starts. Nil isn't counted, it
+// doesn't hold anything to share.
+pub fn shared_tail(a: &Rc<List>, b: &Rc<List>) -> Option<Rc<List>> {
+    let in_a = nodes(a);
+    nodes(b)
+        .into_iter()
+        .find(|node| in_a.iter().any(|other| Rc::ptr_eq(node, other)))
+        .map(Rc::clone)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use List::Nil;
+
+    fn five_ten() -> Rc<List> {
+        Rc::new(Cons(5, Rc::new(Cons(10, Rc::new(Nil)))))
+    }
+
+    #[test]
+    fn strong_count_follows_clones() {
+        // the same steps reference_counted.rs prints
+        let a = five_ten();
+        assert_eq!(Rc::strong_count(&a), 1);
+        let _b = Cons(3, Rc::clone(&a));
+        assert_eq!(Rc::strong_count(&a), 2);
+        let _c = Cons(4, Rc::clone(&a));
+        assert_eq!(Rc::strong_count(&a), 3);
+        {
+            let _d = Cons(6, Rc::clone(&a));
+            assert_eq!(Rc::strong_count(&a), 4);
+        }
+        // _d went out of scope and gave its count back
+        assert_eq!(Rc::strong_count(&a), 3);
+    }
+
+    #[test]
+    fn dropping_a_list_drops_its_clone() {
+        let a = five_ten();
+        let b = Cons(3, Rc::clone(&a));
+        assert_eq!(Rc::strong_count(&a), 2);
+        drop(b);
+        assert_eq!(Rc::strong_count(&a), 1);
+    }
+
+    #[test]
+    fn only_the_head_is_counted() {
+        // b holds a clone of the Rc pointing at a's first node, the nodes
+        // further down still only have one owner each (the node before them)
+        let a = five_ten();
+        let _b = Cons(3, Rc::clone(&a));
+        match &*a {
+            Cons(_, rest) => assert_eq!(Rc::strong_count(rest), 1),
+            Nil => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn finds_where_two_lists_join() {
+        let a = five_ten();
+        let b = Rc::new(Cons(3, Rc::clone(&a)));
+        let c = Rc::new(Cons(4, Rc::clone(&a)));
+
+        let shared = shared_tail(&b, &c).expect("b and c share a");
+        assert!(Rc::ptr_eq(&shared, &a));
+        // and the result is one more owner
+        assert_eq!(Rc::strong_count(&a), 4);
+    }
+
+    #[test]
+    fn a_list_shares_itself_from_the_start() {
+        let a = five_ten();
+        let b = Rc::new(Cons(3, Rc::clone(&a)));
+        assert!(Rc::ptr_eq(&shared_tail(&a, &b).unwrap(), &a));
+        assert!(Rc::ptr_eq(&shared_tail(&b, &b).unwrap(), &b));
+    }
+
+    #[test]
+    fn joins_partway_down() {
+        let tail = Rc::new(Cons(10, Rc::new(Nil)));
+        let a = Rc::new(Cons(1, Rc::new(Cons(2, Rc::clone(&tail)))));
+        let b = Rc::new(Cons(7, Rc::clone(&tail)));
+        assert!(Rc::ptr_eq(&shared_tail(&a, &b).unwrap(), &tail));
+    }
+
+    #[test]
+    fn equal_values_are_not_shared() {
+        // same numbers, different nodes
+        let a = five_ten();
+        let b = five_ten();
+        assert_eq!(a, b);
+        assert_eq!(shared_tail(&a, &b), None);
+    }
+
+    #[test]
+    fn empty_lists_share_nothing() {
+        let nil = Rc::new(Nil);
+        assert_eq!(shared_tail(&nil, &nil), None);
+        assert_eq!(shared_tail(&nil, &five_ten()), None);
+    }
+}
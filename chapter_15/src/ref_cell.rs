@@ -97,47 +97,64 @@ pub fn run() {
     implements a trait we'll provide called Messenger.
     */
 
-    rc_and_ref_cell();
-}
-
-    pub trait Messenger {
-        fn send(&self, msg: &str);
-    }
-
-    pub struct LimitTracker<'a, T: Messenger> {
-        messenger: &'a T,
-        value: usize,
-        max: usize,
+    // the finished tracker (limit_tracker.rs) sending to the console and a
+    // buffer at the same time (messengers.rs)
+    use crate::limit_tracker::LimitTracker;
+    use crate::messengers::{BufferMessenger, ConsoleMessenger, MultiMessenger};
+
+    let console = ConsoleMessenger::with_prefix("quota: ");
+    let history = BufferMessenger::new();
+    let both = MultiMessenger::new().with(&console).with(&history);
+    let mut tracker = LimitTracker::new(&both, 100);
+    for used in [50, 80, 85, 95, 100] {
+        tracker.set_value(used);
     }
+    println!("{} messages kept in the buffer", history.messages().len());
 
-    impl<'a, T> LimitTracker<'a, T>
-    where
-        T: Messenger,
-    {
-        pub fn new(messenger: &'a T, max: usize) -> LimitTracker<'a, T> {
-            LimitTracker {
-                messenger,
-                value: 0,
-                max,
-            }
-        }
-
-        pub fn set_value(&mut self, value: usize) {
-            self.value = value;
-
-            let percentage_of_max = self.value as f64 / self.max as f64;
+    rc_and_ref_cell();
+}
 
-            if percentage_of_max >= 1.0 {
-                self.messenger.send("Error: You are over your quota!");
-            } else if percentage_of_max >= 0.9 {
-                self.messenger
-                    .send("Urgent warning: You've used up over 90% of your quota!");
-            } else if percentage_of_max >= 0.75 {
-                self.messenger
-                    .send("Warning: You've used up over 75% of your quota!")
-            }
-        }
-    }
+    // Messenger and LimitTracker now live in limit_tracker.rs, where the
+    // thresholds can be configured. The book's version:
+
+    // pub trait Messenger {
+    //     fn send(&self, msg: &str);
+    // }
+
+    // pub struct LimitTracker<'a, T: Messenger> {
+    //     messenger: &'a T,
+    //     value: usize,
+    //     max: usize,
+    // }
+
+    // impl<'a, T> LimitTracker<'a, T>
+    // where
+    //     T: Messenger,
+    // {
+    //     pub fn new(messenger: &'a T, max: usize) -> LimitTracker<'a, T> {
+    //         LimitTracker {
+    //             messenger,
+    //             value: 0,
+    //             max,
+    //         }
+    //     }
+
+    //     pub fn set_value(&mut self, value: usize) {
+    //         self.value = value;
+
+    //         let percentage_of_max = self.value as f64 / self.max as f64;
+
+    //         if percentage_of_max >= 1.0 {
+    //             self.messenger.send("Error: You are over your quota!");
+    //         } else if percentage_of_max >= 0.9 {
+    //             self.messenger
+    //                 .send("Urgent warning: You've used up over 90% of your quota!");
+    //         } else if percentage_of_max >= 0.75 {
+    //             self.messenger
+    //                 .send("Warning: You've used up over 75% of your quota!")
+    //         }
+    //     }
+    // }
 
     /*
     One important part of this code is that the Messenger trait has one method called
@@ -202,39 +219,8 @@ pub fn run() {
     to store the messages we've seen. 
     */
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::cell::RefCell;
-
-    struct MockMessenger {
-        sent_messages: RefCell<Vec<String>>,
-    }
-
-    impl MockMessenger {
-        fn new() -> MockMessenger {
-            MockMessenger {
-                sent_messages: RefCell::new(vec![]),
-            }
-        }
-    }
-
-    impl Messenger for MockMessenger {
-        fn send(&self, message: &str) {
-            self.sent_messages.borrow_mut().push(String::from(message))
-        }
-    }
-
-    #[test]
-    fn it_sends_an_over_75_percent_warning_message() {
-        let mock_messenger = MockMessenger::new();
-        let mut limit_tracker = LimitTracker::new(&mock_messenger, 100);
-
-        limit_tracker.set_value(80);
-
-        assert_eq!(mock_messenger.sent_messages.borrow().len(), 1);   
-    }
-}
+// The MockMessenger and its test are in limit_tracker.rs now, next to the
+// LimitTracker they test
 
 // Having Multiple Owners of Mutable Data by Combining Rc<T> and RefCell<T>
 
@@ -253,13 +239,16 @@ add in RefCell<T> to gain the ability to change the values in the lists.
 fn rc_and_ref_cell() {
     use std::cell::RefCell;
     use std::rc::Rc;
-    use List::{Cons, Nil};
+    // use List::{Cons, Nil};
 
-    #[derive(Debug)]
-    enum List {
-        Cons(Rc<RefCell<i32>>, Rc<List>),
-        Nil
-    }
+    // #[derive(Debug)]
+    // enum List {
+    //     Cons(Rc<RefCell<i32>>, Rc<List>),
+    //     Nil
+    // }
+
+    // List now lives in shared_list.rs, with tests checking a, b and c
+    use crate::shared_list::List::{Cons, Nil};
 
     let value = Rc::new(RefCell::new(5));
 
@@ -273,4 +262,9 @@ fn rc_and_ref_cell() {
     println!("a after = {:?}", a);
     println!("b after = {:?}", b);
     println!("c after = {:?}", c);
+
+    // shared_list also lets us mutate through a list itself, changing b's
+    // values changes the part it shares with a and c
+    b.add_to_all(1);
+    println!("c after b.add_to_all(1) = {:?}", c.values());
 }
@@ -51,13 +51,18 @@ pub fn run() {
     // lifetimes that won't be true.
     // instead we'll change the definition to use Rc<T> instead of Box<T>
 
-    use List::{Cons, Nil};
-    use std::rc::Rc;
+    // use List::{Cons, Nil};
+    // use std::rc::Rc;
 
-    enum List {
-        Cons(i32, Rc<List>),
-        Nil,
-    }
+    // enum List {
+    //     Cons(i32, Rc<List>),
+    //     Nil,
+    // }
+
+    // this List now lives in rc_list.rs, where tests assert each of the counts
+    // printed below
+    use crate::rc_list::{self, List::{Cons, Nil}};
+    use std::rc::Rc;
 
     let a = Rc::new(Cons(5, Rc::new(Cons(10, Rc::new(Nil)))));
     println!("count after creating a = {}", Rc::strong_count(&a));
@@ -77,4 +82,11 @@ pub fn run() {
     The println!s above illustrate the incrementing and decrementing of the 
     reference count as clones are created and go out of scope. 
     */
+
+    // b and c both lead to a, so that's where they join
+    let b = Rc::new(Cons(3, Rc::clone(&a)));
+    let c = Rc::new(Cons(4, Rc::clone(&a)));
+    if let Some(shared) = rc_list::shared_tail(&b, &c) {
+        println!("b and c share {:?}", shared);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,155 @@
+// 15.3 (continued) Scope Guards: Running Code When a Scope Ends
+
+/*
+Drop runs when a value goes out of scope, however the scope is left: falling
+off the end, an early return, a ? passing an error up, even a panic unwinding.
+A scope guard uses that to run any cleanup we like. It's a struct holding a
+closure, and its Drop calls the closure. Other languages have this built in as
+`defer` (Go, Swift, Zig).
+
+dismiss() is for "clean up unless everything went fine", like rolling back
+half done work: the guard is set up first, and once the work succeeds it's
+dismissed so the rollback never happens.
+
+The closure is stored in an Option because drop only gets &mut self, and an
+FnOnce has to be moved out to be called. take() does that (the same trick as
+the drop hook in my_box.rs).
+*/
+
+pub struct ScopeGuard<F: FnOnce()> {
+    on_drop: Option<F>,
+}
+
+impl<F: FnOnce()> ScopeGuard<F> {
+    pub fn new(on_drop: F) -> ScopeGuard<F> {
+        ScopeGuard {
+            on_drop: Some(on_drop),
+        }
+    }
+
+    // takes self, so the guard is dropped right here, with nothing to run
+    pub fn dismiss(mut self) {
+        self.on_drop = None;
+    }
+}
+
+impl<F: FnOnce()> Drop for ScopeGuard<F> {
+    fn drop(&mut self) {
+        if let Some(on_drop) = self.on_drop.take() {
+            on_drop();
+        }
+    }
+}
+
+/*
+defer! { ... } makes a guard that runs the block at the end of the current
+scope. It has to be bound to a name: `let _ = ScopeGuard::new(..)` would drop
+the guard (and run the block) immediately. The name is `_guard` inside the
+macro, and macro hygiene keeps it separate from any other `_guard`, so several
+defer!s in one scope don't shadow each other. Like any locals they're dropped
+in reverse order, so the last defer! runs first.
+*/
+#[macro_export]
+macro_rules! defer {
+    ($($body:tt)*) => {
+        let _guard = $crate::scope_guard::ScopeGuard::new(|| {
+            $($body)*
+        });
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::panic;
+
+    #[test]
+    fn runs_at_end_of_scope() {
+        let log = RefCell::new(Vec::new());
+        {
+            let _guard = ScopeGuard::new(|| log.borrow_mut().push("cleanup"));
+            log.borrow_mut().push("work");
+        }
+        assert_eq!(*log.borrow(), vec!["work", "cleanup"]);
+    }
+
+    #[test]
+    fn dismissed_guard_does_nothing() {
+        let log = RefCell::new(Vec::new());
+        {
+            let guard = ScopeGuard::new(|| log.borrow_mut().push("rollback"));
+            log.borrow_mut().push("work");
+            guard.dismiss();
+        }
+        assert_eq!(*log.borrow(), vec!["work"]);
+    }
+
+    #[test]
+    fn defers_run_last_first() {
+        let log = RefCell::new(Vec::new());
+        {
+            defer! { log.borrow_mut().push("first defer") }
+            defer! { log.borrow_mut().push("second defer") }
+            log.borrow_mut().push("body");
+        }
+        assert_eq!(*log.borrow(), vec!["body", "second defer", "first defer"]);
+    }
+
+    #[test]
+    fn ordered_with_other_drops() {
+        // guards are locals like any other, reverse order of creation
+        struct Loud<'a>(&'static str, &'a RefCell<Vec<&'static str>>);
+        impl Drop for Loud<'_> {
+            fn drop(&mut self) {
+                self.1.borrow_mut().push(self.0);
+            }
+        }
+
+        let log = RefCell::new(Vec::new());
+        {
+            let _a = Loud("a dropped", &log);
+            defer! { log.borrow_mut().push("deferred") }
+            let _b = Loud("b dropped", &log);
+        }
+        assert_eq!(*log.borrow(), vec!["b dropped", "deferred", "a dropped"]);
+    }
+
+    #[test]
+    fn runs_on_early_return() {
+        fn parse(input: &str, log: &RefCell<Vec<String>>) -> Result<i32, String> {
+            defer! { log.borrow_mut().push(format!("done with {:?}", input)) }
+            let n: i32 = input.trim().parse().map_err(|_| String::from("bad"))?;
+            Ok(n * 2)
+        }
+
+        let log = RefCell::new(Vec::new());
+        assert_eq!(parse("21", &log), Ok(42));
+        assert_eq!(parse("x", &log), Err(String::from("bad")));
+        assert_eq!(*log.borrow(), vec!["done with \"21\"", "done with \"x\""]);
+    }
+
+    #[test]
+    fn runs_while_unwinding_a_panic() {
+        let log = RefCell::new(Vec::new());
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            defer! { log.borrow_mut().push("cleaned up") }
+            panic!("boom");
+        }));
+        assert!(result.is_err());
+        assert_eq!(*log.borrow(), vec!["cleaned up"]);
+    }
+
+    #[test]
+    fn moved_guard_runs_where_it_ends_up() {
+        let log = RefCell::new(Vec::new());
+        let outer;
+        {
+            let guard = ScopeGuard::new(|| log.borrow_mut().push("ran"));
+            outer = guard;
+        }
+        assert!(log.borrow().is_empty());
+        drop(outer);
+        assert_eq!(*log.borrow(), vec!["ran"]);
+    }
+}
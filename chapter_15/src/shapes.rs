@@ -0,0 +1,211 @@
+// 15.1 (continued) Box<dyn Trait>: Owning a Value We Only Know by Its Trait
+
+/*
+The third reason box_pointer.rs lists for using a Box: "when you want to own a
+value and you care only that it's a type that implements a particular trait
+rather than being a specific type". A Vec needs every element to be the same
+size, and a Rectangle and a Circle aren't. A Box<dyn Shape> always is (it's a
+pointer to the value plus a pointer to the Shape methods for its type), so a
+Vec<Box<dyn Shape>> can hold any mix of shapes and own them all.
+
+Chapter 5 never had a Shape trait, so the trait is defined here. Rectangle is
+the domain crate's, the same one the other chapters use, re-exported so
+callers can keep writing chapter_15::shapes::Rectangle. A trait we define can
+be implemented for a type from another crate (it's only a foreign trait on a
+foreign type that isn't allowed). Trait objects get the full treatment in 17.2.
+
+That Rectangle already has an inherent area() returning u32, and inherent
+methods win over trait methods, so rect.area() on a plain Rectangle is the
+u32 one. Calls through a Box<dyn Shape> or an S: Shape always get this trait's
+f64 version, and Shape::area(&rect) asks for it by name (19.2's fully
+qualified syntax).
+*/
+
+use std::f64::consts::PI;
+
+pub use domain::Rectangle;
+
+pub trait Shape {
+    fn area(&self) -> f64;
+    fn name(&self) -> String;
+}
+
+impl Shape for Rectangle {
+    fn area(&self) -> f64 {
+        Rectangle::area(self) as f64
+    }
+
+    fn name(&self) -> String {
+        format!("{}x{} rectangle", self.width, self.height)
+    }
+}
+
+// f64 is only PartialEq (NaN != NaN), so shapes made of floats can't be Eq,
+// and without Eq they can't be Hash either: no HashMap keys
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Circle {
+    pub radius: f64,
+}
+
+impl Shape for Circle {
+    fn area(&self) -> f64 {
+        PI * self.radius * self.radius
+    }
+
+    fn name(&self) -> String {
+        format!("circle of radius {}", self.radius)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Triangle {
+    pub base: f64,
+    pub height: f64,
+}
+
+impl Shape for Triangle {
+    fn area(&self) -> f64 {
+        self.base * self.height / 2.0
+    }
+
+    fn name(&self) -> String {
+        format!("triangle {} wide and {} high", self.base, self.height)
+    }
+}
+
+// owns every shape added to it, whatever type it really is
+#[derive(Default)]
+pub struct Shapes {
+    shapes: Vec<Box<dyn Shape>>,
+}
+
+impl Shapes {
+    pub fn new() -> Shapes {
+        Shapes::default()
+    }
+
+    // generic so callers can pass a plain Rectangle, we do the boxing. The
+    // 'static says the shape can't be holding any borrowed references, since
+    // the collection keeps it for as long as it likes
+    pub fn add(&mut self, shape: impl Shape + 'static) {
+        self.shapes.push(Box::new(shape));
+    }
+
+    pub fn len(&self) -> usize {
+        self.shapes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shapes.is_empty()
+    }
+
+    pub fn total_area(&self) -> f64 {
+        self.shapes.iter().map(|shape| shape.area()).sum()
+    }
+
+    // None when empty. f64 isn't Ord, so max_by with total_cmp instead of
+    // max_by_key. On a tie the later shape wins, like Iterator::max_by
+    pub fn largest(&self) -> Option<&dyn Shape> {
+        self.shapes
+            .iter()
+            .max_by(|a, b| a.area().total_cmp(&b.area()))
+            .map(|shape| shape.as_ref())
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.shapes.iter().map(|shape| shape.name()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mixed() -> Shapes {
+        let mut shapes = Shapes::new();
+        shapes.add(Rectangle {
+            width: 30,
+            height: 50,
+        });
+        shapes.add(Circle { radius: 1.0 });
+        shapes.add(Triangle {
+            base: 4.0,
+            height: 3.0,
+        });
+        shapes.add(Rectangle::square(2));
+        shapes
+    }
+
+    #[test]
+    fn holds_different_types() {
+        let shapes = mixed();
+        assert_eq!(shapes.len(), 4);
+        assert_eq!(
+            shapes.names(),
+            vec![
+                "30x50 rectangle",
+                "circle of radius 1",
+                "triangle 4 wide and 3 high",
+                "2x2 rectangle",
+            ]
+        );
+    }
+
+    #[test]
+    fn total_area_adds_every_kind() {
+        // 1500 + pi + 6 + 4
+        let expected = 1510.0 + PI;
+        assert!((mixed().total_area() - expected).abs() < 1e-9);
+        assert_eq!(Shapes::new().total_area(), 0.0);
+    }
+
+    #[test]
+    fn largest_shape() {
+        let shapes = mixed();
+        assert_eq!(shapes.largest().unwrap().name(), "30x50 rectangle");
+
+        let mut shapes = Shapes::new();
+        shapes.add(Rectangle::square(3));
+        shapes.add(Circle { radius: 2.0 });
+        assert_eq!(shapes.largest().unwrap().name(), "circle of radius 2");
+    }
+
+    #[test]
+    fn largest_of_nothing() {
+        assert!(Shapes::new().largest().is_none());
+        assert!(Shapes::new().is_empty());
+    }
+
+    #[test]
+    fn boxes_are_all_the_same_size() {
+        use std::mem::size_of;
+        // whatever's inside, the Vec only ever stores two pointers per shape
+        assert_eq!(size_of::<Box<dyn Shape>>(), 2 * size_of::<usize>());
+        assert_ne!(size_of::<Rectangle>(), size_of::<Triangle>());
+    }
+
+    #[test]
+    fn rectangles_as_keys_floats_only_compare() {
+        use std::collections::HashMap;
+
+        let mut areas: HashMap<Rectangle, f64> = HashMap::new();
+        for rect in [
+            Rectangle::square(2),
+            Rectangle::square(3),
+            Rectangle::square(2),
+        ] {
+            areas.insert(rect, Shape::area(&rect));
+        }
+        assert_eq!(areas.len(), 2);
+        assert_eq!(areas[&Rectangle::square(3)], 9.0);
+
+        let circle = Circle { radius: 1.5 };
+        assert_eq!(circle, circle.clone());
+        assert_ne!(circle, Circle { radius: 2.0 });
+        let nan = Triangle {
+            base: f64::NAN,
+            height: 1.0,
+        };
+        assert_ne!(nan, nan);
+    }
+}
@@ -0,0 +1,114 @@
+// 15.5 (continued) Multiple Owners of Mutable Data: Rc<RefCell<T>>
+
+/*
+The last example in ref_cell.rs, pulled out so it can be tested. Each value in
+the list is an Rc<RefCell<i32>>: the Rc lets several lists own the same value,
+and the RefCell lets any of those owners change it, even though Rc only ever
+hands out shared references. Change a value through one owner and every list
+holding it sees the new number.
+*/
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[derive(Debug)]
+pub enum List {
+    Cons(Rc<RefCell<i32>>, Rc<List>),
+    Nil,
+}
+
+use List::Cons;
+
+impl List {
+    // a snapshot of what the list holds right now
+    pub fn values(&self) -> Vec<i32> {
+        let mut values = Vec::new();
+        let mut current = self;
+        while let Cons(value, next) = current {
+            values.push(*value.borrow());
+            current = next;
+        }
+        values
+    }
+
+    // only needs &self: the borrow_mut is checked at runtime by the RefCell,
+    // not by the compiler. Any value this list shares with another list gets
+    // changed for that list too
+    pub fn add_to_all(&self, amount: i32) {
+        let mut current = self;
+        while let Cons(value, next) = current {
+            *value.borrow_mut() += amount;
+            current = next;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use List::Nil;
+
+    // the book's three lists: a is (5), and b and c both continue into a
+    fn book_lists() -> (Rc<RefCell<i32>>, Rc<List>, List, List) {
+        let value = Rc::new(RefCell::new(5));
+        let a = Rc::new(Cons(Rc::clone(&value), Rc::new(Nil)));
+        let b = Cons(Rc::new(RefCell::new(3)), Rc::clone(&a));
+        let c = Cons(Rc::new(RefCell::new(4)), Rc::clone(&a));
+        (value, a, b, c)
+    }
+
+    #[test]
+    fn starts_out_like_the_book() {
+        let (_, a, b, c) = book_lists();
+        assert_eq!(a.values(), vec![5]);
+        assert_eq!(b.values(), vec![3, 5]);
+        assert_eq!(c.values(), vec![4, 5]);
+    }
+
+    #[test]
+    fn mutation_is_visible_through_every_owner() {
+        let (value, a, b, c) = book_lists();
+        *value.borrow_mut() += 10;
+        assert_eq!(a.values(), vec![15]);
+        assert_eq!(b.values(), vec![3, 15]);
+        assert_eq!(c.values(), vec![4, 15]);
+    }
+
+    #[test]
+    fn changing_b_changes_the_shared_part_of_c() {
+        let (value, a, b, c) = book_lists();
+        b.add_to_all(1);
+        // b's own 3 is b's alone, but the 5 belongs to a, b and c
+        assert_eq!(b.values(), vec![4, 6]);
+        assert_eq!(a.values(), vec![6]);
+        assert_eq!(c.values(), vec![4, 6]);
+        assert_eq!(*value.borrow(), 6);
+    }
+
+    #[test]
+    fn owners_are_counted() {
+        let (value, a, b, c) = book_lists();
+        // value itself, and the Cons in a
+        assert_eq!(Rc::strong_count(&value), 2);
+        // a, plus the tails of b and c
+        assert_eq!(Rc::strong_count(&a), 3);
+        drop(b);
+        drop(c);
+        assert_eq!(Rc::strong_count(&a), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "already borrowed")]
+    fn borrowing_mutably_twice_panics() {
+        // the rules haven't gone away, they're checked at runtime instead
+        let (value, a, _, _) = book_lists();
+        let _held = value.borrow();
+        a.add_to_all(1);
+    }
+
+    #[test]
+    fn nil_has_nothing() {
+        assert!(Nil.values().is_empty());
+        Nil.add_to_all(5);
+    }
+}
@@ -0,0 +1,176 @@
+// 15.6 Preventing Reference Cycles: Turning an Rc<T> into a Weak<T>
+
+/*
+Rc::clone bumps the strong_count, and a value is only cleaned up once its
+strong_count is back to 0. Rc::downgrade gives a Weak<T> instead, which bumps
+the weak_count. Weak references don't keep the value alive: when the last
+strong reference goes, the value is dropped no matter how many Weaks are left.
+Because of that a Weak might point at something that's gone, so using one
+means calling upgrade(), which returns an Option<Rc<T>>.
+
+The book's example is a tree where nodes know both their children and their
+parent. A parent should own its children (drop the parent and the children go
+with it), but a child shouldn't own its parent. So children are Rcs and the
+parent is a Weak. If both directions were Rcs, parent and child would keep
+each other alive forever.
+
+The RefCells are there so we can change who a node's parent and children are
+after the node has been created and shared.
+*/
+
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+#[derive(Debug)]
+pub struct Node {
+    pub value: i32,
+    pub parent: RefCell<Weak<Node>>,
+    pub children: RefCell<Vec<Rc<Node>>>,
+}
+
+impl Node {
+    // Weak::new() points at nothing, so a new node has no parent yet
+    pub fn new(value: i32) -> Rc<Node> {
+        Rc::new(Node {
+            value,
+            parent: RefCell::new(Weak::new()),
+            children: RefCell::new(vec![]),
+        })
+    }
+
+    // `self: &Rc<Self>` so we have an Rc to downgrade into the child's parent
+    // pointer, a plain &self wouldn't give us one
+    pub fn add_child(self: &Rc<Self>, child: Rc<Node>) {
+        *child.parent.borrow_mut() = Rc::downgrade(self);
+        self.children.borrow_mut().push(child);
+    }
+
+    // None if there's no parent, or if it's already been dropped
+    pub fn parent(&self) -> Option<Rc<Node>> {
+        self.parent.borrow().upgrade()
+    }
+
+    // parent, grandparent, ... up to the root
+    pub fn ancestors(&self) -> Ancestors {
+        Ancestors {
+            next: self.parent(),
+        }
+    }
+
+    // how many steps up to the root, so the root itself is 0
+    pub fn depth(&self) -> usize {
+        self.ancestors().count()
+    }
+}
+
+pub struct Ancestors {
+    next: Option<Rc<Node>>,
+}
+
+impl Iterator for Ancestors {
+    type Item = Rc<Node>;
+
+    fn next(&mut self) -> Option<Rc<Node>> {
+        let current = self.next.take()?;
+        self.next = current.parent();
+        Some(current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(nodes: impl Iterator<Item = Rc<Node>>) -> Vec<i32> {
+        nodes.map(|node| node.value).collect()
+    }
+
+    #[test]
+    fn book_example_counts() {
+        let leaf = Node::new(3);
+        assert_eq!(Rc::strong_count(&leaf), 1);
+        assert_eq!(Rc::weak_count(&leaf), 0);
+        assert!(leaf.parent().is_none());
+
+        {
+            let branch = Node::new(5);
+            branch.add_child(Rc::clone(&leaf));
+
+            // branch is owned once (by `branch`) and pointed to weakly once
+            // (by leaf's parent)
+            assert_eq!(Rc::strong_count(&branch), 1);
+            assert_eq!(Rc::weak_count(&branch), 1);
+            // leaf is owned by `leaf` and by branch's children
+            assert_eq!(Rc::strong_count(&leaf), 2);
+            assert_eq!(Rc::weak_count(&leaf), 0);
+
+            assert_eq!(leaf.parent().map(|p| p.value), Some(5));
+        }
+
+        // branch had no other strong owners, so it's gone even though leaf
+        // still has a Weak to it
+        assert!(leaf.parent().is_none());
+        assert_eq!(Rc::strong_count(&leaf), 1);
+        assert_eq!(Rc::weak_count(&leaf), 0);
+    }
+
+    #[test]
+    fn upgrading_counts_while_held() {
+        let branch = Node::new(5);
+        let leaf = Node::new(3);
+        branch.add_child(Rc::clone(&leaf));
+
+        let parent = leaf.parent().unwrap();
+        // the upgraded Rc is a real owner while we hold it
+        assert_eq!(Rc::strong_count(&branch), 2);
+        drop(parent);
+        assert_eq!(Rc::strong_count(&branch), 1);
+    }
+
+    #[test]
+    fn children_are_owned_by_the_parent() {
+        let root = Node::new(1);
+        root.add_child(Node::new(2));
+        root.add_child(Node::new(3));
+        let children = root.children.borrow();
+        assert_eq!(children.len(), 2);
+        // nobody else owns them
+        assert!(children.iter().all(|child| Rc::strong_count(child) == 1));
+        assert_eq!(Rc::weak_count(&root), 2);
+    }
+
+    #[test]
+    fn depth_and_ancestors() {
+        let root = Node::new(1);
+        let middle = Node::new(2);
+        let leaf = Node::new(3);
+        root.add_child(Rc::clone(&middle));
+        middle.add_child(Rc::clone(&leaf));
+
+        assert_eq!(root.depth(), 0);
+        assert_eq!(middle.depth(), 1);
+        assert_eq!(leaf.depth(), 2);
+        assert_eq!(values(leaf.ancestors()), vec![2, 1]);
+        assert_eq!(values(root.ancestors()), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn dropping_the_root_drops_the_whole_tree() {
+        let root = Node::new(1);
+        let middle = Node::new(2);
+        root.add_child(Rc::clone(&middle));
+        let watch_middle = Rc::downgrade(&middle);
+        let leaf = Node::new(3);
+        middle.add_child(Rc::clone(&leaf));
+        drop(middle);
+
+        // root keeps middle alive through its children
+        assert_eq!(leaf.depth(), 2);
+
+        drop(root);
+        // nothing owns middle any more, so leaf's ancestors are gone
+        assert!(watch_middle.upgrade().is_none());
+        assert_eq!(leaf.depth(), 0);
+        assert_eq!(Rc::strong_count(&leaf), 1);
+    }
+}
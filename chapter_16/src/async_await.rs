@@ -0,0 +1,28 @@
+// Async/Await
+
+/*
+Futures, async fn, and .await, with a hand written Timer and executor so
+nothing is hidden. async_basics.rs has the details. The tokio version of the
+16.2 pipeline is in async_pipeline.rs, behind the `tokio` feature.
+*/
+
+use crate::async_basics::{self, Timer};
+use std::time::{Duration, Instant};
+
+pub fn run() {
+    let start = Instant::now();
+
+    // calling an async fn runs none of it, it just makes a future
+    let future = async_basics::add_later(2, 3, Duration::from_millis(100));
+    println!("made the future after {:?}", start.elapsed());
+
+    // block_on polls it until it's done
+    println!(
+        "2 + 3 = {} after {:?}",
+        async_basics::block_on(future),
+        start.elapsed()
+    );
+
+    let (_, polls) = async_basics::block_on_counting(Timer::new(Duration::from_millis(50)));
+    println!("a 50ms timer was polled {polls} times");
+}
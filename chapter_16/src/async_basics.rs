@@ -0,0 +1,185 @@
+// Async/Await: Futures Without a Runtime
+
+/*
+Threads (16.1) let the OS switch between tasks. async is the other way of
+doing several things at once: an async fn doesn't run when it's called, it
+returns a Future, a value that can be asked "are you done yet?" by calling
+poll(). poll returns either
+
+    Poll::Ready(value)   finished, here's the result
+    Poll::Pending        not yet, I'll call the Waker when it's worth asking again
+
+`.await` is the compiler writing that loop for us: inside an async fn,
+`timer.await` polls the timer, and while it's Pending the whole async fn
+returns Pending too, handing the thread back to whoever is polling.
+
+Somebody has to do the polling at the top, and std doesn't include one. That's
+what a runtime like tokio is for (async_pipeline.rs). To see there's no magic,
+this module has both halves written by hand:
+
+    - Timer, a future that becomes ready after a Duration. A helper thread
+      sleeps, then marks it done and calls the waker
+    - block_on, the smallest possible executor: poll the future, and if it's
+      Pending, park the thread until the waker unparks it
+
+A real runtime does the same thing with many futures per thread, which is why
+thousands of async tasks are cheap where thousands of threads aren't.
+*/
+
+use std::future::Future;
+use std::pin::pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+use std::time::Duration;
+
+// what the helper thread and the future share
+struct TimerState {
+    completed: bool,
+    // the waker from the most recent poll, a future can move between tasks,
+    // so it has to be replaced every time
+    waker: Option<Waker>,
+}
+
+pub struct Timer {
+    state: Arc<Mutex<TimerState>>,
+}
+
+impl Timer {
+    pub fn new(duration: Duration) -> Timer {
+        let state = Arc::new(Mutex::new(TimerState {
+            completed: false,
+            waker: None,
+        }));
+
+        let thread_state = Arc::clone(&state);
+        thread::spawn(move || {
+            thread::sleep(duration);
+            let mut state = thread_state.lock().unwrap();
+            state.completed = true;
+            // nobody may have polled yet, in which case there's no one to wake
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+        });
+
+        Timer { state }
+    }
+}
+
+impl Future for Timer {
+    type Output = ();
+
+    fn poll(self: std::pin::Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.state.lock().unwrap();
+        if state.completed {
+            Poll::Ready(())
+        } else {
+            // checked and stored under one lock, so the helper thread can't
+            // finish in between and miss the waker
+            state.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+// Waking this executor means unparking the thread blocked in block_on
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+// runs a future to completion on the current thread
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    block_on_counting(future).0
+}
+
+// the same, also counting how many times the future was polled
+pub fn block_on_counting<F: Future>(future: F) -> (F::Output, usize) {
+    // poll needs a Pin<&mut F>: once polled, an async fn's future may hold
+    // references into itself, so it mustn't move. pin! pins it to this stack frame
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+
+    let mut polls = 0;
+    loop {
+        polls += 1;
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return (output, polls),
+            // park can wake up spuriously, that's fine, it only costs an
+            // extra poll that returns Pending again
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+// Two timers one after the other: the second isn't even created until the
+// first is done, so this takes about first + second
+pub async fn one_after_another(first: Duration, second: Duration) -> &'static str {
+    Timer::new(first).await;
+    Timer::new(second).await;
+    "both timers finished"
+}
+
+// an async fn can await other async fns, and return whatever it likes
+pub async fn add_later(a: i32, b: i32, delay: Duration) -> i32 {
+    Timer::new(delay).await;
+    a + b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    const SHORT: Duration = Duration::from_millis(20);
+
+    #[test]
+    fn ready_futures_need_one_poll() {
+        let (value, polls) = block_on_counting(async { 7 });
+        assert_eq!(value, 7);
+        assert_eq!(polls, 1);
+    }
+
+    #[test]
+    fn timer_waits_at_least_its_duration() {
+        let start = Instant::now();
+        let (_, polls) = block_on_counting(Timer::new(SHORT));
+        assert!(start.elapsed() >= SHORT);
+        // pending at least once, then ready after the wake
+        assert!(polls >= 2, "polled {} times", polls);
+    }
+
+    #[test]
+    fn nothing_happens_until_polled() {
+        // calling an async fn only builds the future...
+        let future = add_later(2, 3, SHORT);
+        thread::sleep(SHORT * 2);
+        // ...its timer is created on the first poll, so this still waits
+        let start = Instant::now();
+        assert_eq!(block_on(future), 5);
+        assert!(start.elapsed() >= SHORT);
+    }
+
+    #[test]
+    fn awaits_run_in_order() {
+        let start = Instant::now();
+        assert_eq!(
+            block_on(one_after_another(SHORT, SHORT)),
+            "both timers finished"
+        );
+        assert!(start.elapsed() >= SHORT * 2);
+    }
+
+    #[test]
+    fn timer_already_done_before_first_poll() {
+        let timer = Timer::new(Duration::ZERO);
+        thread::sleep(SHORT);
+        // completed with no waker stored, the first poll just sees it's done
+        assert_eq!(block_on_counting(timer).1, 1);
+    }
+}
@@ -0,0 +1,130 @@
+// Async/Await (continued) The Pipeline on tokio
+
+/*
+pipeline::run_pipeline again, with the three stages as tokio tasks instead of
+threads, and tokio's mpsc instead of std's. Only built with the feature:
+
+    cargo test --features tokio
+
+Side by side with the thread version:
+
+    threads (pipeline.rs)            tasks (here)
+    thread::spawn(move || ..)        tokio::spawn(async move { .. })
+    mpsc::channel() (unbounded)      mpsc::channel(n), bounded: send().await
+                                     waits while n items are queued
+    for item in rx { .. }            while let Some(item) = rx.recv().await { .. }
+    handle.join().unwrap()           handle.await.unwrap()
+
+The shape is the same, and so is the way the stages shut down: each one ends
+when its receiver's senders are all dropped. The difference is what's waiting
+underneath. A blocked thread is an OS thread doing nothing. A task waiting in
+recv().await has returned Pending, so the runtime's few threads run other tasks
+in the meantime.
+
+The bounded channels are a real difference though: with std's unbounded
+channel a fast producer can fill memory if the consumer falls behind. Here it
+just waits (backpressure). std has sync_channel(n) for the same with threads.
+*/
+
+use tokio::sync::mpsc;
+
+// how many items can queue between two stages before the sender waits
+const BUFFER: usize = 16;
+
+pub async fn run_pipeline<T, U, A>(
+    items: Vec<T>,
+    transform: impl Fn(T) -> U + Send + 'static,
+    init: A,
+    fold: impl Fn(A, U) -> A + Send + 'static,
+) -> A
+where
+    T: Send + 'static,
+    U: Send + 'static,
+    A: Send + 'static,
+{
+    let (raw_tx, mut raw_rx) = mpsc::channel(BUFFER);
+    let (done_tx, mut done_rx) = mpsc::channel(BUFFER);
+
+    tokio::spawn(async move {
+        for item in items {
+            if raw_tx.send(item).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    tokio::spawn(async move {
+        while let Some(item) = raw_rx.recv().await {
+            if done_tx.send(transform(item)).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let aggregator = tokio::spawn(async move {
+        let mut acc = init;
+        while let Some(value) = done_rx.recv().await {
+            acc = fold(acc, value);
+        }
+        acc
+    });
+
+    aggregator.await.unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline;
+
+    #[tokio::test]
+    async fn same_result_as_the_threads() {
+        let numbers: Vec<u64> = (1..=1_000).collect();
+        let with_tasks = run_pipeline(numbers.clone(), |n| n * n, 0, |sum, n| sum + n).await;
+        let with_threads = pipeline::run_pipeline(numbers, |n| n * n, 0, |sum, n| sum + n);
+        assert_eq!(with_tasks, with_threads);
+        assert_eq!(with_tasks, 333_833_500);
+    }
+
+    #[tokio::test]
+    async fn order_survives_the_stages() {
+        let joined = run_pipeline(
+            vec!['a', 'b', 'c'],
+            |c: char| c.to_ascii_uppercase(),
+            String::new(),
+            |mut s, c| {
+                s.push(c);
+                s
+            },
+        )
+        .await;
+        assert_eq!(joined, "ABC");
+    }
+
+    #[tokio::test]
+    async fn more_items_than_the_buffer_holds() {
+        // far more than BUFFER, so the producer has to wait on the others
+        let count = run_pipeline((0..BUFFER * 50).collect(), |n: usize| n, 0, |c, _| c + 1).await;
+        assert_eq!(count, BUFFER * 50);
+    }
+
+    #[tokio::test]
+    async fn nothing_to_do() {
+        assert_eq!(run_pipeline(vec![], |n: i32| n, 7, |a, n| a + n).await, 7);
+    }
+
+    #[test]
+    fn driven_from_plain_sync_code() {
+        // #[tokio::test] builds a runtime behind the scenes, this is the same
+        // by hand. Runtime::block_on plays the part of block_on in
+        // async_basics.rs, with tokio::spawn support on top
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let total = runtime.block_on(run_pipeline(
+            vec![1, 2, 3],
+            |n: i32| n * 10,
+            0,
+            |a, n| a + n,
+        ));
+        assert_eq!(total, 60);
+    }
+}
@@ -0,0 +1,61 @@
+// 16.4 Extensible Concurrency with the Sync and Send Traits
+
+/*
+Almost none of the concurrency features so far are part of the language
+itself, they're in the standard library, and anyone could write their own.
+What the language does provide are two marker traits in std::marker: Send and
+Sync. Marker traits have no methods, they only mark a type as having a
+property the compiler can check.
+
+Allowing Transference of Ownership Between Threads with Send
+
+Send means ownership of a value can be transferred to another thread. Almost
+every type is Send. Rc<T> isn't: if an Rc were cloned and the clone sent to
+another thread, both threads could update the (non-atomic) reference count at
+the same time. That's why the compiler rejected Rc<Mutex<i32>> in 16.3.
+
+Any type made up entirely of Send types is automatically Send too. Raw
+pointers are the main other exception (more in chapter 19).
+
+Allowing Access from Multiple Threads with Sync
+
+Sync means it's safe to reference a value from multiple threads: T is Sync if
+&T is Send. Primitive types are Sync, and types made entirely of Sync types
+are Sync. Rc<T> isn't Sync for the same reason it isn't Send. RefCell<T> and
+Cell<T> aren't Sync, their borrow checking at runtime isn't thread safe.
+Mutex<T> is Sync, that's the whole point of it.
+
+Implementing Send and Sync Manually Is Unsafe
+
+Since types made of Send and Sync parts are automatically Send and Sync, we
+almost never implement them by hand. Doing it means promising guarantees the
+compiler can't check, which needs unsafe code (chapter 19).
+*/
+
+use crate::send_sync::{LocalTally, SharedTally};
+use std::thread;
+
+pub fn run() {
+    // everything on one thread: Rc<RefCell<..>> is cheaper and works fine
+    let local = LocalTally::new();
+    let also_local = local.clone();
+    local.add("apple");
+    also_local.add("apple");
+    println!("local apples: {}", local.count("apple"));
+
+    // thread::spawn(move || local.add("pear")); // error: Rc cannot be sent between threads safely
+
+    // the same thing across threads needs Arc<Mutex<..>>
+    let shared = SharedTally::new();
+    let handles: Vec<_> = ["apple", "pear", "apple"]
+        .into_iter()
+        .map(|word| {
+            let shared = shared.clone();
+            thread::spawn(move || shared.add(word))
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    println!("shared apples: {}", shared.count("apple"));
+}
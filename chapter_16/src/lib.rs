@@ -0,0 +1,62 @@
+// Chapter 16: Fearless Concurrency (library)
+// Work split across threads and joined back up, channels wired into
+// producer -> transformer -> aggregator pipelines, Mutex and Arc shared
+// state, what Send and Sync allow, and a first look at async
+// The walkthroughs that put those to use are here as well, exercises() lists
+// them for the runner
+
+pub mod async_await;
+pub mod async_basics;
+#[cfg(feature = "tokio")]
+pub mod async_pipeline;
+pub mod extensible_concurrency;
+pub mod message_passing;
+pub mod parallel;
+pub mod pipeline;
+pub mod send_sync;
+pub mod shared;
+pub mod shared_state;
+pub mod threads;
+
+use domain::Section;
+
+// every section main runs, in the same order, for the runner
+pub fn exercises() -> Vec<Section> {
+    vec![
+        Section {
+            name: "threads",
+            run: || {
+                threads::run();
+                Ok(())
+            },
+        },
+        Section {
+            name: "message_passing",
+            run: || {
+                message_passing::run();
+                Ok(())
+            },
+        },
+        Section {
+            name: "shared_state",
+            run: || {
+                shared_state::run();
+                Ok(())
+            },
+        },
+        Section {
+            name: "extensible_concurrency",
+            run: || {
+                extensible_concurrency::run();
+                Ok(())
+            },
+        },
+        Section {
+            name: "async_await",
+            run: || {
+                async_await::run();
+                Ok(())
+            },
+        },
+    ]
+}
@@ -0,0 +1,31 @@
+// Chapter 16: Fearless Concurrency
+
+/*
+Concurrent programming is when different parts of a program execute
+independently, parallel programming is when different parts execute at the
+same time. Both are getting more important as computers get more processors,
+and both have historically been hard to get right.
+
+The Rust team found that the ownership and type systems that manage memory
+safety also catch a lot of concurrency bugs, at compile time instead of as
+hard to reproduce bugs in production. That's what "fearless concurrency"
+means: code with subtle concurrency bugs often won't compile, and the error
+message explains the problem.
+
+This chapter covers:
+    - creating threads to run multiple pieces of code at the same time
+    - message passing concurrency, where channels send messages between threads
+    - shared state concurrency, where multiple threads have access to some data
+    - the Sync and Send traits, which extend Rust's concurrency guarantees to
+      user-defined types as well as types provided by the standard library
+*/
+
+use chapter_16::{async_await, extensible_concurrency, message_passing, shared_state, threads};
+
+fn main() {
+    threads::run();
+    message_passing::run();
+    shared_state::run();
+    extensible_concurrency::run();
+    async_await::run();
+}
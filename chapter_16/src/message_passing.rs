@@ -0,0 +1,108 @@
+// 16.2 Using Message Passing to Transfer Data Between Threads
+
+/*
+"Do not communicate by sharing memory; instead, share memory by communicating."
+(from the Go docs)
+
+A channel is how Rust does message passing. It has two halves: a transmitter
+and a receiver. One part of the code calls methods on the transmitter with the
+data it wants to send, another part checks the receiving end for arriving
+messages. A channel is closed when either half is dropped.
+
+mpsc stands for multiple producer, single consumer: a channel can have many
+sending ends but only one receiving end.
+*/
+
+use crate::pipeline;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+pub fn run() {
+    // mpsc::channel returns a tuple (transmitter, receiver), traditionally
+    // called tx and rx. The spawned thread has to own tx, so the closure is move
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        let val = String::from("hi");
+        tx.send(val).unwrap();
+        // println!("val is {val}"); // error: val was moved into send
+    });
+
+    /*
+    recv blocks until a value arrives, and returns Err once every transmitter
+    has been dropped (no more values can come). try_recv doesn't block, it
+    returns Err right away if nothing is waiting, which is handy when the
+    thread has other work to do between checks.
+    */
+    let received = rx.recv().unwrap();
+    println!("Got: {received}");
+
+    // Sending Multiple Values and Seeing the Receiver Waiting
+
+    // the receiver can be used as an iterator, which ends when the channel closes
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        let vals = vec![
+            String::from("hi"),
+            String::from("from"),
+            String::from("the"),
+            String::from("thread"),
+        ];
+
+        for val in vals {
+            tx.send(val).unwrap();
+            thread::sleep(Duration::from_millis(100));
+        }
+    });
+
+    for received in rx {
+        println!("Got: {received}");
+    }
+
+    // Creating Multiple Producers by Cloning the Transmitter
+
+    let (tx, rx) = mpsc::channel();
+
+    let tx1 = tx.clone();
+    thread::spawn(move || {
+        let vals = vec![
+            String::from("hi"),
+            String::from("from"),
+            String::from("the"),
+            String::from("thread"),
+        ];
+
+        for val in vals {
+            tx1.send(val).unwrap();
+            thread::sleep(Duration::from_millis(100));
+        }
+    });
+
+    thread::spawn(move || {
+        let vals = vec![
+            String::from("more"),
+            String::from("messages"),
+            String::from("for"),
+            String::from("you"),
+        ];
+
+        for val in vals {
+            tx.send(val).unwrap();
+            thread::sleep(Duration::from_millis(100));
+        }
+    });
+
+    // the two producers interleave differently every run
+    for received in rx {
+        println!("Got: {received}");
+    }
+
+    // a few threads chained together with channels, see pipeline.rs
+    let lines = vec![
+        String::from("I'm nobody! Who are you?"),
+        String::from("Are you nobody, too?"),
+    ];
+    println!("{:?}", pipeline::word_stats(lines));
+}
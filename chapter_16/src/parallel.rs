@@ -0,0 +1,159 @@
+// 16.1 (continued) Splitting Work Across Threads
+
+/*
+A thread's closure can return a value, and join() hands it back. So to split
+up a job: cut the input into chunks, spawn a thread per chunk that owns its
+chunk (move), keep every JoinHandle, then join them in the order they were
+spawned. Joining in spawn order means the results come back in input order,
+however the threads were actually scheduled.
+
+The bounds say what crossing into a thread needs. Everything moved in has to
+be Send (safe to hand to another thread, 16.4) and 'static (it can't borrow
+from the caller, since the thread might outlive it). The closure is cloned so
+each thread gets its own copy.
+*/
+
+use std::any::Any;
+use std::thread;
+
+// a vector moved into a thread, summed there, and the total handed back
+pub fn sum_in_thread(numbers: Vec<i64>) -> i64 {
+    let handle = thread::spawn(move || numbers.iter().sum());
+    handle.join().unwrap()
+}
+
+// applies `f` to every item, spread over `threads` threads, keeping input order
+pub fn map_in_threads<T, R, F>(items: Vec<T>, threads: usize, f: F) -> Vec<R>
+where
+    T: Send + 'static,
+    R: Send + 'static,
+    F: Fn(T) -> R + Send + Clone + 'static,
+{
+    assert!(threads > 0, "need at least one thread");
+    // rounded up, so there are never more than `threads` chunks
+    let chunk_size = items.len().div_ceil(threads).max(1);
+
+    let mut handles = Vec::new();
+    let mut items = items.into_iter();
+    loop {
+        let chunk: Vec<T> = items.by_ref().take(chunk_size).collect();
+        if chunk.is_empty() {
+            break;
+        }
+        let f = f.clone();
+        handles.push(thread::spawn(move || {
+            chunk.into_iter().map(f).collect::<Vec<R>>()
+        }));
+    }
+
+    handles
+        .into_iter()
+        .flat_map(|handle| handle.join().unwrap())
+        .collect()
+}
+
+pub fn parallel_sum(numbers: Vec<u64>, threads: usize) -> u64 {
+    assert!(threads > 0, "need at least one thread");
+    let chunk_size = numbers.len().div_ceil(threads).max(1);
+    let handles: Vec<thread::JoinHandle<u64>> = numbers
+        .chunks(chunk_size)
+        // each chunk is copied into a Vec the thread can own
+        .map(|chunk| chunk.to_vec())
+        .map(|chunk| thread::spawn(move || chunk.iter().sum()))
+        .collect();
+
+    handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .sum()
+}
+
+// Runs `f` on its own thread. A panic there doesn't take down the caller,
+// join just returns Err holding the panic's payload, which is usually the
+// message as a &str or a String
+pub fn run_catching<T, F>(f: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    thread::spawn(f).join().map_err(panic_message)
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => String::from("unknown panic"),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn moved_vector_summed_in_a_thread() {
+        let numbers = vec![1, 2, 3, -4];
+        assert_eq!(sum_in_thread(numbers), 2);
+        // numbers was moved, using it here wouldn't compile
+    }
+
+    #[test]
+    fn map_keeps_input_order() {
+        let doubled = map_in_threads((1..=10).collect(), 3, |n: i32| n * 2);
+        assert_eq!(doubled, (1..=10).map(|n| n * 2).collect::<Vec<i32>>());
+    }
+
+    #[test]
+    fn map_really_uses_several_threads() {
+        // each item reports which thread it ran on
+        let ids = map_in_threads((0..8).collect(), 4, |_: i32| thread::current().id());
+        let distinct: HashSet<_> = ids.into_iter().collect();
+        assert_eq!(distinct.len(), 4);
+        assert!(!distinct.contains(&thread::current().id()));
+    }
+
+    #[test]
+    fn more_threads_than_items() {
+        let words = vec![String::from("a"), String::from("bb")];
+        assert_eq!(map_in_threads(words, 10, |w: String| w.len()), vec![1, 2]);
+        assert!(map_in_threads(Vec::<i32>::new(), 4, |n| n).is_empty());
+    }
+
+    #[test]
+    fn parallel_sum_matches_serial() {
+        let numbers: Vec<u64> = (1..=10_001).collect();
+        let expected: u64 = numbers.iter().sum();
+        for threads in [1, 2, 3, 7, 16] {
+            assert_eq!(parallel_sum(numbers.clone(), threads), expected);
+        }
+        assert_eq!(parallel_sum(vec![], 3), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one thread")]
+    fn zero_threads() {
+        parallel_sum(vec![1], 0);
+    }
+
+    #[test]
+    fn join_gives_back_the_return_value() {
+        assert_eq!(run_catching(|| 6 * 7), Ok(42));
+    }
+
+    #[test]
+    fn panics_come_back_as_errors() {
+        assert_eq!(
+            run_catching(|| -> i32 { panic!("static message") }),
+            Err(String::from("static message"))
+        );
+        let n = 3;
+        assert_eq!(
+            run_catching(move || -> i32 { panic!("formatted {}", n) }),
+            Err(String::from("formatted 3"))
+        );
+    }
+}
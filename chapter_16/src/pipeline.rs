@@ -0,0 +1,281 @@
+// 16.2 (continued) Producers, Consumers, and Pipelines
+
+/*
+The patterns from the message passing walkthrough, as functions we can test:
+
+    - send_all: one producer thread, the receiver iterates until it closes
+    - fan_in: several producers, each with its own clone of tx
+    - run_pipeline: producer -> transformer -> aggregator, each stage its own
+      thread, joined by two channels
+
+The thing all of them rely on is how a channel closes. Iterating over rx ends
+only once *every* transmitter is gone, so any tx left alive (like the original
+one after handing clones to the producers) keeps the receiver waiting forever.
+It works the other way too: once the receiver is dropped, send returns Err,
+which is how a producer finds out nobody is listening any more.
+*/
+
+use std::panic;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::{self, JoinHandle};
+
+// Sends items in order until they run out or the receiver hangs up, and
+// returns how many actually got through. `items` can even be endless, the
+// producer still stops once nobody is receiving
+pub fn produce<T>(items: impl IntoIterator<Item = T>, tx: Sender<T>) -> usize {
+    let mut sent = 0;
+    for item in items {
+        if tx.send(item).is_err() {
+            break;
+        }
+        sent += 1;
+    }
+    sent
+}
+
+// one producer thread, values come out the other end in the order they went in
+pub fn send_all<T: Send + 'static>(items: Vec<T>) -> Vec<T> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || produce(items, tx));
+    rx.into_iter().collect()
+}
+
+// One producer per batch, all sending on clones of the same tx. Each batch
+// arrives in its own order, but the batches interleave however the threads ran
+pub fn fan_in<T: Send + 'static>(batches: Vec<Vec<T>>) -> Vec<T> {
+    let (tx, rx) = mpsc::channel();
+    for batch in batches {
+        let tx = tx.clone();
+        thread::spawn(move || produce(batch, tx));
+    }
+    // without this the original tx would keep the channel open after every
+    // producer finished, and the collect below would never return
+    drop(tx);
+    rx.into_iter().collect()
+}
+
+// Every item goes through `transform` on one thread, then gets folded into
+// `init` on another. The aggregator's result comes back through its JoinHandle.
+//
+// Every stage is joined, not just the last one. If `transform` panics, its
+// thread ends and drops done_tx, so the aggregator sees a closed channel and
+// happily returns a fold over only some of the items. Joining the transformer
+// is what notices, and the panic is carried on into the caller's thread
+pub fn run_pipeline<T, U, A>(
+    items: Vec<T>,
+    transform: impl Fn(T) -> U + Send + 'static,
+    init: A,
+    fold: impl Fn(A, U) -> A + Send + 'static,
+) -> A
+where
+    T: Send + 'static,
+    U: Send + 'static,
+    A: Send + 'static,
+{
+    let (raw_tx, raw_rx) = mpsc::channel();
+    let (done_tx, done_rx) = mpsc::channel();
+
+    let producer = thread::spawn(move || produce(items, raw_tx));
+    let middle = thread::spawn(move || transformer(raw_rx, done_tx, transform));
+    let aggregator = thread::spawn(move || done_rx.into_iter().fold(init, fold));
+
+    join_or_resume(producer);
+    join_or_resume(middle);
+    join_or_resume(aggregator)
+}
+
+// join() gives a panicked thread's payload back as an Err, resume_unwind
+// panics with that same payload (so the same message) on this thread
+fn join_or_resume<R>(handle: JoinHandle<R>) -> R {
+    match handle.join() {
+        Ok(result) => result,
+        Err(payload) => panic::resume_unwind(payload),
+    }
+}
+
+// the middle stage: ends when its input closes, which then closes its output
+fn transformer<T, U>(rx: Receiver<T>, tx: Sender<U>, transform: impl Fn(T) -> U) {
+    for item in rx {
+        if tx.send(transform(item)).is_err() {
+            break;
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Default)]
+pub struct WordStats {
+    pub lines: usize,
+    pub words: usize,
+    pub longest: String,
+}
+
+// lines -> (words in the line, its longest word) -> totals for all the lines
+pub fn word_stats(lines: Vec<String>) -> WordStats {
+    run_pipeline(
+        lines,
+        |line: String| {
+            let words: Vec<String> = line
+                .split_whitespace()
+                .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()))
+                .filter(|word| !word.is_empty())
+                .map(String::from)
+                .collect();
+            let longest = words.iter().fold(String::new(), |longest, word| {
+                if word.len() > longest.len() {
+                    word.clone()
+                } else {
+                    longest
+                }
+            });
+            (words.len(), longest)
+        },
+        WordStats::default(),
+        |mut stats, (words, longest)| {
+            stats.lines += 1;
+            stats.words += words;
+            if longest.len() > stats.longest.len() {
+                stats.longest = longest;
+            }
+            stats
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc::RecvError;
+
+    #[test]
+    fn single_producer_keeps_order() {
+        let words = vec!["hi", "from", "the", "thread"];
+        assert_eq!(send_all(words.clone()), words);
+        assert!(send_all(Vec::<i32>::new()).is_empty());
+    }
+
+    #[test]
+    fn fan_in_gets_everything() {
+        let batches = vec![(0..100).collect(), (100..150).collect(), vec![], vec![150]];
+        let mut received: Vec<i32> = fan_in(batches);
+        assert_eq!(received.len(), 151);
+        received.sort();
+        assert_eq!(received, (0..=150).collect::<Vec<i32>>());
+    }
+
+    #[test]
+    fn fan_in_keeps_each_producers_order() {
+        let batches = vec![
+            (0..50).map(|n| ('a', n)).collect(),
+            (0..50).map(|n| ('b', n)).collect(),
+        ];
+        let received = fan_in(batches);
+        for producer in ['a', 'b'] {
+            let seen: Vec<i32> = received
+                .iter()
+                .filter(|(from, _)| *from == producer)
+                .map(|(_, n)| *n)
+                .collect();
+            assert_eq!(seen, (0..50).collect::<Vec<i32>>());
+        }
+    }
+
+    #[test]
+    fn pipeline_transforms_then_folds() {
+        let total = run_pipeline((1..=10).collect(), |n: u64| n * n, 0, |sum, n| sum + n);
+        assert_eq!(total, 385);
+
+        let joined = run_pipeline(
+            vec!['a', 'b', 'c'],
+            |c: char| c.to_ascii_uppercase(),
+            String::new(),
+            |mut s, c| {
+                s.push(c);
+                s
+            },
+        );
+        // a single chain of threads, so order survives the trip
+        assert_eq!(joined, "ABC");
+    }
+
+    #[test]
+    fn pipeline_with_nothing_to_do() {
+        assert_eq!(run_pipeline(vec![], |n: i32| n, 7, |a, n| a + n), 7);
+    }
+
+    #[test]
+    #[should_panic(expected = "can't transform 3")]
+    fn pipeline_passes_on_a_transformer_panic() {
+        run_pipeline(
+            (1..=5).collect(),
+            |n: i32| {
+                assert!(n != 3, "can't transform {}", n);
+                n
+            },
+            0,
+            |sum, n| sum + n,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "fold gave up")]
+    fn pipeline_passes_on_an_aggregator_panic() {
+        run_pipeline(
+            vec![1],
+            |n: i32| n,
+            0,
+            |_, _: i32| -> i32 { panic!("fold gave up") },
+        );
+    }
+
+    #[test]
+    fn word_stats_aggregates_every_line() {
+        let lines = vec![
+            String::from("I'm nobody! Who are you?"),
+            String::from("Are you nobody, too?"),
+            String::from(""),
+        ];
+        assert_eq!(
+            word_stats(lines),
+            WordStats {
+                lines: 3,
+                words: 9,
+                longest: String::from("nobody"),
+            }
+        );
+    }
+
+    #[test]
+    fn receiver_errors_once_every_sender_is_gone() {
+        let (tx, rx) = mpsc::channel();
+        let tx2 = tx.clone();
+        tx.send(1).unwrap();
+        drop(tx);
+        // a clone is still alive, so the channel is open
+        tx2.send(2).unwrap();
+        drop(tx2);
+        // whatever was already sent can still be read...
+        assert_eq!(rx.recv(), Ok(1));
+        assert_eq!(rx.recv(), Ok(2));
+        // ...and only then does it report being closed
+        assert_eq!(rx.recv(), Err(RecvError));
+    }
+
+    #[test]
+    fn producer_stops_when_the_receiver_hangs_up() {
+        let (tx, rx) = mpsc::channel();
+        // 0.. never runs out, so only the receiver hanging up can stop this
+        let producer = thread::spawn(move || produce(0.., tx));
+        let first: Vec<i32> = rx.iter().take(3).collect();
+        drop(rx);
+        assert_eq!(first, vec![0, 1, 2]);
+        let sent = producer.join().unwrap();
+        assert!(sent >= 3, "sent {}", sent);
+    }
+
+    #[test]
+    fn produce_into_a_closed_channel_sends_nothing() {
+        let (tx, rx) = mpsc::channel();
+        drop(rx);
+        assert_eq!(produce(vec![1, 2, 3], tx), 0);
+    }
+}
@@ -0,0 +1,176 @@
+// 16.4 (continued) One Type, Two Versions: !Send and Send
+
+/*
+LocalTally and SharedTally do the same job, counting words, and both can be
+cloned to get another handle onto the same counts. The only difference is the
+smart pointers inside:
+
+    LocalTally:  Rc<RefCell<HashMap<..>>>   neither Send nor Sync
+    SharedTally: Arc<Mutex<HashMap<..>>>    both Send and Sync
+
+Nobody wrote `impl Send` for either of them, the compiler worked it out from
+the fields. One Rc field is enough to make the whole struct !Send.
+
+The two compile_fail examples below are doc tests: `cargo test` compiles them
+and the test *passes* only if they fail to compile (with E0277, "the trait
+bound is not satisfied"). So they double as a check that LocalTally stays
+!Send, if someone swapped its Rc for an Arc they'd start compiling and fail.
+*/
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+/// Word counts for a single thread.
+///
+/// It can't be moved into another thread:
+///
+/// ```compile_fail,E0277
+/// use chapter_16::send_sync::LocalTally;
+/// use std::thread;
+///
+/// let tally = LocalTally::new();
+/// thread::spawn(move || tally.add("hi"));
+/// ```
+///
+/// and it can't be shared with one by reference either, it isn't Sync:
+///
+/// ```compile_fail,E0277
+/// use chapter_16::send_sync::LocalTally;
+///
+/// fn needs_sync<T: Sync>(_: &T) {}
+/// needs_sync(&LocalTally::new());
+/// ```
+#[derive(Clone, Default)]
+pub struct LocalTally {
+    counts: Rc<RefCell<HashMap<String, usize>>>,
+}
+
+impl LocalTally {
+    pub fn new() -> LocalTally {
+        LocalTally::default()
+    }
+
+    pub fn add(&self, word: &str) {
+        *self
+            .counts
+            .borrow_mut()
+            .entry(word.to_string())
+            .or_insert(0) += 1;
+    }
+
+    pub fn count(&self, word: &str) -> usize {
+        self.counts.borrow().get(word).copied().unwrap_or(0)
+    }
+
+    pub fn total(&self) -> usize {
+        self.counts.borrow().values().sum()
+    }
+}
+
+/// Word counts any number of threads can add to at once.
+///
+/// ```
+/// use chapter_16::send_sync::SharedTally;
+/// use std::thread;
+///
+/// let tally = SharedTally::new();
+/// let handle = {
+///     let tally = tally.clone();
+///     thread::spawn(move || tally.add("hi"))
+/// };
+/// handle.join().unwrap();
+/// assert_eq!(tally.count("hi"), 1);
+/// ```
+#[derive(Clone, Default)]
+pub struct SharedTally {
+    counts: Arc<Mutex<HashMap<String, usize>>>,
+}
+
+impl SharedTally {
+    pub fn new() -> SharedTally {
+        SharedTally::default()
+    }
+
+    pub fn add(&self, word: &str) {
+        *self
+            .counts
+            .lock()
+            .unwrap()
+            .entry(word.to_string())
+            .or_insert(0) += 1;
+    }
+
+    pub fn count(&self, word: &str) -> usize {
+        self.counts.lock().unwrap().get(word).copied().unwrap_or(0)
+    }
+
+    pub fn total(&self) -> usize {
+        self.counts.lock().unwrap().values().sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    // these only compile if T has the trait, so calling them is the test
+    fn assert_send<T: Send>() {}
+    fn assert_sync<T: Sync>() {}
+
+    #[test]
+    fn shared_tally_is_send_and_sync() {
+        assert_send::<SharedTally>();
+        assert_sync::<SharedTally>();
+    }
+
+    #[test]
+    fn local_tally_works_on_one_thread() {
+        let tally = LocalTally::new();
+        let other = tally.clone();
+        tally.add("a");
+        other.add("a");
+        other.add("b");
+        assert_eq!(tally.count("a"), 2);
+        assert_eq!(tally.count("missing"), 0);
+        assert_eq!(tally.total(), 3);
+    }
+
+    #[test]
+    fn clones_added_to_from_many_threads() {
+        let tally = SharedTally::new();
+        let handles: Vec<_> = (0..8)
+            .map(|t| {
+                let tally = tally.clone();
+                thread::spawn(move || {
+                    for _ in 0..100 {
+                        tally.add("shared");
+                    }
+                    tally.add(&format!("thread {}", t));
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(tally.count("shared"), 800);
+        assert_eq!(tally.count("thread 3"), 1);
+        assert_eq!(tally.total(), 808);
+    }
+
+    #[test]
+    fn shared_by_reference_with_scoped_threads() {
+        // Sync is what lets several threads hold a plain &SharedTally at once.
+        // thread::scope guarantees the threads finish before `tally` is
+        // dropped, so they can borrow it without cloning the Arc
+        let tally = SharedTally::new();
+        thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| tally.add("scoped"));
+            }
+        });
+        assert_eq!(tally.count("scoped"), 4);
+    }
+}
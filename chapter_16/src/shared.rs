@@ -0,0 +1,272 @@
+// 16.3 (continued) Wrapping Shared State in a Safe API
+
+/*
+Arc<Mutex<T>> works, but every caller has to remember to clone the Arc, lock,
+and decide what to do when lock() returns Err. SharedRegistry hides all of that
+behind methods that take &self: clone the registry (which only clones the Arc)
+to hand it to another thread, and every clone sees the same map.
+
+None of the methods give out the MutexGuard or a reference into the map, they
+copy values out instead. A reference into the map would have to keep the lock
+held for as long as it lives, which makes it easy to deadlock by accidentally
+calling another method while still holding it.
+
+Poisoning: if a thread panics while it holds the lock, the mutex is marked
+poisoned and every later lock() returns Err. That's there because the panic
+might have left the data half updated. Our own code never panics part way
+through changing the map, the only code that can is a closure passed to
+update(), and that can only leave its one value half changed. So the registry
+recovers the lock (PoisonError::into_inner) and keeps working, is_poisoned()
+tells callers it happened, and clear_poison() resets it once they've checked.
+*/
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+
+// the book's counter, with any number of threads each adding `increments` times
+pub fn count_in_threads(threads: usize, increments: usize) -> usize {
+    let counter = Arc::new(Mutex::new(0));
+    let handles: Vec<_> = (0..threads)
+        .map(|_| {
+            let counter = Arc::clone(&counter);
+            thread::spawn(move || {
+                for _ in 0..increments {
+                    // lock per increment, so the threads really interleave
+                    *counter.lock().unwrap() += 1;
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let total = *counter.lock().unwrap();
+    total
+}
+
+pub struct SharedRegistry<K, V> {
+    entries: Arc<Mutex<HashMap<K, V>>>,
+}
+
+// derive(Clone) would require K: Clone and V: Clone, but cloning the Arc
+// doesn't need either
+impl<K, V> Clone for SharedRegistry<K, V> {
+    fn clone(&self) -> Self {
+        SharedRegistry {
+            entries: Arc::clone(&self.entries),
+        }
+    }
+}
+
+impl<K, V> Default for SharedRegistry<K, V> {
+    fn default() -> Self {
+        SharedRegistry {
+            entries: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+impl<K: Eq + Hash, V> SharedRegistry<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<K, V>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    // returns the value that was there before, like HashMap::insert
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        self.lock().insert(key, value)
+    }
+
+    pub fn remove(&self, key: &K) -> Option<V> {
+        self.lock().remove(key)
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.lock().contains_key(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    // Changes a value in place while holding the lock, so no other thread can
+    // get in between reading and writing it. false if the key isn't there
+    pub fn update(&self, key: &K, f: impl FnOnce(&mut V)) -> bool {
+        match self.lock().get_mut(key) {
+            Some(value) => {
+                f(value);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // true if some thread panicked while holding the lock
+    pub fn is_poisoned(&self) -> bool {
+        self.entries.is_poisoned()
+    }
+
+    pub fn clear_poison(&self) {
+        self.entries.clear_poison();
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> SharedRegistry<K, V> {
+    pub fn get(&self, key: &K) -> Option<V> {
+        self.lock().get(key).cloned()
+    }
+
+    // the value for `key`, inserting `make()` first if there isn't one. The
+    // check and the insert happen under one lock, so two threads can't both
+    // decide the key is missing
+    pub fn get_or_insert_with(&self, key: K, make: impl FnOnce() -> V) -> V {
+        self.lock().entry(key).or_insert_with(make).clone()
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> SharedRegistry<K, V> {
+    // a copy of the whole map at one moment
+    pub fn snapshot(&self) -> HashMap<K, V> {
+        self.lock().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ten_threads_counter() {
+        assert_eq!(count_in_threads(10, 1), 10);
+        assert_eq!(count_in_threads(10, 10_000), 100_000);
+        assert_eq!(count_in_threads(0, 5), 0);
+    }
+
+    #[test]
+    fn single_thread_map_operations() {
+        let registry = SharedRegistry::new();
+        assert!(registry.is_empty());
+        assert_eq!(registry.insert("a", 1), None);
+        assert_eq!(registry.insert("a", 2), Some(1));
+        assert_eq!(registry.get(&"a"), Some(2));
+        assert!(registry.contains_key(&"a"));
+        assert_eq!(registry.len(), 1);
+        assert_eq!(registry.remove(&"a"), Some(2));
+        assert_eq!(registry.get(&"a"), None);
+        assert!(!registry.update(&"a", |v| *v += 1));
+    }
+
+    #[test]
+    fn clones_share_one_map() {
+        let registry = SharedRegistry::new();
+        let other = registry.clone();
+        other.insert(String::from("key"), vec![1]);
+        assert_eq!(registry.get(&String::from("key")), Some(vec![1]));
+        assert_eq!(Arc::strong_count(&registry.entries), 2);
+    }
+
+    #[test]
+    fn inserts_from_many_threads() {
+        let registry = SharedRegistry::new();
+        let handles: Vec<_> = (0..8)
+            .map(|t| {
+                let registry = registry.clone();
+                thread::spawn(move || {
+                    for i in 0..100 {
+                        registry.insert(t * 100 + i, t);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        let snapshot = registry.snapshot();
+        assert_eq!(snapshot.len(), 800);
+        assert_eq!(snapshot[&742], 7);
+    }
+
+    #[test]
+    fn updates_from_many_threads_are_not_lost() {
+        let registry = SharedRegistry::new();
+        registry.insert("hits", 0);
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let registry = registry.clone();
+                thread::spawn(move || {
+                    for _ in 0..1_000 {
+                        // a separate get then insert could lose increments,
+                        // update does the read and write under one lock
+                        registry.update(&"hits", |hits| *hits += 1);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(registry.get(&"hits"), Some(8_000));
+    }
+
+    #[test]
+    fn get_or_insert_with_only_makes_one_value() {
+        let registry = SharedRegistry::new();
+        let made = Arc::new(Mutex::new(0));
+        let handles: Vec<_> = (0..8)
+            .map(|t| {
+                let registry = registry.clone();
+                let made = Arc::clone(&made);
+                thread::spawn(move || {
+                    registry.get_or_insert_with("config", || {
+                        *made.lock().unwrap() += 1;
+                        t
+                    })
+                })
+            })
+            .collect();
+        let seen: Vec<i32> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        assert_eq!(*made.lock().unwrap(), 1);
+        // every thread saw whichever value got there first
+        assert!(seen.iter().all(|v| *v == seen[0]));
+    }
+
+    #[test]
+    fn survives_a_panic_while_locked() {
+        let registry = SharedRegistry::new();
+        registry.insert("a", 1);
+        registry.insert("b", 2);
+
+        let panicking = registry.clone();
+        let result = thread::spawn(move || {
+            panicking.update(&"a", |value| {
+                *value = 100;
+                panic!("gave up half way");
+            });
+        })
+        .join();
+        assert!(result.is_err());
+
+        assert!(registry.is_poisoned());
+        // still usable, and the change made before the panic stuck
+        assert_eq!(registry.get(&"a"), Some(100));
+        assert_eq!(registry.get(&"b"), Some(2));
+        registry.insert("c", 3);
+        assert_eq!(registry.len(), 3);
+
+        registry.clear_poison();
+        assert!(!registry.is_poisoned());
+    }
+}
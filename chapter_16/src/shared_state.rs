@@ -0,0 +1,100 @@
+// 16.3 Shared-State Concurrency
+
+/*
+Channels are single ownership: once a value is sent, the sender can't use it
+any more. Shared memory is more like multiple ownership, several threads can
+get at the same location at the same time. Smart pointers made multiple
+ownership possible in chapter 15, and Rust's type system and ownership rules
+help a lot to get the management of that right here too.
+*/
+
+use crate::shared::{self, SharedRegistry};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+pub fn run() {
+    // Using Mutexes to Allow Access to Data from One Thread at a Time
+
+    /*
+    Mutex is short for mutual exclusion: only one thread can access the data
+    at a time. To get at it, a thread asks to acquire the mutex's lock, which
+    keeps track of who currently has exclusive access. The two rules:
+        - you have to acquire the lock before using the data
+        - when you're done, you have to unlock it so others can have a turn
+
+    Rust handles the second one for us. lock() returns a MutexGuard, a smart
+    pointer that Derefs to the inner data and releases the lock in its Drop.
+    lock() returns a Result because it fails if another thread panicked while
+    holding the lock (the mutex is then "poisoned").
+    */
+
+    let m = Mutex::new(5);
+
+    {
+        let mut num = m.lock().unwrap();
+        *num = 6;
+    } // the guard goes out of scope here, which unlocks the mutex
+
+    println!("m = {m:?}");
+
+    // Sharing a Mutex<T> Between Multiple Threads
+
+    /*
+    The counter can't be moved into more than one thread's closure, and
+    Rc<Mutex<i32>> doesn't compile either:
+
+        `Rc<Mutex<i32>>` cannot be sent between threads safely
+
+    Rc's count isn't updated atomically, so two threads changing it at once
+    could get it wrong. Arc<T> is the atomically reference counted version,
+    with the same API. Atomics cost a bit more, which is why Rc isn't just
+    always atomic.
+    */
+
+    let counter = Arc::new(Mutex::new(0));
+    let mut handles = vec![];
+
+    for _ in 0..10 {
+        let counter = Arc::clone(&counter);
+        let handle = thread::spawn(move || {
+            let mut num = counter.lock().unwrap();
+
+            *num += 1;
+        });
+        handles.push(handle);
+    }
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    println!("Result: {}", *counter.lock().unwrap());
+
+    /*
+    Similarities Between RefCell<T>/Rc<T> and Mutex<T>/Arc<T>
+
+    counter is immutable, but we could get a mutable reference to the value
+    inside it: Mutex<T> provides interior mutability the same way RefCell<T>
+    does. And just as Rc<T> came with the risk of reference cycles, Mutex<T>
+    comes with the risk of deadlocks, where an operation needs to lock two
+    resources and two threads each hold one of the locks.
+    */
+
+    println!("Result again: {}", shared::count_in_threads(10, 1_000));
+
+    // a HashMap many threads can use at once, see shared.rs
+    let registry = SharedRegistry::new();
+    let handles: Vec<_> = ["alice", "bob", "carol"]
+        .into_iter()
+        .map(|name| {
+            let registry = registry.clone();
+            thread::spawn(move || {
+                registry.insert(String::from(name), name.len());
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    println!("registry has {} entries", registry.len());
+}
@@ -0,0 +1,112 @@
+// 16.1 Using Threads to Run Code Simultaneously
+
+/*
+In most operating systems a program's code runs in a process, and the OS
+manages several processes at once. Within a program we can also have
+independent parts that run at the same time, called threads.
+
+Splitting work across threads can improve performance, but it adds complexity.
+There's no guarantee about the order the threads' code runs in, which can lead
+to:
+    - race conditions, where threads access data or resources in an
+      inconsistent order
+    - deadlocks, where two threads wait for each other, so neither continues
+    - bugs that only happen in certain situations and are hard to reproduce
+
+The standard library uses a 1:1 model of thread implementation: one OS thread
+for every language thread.
+*/
+
+use crate::parallel;
+use std::thread;
+use std::time::Duration;
+
+pub fn run() {
+    // Creating a New Thread with spawn
+
+    /*
+    thread::spawn takes a closure holding the code the new thread should run.
+    When the main thread finishes, all spawned threads are shut down whether
+    or not they're done, so this spawned thread probably won't get to 9.
+    */
+
+    thread::spawn(|| {
+        for i in 1..10 {
+            println!("hi number {i} from the spawned thread!");
+            thread::sleep(Duration::from_millis(1));
+        }
+    });
+
+    for i in 1..5 {
+        println!("hi number {i} from the main thread!");
+        thread::sleep(Duration::from_millis(1));
+    }
+
+    // Waiting for All Threads to Finish Using join Handles
+
+    /*
+    spawn returns a JoinHandle, an owned value that, when we call join on it,
+    waits for its thread to finish. join blocks the current thread until then.
+    Where join is called matters: calling it before the main thread's loop
+    would make the spawned thread run to completion first, with no
+    interleaving.
+    */
+
+    let handle = thread::spawn(|| {
+        for i in 1..10 {
+            println!("hi number {i} from the joined thread!");
+            thread::sleep(Duration::from_millis(1));
+        }
+    });
+
+    for i in 1..5 {
+        println!("hi number {i} from the main thread, again!");
+        thread::sleep(Duration::from_millis(1));
+    }
+
+    handle.join().unwrap();
+
+    // Using move Closures with Threads
+
+    /*
+    A closure passed to spawn can't borrow from the main thread: Rust can't
+    tell how long the spawned thread will run, so it can't know the borrow
+    stays valid (main could drop the vector while the thread is still using
+    it). `move` makes the closure take ownership of what it uses instead.
+
+        let v = vec![1, 2, 3];
+        let handle = thread::spawn(|| {
+            println!("Here's a vector: {v:?}"); // error: may outlive borrowed value `v`
+        });
+        drop(v);
+    */
+
+    let v = vec![1, 2, 3];
+
+    let handle = thread::spawn(move || {
+        println!("Here's a vector: {v:?}");
+    });
+
+    // drop(v); // won't compile now, v was moved into the thread
+
+    handle.join().unwrap();
+
+    // Getting Values Back Out
+
+    /*
+    JoinHandle<T> is generic over what the closure returns, and join() gives
+    back a Result: Ok(T) if the thread finished, Err if it panicked. parallel.rs
+    uses that to split work over several threads and collect their results.
+    */
+
+    let numbers: Vec<u64> = (1..=1_000).collect();
+    println!(
+        "sum of 1..=1000 over 4 threads: {}",
+        parallel::parallel_sum(numbers, 4)
+    );
+
+    match parallel::run_catching(|| panic!("oops")) {
+        Ok(()) => println!("the thread finished"),
+        Err(message) => println!("the thread panicked: {message}"),
+    }
+}
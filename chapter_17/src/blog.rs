@@ -0,0 +1,232 @@
+// 17.3 Implementing an Object-Oriented Design Pattern
+
+/*
+The state pattern: a value has some internal state, represented by a set of
+state objects, and its behavior changes based on that state. Each state object
+is responsible for its own behavior and for deciding when to change into
+another state. The value holding the state doesn't know anything about the
+different behaviors or when to transition.
+
+The blog post workflow:
+    1. a post starts as an empty draft
+    2. when the draft is done, a review of the post is requested
+    3. when the post is approved, it gets published
+    4. only published posts return content to print, so unapproved posts
+       can't accidentally be published
+
+Any other change attempted on a post should have no effect: approving a draft
+before review was requested leaves it a draft.
+*/
+
+pub struct Post {
+    // Option so the methods below can take() the state out, leaving None for
+    // a moment, and put the new state back. Rust doesn't allow unpopulated
+    // fields in structs, so we can't just move state out of &mut self
+    state: Option<Box<dyn State>>,
+    content: String,
+}
+
+impl Post {
+    pub fn new() -> Post {
+        Post {
+            state: Some(Box::new(Draft {})),
+            content: String::new(),
+        }
+    }
+
+    pub fn add_text(&mut self, text: &str) {
+        // only drafts can be edited, the state decides
+        if self.state.as_ref().unwrap().can_edit() {
+            self.content.push_str(text);
+        }
+    }
+
+    // the state decides what to return: "" until the post is published
+    pub fn content(&self) -> &str {
+        self.state.as_ref().unwrap().content(self)
+    }
+
+    pub fn request_review(&mut self) {
+        if let Some(s) = self.state.take() {
+            self.state = Some(s.request_review())
+        }
+    }
+
+    pub fn approve(&mut self) {
+        if let Some(s) = self.state.take() {
+            self.state = Some(s.approve())
+        }
+    }
+
+    // one of the book's suggested extras: send a post back to draft
+    pub fn reject(&mut self) {
+        if let Some(s) = self.state.take() {
+            self.state = Some(s.reject())
+        }
+    }
+
+    pub fn status(&self) -> &'static str {
+        self.state.as_ref().unwrap().name()
+    }
+}
+
+impl Default for Post {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/*
+self: Box<Self> means the method is only valid when called on a Box holding
+the type. It takes ownership of the Box, invalidating the old state, so the
+state value can transform into a new one.
+
+The default methods mean a state only has to write the transitions it
+actually cares about. Everything else leaves the state as it was.
+*/
+trait State {
+    fn request_review(self: Box<Self>) -> Box<dyn State>;
+    fn approve(self: Box<Self>) -> Box<dyn State>;
+
+    fn reject(self: Box<Self>) -> Box<dyn State>;
+
+    // the lifetime ties the returned &str to the post, not the state
+    fn content<'a>(&self, _post: &'a Post) -> &'a str {
+        ""
+    }
+
+    fn can_edit(&self) -> bool {
+        false
+    }
+
+    fn name(&self) -> &'static str;
+}
+
+struct Draft {}
+
+impl State for Draft {
+    fn request_review(self: Box<Self>) -> Box<dyn State> {
+        Box::new(PendingReview {})
+    }
+
+    fn approve(self: Box<Self>) -> Box<dyn State> {
+        self
+    }
+
+    fn reject(self: Box<Self>) -> Box<dyn State> {
+        self
+    }
+
+    fn can_edit(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> &'static str {
+        "draft"
+    }
+}
+
+struct PendingReview {}
+
+impl State for PendingReview {
+    fn request_review(self: Box<Self>) -> Box<dyn State> {
+        self
+    }
+
+    fn approve(self: Box<Self>) -> Box<dyn State> {
+        Box::new(Published {})
+    }
+
+    fn reject(self: Box<Self>) -> Box<dyn State> {
+        Box::new(Draft {})
+    }
+
+    fn name(&self) -> &'static str {
+        "pending review"
+    }
+}
+
+struct Published {}
+
+impl State for Published {
+    fn request_review(self: Box<Self>) -> Box<dyn State> {
+        self
+    }
+
+    fn approve(self: Box<Self>) -> Box<dyn State> {
+        self
+    }
+
+    fn reject(self: Box<Self>) -> Box<dyn State> {
+        self
+    }
+
+    fn content<'a>(&self, post: &'a Post) -> &'a str {
+        &post.content
+    }
+
+    fn name(&self) -> &'static str {
+        "published"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_books_workflow() {
+        let mut post = Post::new();
+
+        post.add_text("I ate a salad for lunch today");
+        assert_eq!("", post.content());
+
+        post.request_review();
+        assert_eq!("", post.content());
+
+        post.approve();
+        assert_eq!("I ate a salad for lunch today", post.content());
+    }
+
+    #[test]
+    fn out_of_order_calls_do_nothing() {
+        let mut post = Post::new();
+        post.add_text("hello");
+        post.approve();
+        assert_eq!(post.status(), "draft");
+        assert_eq!(post.content(), "");
+
+        post.request_review();
+        post.request_review();
+        assert_eq!(post.status(), "pending review");
+    }
+
+    #[test]
+    fn reject_sends_it_back_to_draft() {
+        let mut post = Post::new();
+        post.add_text("first try");
+        post.request_review();
+        post.reject();
+        assert_eq!(post.status(), "draft");
+
+        post.add_text(", fixed");
+        post.request_review();
+        post.approve();
+        assert_eq!(post.content(), "first try, fixed");
+
+        // too late to reject once it's published
+        post.reject();
+        assert_eq!(post.status(), "published");
+    }
+
+    #[test]
+    fn text_can_only_be_added_to_drafts() {
+        let mut post = Post::new();
+        post.add_text("draft");
+        post.request_review();
+        post.add_text(" sneaky edit");
+        post.approve();
+        post.add_text(" another one");
+        assert_eq!(post.content(), "draft");
+    }
+}
@@ -0,0 +1,330 @@
+// 17.2 (continued) Generics vs. Trait Objects vs. Enums
+
+/*
+There are three ways to call area() on "some shape":
+
+  1. generics: fn largest_generic<S: Shape>(shapes: &[S]). Monomorphization
+     compiles a copy for each S, so every call is direct and can be inlined.
+     The catch is that a slice holds one type, so a mix of shapes has to be
+     kept as one Vec per type (ShapesByType).
+  2. trait objects: &[Box<dyn Shape>]. Any mix in one Vec, and types we've
+     never heard of too, but each call looks the method up in the vtable at
+     runtime, and each shape is its own heap allocation.
+  3. an enum: &[AnyShape], where AnyShape has one variant per shape and its
+     area() matches on them. Any mix in one Vec with no boxing and direct
+     calls, but only for the types listed in the enum (chapter 8's
+     SpreadsheetCell idea).
+
+The same goes for chapter_10's Summary with NewsArticle and Tweet. The generic
+and trait object versions of that are chapter_10's newest_generic and
+newest_dyn, so only the enum one is written here.
+
+Each version works out something that doesn't depend on the order it visits
+the values in (the largest area, the newest post), so the tests can check all
+three get exactly the same answer. Timing the shapes is an #[ignore]d benchmark
+like the ones in chapter_10 and chapter_13 (chapter_10's times the Summary
+calls), run it with
+    cargo test --release -- --ignored --nocapture
+*/
+
+use chapter_10::aggregator::{newest_generic, NewsArticle, Summary, Tweet};
+use chapter_15::shapes::{Circle, Rectangle, Shape, Triangle};
+
+// Shapes
+
+pub enum AnyShape {
+    Rectangle(Rectangle),
+    Circle(Circle),
+    Triangle(Triangle),
+}
+
+impl Shape for AnyShape {
+    fn area(&self) -> f64 {
+        match self {
+            AnyShape::Rectangle(rectangle) => Shape::area(rectangle),
+            AnyShape::Circle(circle) => circle.area(),
+            AnyShape::Triangle(triangle) => triangle.area(),
+        }
+    }
+
+    fn name(&self) -> String {
+        match self {
+            AnyShape::Rectangle(rectangle) => rectangle.name(),
+            AnyShape::Circle(circle) => circle.name(),
+            AnyShape::Triangle(triangle) => triangle.name(),
+        }
+    }
+}
+
+impl AnyShape {
+    // the same shape as a trait object
+    pub fn into_boxed(self) -> Box<dyn Shape> {
+        match self {
+            AnyShape::Rectangle(rectangle) => Box::new(rectangle),
+            AnyShape::Circle(circle) => Box::new(circle),
+            AnyShape::Triangle(triangle) => Box::new(triangle),
+        }
+    }
+}
+
+// what generics need for a mix of shapes: one Vec per concrete type
+#[derive(Default)]
+pub struct ShapesByType {
+    pub rectangles: Vec<Rectangle>,
+    pub circles: Vec<Circle>,
+    pub triangles: Vec<Triangle>,
+}
+
+impl FromIterator<AnyShape> for ShapesByType {
+    fn from_iter<I: IntoIterator<Item = AnyShape>>(shapes: I) -> ShapesByType {
+        let mut by_type = ShapesByType::default();
+        for shape in shapes {
+            match shape {
+                AnyShape::Rectangle(rectangle) => by_type.rectangles.push(rectangle),
+                AnyShape::Circle(circle) => by_type.circles.push(circle),
+                AnyShape::Triangle(triangle) => by_type.triangles.push(triangle),
+            }
+        }
+        by_type
+    }
+}
+
+impl ShapesByType {
+    // three calls, one to each monomorphized copy of largest_generic
+    pub fn largest(&self) -> f64 {
+        largest_generic(&self.rectangles)
+            .max(largest_generic(&self.circles))
+            .max(largest_generic(&self.triangles))
+    }
+}
+
+// 0.0 for no shapes at all
+pub fn largest_generic<S: Shape>(shapes: &[S]) -> f64 {
+    shapes.iter().map(|shape| shape.area()).fold(0.0, f64::max)
+}
+
+pub fn largest_dyn(shapes: &[Box<dyn Shape>]) -> f64 {
+    shapes.iter().map(|shape| shape.area()).fold(0.0, f64::max)
+}
+
+pub fn largest_enum(shapes: &[AnyShape]) -> f64 {
+    shapes.iter().map(|shape| shape.area()).fold(0.0, f64::max)
+}
+
+// a repeatable mix of all three shapes
+pub fn sample_shapes(n: usize) -> Vec<AnyShape> {
+    (0..n)
+        .map(|i| {
+            let size = (i * 37 % 101) as u32 + 1;
+            match i % 3 {
+                0 => AnyShape::Rectangle(Rectangle {
+                    width: size,
+                    height: size / 2 + 1,
+                }),
+                1 => AnyShape::Circle(Circle {
+                    radius: size as f64 / 10.0,
+                }),
+                _ => AnyShape::Triangle(Triangle {
+                    base: size as f64,
+                    height: (i % 7) as f64,
+                }),
+            }
+        })
+        .collect()
+}
+
+// Posts
+
+pub enum AnyPost {
+    Article(NewsArticle),
+    Tweet(Tweet),
+}
+
+impl Summary for AnyPost {
+    fn summarize_author(&self) -> String {
+        match self {
+            AnyPost::Article(article) => article.summarize_author(),
+            AnyPost::Tweet(tweet) => tweet.summarize_author(),
+        }
+    }
+
+    // forwarded too, or an Article would get the default summarize instead
+    // of its own
+    fn summarize(&self) -> String {
+        match self {
+            AnyPost::Article(article) => article.summarize(),
+            AnyPost::Tweet(tweet) => tweet.summarize(),
+        }
+    }
+
+    fn published_at(&self) -> u64 {
+        match self {
+            AnyPost::Article(article) => article.published_at(),
+            AnyPost::Tweet(tweet) => tweet.published_at(),
+        }
+    }
+}
+
+impl AnyPost {
+    pub fn into_boxed(self) -> Box<dyn Summary> {
+        match self {
+            AnyPost::Article(article) => Box::new(article),
+            AnyPost::Tweet(tweet) => Box::new(tweet),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct PostsByType {
+    pub articles: Vec<NewsArticle>,
+    pub tweets: Vec<Tweet>,
+}
+
+impl FromIterator<AnyPost> for PostsByType {
+    fn from_iter<I: IntoIterator<Item = AnyPost>>(posts: I) -> PostsByType {
+        let mut by_type = PostsByType::default();
+        for post in posts {
+            match post {
+                AnyPost::Article(article) => by_type.articles.push(article),
+                AnyPost::Tweet(tweet) => by_type.tweets.push(tweet),
+            }
+        }
+        by_type
+    }
+}
+
+impl PostsByType {
+    pub fn newest(&self) -> u64 {
+        newest_generic(&self.articles).max(newest_generic(&self.tweets))
+    }
+}
+
+pub fn newest_enum(posts: &[AnyPost]) -> u64 {
+    posts
+        .iter()
+        .map(|post| post.published_at())
+        .max()
+        .unwrap_or(0)
+}
+
+// every fourth post is an article, timestamps jump around so the newest
+// isn't simply the last one
+pub fn sample_posts(n: usize) -> Vec<AnyPost> {
+    (0..n)
+        .map(|i| {
+            let published_at = (i as u64 * 7919) % 100_003;
+            if i % 4 == 0 {
+                AnyPost::Article(NewsArticle {
+                    headline: format!("Story {}", i),
+                    location: String::from("Pittsburgh, PA, USA"),
+                    author: String::from("Iceburgh"),
+                    content: String::new(),
+                    published_at,
+                })
+            } else {
+                AnyPost::Tweet(Tweet {
+                    username: format!("user{}", i % 10),
+                    content: String::new(),
+                    reply: false,
+                    retweet: false,
+                    published_at,
+                })
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chapter_10::aggregator::newest_dyn;
+    use chapter_11::bench::time_it;
+
+    fn boxed_shapes(n: usize) -> Vec<Box<dyn Shape>> {
+        sample_shapes(n)
+            .into_iter()
+            .map(AnyShape::into_boxed)
+            .collect()
+    }
+
+    fn boxed_posts(n: usize) -> Vec<Box<dyn Summary>> {
+        sample_posts(n)
+            .into_iter()
+            .map(AnyPost::into_boxed)
+            .collect()
+    }
+
+    #[test]
+    fn shapes_agree() {
+        for n in [0, 1, 2, 3, 10, 1000] {
+            let by_type: ShapesByType = sample_shapes(n).into_iter().collect();
+            let generic = by_type.largest();
+            assert_eq!(generic, largest_dyn(&boxed_shapes(n)), "n = {}", n);
+            assert_eq!(generic, largest_enum(&sample_shapes(n)), "n = {}", n);
+        }
+    }
+
+    #[test]
+    fn posts_agree() {
+        for n in [0, 1, 4, 5, 1000] {
+            let by_type: PostsByType = sample_posts(n).into_iter().collect();
+            let generic = by_type.newest();
+            assert_eq!(generic, newest_dyn(&boxed_posts(n)), "n = {}", n);
+            assert_eq!(generic, newest_enum(&sample_posts(n)), "n = {}", n);
+        }
+    }
+
+    #[test]
+    fn the_enum_forwards_to_the_real_type() {
+        let shapes = sample_shapes(3);
+        let names: Vec<String> = shapes.iter().map(|shape| shape.name()).collect();
+        let boxed: Vec<String> = boxed_shapes(3).iter().map(|shape| shape.name()).collect();
+        assert_eq!(names, boxed);
+        assert_eq!(names[0], "1x1 rectangle");
+
+        // NewsArticle overrides summarize, the enum has to keep that
+        let posts = sample_posts(2);
+        assert_eq!(
+            posts[0].summarize(),
+            "Story 0, by Iceburgh (Pittsburgh, PA, USA)"
+        );
+        assert_eq!(posts[1].summarize(), "(Read more from @user1...)");
+    }
+
+    #[test]
+    fn splitting_by_type_keeps_every_value() {
+        let by_type: ShapesByType = sample_shapes(10).into_iter().collect();
+        assert_eq!(by_type.rectangles.len(), 4);
+        assert_eq!(by_type.circles.len(), 3);
+        assert_eq!(by_type.triangles.len(), 3);
+
+        let by_type: PostsByType = sample_posts(10).into_iter().collect();
+        assert_eq!(by_type.articles.len(), 3);
+        assert_eq!(by_type.tweets.len(), 7);
+    }
+
+    #[test]
+    fn nothing_to_compare() {
+        assert_eq!(ShapesByType::default().largest(), 0.0);
+        assert_eq!(PostsByType::default().newest(), 0);
+    }
+
+    // the times are only printed, what's asserted is the answers
+    #[test]
+    #[ignore]
+    fn bench_shape_dispatch() {
+        let n = 300_000;
+        let by_type: ShapesByType = sample_shapes(n).into_iter().collect();
+        let boxed = boxed_shapes(n);
+        let enums = sample_shapes(n);
+
+        let generic = time_it("shapes, generic", 100, || by_type.largest());
+        let dynamic = time_it("shapes, dyn", 100, || largest_dyn(&boxed));
+        let matched = time_it("shapes, enum", 100, || largest_enum(&enums));
+        println!("dyn / generic: {:.2}x", dynamic.ratio_to(&generic));
+        println!("enum / generic: {:.2}x", matched.ratio_to(&generic));
+
+        assert_eq!(by_type.largest(), largest_dyn(&boxed));
+        assert_eq!(by_type.largest(), largest_enum(&enums));
+    }
+}
@@ -0,0 +1,239 @@
+// 17.2 (continued) A Tiny GUI Library
+
+/*
+The book's gui crate, drawing into a writer instead of just being a
+placeholder, so a test can see what Screen::run drew.
+
+Screen could have been generic instead:
+
+    pub struct Screen<T: Draw> {
+        pub components: Vec<T>,
+    }
+
+but then a screen could only hold one type of component, all Buttons or all
+SelectBoxes. That's fine (and a bit faster) for a homogeneous collection.
+Vec<Box<dyn Draw>> lets one screen mix any types that implement Draw.
+*/
+
+use std::io::{self, Write};
+
+pub trait Draw {
+    fn draw(&self, out: &mut dyn Write) -> io::Result<()>;
+}
+
+pub struct Screen {
+    pub components: Vec<Box<dyn Draw>>,
+}
+
+impl Screen {
+    // draws every component, in order
+    pub fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        for component in self.components.iter() {
+            component.draw(out)?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Button {
+    pub width: u32,
+    pub height: u32,
+    pub label: String,
+}
+
+/*
+A button is a box `width` characters wide and `height` rows tall (borders
+included), with the label centered on the middle row:
+
+    +--------+
+    |   OK   |
+    +--------+
+*/
+impl Draw for Button {
+    fn draw(&self, out: &mut dyn Write) -> io::Result<()> {
+        let inner = inner_width(self.width);
+        let rows = self.height.saturating_sub(2).max(1);
+        let label_row = rows / 2;
+        border(out, inner)?;
+        for row in 0..rows {
+            let text = if row == label_row {
+                &self.label[..]
+            } else {
+                ""
+            };
+            writeln!(out, "|{:^inner$}|", fit(text, inner))?;
+        }
+        border(out, inner)
+    }
+}
+
+pub struct SelectBox {
+    pub width: u32,
+    pub height: u32,
+    pub options: Vec<String>,
+}
+
+/*
+A select box shows as many options as fit in its height, and says how many
+are hidden below:
+
+    +--------+
+    | Yes    |
+    | No     |
+    | (+1)   |
+    +--------+
+*/
+impl Draw for SelectBox {
+    fn draw(&self, out: &mut dyn Write) -> io::Result<()> {
+        let inner = inner_width(self.width);
+        let rows = self.height.saturating_sub(2).max(1) as usize;
+        // if they don't all fit, the last row says how many are hidden
+        let shown = if self.options.len() > rows {
+            rows - 1
+        } else {
+            self.options.len()
+        };
+        border(out, inner)?;
+        for option in self.options.iter().take(shown) {
+            writeln!(
+                out,
+                "| {:<width$}|",
+                fit(option, inner - 1),
+                width = inner - 1
+            )?;
+        }
+        if shown < self.options.len() {
+            let more = format!("(+{})", self.options.len() - shown);
+            writeln!(
+                out,
+                "| {:<width$}|",
+                fit(&more, inner - 1),
+                width = inner - 1
+            )?;
+        }
+        border(out, inner)
+    }
+}
+
+// room between the two borders, never less than 2 so there's space for text
+fn inner_width(width: u32) -> usize {
+    (width as usize).saturating_sub(2).max(2)
+}
+
+fn border(out: &mut dyn Write, inner: usize) -> io::Result<()> {
+    writeln!(out, "+{}+", "-".repeat(inner))
+}
+
+// text cut down to `width` characters (chars, not bytes, so this can't split
+// a multi-byte character)
+fn fit(text: &str, width: usize) -> String {
+    text.chars().take(width).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chapter_11::capture::{capture, capture_lines};
+
+    fn button(width: u32, height: u32, label: &str) -> Button {
+        Button {
+            width,
+            height,
+            label: String::from(label),
+        }
+    }
+
+    fn select_box(width: u32, height: u32, options: &[&str]) -> SelectBox {
+        SelectBox {
+            width,
+            height,
+            options: options.iter().map(|o| o.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn button_centers_its_label() {
+        let lines = capture_lines(|out| button(10, 3, "OK").draw(out));
+        assert_eq!(lines, vec!["+--------+", "|   OK   |", "+--------+"]);
+    }
+
+    #[test]
+    fn tall_button_puts_label_in_the_middle() {
+        let lines = capture_lines(|out| button(6, 5, "go").draw(out));
+        assert_eq!(
+            lines,
+            vec!["+----+", "|    |", "| go |", "|    |", "+----+"]
+        );
+    }
+
+    #[test]
+    fn long_labels_are_cut_to_fit() {
+        let lines = capture_lines(|out| button(6, 3, "Cancel").draw(out));
+        assert_eq!(lines[1], "|Canc|");
+        // every row is as wide as the button
+        assert!(lines.iter().all(|line| line.chars().count() == 6));
+    }
+
+    #[test]
+    fn select_box_lists_options() {
+        let lines = capture_lines(|out| select_box(10, 4, &["Yes", "No"]).draw(out));
+        assert_eq!(
+            lines,
+            vec!["+--------+", "| Yes    |", "| No     |", "+--------+"]
+        );
+    }
+
+    #[test]
+    fn select_box_says_how_many_are_hidden() {
+        let options = ["Yes", "Maybe", "No", "Ask later"];
+        let lines = capture_lines(|out| select_box(10, 4, &options).draw(out));
+        assert_eq!(
+            lines,
+            vec!["+--------+", "| Yes    |", "| (+3)   |", "+--------+"]
+        );
+    }
+
+    #[test]
+    fn screen_draws_components_in_order() {
+        let screen = Screen {
+            components: vec![
+                Box::new(select_box(8, 3, &["A"])),
+                Box::new(button(8, 3, "B")),
+            ],
+        };
+        let output = capture(|out| screen.run(out));
+        assert_eq!(
+            output,
+            "+------+\n| A    |\n+------+\n+------+\n|  B   |\n+------+\n"
+        );
+    }
+
+    #[test]
+    fn screen_takes_components_it_has_never_heard_of() {
+        struct Image {
+            name: &'static str,
+        }
+
+        impl Draw for Image {
+            fn draw(&self, out: &mut dyn Write) -> io::Result<()> {
+                writeln!(out, "<img {}>", self.name)
+            }
+        }
+
+        let screen = Screen {
+            components: vec![
+                Box::new(Image { name: "cat.png" }),
+                Box::new(button(4, 3, "x")),
+            ],
+        };
+        let lines = capture_lines(|out| screen.run(out));
+        assert_eq!(lines[0], "<img cat.png>");
+        assert_eq!(lines.len(), 4);
+    }
+
+    #[test]
+    fn empty_screen_draws_nothing() {
+        let screen = Screen { components: vec![] };
+        assert_eq!(capture(|out| screen.run(out)), "");
+    }
+}
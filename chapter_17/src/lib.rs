@@ -0,0 +1,35 @@
+// Chapter 17: Object-Oriented Programming Features of Rust (library)
+// A GUI Screen drawing a list of Box<dyn Draw> components, generics vs trait
+// objects vs enums side by side, and the blog post workflow twice: once as the
+// state pattern with trait objects, once with the states as separate types
+// The two walkthroughs that use them are here as well, exercises() lists them
+// for the runner
+
+pub mod blog;
+pub mod dispatch;
+pub mod gui;
+pub mod state_pattern;
+pub mod trait_objects;
+pub mod typed_blog;
+
+use domain::Section;
+
+// every section main runs, in the same order, for the runner
+pub fn exercises() -> Vec<Section> {
+    vec![
+        Section {
+            name: "trait_objects",
+            run: || {
+                trait_objects::run();
+                Ok(())
+            },
+        },
+        Section {
+            name: "state_pattern",
+            run: || {
+                state_pattern::run();
+                Ok(())
+            },
+        },
+    ]
+}
@@ -0,0 +1,28 @@
+// Chapter 17: Object-Oriented Programming Features of Rust
+
+/*
+There's no consensus on which features make a language object oriented. Going
+by the Gang of Four's definition, "object-oriented programs are made up of
+objects. An object packages both data and the procedures that operate on that
+data", Rust qualifies: structs and enums have data, and impl blocks give them
+methods.
+
+Encapsulation: the implementation details of an object aren't accessible to
+code using it. Rust has pub to decide which modules, types, functions, and
+methods are public, everything else is private by default.
+
+Inheritance: an object inheriting another object's data and behavior without
+defining them again. Rust doesn't have it, there's no way to define a struct
+that inherits a parent struct's fields and method implementations. The two
+reasons people reach for it are covered other ways:
+    - reusing code: default trait method implementations
+    - polymorphism (code that works with data of several types): generics with
+      trait bounds, and trait objects, which are what this chapter is about
+*/
+
+use chapter_17::{state_pattern, trait_objects};
+
+fn main() {
+    trait_objects::run();
+    state_pattern::run();
+}
@@ -0,0 +1,45 @@
+// 17.3 Implementing an Object-Oriented Design Pattern
+
+/*
+The two versions of the blog post workflow live in lib.rs:
+    blog.rs:       the state pattern, Post holds a Box<dyn State>
+    typed_blog.rs: each state is its own type, so wrong transitions don't compile
+
+Both have notes on how they work, this just drives them.
+*/
+
+use crate::{blog, typed_blog};
+
+pub fn run() {
+    // the state pattern: one Post whose behavior changes as its state does
+    let mut post = blog::Post::new();
+
+    post.add_text("I ate a salad for lunch today");
+    println!("{}: {:?}", post.status(), post.content());
+
+    post.request_review();
+    println!("{}: {:?}", post.status(), post.content());
+
+    post.approve();
+    println!("{}: {:?}", post.status(), post.content());
+
+    // the same with types: each step hands back a new value
+    let mut post = typed_blog::Post::new();
+
+    post.add_text("I ate a salad for lunch today");
+    // println!("{}", post.content()); // error: no method named `content` on DraftPost
+
+    let post = post.request_review();
+
+    let post = post.approve();
+
+    println!("published: {:?}", post.content());
+
+    /*
+    So is the state pattern worth it in Rust? Sometimes, it's still a good fit
+    when the states really should be hidden from the caller. But Rust's
+    ownership and type system make other designs possible, and catching the
+    invalid transitions at compile time is something an object oriented
+    language with inheritance can't easily give you.
+    */
+}
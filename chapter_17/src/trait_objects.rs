@@ -0,0 +1,81 @@
+// 17.2 Using Trait Objects That Allow for Values of Different Types
+
+/*
+Back in chapter 8, a vector could only hold one type, so to store ints,
+floats, and text in a spreadsheet row we used an enum. That's great when the
+set of types is known when the code is written. Sometimes it isn't: the book's
+example is a GUI library where users will want to add components we've never
+heard of, and the library just needs to call draw() on each of them.
+
+In a language with inheritance, there'd be a Component class with a draw
+method, and Button, Image, SelectBox would inherit from it. In Rust we define
+a trait, Draw, and store trait objects: Box<dyn Draw>.
+
+A trait object points to both an instance of a type implementing the trait
+and a table used to look up that type's trait methods at runtime. We can't add
+data to a trait object, its purpose is to allow abstraction over common
+behavior. The gui module in lib.rs has the Draw trait, the Screen, and two
+components.
+*/
+
+use crate::gui::{Button, Draw, Screen, SelectBox};
+use std::io::{self, Write};
+
+// a component the gui library knows nothing about. As long as it implements
+// Draw, the Screen can hold it next to the library's own components
+struct Label {
+    text: String,
+}
+
+impl Draw for Label {
+    fn draw(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "{}", self.text)
+    }
+}
+
+pub fn run() {
+    let screen = Screen {
+        components: vec![
+            Box::new(Label {
+                text: String::from("Ready to go?"),
+            }),
+            Box::new(SelectBox {
+                width: 14,
+                height: 5,
+                options: vec![
+                    String::from("Yes"),
+                    String::from("Maybe"),
+                    String::from("No"),
+                ],
+            }),
+            Box::new(Button {
+                width: 14,
+                height: 3,
+                label: String::from("OK"),
+            }),
+        ],
+    };
+
+    screen.run(&mut io::stdout()).unwrap();
+
+    /*
+    Without a trait object this doesn't compile, String doesn't implement Draw:
+
+        let screen = Screen {
+            components: vec![Box::new(String::from("Hi"))],
+        };
+        // error[E0277]: the trait bound `String: Draw` is not satisfied
+
+    Trait Objects Perform Dynamic Dispatch
+
+    With generics the compiler does static dispatch: monomorphization generates
+    a copy of the function for every concrete type, and it knows at compile time
+    which method is called. With trait objects it can't know, so at runtime Rust
+    uses the pointers inside the trait object to find the method. That lookup
+    costs a little, and stops the compiler inlining the call, but it's what gives
+    us the flexibility.
+
+    How much it costs is measured in dispatch.rs, next to a third option: an
+    enum of the known types, matched on in each method.
+    */
+}
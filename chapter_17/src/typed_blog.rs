@@ -0,0 +1,148 @@
+// 17.3 (continued) Encoding States and Behavior as Types
+
+/*
+The state pattern in blog.rs works, but it has downsides the book points out:
+    - states are coupled to each other: adding a state between PendingReview
+      and Published means changing PendingReview
+    - there's duplicated logic, every state has to write out request_review
+      and approve even when they just return self
+    - and the big one, mistakes only show up at runtime. Approving a draft is
+      silently ignored, calling content() on a draft just returns ""
+
+The alternative is to make each state its own type. A DraftPost has no
+content() method at all, so trying to print an unpublished post is a compile
+error instead of an empty string. The transitions take self by value and
+return the next type, so once a draft has been turned into a PendingReviewPost,
+the old draft can't be used any more (it was moved).
+
+Because the transitions return a new value, the caller has to use shadowing
+(let post = post.request_review();) instead of calling methods on one post
+that changes underneath. That's the trade off: the workflow is no longer
+hidden inside Post, but the compiler checks it.
+*/
+
+/// A published post, the only state with content to show.
+///
+/// An unpublished post has no content() to call:
+///
+/// ```compile_fail,E0599
+/// use chapter_17::typed_blog::Post;
+///
+/// let mut post = Post::new();
+/// post.add_text("I ate a salad for lunch today");
+/// println!("{}", post.content());
+/// ```
+///
+/// and skipping review doesn't compile either, only a PendingReviewPost has
+/// approve():
+///
+/// ```compile_fail,E0599
+/// use chapter_17::typed_blog::Post;
+///
+/// let post = Post::new().approve();
+/// ```
+pub struct Post {
+    content: String,
+}
+
+impl Post {
+    // every post starts out as a draft, there's no way to make a Post directly
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new() -> DraftPost {
+        DraftPost {
+            content: String::new(),
+        }
+    }
+
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+}
+
+pub struct DraftPost {
+    content: String,
+}
+
+impl DraftPost {
+    pub fn add_text(&mut self, text: &str) {
+        self.content.push_str(text);
+    }
+
+    pub fn request_review(self) -> PendingReviewPost {
+        PendingReviewPost {
+            content: self.content,
+        }
+    }
+}
+
+// no add_text here, so a post under review can't be edited
+pub struct PendingReviewPost {
+    content: String,
+}
+
+impl PendingReviewPost {
+    pub fn approve(self) -> Post {
+        Post {
+            content: self.content,
+        }
+    }
+
+    // back to a draft, with the text kept so it can be fixed
+    pub fn reject(self) -> DraftPost {
+        DraftPost {
+            content: self.content,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_books_workflow() {
+        let mut post = Post::new();
+
+        post.add_text("I ate a salad for lunch today");
+
+        let post = post.request_review();
+
+        let post = post.approve();
+
+        assert_eq!("I ate a salad for lunch today", post.content());
+    }
+
+    #[test]
+    fn rejected_posts_can_be_edited_again() {
+        let mut draft = Post::new();
+        draft.add_text("first try");
+
+        let mut draft = draft.request_review().reject();
+        draft.add_text(", fixed");
+
+        let post = draft.request_review().approve();
+        assert_eq!(post.content(), "first try, fixed");
+    }
+
+    #[test]
+    fn empty_post_can_still_be_published() {
+        let post = Post::new().request_review().approve();
+        assert_eq!(post.content(), "");
+    }
+
+    #[test]
+    fn both_designs_publish_the_same_thing() {
+        let text = "Rust has no inheritance";
+
+        let mut dynamic = crate::blog::Post::new();
+        dynamic.add_text(text);
+        dynamic.request_review();
+        dynamic.approve();
+
+        let mut typed = Post::new();
+        typed.add_text(text);
+        let typed = typed.request_review().approve();
+
+        assert_eq!(dynamic.content(), typed.content());
+    }
+}
@@ -0,0 +1,33 @@
+// Chapter 18: Patterns and Matching (library)
+// Every pattern form from 18.3 in functions that return what they matched,
+// and which patterns are refutable (if let, let-else) or irrefutable (let,
+// function parameters)
+// The walkthroughs that print them are here as well, exercises() lists them
+// for the runner
+
+pub mod pattern_syntax;
+pub mod places;
+pub mod refutability;
+pub mod syntax;
+
+use domain::Section;
+
+// every section main runs, in the same order, for the runner
+pub fn exercises() -> Vec<Section> {
+    vec![
+        Section {
+            name: "places",
+            run: || {
+                places::run();
+                Ok(())
+            },
+        },
+        Section {
+            name: "pattern_syntax",
+            run: || {
+                pattern_syntax::run();
+                Ok(())
+            },
+        },
+    ]
+}
@@ -0,0 +1,25 @@
+// Chapter 18: Patterns and Matching
+
+/*
+Patterns are a special syntax in Rust for matching against the structure of
+types, both complex and simple. Using patterns together with match expressions
+and other constructs gives more control over a program's control flow.
+
+A pattern consists of some combination of:
+    - literals
+    - destructured arrays, enums, structs, or tuples
+    - variables
+    - wildcards
+    - placeholders
+
+Some examples are x, (a, 3), and Some(Color::Red). To use a pattern we compare
+it to some value. If the pattern matches the value, we use the value parts in
+our code.
+*/
+
+use chapter_18::{pattern_syntax, places};
+
+fn main() {
+    places::run();
+    pattern_syntax::run();
+}
@@ -0,0 +1,63 @@
+// 18.3 Pattern Syntax
+
+/*
+Every pattern form the book covers is in syntax.rs as a small function that
+returns what it matched, so the tests there can check it. This runs a few of
+each.
+*/
+
+use crate::syntax::{self, Color, Message, Point};
+
+pub fn run() {
+    // Matching Literals, Multiple Patterns, and Ranges of Values
+    for x in [1, 2, 4, 7, 42] {
+        println!("{x}: {}", syntax::describe_number(x));
+    }
+    for c in ['c', 'X', '7'] {
+        println!("{c}: {}", syntax::letter_kind(c));
+    }
+
+    // Matching Named Variables (and how a match arm shadows them)
+    println!("{}", syntax::shadowing(Some(5), 10));
+    println!("{}", syntax::shadowing(None, 10));
+
+    // Destructuring Structs
+    for p in [
+        Point { x: 0, y: 7 },
+        Point { x: 3, y: 0 },
+        Point { x: 1, y: 1 },
+    ] {
+        println!("{}", syntax::locate(&p));
+    }
+
+    // Destructuring Enums, including nested ones
+    let messages = [
+        Message::Quit,
+        Message::Move { x: 1, y: 2 },
+        Message::Write(String::from("hello")),
+        Message::ChangeColor(Color::Rgb(0, 160, 255)),
+        Message::ChangeColor(Color::Hsv(0, 160, 255)),
+    ];
+    for msg in &messages {
+        println!("{}", syntax::describe_message(msg));
+    }
+
+    // Destructuring Structs and Tuples together
+    println!(
+        "{}",
+        syntax::nested_total(((3, 10), Point { x: 3, y: -10 }))
+    );
+
+    // Ignoring Values with _ and ..
+    println!("{:?}", syntax::update_setting(Some(5), Some(10)));
+    println!("{:?}", syntax::first_and_last((2, 4, 8, 16, 32)));
+
+    // Extra Conditionals with Match Guards
+    println!("{}", syntax::parity(Some(4)));
+    println!("{}", syntax::guard_precedence(4, false));
+
+    // @ Bindings
+    for id in [5, 11, 42] {
+        println!("{}", syntax::describe_id(id));
+    }
+}
@@ -0,0 +1,49 @@
+// 18.1 All the Places Patterns Can Be Used
+// 18.2 Refutability: Whether a Pattern Might Fail to Match
+
+/*
+Patterns show up in more places than match:
+    match arms:   match VALUE { PATTERN => EXPRESSION, ... }, which has to be
+                  exhaustive, often with a catchall _ as the last arm
+    if let:       a match that only cares about one case
+    while let:    a loop that runs as long as a pattern keeps matching
+    for loops:    the value right after `for` is a pattern
+    let:          let PATTERN = EXPRESSION; even `let x = 5;` is a pattern
+    parameters:   function and closure parameters are patterns too
+
+18.2: the ones in let, for, and parameters have to be irrefutable. This
+doesn't compile, because None wouldn't match:
+
+    let Some(x) = some_option_value;
+    // error[E0005]: refutable pattern in local binding
+
+The fix is if let, or let-else when the rest of the function needs x.
+refutability.rs has examples of each.
+*/
+
+use crate::refutability::{self, Rect};
+
+pub fn run() {
+    let stack = vec![1, 2, 3];
+    println!("popped in order: {:?}", refutability::drain_stack(stack));
+
+    println!(
+        "background: {:?}",
+        refutability::choose_background(None, false, "34".parse())
+    );
+
+    for line in refutability::indexed(&['a', 'b', 'c']) {
+        println!("{line}");
+    }
+
+    println!("{}", refutability::coordinates(&(3, 5)));
+    println!(
+        "area: {}",
+        refutability::area(&Rect {
+            width: 30,
+            height: 50
+        })
+    );
+    println!("{:?}", refutability::parse_pair("3,4"));
+    println!("{:?}", refutability::parse_pair("three,four"));
+}
@@ -0,0 +1,204 @@
+// 18.1 & 18.2 Where Patterns Go, and Whether They Can Fail
+
+/*
+Patterns come in two forms:
+    - irrefutable: they match any possible value. `x` in `let x = 5;`, or
+      `(a, b)` against a 2-tuple
+    - refutable: they can fail to match some value. `Some(x)` doesn't match
+      None
+
+let, function parameters, and for loops only accept irrefutable patterns,
+there's nothing sensible the program could do if they didn't match.
+if let, while let, and let-else accept refutable ones, they exist to handle
+the failure case. (An irrefutable pattern in an if let works, but gets a
+warning, since the else could never run.)
+
+Everything here returns data so the tests can check which branch ran.
+*/
+
+// while let: loop for as long as the pattern keeps matching. pop() returns
+// Some until the stack is empty, then None ends the loop
+pub fn drain_stack<T>(mut stack: Vec<T>) -> Vec<T> {
+    let mut popped = Vec::new();
+    while let Some(top) = stack.pop() {
+        popped.push(top);
+    }
+    popped
+}
+
+// next_if only hands out the next line if it passes the check, so the loop
+// stops at the first blank line and leaves the rest unread
+pub fn take_until_blank<'a>(lines: &[&'a str]) -> Vec<&'a str> {
+    let mut lines = lines.iter().peekable();
+    let mut taken = Vec::new();
+    while let Some(&line) = lines.next_if(|line| !line.trim().is_empty()) {
+        taken.push(line);
+    }
+    taken
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Background {
+    Favorite(String),
+    Green,
+    Purple,
+    Orange,
+    Blue,
+}
+
+/*
+The book's background color example: a chain of if let, else if, and else if
+let, where each check only runs if the ones before it didn't match. Nothing
+forces the conditions to be related, each one looks at a different option.
+
+The `age` binding in `if let Ok(age) = age` shadows the Result, the new age
+is only valid inside that block, which is why `age > 30` has to be a separate
+if instead of being written as `if let Ok(age) = age && age > 30`.
+*/
+pub fn choose_background(
+    favorite_color: Option<&str>,
+    is_tuesday: bool,
+    age: Result<u8, std::num::ParseIntError>,
+) -> Background {
+    if let Some(color) = favorite_color {
+        Background::Favorite(color.to_string())
+    } else if is_tuesday {
+        Background::Green
+    } else if let Ok(age) = age {
+        if age > 30 {
+            Background::Purple
+        } else {
+            Background::Orange
+        }
+    } else {
+        Background::Blue
+    }
+}
+
+// let-else: a refutable let has to say what happens when it doesn't match,
+// and the else block must leave the function (return, break, panic...). After
+// it, the bound names are usable as plain values
+pub fn parse_pair(text: &str) -> Result<(i32, i32), String> {
+    let Some((left, right)) = text.split_once(',') else {
+        return Err(format!("no comma in {text:?}"));
+    };
+    let (Ok(x), Ok(y)) = (left.trim().parse(), right.trim().parse()) else {
+        return Err(format!("not two numbers: {text:?}"));
+    };
+    Ok((x, y))
+}
+
+// for loops destructure each item, here enumerate's (index, value) tuples
+pub fn indexed(values: &[char]) -> Vec<String> {
+    let mut out = Vec::new();
+    for (index, value) in values.iter().enumerate() {
+        out.push(format!("{value} is at index {index}"));
+    }
+    out
+}
+
+// let destructuring a tuple: the pattern has to have exactly as many parts
+// as the tuple, let (x, y) = (1, 2, 3); is a compile error
+pub fn rotate((a, b, c): (i32, i32, i32)) -> (i32, i32, i32) {
+    let (x, y, z) = (b, c, a);
+    (x, y, z)
+}
+
+// function parameters are patterns too. &(x, y) matches the reference and
+// the tuple inside it, so the body gets plain i32s
+pub fn coordinates(&(x, y): &(i32, i32)) -> String {
+    format!("Current location: ({x}, {y})")
+}
+
+pub struct Rect {
+    pub width: u32,
+    pub height: u32,
+}
+
+// and structs can be taken apart right in the parameter list
+pub fn area(Rect { width, height }: &Rect) -> u32 {
+    width * height
+}
+
+// closure parameters work the same way
+pub fn total_area(sizes: &[(u32, u32)]) -> u32 {
+    sizes.iter().map(|&(w, h)| w * h).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn while_let_drains_top_first() {
+        assert_eq!(drain_stack(vec![1, 2, 3]), vec![3, 2, 1]);
+        assert!(drain_stack(Vec::<i32>::new()).is_empty());
+    }
+
+    #[test]
+    fn while_let_over_an_iterator() {
+        let lines = ["a", "b", "  ", "c"];
+        assert_eq!(take_until_blank(&lines), vec!["a", "b"]);
+        assert_eq!(take_until_blank(&["x"]), vec!["x"]);
+    }
+
+    #[test]
+    fn if_let_chain_checks_in_order() {
+        let age: Result<u8, _> = "34".parse();
+        assert_eq!(
+            choose_background(Some("red"), true, age.clone()),
+            Background::Favorite(String::from("red"))
+        );
+        assert_eq!(
+            choose_background(None, true, age.clone()),
+            Background::Green
+        );
+        assert_eq!(choose_background(None, false, age), Background::Purple);
+        assert_eq!(
+            choose_background(None, false, "30".parse()),
+            Background::Orange
+        );
+        assert_eq!(
+            choose_background(None, false, "not an age".parse()),
+            Background::Blue
+        );
+    }
+
+    #[test]
+    fn let_else_bails_out_on_mismatch() {
+        assert_eq!(parse_pair("3,4"), Ok((3, 4)));
+        assert_eq!(parse_pair(" -1 , 10 "), Ok((-1, 10)));
+        assert_eq!(parse_pair("3 4"), Err(String::from("no comma in \"3 4\"")));
+        assert_eq!(
+            parse_pair("3,four"),
+            Err(String::from("not two numbers: \"3,four\""))
+        );
+        assert!(parse_pair("").is_err());
+    }
+
+    #[test]
+    fn for_destructures_each_item() {
+        assert_eq!(
+            indexed(&['a', 'b']),
+            vec!["a is at index 0", "b is at index 1"]
+        );
+    }
+
+    #[test]
+    fn tuple_destructuring() {
+        assert_eq!(rotate((1, 2, 3)), (2, 3, 1));
+    }
+
+    #[test]
+    fn patterns_in_parameters() {
+        assert_eq!(coordinates(&(3, 5)), "Current location: (3, 5)");
+        assert_eq!(
+            area(&Rect {
+                width: 4,
+                height: 5
+            }),
+            20
+        );
+        assert_eq!(total_area(&[(1, 2), (3, 4)]), 14);
+    }
+}
@@ -0,0 +1,343 @@
+// 18.3 (continued) Every Pattern Form as a Function
+
+/*
+Each function here uses one kind of pattern and returns what it matched
+instead of printing it, so the tests at the bottom can check each form.
+*/
+
+// Matching Literals, Multiple Patterns, and Ranges of Values
+
+/*
+A literal matches exactly that value. | means "or", the arm matches if any of
+its patterns do. ..= matches an inclusive range. Ranges are only allowed for
+numbers and chars, the compiler has to be able to check the range isn't empty
+at compile time.
+*/
+pub fn describe_number(x: i32) -> &'static str {
+    match x {
+        1 => "one",
+        2 | 3 => "two or three",
+        4..=9 => "a single digit",
+        i32::MIN..=0 => "zero or less",
+        _ => "something big",
+    }
+}
+
+pub fn letter_kind(c: char) -> &'static str {
+    match c {
+        'a'..='j' => "early ASCII letter",
+        'k'..='z' => "late ASCII letter",
+        'A'..='Z' => "uppercase ASCII letter",
+        _ => "something else",
+    }
+}
+
+// Matching Named Variables
+
+/*
+A match starts a new scope, so a variable named in a pattern shadows any
+variable with the same name outside it. The `y` in `Some(y)` below isn't the
+`y` parameter, it's a new variable bound to whatever is inside the Some. To
+compare against the outer y we'd need a match guard (see guard_against below).
+*/
+pub fn shadowing(x: Option<i32>, y: i32) -> String {
+    let matched = match x {
+        Some(50) => String::from("Got 50"),
+        Some(y) => format!("Matched, y = {y}"),
+        _ => format!("Default case, x = {x:?}"),
+    };
+    // back outside the match, y is the parameter again
+    format!("{matched} (outer y = {y})")
+}
+
+// Destructuring Structs
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+// Point { x: a, y: b } would bind the fields to a and b, the shorthand
+// Point { x, y } uses the field names. Literals can go in the pattern too
+pub fn locate(p: &Point) -> String {
+    match *p {
+        Point { x: 0, y: 0 } => String::from("At the origin"),
+        Point { x, y: 0 } => format!("On the x axis at {x}"),
+        Point { x: 0, y } => format!("On the y axis at {y}"),
+        Point { x, y } => format!("On neither axis: ({x}, {y})"),
+    }
+}
+
+pub fn swap(p: Point) -> Point {
+    let Point { x: a, y: b } = p;
+    Point { x: b, y: a }
+}
+
+// Destructuring Enums (and Nested Structs and Enums)
+
+pub enum Color {
+    Rgb(i32, i32, i32),
+    Hsv(i32, i32, i32),
+}
+
+pub enum Message {
+    Quit,
+    Move { x: i32, y: i32 },
+    Write(String),
+    ChangeColor(Color),
+}
+
+/*
+The pattern mirrors how the variant was defined: nothing for a unit variant,
+braces for struct-like ones, parentheses for tuple-like ones. Patterns nest,
+so one arm can match ChangeColor *and* which kind of Color it holds
+*/
+pub fn describe_message(msg: &Message) -> String {
+    match msg {
+        Message::Quit => String::from("The Quit variant has no data to destructure."),
+        Message::Move { x, y } => format!("Move in the x direction {x} and in the y direction {y}"),
+        Message::Write(text) => format!("Text message: {text}"),
+        Message::ChangeColor(Color::Rgb(r, g, b)) => {
+            format!("Change color to red {r}, green {g}, and blue {b}")
+        }
+        Message::ChangeColor(Color::Hsv(h, s, v)) => {
+            format!("Change color to hue {h}, saturation {s}, value {v}")
+        }
+    }
+}
+
+// Destructuring Structs and Tuples
+
+// tuples and structs mixed together, pulled apart in one let
+pub fn nested_total(value: ((i32, i32), Point)) -> i32 {
+    let ((feet, inches), Point { x, y }) = value;
+    feet + inches + x + y
+}
+
+// Ignoring Values in a Pattern
+
+/*
+_ matches anything and doesn't bind it. Here we only care whether both are
+Some, not what's inside: an existing setting can't be overwritten, but an
+unset one can be set.
+*/
+pub fn update_setting(existing: Option<i32>, new: Option<i32>) -> Option<i32> {
+    match (existing, new) {
+        (Some(_), Some(_)) => existing,
+        _ => new.or(existing),
+    }
+}
+
+// _ can ignore parts of a tuple too
+pub fn even_positions(numbers: (i32, i32, i32, i32, i32)) -> (i32, i32, i32) {
+    let (first, _, third, _, fifth) = numbers;
+    (first, third, fifth)
+}
+
+// .. ignores everything that's left, and can sit between the parts we want.
+// It can only appear once per tuple, (.., second, ..) would be ambiguous
+pub fn first_and_last(numbers: (i32, i32, i32, i32, i32)) -> (i32, i32) {
+    let (first, .., last) = numbers;
+    (first, last)
+}
+
+pub struct Point3 {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+pub fn only_x(p: &Point3) -> i32 {
+    let Point3 { x, .. } = p;
+    *x
+}
+
+/*
+A name starting with _ is still a binding, it only silences the unused
+variable warning. Plain _ doesn't bind at all. The difference shows with a
+value that isn't Copy: `if let Some(_s) = s` moves the String into _s and s
+can't be used afterwards, `if let Some(_) = s` never moves it.
+*/
+// clippy would rather this were s.is_some(), but the pattern is the point
+#[allow(clippy::redundant_pattern_matching)]
+pub fn underscore_does_not_move(s: Option<String>) -> Option<String> {
+    if let Some(_) = s {
+        // nothing was bound, so s still owns its String
+    }
+    s
+}
+
+// Extra Conditionals with Match Guards
+
+/*
+A match guard is an extra `if` condition after the pattern. Guards can
+express things patterns can't, like "even". The compiler doesn't look inside
+guards when checking exhaustiveness, so the final arm is still needed.
+*/
+pub fn parity(x: Option<i32>) -> String {
+    match x {
+        Some(x) if x % 2 == 0 => format!("The number {x} is even"),
+        Some(x) => format!("The number {x} is odd"),
+        None => String::from("No number"),
+    }
+}
+
+// the fix for shadowing(): no new y is bound, the guard uses the outer one
+pub fn guard_against(x: Option<i32>, y: i32) -> String {
+    match x {
+        Some(50) => String::from("Got 50"),
+        Some(n) if n == y => format!("Matched, n = {n}"),
+        _ => format!("Default case, x = {x:?}"),
+    }
+}
+
+// The guard applies to the whole `4 | 5 | 6`, it behaves like
+// (4 | 5 | 6) if y, not 4 | 5 | (6 if y)
+#[allow(clippy::manual_range_patterns)]
+pub fn guard_precedence(x: i32, y: bool) -> &'static str {
+    match x {
+        4 | 5 | 6 if y => "yes",
+        _ => "no",
+    }
+}
+
+// @ Bindings
+
+/*
+@ tests a value against a pattern and binds it at the same time. In the first
+arm we know the id is 3 to 7 *and* have it in id_variable. The second arm
+checks the range but doesn't bind it, so the value isn't available there.
+*/
+pub fn describe_id(id: i32) -> String {
+    match id {
+        id_variable @ 3..=7 => format!("Found an id in range: {id_variable}"),
+        10..=12 => String::from("Found an id in another range"),
+        id => format!("Found some other id: {id}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn literals_or_and_ranges() {
+        assert_eq!(describe_number(1), "one");
+        assert_eq!(describe_number(2), "two or three");
+        assert_eq!(describe_number(3), "two or three");
+        assert_eq!(describe_number(4), "a single digit");
+        assert_eq!(describe_number(9), "a single digit");
+        assert_eq!(describe_number(0), "zero or less");
+        assert_eq!(describe_number(i32::MIN), "zero or less");
+        assert_eq!(describe_number(10), "something big");
+    }
+
+    #[test]
+    fn char_ranges() {
+        assert_eq!(letter_kind('a'), "early ASCII letter");
+        assert_eq!(letter_kind('j'), "early ASCII letter");
+        assert_eq!(letter_kind('k'), "late ASCII letter");
+        assert_eq!(letter_kind('Q'), "uppercase ASCII letter");
+        assert_eq!(letter_kind('é'), "something else");
+    }
+
+    #[test]
+    fn match_arms_shadow_outer_variables() {
+        assert_eq!(shadowing(Some(50), 10), "Got 50 (outer y = 10)");
+        // the inner y is 5, even though the parameter y is 10
+        assert_eq!(shadowing(Some(5), 10), "Matched, y = 5 (outer y = 10)");
+        assert_eq!(shadowing(None, 10), "Default case, x = None (outer y = 10)");
+    }
+
+    #[test]
+    fn guards_can_use_outer_variables() {
+        assert_eq!(guard_against(Some(5), 10), "Default case, x = Some(5)");
+        assert_eq!(guard_against(Some(10), 10), "Matched, n = 10");
+        assert_eq!(guard_against(Some(50), 50), "Got 50");
+    }
+
+    #[test]
+    fn struct_patterns_with_literals() {
+        assert_eq!(locate(&Point { x: 0, y: 0 }), "At the origin");
+        assert_eq!(locate(&Point { x: 3, y: 0 }), "On the x axis at 3");
+        assert_eq!(locate(&Point { x: 0, y: 7 }), "On the y axis at 7");
+        assert_eq!(locate(&Point { x: 1, y: 2 }), "On neither axis: (1, 2)");
+        assert_eq!(swap(Point { x: 1, y: 2 }), Point { x: 2, y: 1 });
+    }
+
+    #[test]
+    fn enum_patterns_nested() {
+        assert_eq!(
+            describe_message(&Message::Quit),
+            "The Quit variant has no data to destructure."
+        );
+        assert_eq!(
+            describe_message(&Message::Move { x: 3, y: -1 }),
+            "Move in the x direction 3 and in the y direction -1"
+        );
+        assert_eq!(
+            describe_message(&Message::Write(String::from("hi"))),
+            "Text message: hi"
+        );
+        assert_eq!(
+            describe_message(&Message::ChangeColor(Color::Rgb(1, 2, 3))),
+            "Change color to red 1, green 2, and blue 3"
+        );
+        assert_eq!(
+            describe_message(&Message::ChangeColor(Color::Hsv(4, 5, 6))),
+            "Change color to hue 4, saturation 5, value 6"
+        );
+    }
+
+    #[test]
+    fn tuples_and_structs_together() {
+        assert_eq!(nested_total(((3, 10), Point { x: 3, y: -10 })), 6);
+    }
+
+    #[test]
+    fn ignoring_with_underscore() {
+        assert_eq!(update_setting(Some(5), Some(10)), Some(5));
+        assert_eq!(update_setting(None, Some(10)), Some(10));
+        assert_eq!(update_setting(Some(5), None), Some(5));
+        assert_eq!(update_setting(None, None), None);
+        assert_eq!(even_positions((2, 4, 8, 16, 32)), (2, 8, 32));
+    }
+
+    #[test]
+    fn ignoring_the_rest_with_dot_dot() {
+        assert_eq!(first_and_last((2, 4, 8, 16, 32)), (2, 32));
+        assert_eq!(only_x(&Point3 { x: 1, y: 2, z: 3 }), 1);
+    }
+
+    #[test]
+    fn plain_underscore_never_binds() {
+        let s = Some(String::from("Hello!"));
+        assert_eq!(underscore_does_not_move(s), Some(String::from("Hello!")));
+    }
+
+    #[test]
+    fn guards_on_top_of_patterns() {
+        assert_eq!(parity(Some(4)), "The number 4 is even");
+        assert_eq!(parity(Some(-3)), "The number -3 is odd");
+        assert_eq!(parity(None), "No number");
+    }
+
+    #[test]
+    fn guard_covers_every_alternative() {
+        assert_eq!(guard_precedence(4, true), "yes");
+        assert_eq!(guard_precedence(6, true), "yes");
+        // 4 matches the pattern, but the guard applies to 4 too
+        assert_eq!(guard_precedence(4, false), "no");
+        assert_eq!(guard_precedence(6, false), "no");
+        assert_eq!(guard_precedence(7, true), "no");
+    }
+
+    #[test]
+    fn at_bindings() {
+        assert_eq!(describe_id(5), "Found an id in range: 5");
+        assert_eq!(describe_id(3), "Found an id in range: 3");
+        assert_eq!(describe_id(11), "Found an id in another range");
+        assert_eq!(describe_id(42), "Found some other id: 42");
+    }
+}
@@ -0,0 +1,49 @@
+// 19.5 (continued) How to Write a Custom derive Macro
+
+/*
+proc-macro = true in Cargo.toml makes this a procedural macro crate. The
+compiler runs the function below while compiling any crate that uses
+#[derive(HelloMacro)]: it gets the item the derive is on as a TokenStream, and
+the TokenStream it returns is added to that crate's code.
+
+Two crates do the heavy lifting:
+    - syn parses the TokenStream into a syntax tree we can look at. For a
+      derive that's a DeriveInput, which has the type's name (ident), its
+      generics, and its fields or variants
+    - quote! turns Rust-looking code back into a TokenStream. #name inside it
+      is replaced by the value of the variable `name`
+
+stringify! turns an expression into a string literal at compile time, so the
+generated name() returns "Pancakes" with no work at runtime.
+*/
+
+use proc_macro::TokenStream;
+use quote::quote;
+
+#[proc_macro_derive(HelloMacro)]
+pub fn hello_macro_derive(input: TokenStream) -> TokenStream {
+    // Construct a representation of Rust code as a syntax tree that we can
+    // manipulate. A derive is only ever handed a struct, enum, or union, so
+    // the book just unwraps. Panicking in a proc macro becomes a compile error
+    // in the crate using it
+    let ast = syn::parse(input).unwrap();
+
+    // Build the trait implementation
+    impl_hello_macro(&ast)
+}
+
+fn impl_hello_macro(ast: &syn::DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+    // The book's version only handles types without generics. split_for_impl
+    // gives back the three pieces needed for `impl<T> HelloMacro for Stack<T>`:
+    // the parameters to declare, the arguments to the type, and any where clause
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let generated = quote! {
+        impl #impl_generics HelloMacro for #name #ty_generics #where_clause {
+            fn name() -> &'static str {
+                stringify!(#name)
+            }
+        }
+    };
+    generated.into()
+}
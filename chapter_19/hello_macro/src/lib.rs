@@ -0,0 +1,48 @@
+// 19.5 (continued) The HelloMacro Trait
+
+/*
+The trait a #[derive(HelloMacro)] implements. Rust has no reflection, so a
+default method can't look up the name of the type it's called on. That one
+piece, name(), is what the derive macro in hello_macro_derive generates from
+the type's definition, and the two default methods build on it.
+
+The trait and its derive have to live in separate crates: a proc-macro crate
+can only export procedural macros. By convention the derive crate for foo is
+called foo_derive, and lives inside foo's directory. Users then depend on both
+(pancakes/ does).
+*/
+
+pub trait HelloMacro {
+    // generated by #[derive(HelloMacro)]
+    fn name() -> &'static str;
+
+    fn hello_message() -> String {
+        format!("Hello, Macro! My name is {}!", Self::name())
+    }
+
+    fn hello_macro() {
+        println!("{}", Self::hello_message());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // what the derive would write, done by hand
+    struct Waffles;
+
+    impl HelloMacro for Waffles {
+        fn name() -> &'static str {
+            "Waffles"
+        }
+    }
+
+    #[test]
+    fn default_methods_use_the_name() {
+        assert_eq!(
+            Waffles::hello_message(),
+            "Hello, Macro! My name is Waffles!"
+        );
+    }
+}
@@ -0,0 +1,63 @@
+// Using the HelloMacro derive from another crate, like a user of it would
+
+/*
+The trait and the derive come from two crates, and both have to be in scope:
+the trait for calling hello_macro(), the derive for #[derive(HelloMacro)].
+*/
+
+use hello_macro::HelloMacro;
+use hello_macro_derive::HelloMacro;
+
+#[derive(HelloMacro)]
+struct Pancakes;
+
+fn main() {
+    Pancakes::hello_macro();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(HelloMacro)]
+    struct Waffles {
+        _syrup: bool,
+    }
+
+    #[derive(HelloMacro)]
+    enum Breakfast {
+        _Eggs,
+        _Toast,
+    }
+
+    // generics make it past the derive too
+    #[derive(HelloMacro)]
+    struct Stack<T: Clone> {
+        _items: Vec<T>,
+    }
+
+    #[test]
+    fn derived_name_is_the_type_name() {
+        assert_eq!(Pancakes::name(), "Pancakes");
+        assert_eq!(Waffles::name(), "Waffles");
+        assert_eq!(Breakfast::name(), "Breakfast");
+    }
+
+    #[test]
+    fn default_methods_get_the_derived_name() {
+        assert_eq!(
+            Pancakes::hello_message(),
+            "Hello, Macro! My name is Pancakes!"
+        );
+    }
+
+    #[test]
+    fn generic_types_derive_without_their_parameters() {
+        // the name is the type's name, without the <T>
+        assert_eq!(Stack::<u8>::name(), "Stack");
+        assert_eq!(
+            Stack::<String>::hello_message(),
+            "Hello, Macro! My name is Stack!"
+        );
+    }
+}
@@ -0,0 +1,52 @@
+// 19.2 Advanced Traits
+
+/*
+Chapter 10 covered traits, this goes into the details that were skipped. The
+code is in traits.rs, this walks through it.
+*/
+
+use crate::traits::{
+    Animal, Counter, Dog, Human, Meters, Millimeters, MyIterator, OutlinePrint, Pilot, Point,
+    Wizard, Wrapper,
+};
+
+pub fn run() {
+    // Associated types: Counter says once that its Item is u32
+    let mut counter = Counter::new();
+    while let Some(n) = counter.next() {
+        print!("{n} ");
+    }
+    println!();
+
+    // Default generic type parameters: Point + Point, and Millimeters + Meters
+    println!("{:?}", Point { x: 1, y: 0 } + Point { x: 2, y: 3 });
+    println!("{:?}", Millimeters(500) + Meters(2));
+
+    // Methods with the same name from different traits
+    let person = Human;
+    println!("{}", Pilot::fly(&person));
+    println!("{}", Wizard::fly(&person));
+    println!("{}", person.fly());
+
+    // ...and associated functions, which need fully qualified syntax
+    println!("A baby dog is called a {}", Dog::baby_name());
+    println!("A baby dog is called a {}", <Dog as Animal>::baby_name());
+
+    /*
+    Writing `Animal::baby_name()` on its own doesn't compile:
+
+        error[E0790]: cannot call associated function on trait without specifying the corresponding `impl` type
+
+    Rust can't tell which type's implementation we want, there could be any
+    number of types implementing Animal.
+    */
+
+    // Supertraits: OutlinePrint can only be implemented for Display types
+    println!("{}", Point { x: 1, y: 3 }.outline());
+
+    // The newtype pattern: Display for a Vec, by way of a local wrapper
+    let w = Wrapper(vec![String::from("hello"), String::from("world")]);
+    println!("w = {w}");
+    // and Deref gets the Vec's methods back
+    println!("w has {} items, the first is {:?}", w.len(), w.first());
+}
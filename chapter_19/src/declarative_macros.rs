@@ -0,0 +1,89 @@
+// 19.5 (continued) Declarative Macros with macro_rules!
+
+/*
+A macro_rules! definition is like a match on Rust source code instead of on a
+value. Each arm is (pattern) => { code to expand to }. In the pattern:
+
+    $x:expr      matches any expression and names it $x. Other fragment
+                 types include ident, ty, tt (a single token tree), literal
+    $( ... ),*   matches the inside zero or more times, separated by commas.
+                 + instead of * means one or more
+    $(,)?        an optional trailing comma
+
+In the expansion, $( ... )* repeats its contents once for every match.
+
+#[macro_export] puts the macro at the root of the crate, so it's used as
+chapter_19::my_vec! and not chapter_19::macros::my_vec!. Inside a macro,
+$crate means "the crate this macro was defined in", so the expansion works
+from any crate, and ::std paths can't be broken by a local module named std.
+
+The double braces {{ }} in the expansions make each one a single block
+expression, so a macro with several statements can be used anywhere a value
+is expected, like `let v = my_vec![1, 2];`.
+
+The tests are in tests/declarative_macros.rs rather than down here. Inside
+this crate every macro_rules! macro is already in scope after its definition,
+so tests here would pass even with a broken #[macro_export] or a `max!` that
+forgot its $crate. From another crate they only work if both are right.
+*/
+
+// the book's vec! without the preallocation the real one does
+#[macro_export]
+macro_rules! my_vec {
+    () => {
+        ::std::vec::Vec::new()
+    };
+    // [elem; n], n clones of elem
+    ($elem:expr; $n:expr) => {{
+        let mut temp_vec = ::std::vec::Vec::new();
+        temp_vec.resize($n, $elem);
+        temp_vec
+    }};
+    ( $( $x:expr ),+ $(,)? ) => {{
+        let mut temp_vec = ::std::vec::Vec::new();
+        // written as Vec::push(&mut temp_vec, ..) (19.2's fully qualified
+        // syntax) only because clippy suggests vec![] for temp_vec.push(..)
+        // calls right after Vec::new(), which rather misses the point here
+        $(
+            ::std::vec::Vec::push(&mut temp_vec, $x);
+        )+
+        temp_vec
+    }};
+}
+
+// hashmap! { key => value, ... }. => can be used as a separator in the pattern
+// because it can't appear inside an expr, the parser knows where $key ends
+#[macro_export]
+macro_rules! hashmap {
+    ( $( $key:expr => $value:expr ),* $(,)? ) => {{
+        // hashmap! {} never inserts, so the mut goes unused there
+        #[allow(unused_mut)]
+        let mut map = ::std::collections::HashMap::new();
+        $(
+            map.insert($key, $value);
+        )*
+        map
+    }};
+}
+
+/*
+max! takes one or more values. It calls itself on the rest of the list, so
+max!(a, b, c) expands to a comparison of a against max!(b, c), and so on down
+to the single value arm. Each argument is bound to a local first so it's only
+evaluated once, max!(next(), 3) mustn't call next() twice.
+*/
+#[macro_export]
+macro_rules! max {
+    ($x:expr $(,)?) => {
+        $x
+    };
+    ($x:expr, $( $rest:expr ),+ $(,)?) => {{
+        let first = $x;
+        let rest = $crate::max!($( $rest ),+);
+        if first >= rest {
+            first
+        } else {
+            rest
+        }
+    }};
+}
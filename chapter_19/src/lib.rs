@@ -0,0 +1,42 @@
+// Chapter 19: Advanced Features (library)
+// A safe my_split_at_mut over raw pointers, traits with associated types,
+// default type parameters and fully qualified calls, and the macro_rules!
+// macros exported from the crate root
+// The walkthroughs that use them are here as well, exercises() lists them for
+// the runner
+
+pub mod advanced_traits;
+pub mod declarative_macros;
+pub mod macros;
+pub mod raw;
+pub mod traits;
+pub mod unsafe_rust;
+
+use domain::Section;
+
+// every section main runs, in the same order, for the runner
+pub fn exercises() -> Vec<Section> {
+    vec![
+        Section {
+            name: "unsafe_rust",
+            run: || {
+                unsafe_rust::run();
+                Ok(())
+            },
+        },
+        Section {
+            name: "advanced_traits",
+            run: || {
+                advanced_traits::run();
+                Ok(())
+            },
+        },
+        Section {
+            name: "macros",
+            run: || {
+                macros::run();
+                Ok(())
+            },
+        },
+    ]
+}
@@ -0,0 +1,81 @@
+// 19.5 Macros
+
+/*
+"Macro" covers a family of features in Rust:
+    - declarative macros, written with macro_rules!
+    - three kinds of procedural macros:
+        - custom #[derive] macros, which add code to structs and enums
+        - attribute-like macros, custom attributes usable on any item
+        - function-like macros, which look like function calls but operate on
+          the tokens passed to them
+
+The Difference Between Macros and Functions
+
+Macros are a way of writing code that writes other code, metaprogramming.
+println! and vec! expand to more code than we wrote by hand. Things a macro
+can do that a function can't:
+    - take a variable number of arguments: println!("hello") and
+      println!("hello {}", name)
+    - be expanded before the compiler interprets the code, so a macro can,
+      for example, implement a trait on a type. A function is called at
+      runtime, and a trait has to be implemented at compile time
+
+The downside is that macro definitions are harder to read, understand, and
+maintain than functions, since it's Rust code that writes Rust code. And
+macros have to be defined or brought into scope *before* they're called in a
+file, unlike functions which can be defined anywhere.
+*/
+
+use crate::{hashmap, max, my_vec};
+
+pub fn run() {
+    // declarative_macros.rs has the definitions and how each part works
+    let v = my_vec![1, 2, 3];
+    println!("my_vec! built {v:?}");
+
+    let zeros = my_vec![0; 4];
+    println!("my_vec! repeated {zeros:?}");
+
+    let scores = hashmap! {
+        String::from("Blue") => 10,
+        String::from("Yellow") => 50,
+    };
+    println!("Blue team: {}", scores["Blue"]);
+
+    println!("the biggest is {}", max!(3, 17, 8, 12));
+
+    /*
+    Procedural Macros for Generating Code from Attributes
+
+    A procedural macro is a function from a TokenStream (the code it's applied
+    to) to a TokenStream (the code to replace it with). It has to live in its
+    own crate with proc-macro = true. The book's custom derive is in this
+    directory's workspace:
+
+        hello_macro/                      the HelloMacro trait
+        hello_macro/hello_macro_derive/   #[derive(HelloMacro)], using syn and quote
+        pancakes/                         a crate that derives it
+
+    `cargo run -p pancakes` prints "Hello, Macro! My name is Pancakes!"
+
+    Attribute-like macros work the same way but make new attributes, and can
+    go on functions as well as types:
+
+        #[route(GET, "/")]
+        fn index() {}
+
+        #[proc_macro_attribute]
+        pub fn route(attr: TokenStream, item: TokenStream) -> TokenStream {
+
+    attr is the `GET, "/"` part, item is the function it's on.
+
+    Function-like macros look like macro_rules! calls, but get the tokens as a
+    TokenStream and can run any Rust code on them, so they can do things a
+    match on patterns can't, like check SQL syntax at compile time:
+
+        let sql = sql!(SELECT * FROM posts WHERE id=1);
+
+        #[proc_macro]
+        pub fn sql(input: TokenStream) -> TokenStream {
+    */
+}
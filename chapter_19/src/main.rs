@@ -0,0 +1,22 @@
+// Chapter 19: Advanced Features
+
+/*
+This chapter is a grab bag of features that aren't needed every day, but are
+good to recognize when they show up:
+    - unsafe Rust: opting out of some of Rust's guarantees and taking
+      responsibility for upholding them manually
+    - advanced traits: associated types, default type parameters, fully
+      qualified syntax, supertraits, and the newtype pattern
+    - advanced types: more about the newtype pattern, type aliases, the never
+      type, and dynamically sized types
+    - advanced functions and closures: function pointers and returning closures
+    - macros: ways to define code that defines more code at compile time
+*/
+
+use chapter_19::{advanced_traits, macros, unsafe_rust};
+
+fn main() {
+    unsafe_rust::run();
+    advanced_traits::run();
+    macros::run();
+}
@@ -0,0 +1,164 @@
+// 19.1 (continued) Raw Pointers Behind Safe APIs
+
+/*
+Every unsafe block here has a SAFETY comment saying why it's fine, the
+convention std itself uses. The tests only ever call these through their safe
+signatures, so they can be run under Miri (`cargo +nightly miri test raw`),
+which interprets the code and reports undefined behavior like reading out of
+bounds or two live &mut to the same memory.
+*/
+
+use std::slice;
+
+/*
+The book's split_at_mut. The safe version doesn't compile:
+
+    fn split_at_mut(values: &mut [i32], mid: usize) -> (&mut [i32], &mut [i32]) {
+        let len = values.len();
+        assert!(mid <= len);
+        (&mut values[..mid], &mut values[mid..])
+        // error[E0499]: cannot borrow `*values` as mutable more than once at a time
+    }
+
+The function itself isn't unsafe: the assert makes sure both halves are inside
+the slice, and they don't overlap, so callers can't misuse it. Only the block
+that builds the halves is.
+*/
+pub fn my_split_at_mut<T>(values: &mut [T], mid: usize) -> (&mut [T], &mut [T]) {
+    let len = values.len();
+    let ptr = values.as_mut_ptr();
+
+    assert!(
+        mid <= len,
+        "mid {} is past the end of a slice of {}",
+        mid,
+        len
+    );
+
+    // SAFETY: mid <= len, so both ranges are inside the slice, [0, mid) and
+    // [mid, len) don't overlap, and both borrow from `values` so they can't
+    // outlive it. ptr.add(mid) is at most one past the end, which is allowed
+    unsafe {
+        (
+            slice::from_raw_parts_mut(ptr, mid),
+            slice::from_raw_parts_mut(ptr.add(mid), len - mid),
+        )
+    }
+}
+
+/// Reads values[index] without a bounds check.
+///
+/// # Safety
+///
+/// index has to be less than values.len(), anything else reads memory that
+/// isn't part of the slice.
+pub unsafe fn read_unchecked<T: Copy>(values: &[T], index: usize) -> T {
+    // SAFETY: the caller promised index is in bounds
+    unsafe { *values.as_ptr().add(index) }
+}
+
+// the safe wrapper: the check happens here, once, so callers don't need unsafe
+pub fn read_checked<T: Copy>(values: &[T], index: usize) -> Option<T> {
+    if index < values.len() {
+        // SAFETY: just checked index is in bounds
+        Some(unsafe { read_unchecked(values, index) })
+    } else {
+        None
+    }
+}
+
+// swapping through raw pointers, roughly what std::ptr::swap does underneath.
+// read() copies the value out, write() overwrites without dropping the old one
+pub fn swap_raw(a: &mut i32, b: &mut i32) {
+    let pa = a as *mut i32;
+    let pb = b as *mut i32;
+    // SAFETY: both pointers come from live &mut references, which can't
+    // overlap or be null
+    unsafe {
+        let tmp = pa.read();
+        pa.write(pb.read());
+        pb.write(tmp);
+    }
+}
+
+// An immutable and a mutable raw pointer to the same value at once. With
+// references that's a compile error, with raw pointers it's allowed, and it's
+// on us not to use them in a way that breaks the rules
+pub fn aliasing_raw_pointers() -> (i32, i32) {
+    let mut num = 5;
+
+    let r2 = &mut num as *mut i32;
+    let r1 = r2 as *const i32;
+
+    // SAFETY: num is alive for the whole block, and the two pointers are used
+    // one after the other, never as overlapping references
+    unsafe {
+        let before = *r1;
+        *r2 += 1;
+        (before, *r1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_like_std() {
+        let mut ours = vec![1, 2, 3, 4, 5, 6];
+        let mut std = ours.clone();
+        for mid in 0..=6 {
+            let (a, b) = my_split_at_mut(&mut ours, mid);
+            let (c, d) = std.split_at_mut(mid);
+            assert_eq!((&a[..], &b[..]), (&c[..], &d[..]));
+        }
+    }
+
+    #[test]
+    fn both_halves_are_writable() {
+        let mut v = vec![1, 2, 3, 4, 5, 6];
+        let (a, b) = my_split_at_mut(&mut v, 3);
+        assert_eq!(a, &mut [1, 2, 3]);
+        assert_eq!(b, &mut [4, 5, 6]);
+        // at the same time, which a second &mut v couldn't do
+        a.swap_with_slice(b);
+        assert_eq!(v, vec![4, 5, 6, 1, 2, 3]);
+    }
+
+    #[test]
+    fn works_on_any_type_and_empty_slices() {
+        let mut words = [String::from("a"), String::from("b")];
+        let (left, right) = my_split_at_mut(&mut words, 1);
+        left[0].push_str(&right[0]);
+        assert_eq!(words[0], "ab");
+
+        let mut empty: [u8; 0] = [];
+        let (a, b) = my_split_at_mut(&mut empty, 0);
+        assert!(a.is_empty() && b.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "past the end")]
+    fn mid_past_the_end() {
+        let mut v = [1, 2, 3];
+        my_split_at_mut(&mut v, 4);
+    }
+
+    #[test]
+    fn checked_and_unchecked_reads() {
+        let values = [10, 20, 30];
+        // SAFETY: 2 < 3
+        assert_eq!(unsafe { read_unchecked(&values, 2) }, 30);
+        assert_eq!(read_checked(&values, 0), Some(10));
+        assert_eq!(read_checked(&values, 3), None);
+        assert_eq!(read_checked::<i32>(&[], 0), None);
+    }
+
+    #[test]
+    fn raw_pointer_swap_and_aliasing() {
+        let (mut a, mut b) = (1, 2);
+        swap_raw(&mut a, &mut b);
+        assert_eq!((a, b), (2, 1));
+        assert_eq!(aliasing_raw_pointers(), (5, 6));
+    }
+}
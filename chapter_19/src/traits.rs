@@ -0,0 +1,341 @@
+// 19.2 (continued) The Advanced Traits Examples as Working Code
+
+/*
+Everything from the advanced traits walkthrough, with methods that return
+what the book prints so the tests can check them.
+*/
+
+use std::fmt;
+use std::ops::{Add, Deref};
+
+// Specifying Placeholder Types in Trait Definitions with Associated Types
+
+/*
+Item is a placeholder: each implementor says what it is. The difference from
+a generic MyIterator<T> is that a type can only implement this once, so
+callers never have to say which Item they mean, there's only one.
+*/
+pub trait MyIterator {
+    type Item;
+
+    fn next(&mut self) -> Option<Self::Item>;
+
+    // a default method can use the associated type too
+    fn collect_all(mut self) -> Vec<Self::Item>
+    where
+        Self: Sized,
+    {
+        let mut items = Vec::new();
+        while let Some(item) = self.next() {
+            items.push(item);
+        }
+        items
+    }
+}
+
+// counts from 1 to 5, like the Counter from chapter 13
+pub struct Counter {
+    count: u32,
+}
+
+impl Counter {
+    pub fn new() -> Counter {
+        Counter { count: 0 }
+    }
+}
+
+impl Default for Counter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MyIterator for Counter {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.count < 5 {
+            self.count += 1;
+            Some(self.count)
+        } else {
+            None
+        }
+    }
+}
+
+// Default Generic Type Parameters and Operator Overloading
+
+/*
+Operators like + can be overloaded by implementing the traits in std::ops.
+Add is declared with a default type parameter:
+
+    trait Add<Rhs = Self> {
+        type Output;
+        fn add(self, rhs: Rhs) -> Self::Output;
+    }
+
+so `impl Add for Point` means Point + Point. To add something else, fill Rhs
+in: `impl Add<Meters> for Millimeters` is Millimeters + Meters.
+*/
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point {
+            x: self.x + other.x,
+            y: self.y + other.y,
+        }
+    }
+}
+
+// Newtypes keep the units apart: a plain u32 + u32 would happily add
+// millimeters to meters and give a wrong answer
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Millimeters(pub u32);
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Meters(pub u32);
+
+impl Add<Meters> for Millimeters {
+    type Output = Millimeters;
+
+    fn add(self, other: Meters) -> Millimeters {
+        Millimeters(self.0 + (other.0 * 1000))
+    }
+}
+
+// Rhs defaults to Self, so this is Millimeters + Millimeters
+impl Add for Millimeters {
+    type Output = Millimeters;
+
+    fn add(self, other: Millimeters) -> Millimeters {
+        Millimeters(self.0 + other.0)
+    }
+}
+
+// Fully Qualified Syntax for Disambiguation: Calling Methods with the Same Name
+
+pub trait Pilot {
+    fn fly(&self) -> &'static str;
+}
+
+pub trait Wizard {
+    fn fly(&self) -> &'static str;
+}
+
+pub struct Human;
+
+impl Pilot for Human {
+    fn fly(&self) -> &'static str {
+        "This is your captain speaking."
+    }
+}
+
+impl Wizard for Human {
+    fn fly(&self) -> &'static str {
+        "Up!"
+    }
+}
+
+impl Human {
+    // human.fly() picks this one, the method on the type itself wins
+    pub fn fly(&self) -> &'static str {
+        "*waving arms furiously*"
+    }
+}
+
+/*
+Associated functions have no self, so Pilot::fly(&person) style doesn't help:
+Animal::baby_name() doesn't know which implementation we mean. That's what
+fully qualified syntax is for:
+
+    <Type as Trait>::function(receiver_if_method, next_arg, ...);
+*/
+pub trait Animal {
+    fn baby_name() -> String;
+}
+
+pub struct Dog;
+
+impl Dog {
+    pub fn baby_name() -> String {
+        String::from("Spot")
+    }
+}
+
+impl Animal for Dog {
+    fn baby_name() -> String {
+        String::from("puppy")
+    }
+}
+
+// Using Supertraits to Require One Trait's Functionality Within Another Trait
+
+/*
+OutlinePrint: Display means a type can only implement OutlinePrint if it also
+implements Display. That's what lets the default method call to_string() on
+self, which comes from Display. Without the supertrait, the compiler wouldn't
+know self has a to_string().
+
+The book's version prints the outline, this one returns it:
+
+    **********
+    *        *
+    * (1, 3) *
+    *        *
+    **********
+*/
+pub trait OutlinePrint: fmt::Display {
+    fn outline(&self) -> String {
+        let output = self.to_string();
+        let len = output.chars().count();
+        let border = "*".repeat(len + 4);
+        let blank = format!("*{}*", " ".repeat(len + 2));
+        format!("{border}\n{blank}\n* {output} *\n{blank}\n{border}")
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+// nothing to write, the default method does it all. Leaving out the Display
+// impl above would make this line fail with "`Point` doesn't implement
+// `std::fmt::Display`"
+impl OutlinePrint for Point {}
+
+// Using the Newtype Pattern to Implement External Traits on External Types
+
+/*
+The orphan rule says we can only implement a trait on a type if the trait or
+the type is local to our crate. Display and Vec are both from std, so
+`impl fmt::Display for Vec<String>` isn't allowed. Wrapping the Vec in a tuple
+struct of our own gets around it: Wrapper is local, so we can implement
+anything on it. The wrapper is free at runtime, it's gone after compiling.
+
+The downside is that Wrapper doesn't have any of Vec's methods. Implementing
+Deref (chapter 15) gives back all the &self ones, w.len(), w.iter() and so on
+go straight through to the Vec. We don't implement DerefMut, so the contents
+can't be changed through the wrapper, only read.
+*/
+pub struct Wrapper(pub Vec<String>);
+
+impl fmt::Display for Wrapper {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}]", self.0.join(", "))
+    }
+}
+
+impl Deref for Wrapper {
+    type Target = Vec<String>;
+
+    fn deref(&self) -> &Vec<String> {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn associated_type_iterator() {
+        let mut counter = Counter::new();
+        assert_eq!(counter.next(), Some(1));
+        assert_eq!(counter.collect_all(), vec![2, 3, 4, 5]);
+
+        let mut done = Counter::new();
+        for _ in 0..5 {
+            done.next();
+        }
+        assert_eq!(done.next(), None);
+    }
+
+    #[test]
+    fn point_addition() {
+        assert_eq!(
+            Point { x: 1, y: 0 } + Point { x: 2, y: 3 },
+            Point { x: 3, y: 3 }
+        );
+    }
+
+    #[test]
+    fn millimeters_plus_meters() {
+        assert_eq!(Millimeters(500) + Meters(2), Millimeters(2500));
+        assert_eq!(Millimeters(500) + Millimeters(250), Millimeters(750));
+        // chains, since both impls return Millimeters
+        assert_eq!(
+            Millimeters(1) + Meters(1) + Millimeters(9),
+            Millimeters(1010)
+        );
+    }
+
+    #[test]
+    fn choosing_which_fly() {
+        let person = Human;
+        assert_eq!(person.fly(), "*waving arms furiously*");
+        assert_eq!(Pilot::fly(&person), "This is your captain speaking.");
+        assert_eq!(Wizard::fly(&person), "Up!");
+        // the method call is shorthand for this
+        assert_eq!(Human::fly(&person), "*waving arms furiously*");
+    }
+
+    #[test]
+    fn outline_around_display_output() {
+        let outline = Point { x: 1, y: 3 }.outline();
+        assert_eq!(
+            outline,
+            "**********\n*        *\n* (1, 3) *\n*        *\n**********"
+        );
+        // every line is as wide as the border
+        assert!(outline.lines().all(|line| line.len() == 10));
+    }
+
+    #[test]
+    fn outline_works_for_any_display_type() {
+        struct Name(&'static str);
+
+        impl fmt::Display for Name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+
+        impl OutlinePrint for Name {}
+
+        // counted in chars, so the é doesn't throw the border off
+        let lines: Vec<String> = Name("Zoé").outline().lines().map(String::from).collect();
+        assert_eq!(lines[0], "*******");
+        assert_eq!(lines[2], "* Zoé *");
+    }
+
+    #[test]
+    fn wrapper_displays_the_vec() {
+        let w = Wrapper(vec![String::from("hello"), String::from("world")]);
+        assert_eq!(w.to_string(), "[hello, world]");
+        assert_eq!(format!("{}", Wrapper(vec![])), "[]");
+    }
+
+    #[test]
+    fn wrapper_derefs_to_vec_methods() {
+        let w = Wrapper(vec![String::from("a"), String::from("bb")]);
+        assert_eq!(w.len(), 2);
+        assert!(w.contains(&String::from("bb")));
+        assert_eq!(w.iter().map(|s| s.len()).sum::<usize>(), 3);
+        assert_eq!(w.first().map(String::as_str), Some("a"));
+    }
+
+    #[test]
+    fn fully_qualified_associated_function() {
+        assert_eq!(Dog::baby_name(), "Spot");
+        assert_eq!(<Dog as Animal>::baby_name(), "puppy");
+    }
+}
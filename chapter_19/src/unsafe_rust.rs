@@ -0,0 +1,111 @@
+// 19.1 Unsafe Rust
+
+/*
+All the code so far had Rust's memory safety guarantees enforced at compile
+time. Unsafe Rust exists because static analysis is conservative: it's better
+to reject some valid programs than to accept some invalid ones. When the
+compiler can't tell that code is fine, unsafe is how we say "trust me". And
+the hardware underneath is inherently unsafe, some low level work (talking to
+the OS, writing an OS) wouldn't be possible without it.
+
+Inside an unsafe block we get five extra abilities, the "unsafe superpowers":
+    - dereference a raw pointer
+    - call an unsafe function or method
+    - access or modify a mutable static variable
+    - implement an unsafe trait
+    - access fields of unions
+
+unsafe doesn't turn off the borrow checker or any other check, it only unlocks
+those five things. Keeping unsafe blocks small, and wrapping them in a safe
+API, makes it much easier to track down memory bugs: they can only come from
+inside an unsafe block.
+*/
+
+use crate::raw;
+
+pub fn run() {
+    // Dereferencing a Raw Pointer
+
+    /*
+    Raw pointers, *const T and *mut T, are allowed to:
+        - ignore the borrowing rules, having both immutable and mutable
+          pointers, or several mutable pointers, to the same location
+        - point to memory that isn't valid
+        - be null
+        - skip automatic cleanup
+
+    Creating one is safe, it's only dereferencing one that needs unsafe.
+    */
+
+    let mut num = 5;
+
+    let r1 = &num as *const i32;
+    let r2 = &mut num as *mut i32;
+
+    // a pointer to an arbitrary address. It almost certainly isn't valid
+    // memory, but making the pointer is fine as long as nobody reads it
+    let address = 0x012345usize;
+    let _r = address as *const i32;
+
+    unsafe {
+        println!("r1 is: {}", *r1);
+        *r2 += 1;
+        println!("r2 is: {}", *r2);
+    }
+
+    // Calling an Unsafe Function or Method
+
+    /*
+    An unsafe fn has requirements the compiler can't check, so calling it is
+    our promise that we've read its docs and met them.
+    */
+
+    let values = [10, 20, 30];
+    let second = unsafe { raw::read_unchecked(&values, 1) };
+    println!("second value: {second}");
+
+    // Creating a Safe Abstraction over Unsafe Code
+
+    /*
+    split_at_mut can't be written with only safe code: the borrow checker
+    sees two mutable borrows of the same slice and stops there. It doesn't know
+    that they're different, non overlapping parts of it. See raw.rs for the
+    version built on slice::from_raw_parts_mut.
+    */
+
+    let mut v = vec![1, 2, 3, 4, 5, 6];
+    let (a, b) = raw::my_split_at_mut(&mut v, 3);
+    a[0] = 100;
+    b[0] = 400;
+    println!("after split: {v:?}");
+
+    /*
+    Using extern Functions to Call External Code
+
+    extern "C" declares functions from another language. Any of them could do
+    anything, so calling them is always unsafe:
+
+        extern "C" {
+            fn abs(input: i32) -> i32;
+        }
+
+        unsafe {
+            println!("Absolute value of -3 according to C: {}", abs(-3));
+        }
+
+    Accessing or Modifying a Mutable Static Variable
+
+    Globals are statics in Rust. Reading and writing a `static mut` is unsafe,
+    two threads could both be changing it at once (a data race). The
+    concurrency tools from chapter 16 or an atomic are almost always the better
+    choice.
+
+    Implementing an Unsafe Trait
+
+    A trait is unsafe when at least one of its methods has an invariant the
+    compiler can't verify, like Send and Sync for types with raw pointers:
+
+        unsafe trait Foo {}
+        unsafe impl Foo for i32 {}
+    */
+}
@@ -0,0 +1,87 @@
+// Integration tests for chapter_19's exported macros. Being a separate crate,
+// this file can only reach them through #[macro_export], by path
+use chapter_19::{hashmap, max, my_vec};
+use std::cell::Cell;
+use std::collections::HashMap;
+
+#[test]
+fn my_vec_matches_vec() {
+    let empty: Vec<i32> = my_vec![];
+    assert_eq!(empty, vec![]);
+    assert_eq!(my_vec![1, 2, 3], vec![1, 2, 3]);
+    assert_eq!(my_vec!["trailing", "comma",], vec!["trailing", "comma"]);
+    assert_eq!(my_vec![String::from("x"); 3], vec!["x", "x", "x"]);
+    assert_eq!(my_vec![0u8; 0], Vec::<u8>::new());
+}
+
+#[test]
+fn my_vec_takes_any_expression() {
+    let n: i32 = 4;
+    let v = my_vec![n * 2, if n > 3 { 1 } else { 0 }, n.pow(2)];
+    assert_eq!(v, vec![8, 1, 16]);
+}
+
+#[test]
+fn hashmap_literal() {
+    let map = hashmap! {
+        "Blue" => 10,
+        "Yellow" => 50,
+    };
+    let mut expected = HashMap::new();
+    expected.insert("Blue", 10);
+    expected.insert("Yellow", 50);
+    assert_eq!(map, expected);
+
+    let empty: HashMap<String, i32> = hashmap! {};
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn hashmap_later_keys_win() {
+    let map = hashmap! { 1 => "first", 1 => "second" };
+    assert_eq!(map.len(), 1);
+    assert_eq!(map[&1], "second");
+}
+
+#[test]
+fn max_of_any_length() {
+    assert_eq!(max!(7), 7);
+    assert_eq!(max!(1, 9, 3), 9);
+    assert_eq!(max!(-5, -2, -8, -3,), -2);
+    assert_eq!(max!("apple", "pear", "fig"), "pear");
+    assert_eq!(max!(1.5, 0.5), 1.5);
+}
+
+#[test]
+fn max_evaluates_each_argument_once() {
+    let calls = Cell::new(0);
+    let next = || {
+        calls.set(calls.get() + 1);
+        calls.get()
+    };
+    assert_eq!(max!(next(), next(), 0), 2);
+    assert_eq!(calls.get(), 2);
+}
+
+mod renamed {
+    // the `use` at the top doesn't reach into this module, so only `biggest`
+    // is in scope and the recursive calls have to find max! through $crate
+    use chapter_19::max as biggest;
+
+    #[test]
+    fn max_recurses_through_crate_path() {
+        assert_eq!(biggest!(4, 11, 2), 11);
+    }
+}
+
+mod local_std {
+    // a module named std here doesn't change what the expansions' ::std
+    // paths point at
+    mod std {}
+
+    #[test]
+    fn macros_still_expand() {
+        assert_eq!(chapter_19::my_vec![1, 2], vec![1, 2]);
+        assert_eq!(chapter_19::hashmap! { "a" => 1 }.len(), 1);
+    }
+}
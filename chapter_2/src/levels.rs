@@ -0,0 +1,261 @@
+// Difficulty Levels
+
+/*
+The book's game is always 1 to 100 with as many guesses as you like. Here the
+range and a limit on guesses come from a Difficulty:
+
+    easy     1 to 10       5 guesses
+    normal   1 to 100      10 guesses
+    hard     1 to 1000     10 guesses
+
+(binary search needs at most 4, 7, and 10 guesses, so every level can be won)
+
+Guesses are checked with the Guess type from chapter 9's validation.rs, which
+already knows how to say what's wrong with a number outside its bounds. A
+guess that's out of range, or not a number at all, doesn't use up an attempt.
+*/
+
+use crate::Input;
+use chapter_9::validation::{Guess, GuessRules};
+use domain::Rng;
+use std::cmp::Ordering;
+use std::fmt;
+use std::io::{self, Write};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    pub fn range(&self) -> RangeInclusive<u32> {
+        match self {
+            Difficulty::Easy => 1..=10,
+            Difficulty::Normal => 1..=100,
+            Difficulty::Hard => 1..=1000,
+        }
+    }
+
+    // a secret somewhere in range()
+    pub fn pick_secret(&self, rng: &mut dyn Rng) -> u32 {
+        rng.gen_range(self.range())
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        match self {
+            Difficulty::Easy => 5,
+            Difficulty::Normal | Difficulty::Hard => 10,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Difficulty, String> {
+        Difficulty::ALL
+            .into_iter()
+            .find(|level| level.name().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| format!("unknown difficulty {:?}, try easy, normal or hard", s))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum LevelOutcome {
+    Won { attempts: u32 },
+    // used every attempt without finding it
+    Lost,
+    // the input ran out first
+    GaveUp,
+}
+
+// play() from lib.rs, with the level's range and attempt limit
+pub fn play_level(
+    level: Difficulty,
+    secret_number: u32,
+    input: &mut dyn Input,
+    out: &mut dyn Write,
+) -> io::Result<LevelOutcome> {
+    let range = level.range();
+    writeln!(
+        out,
+        "Guess the number between {} and {}! ({}, {} guesses)",
+        range.start(),
+        range.end(),
+        level,
+        level.max_attempts()
+    )?;
+    let mut attempts = 0;
+
+    while attempts < level.max_attempts() {
+        writeln!(out, "Please input your guess.")?;
+
+        let line = match input.read_guess() {
+            Some(line) => line,
+            None => return Ok(LevelOutcome::GaveUp),
+        };
+
+        // i32 rather than u32, so "-3" gets the validator's "less than 1"
+        // message instead of being silently skipped as not a number
+        let guess = match line.trim().parse::<i32>() {
+            Ok(num) => num,
+            Err(_) => {
+                writeln!(out, "That's not a number.")?;
+                continue;
+            }
+        };
+        let guess = match Guess::validate_in(guess, *range.start() as i32, *range.end() as i32) {
+            Ok(guess) => guess.value() as u32,
+            Err(errors) => {
+                for error in errors {
+                    writeln!(out, "{}", error.message)?;
+                }
+                continue;
+            }
+        };
+        attempts += 1;
+
+        writeln!(out, "You guessed: {guess}")?;
+
+        match guess.cmp(&secret_number) {
+            Ordering::Less => writeln!(out, "Too small!")?,
+            Ordering::Greater => writeln!(out, "Too big!")?,
+            Ordering::Equal => {
+                writeln!(out, "You win!")?;
+                return Ok(LevelOutcome::Won { attempts });
+            }
+        }
+    }
+
+    writeln!(out, "Out of guesses! The number was {secret_number}.")?;
+    Ok(LevelOutcome::Lost)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ScriptedInput;
+    use domain::XorShift;
+
+    fn play_with(level: Difficulty, secret: u32, guesses: &[&str]) -> (LevelOutcome, String) {
+        let mut input = ScriptedInput::new(guesses);
+        let mut out: Vec<u8> = Vec::new();
+        let outcome = play_level(level, secret, &mut input, &mut out).unwrap();
+        (outcome, String::from_utf8(out).unwrap())
+    }
+
+    // every guess a binary search would make to find `secret` in `level`
+    fn binary_search(level: Difficulty, secret: u32) -> Vec<String> {
+        let (mut low, mut high) = (*level.range().start(), *level.range().end());
+        let mut script = Vec::new();
+        loop {
+            let guess = (low + high) / 2;
+            script.push(guess.to_string());
+            match guess.cmp(&secret) {
+                Ordering::Less => low = guess + 1,
+                Ordering::Greater => high = guess - 1,
+                Ordering::Equal => return script,
+            }
+        }
+    }
+
+    #[test]
+    fn levels_by_name() {
+        assert_eq!("easy".parse(), Ok(Difficulty::Easy));
+        assert_eq!(" HARD\n".parse(), Ok(Difficulty::Hard));
+        assert!("impossible".parse::<Difficulty>().is_err());
+        for level in Difficulty::ALL {
+            assert_eq!(level.to_string().parse(), Ok(level));
+        }
+    }
+
+    #[test]
+    fn secrets_stay_in_the_levels_range() {
+        let mut rng = XorShift::new(5);
+        for level in Difficulty::ALL {
+            for _ in 0..500 {
+                assert!(level.range().contains(&level.pick_secret(&mut rng)));
+            }
+        }
+        assert_eq!(
+            Difficulty::Hard.pick_secret(&mut XorShift::new(9)),
+            Difficulty::Hard.pick_secret(&mut XorShift::new(9))
+        );
+    }
+
+    #[test]
+    fn wins_count_attempts() {
+        let (outcome, output) = play_with(Difficulty::Easy, 7, &["3", "9", "7"]);
+        assert_eq!(outcome, LevelOutcome::Won { attempts: 3 });
+        assert!(output.starts_with("Guess the number between 1 and 10! (easy, 5 guesses)\n"));
+    }
+
+    #[test]
+    fn out_of_range_guesses_are_explained_and_free() {
+        let (outcome, output) = play_with(Difficulty::Easy, 2, &["11", "0", "-3", "two", "2"]);
+        assert_eq!(outcome, LevelOutcome::Won { attempts: 1 });
+        assert!(output.contains("greater than 10, got: 11\n"));
+        assert!(output.contains("less than 1, got: 0\n"));
+        assert!(output.contains("less than 1, got: -3\n"));
+        assert!(output.contains("That's not a number.\n"));
+    }
+
+    #[test]
+    fn running_out_of_attempts_loses() {
+        let (outcome, output) = play_with(Difficulty::Easy, 10, &["1", "2", "3", "4", "5", "10"]);
+        assert_eq!(outcome, LevelOutcome::Lost);
+        assert!(output.ends_with("Out of guesses! The number was 10.\n"));
+        // the sixth guess was never asked for
+        assert!(!output.contains("You guessed: 10"));
+    }
+
+    #[test]
+    fn running_out_of_input_gives_up() {
+        assert_eq!(
+            play_with(Difficulty::Hard, 500, &["1"]).0,
+            LevelOutcome::GaveUp
+        );
+    }
+
+    #[test]
+    fn hard_accepts_bigger_numbers() {
+        let (outcome, _) = play_with(Difficulty::Hard, 999, &["999"]);
+        assert_eq!(outcome, LevelOutcome::Won { attempts: 1 });
+        let (_, output) = play_with(Difficulty::Normal, 50, &["999", "50"]);
+        assert!(output.contains("greater than 100, got: 999"));
+    }
+
+    #[test]
+    fn every_level_is_winnable() {
+        for level in Difficulty::ALL {
+            for secret in level.range() {
+                let script = binary_search(level, secret);
+                let script: Vec<&str> = script.iter().map(String::as_str).collect();
+                match play_with(level, secret, &script).0 {
+                    LevelOutcome::Won { attempts } => assert!(attempts <= level.max_attempts()),
+                    other => panic!("{} on {}: {:?}", secret, level, other),
+                }
+            }
+        }
+    }
+}
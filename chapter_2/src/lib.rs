@@ -0,0 +1,218 @@
+// Chapter 2: Programming a Guessing Game (library)
+// main.rs sets the game up (the secret number, stdin, stdout), this lib holds
+// the game itself so it can be played by a test instead of a person
+
+/*
+The book's version reads straight from io::stdin(), which means the only way
+to test it is to sit and type guesses. Here the game asks an Input for each
+guess instead. main.rs passes one that reads stdin, and the tests pass one
+that hands out a list of guesses written in advance. Output goes to any
+io::Write for the same reason: stdout for real, a Vec<u8> in tests.
+
+The secret number works the same way: it comes from an Rng (domain's rng.rs),
+the rand crate for a normal game and a seeded XorShift for `--seed N` or a
+test.
+*/
+
+pub mod levels;
+pub mod stats;
+
+use domain::Rng;
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::io::{self, BufRead, Write};
+
+pub trait Input {
+    // the next line the player typed, or None when there's no more input
+    fn read_guess(&mut self) -> Option<String>;
+}
+
+// the real thing: one line from stdin per guess
+pub struct StdinInput;
+
+impl Input for StdinInput {
+    fn read_guess(&mut self) -> Option<String> {
+        let mut guess = String::new();
+        // read_line returns how many bytes it read, 0 means stdin was closed
+        match io::stdin().lock().read_line(&mut guess) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(guess),
+        }
+    }
+}
+
+// guesses decided ahead of time, for tests (or a demo)
+pub struct ScriptedInput {
+    lines: VecDeque<String>,
+}
+
+impl ScriptedInput {
+    pub fn new(lines: &[&str]) -> ScriptedInput {
+        ScriptedInput {
+            lines: lines.iter().map(|line| format!("{}\n", line)).collect(),
+        }
+    }
+}
+
+impl Input for ScriptedInput {
+    fn read_guess(&mut self) -> Option<String> {
+        self.lines.pop_front()
+    }
+}
+
+// rand's thread_rng() behind domain's Rng trait. The wrapper struct is needed
+// because neither the trait nor ThreadRng belong to this crate, and Rust only
+// lets us implement a trait for a type when one of them is ours (the orphan
+// rule, 10.2)
+#[derive(Default)]
+pub struct ThreadRandom(rand::rngs::ThreadRng);
+
+impl Rng for ThreadRandom {
+    fn next_u64(&mut self) -> u64 {
+        rand::RngCore::next_u64(&mut self.0)
+    }
+}
+
+// the book's secret: 1..=100, both ends included
+pub fn pick_secret(rng: &mut dyn Rng) -> u32 {
+    rng.gen_range(1..=100)
+}
+
+// how a finished game went
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    // how many valid guesses it took
+    Won { guesses: u32 },
+    // the input ran out before the number was found
+    GaveUp,
+}
+
+/*
+The game loop from the book. Each time round:
+  - ask for a guess and read a line
+  - parse it into a u32. parse returns a Result, and matching on it lets us
+    skip anything that isn't a number with `continue` instead of crashing
+    like .expect() would
+  - compare it to the secret with cmp, which gives back an Ordering (Less,
+    Greater or Equal), and match on that
+*/
+pub fn play(secret_number: u32, input: &mut dyn Input, out: &mut dyn Write) -> io::Result<Outcome> {
+    writeln!(out, "Guess the number!")?;
+    let mut guesses = 0;
+
+    loop {
+        writeln!(out, "Please input your guess.")?;
+
+        let guess = match input.read_guess() {
+            Some(line) => line,
+            None => return Ok(Outcome::GaveUp),
+        };
+
+        // shadowing: guess is now the parsed number, not the String
+        let guess: u32 = match guess.trim().parse() {
+            Ok(num) => num,
+            Err(_) => continue,
+        };
+        guesses += 1;
+
+        writeln!(out, "You guessed: {guess}")?;
+
+        match guess.cmp(&secret_number) {
+            Ordering::Less => writeln!(out, "Too small!")?,
+            Ordering::Greater => writeln!(out, "Too big!")?,
+            Ordering::Equal => {
+                writeln!(out, "You win!")?;
+                return Ok(Outcome::Won { guesses });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use domain::XorShift;
+
+    // plays a whole game with `guesses`, returning the outcome and the output
+    fn play_with(secret: u32, guesses: &[&str]) -> (Outcome, String) {
+        let mut input = ScriptedInput::new(guesses);
+        let mut out: Vec<u8> = Vec::new();
+        let outcome = play(secret, &mut input, &mut out).unwrap();
+        (outcome, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn first_guess_wins() {
+        let (outcome, output) = play_with(42, &["42"]);
+        assert_eq!(outcome, Outcome::Won { guesses: 1 });
+        assert_eq!(
+            output,
+            "Guess the number!\nPlease input your guess.\nYou guessed: 42\nYou win!\n"
+        );
+    }
+
+    #[test]
+    fn too_small_then_too_big_then_win() {
+        let (outcome, output) = play_with(50, &["25", "75", "50"]);
+        assert_eq!(outcome, Outcome::Won { guesses: 3 });
+        let hints: Vec<&str> = output
+            .lines()
+            .filter(|line| line.starts_with("Too") || *line == "You win!")
+            .collect();
+        assert_eq!(hints, vec!["Too small!", "Too big!", "You win!"]);
+    }
+
+    #[test]
+    fn ignores_things_that_are_not_numbers() {
+        let (outcome, output) = play_with(7, &["seven", "", "-3", "  7  "]);
+        // only the 7 counted as a guess, and it's trimmed before parsing
+        assert_eq!(outcome, Outcome::Won { guesses: 1 });
+        assert!(!output.contains("You guessed: seven"));
+        assert_eq!(output.matches("Please input your guess.").count(), 4);
+    }
+
+    #[test]
+    fn running_out_of_input_gives_up() {
+        let (outcome, output) = play_with(99, &["1", "2"]);
+        assert_eq!(outcome, Outcome::GaveUp);
+        assert!(!output.contains("You win!"));
+
+        let (outcome, _) = play_with(1, &[]);
+        assert_eq!(outcome, Outcome::GaveUp);
+    }
+
+    #[test]
+    fn the_same_seed_picks_the_same_secret() {
+        let first = pick_secret(&mut XorShift::new(42));
+        assert_eq!(pick_secret(&mut XorShift::new(42)), first);
+        assert!((1..=100).contains(&first));
+
+        let mut rng = ThreadRandom::default();
+        for _ in 0..100 {
+            assert!((1..=100).contains(&pick_secret(&mut rng)));
+        }
+    }
+
+    #[test]
+    fn binary_search_always_wins_in_seven() {
+        // halving 1..=100 each time never needs more than 7 guesses
+        for secret in 1..=100 {
+            let (mut low, mut high) = (1, 100);
+            let mut script = Vec::new();
+            loop {
+                let guess = (low + high) / 2;
+                script.push(guess.to_string());
+                match guess.cmp(&secret) {
+                    Ordering::Less => low = guess + 1,
+                    Ordering::Greater => high = guess - 1,
+                    Ordering::Equal => break,
+                }
+            }
+            let script: Vec<&str> = script.iter().map(String::as_str).collect();
+            match play_with(secret, &script).0 {
+                Outcome::Won { guesses } => assert!(guesses <= 7, "{} took {}", secret, guesses),
+                Outcome::GaveUp => panic!("didn't find {}", secret),
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+// Chapter 2: Programming a Guessing Game
+
+/*
+The first real project in the book: the program picks a random number between
+1 and 100, the player guesses, and the program says whether the guess was too
+small or too big until they get it. It touches on a lot that later chapters
+explain properly: let, match, methods, external crates.
+
+The game loop itself is in lib.rs (so it can be tested), main just sets it up.
+
+With a difficulty it plays the version from levels.rs instead, and keeps
+score between games in chapter_2_stats.txt (see stats.rs to put it elsewhere):
+
+    cargo run              the book's game
+    cargo run -- hard      1 to 1000 in 10 guesses
+
+`--seed N` (anywhere in the arguments) picks the secret with a seeded
+generator instead, so the same N is always the same secret:
+
+    cargo run -- --seed 42 easy
+*/
+
+use chapter_2::levels::{self, Difficulty, LevelOutcome};
+use chapter_2::stats::{self, StatsStore};
+use chapter_2::{Outcome, StdinInput, ThreadRandom};
+use domain::prelude::*;
+use std::{env, io, process};
+
+fn main() {
+    let mut args: Vec<String> = env::args().collect();
+    let seed = domain::take_seed(&mut args).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        process::exit(1);
+    });
+    let mut rng: Box<dyn Rng> = match seed {
+        Some(seed) => Box::new(XorShift::new(seed)),
+        None => Box::new(ThreadRandom::default()),
+    };
+
+    if let Some(arg) = args.get(1) {
+        let level: Difficulty = arg.parse().unwrap_or_else(|err| {
+            eprintln!("{err}");
+            process::exit(1);
+        });
+        play_level(level, &mut *rng);
+        return;
+    }
+
+    // Generating a Secret Number
+
+    /*
+    Rust's standard library doesn't include random numbers, so this uses the
+    rand crate, added under [dependencies] in Cargo.toml. The book writes
+    `rand::thread_rng().gen_range(1..=100)`: thread_rng() is a generator local
+    to the current thread, seeded by the OS, and gen_range comes from rand's
+    Rng trait. 1..=100 is an inclusive range, so 100 itself can come up.
+
+    Here thread_rng() is wrapped in ThreadRandom (lib.rs) so it can be swapped
+    for the seeded one, and pick_secret does the gen_range.
+    */
+    let secret_number = chapter_2::pick_secret(&mut *rng);
+
+    match chapter_2::play(secret_number, &mut StdinInput, &mut io::stdout()) {
+        Ok(Outcome::Won { guesses }) => println!("Got it in {guesses} guesses."),
+        Ok(Outcome::GaveUp) => println!("Out of guesses, the number was {secret_number}."),
+        Err(e) => eprintln!("Couldn't write to stdout: {e}"),
+    }
+}
+
+fn play_level(level: Difficulty, rng: &mut dyn Rng) {
+    let secret_number = level.pick_secret(rng);
+
+    let outcome = match levels::play_level(level, secret_number, &mut StdinInput, &mut io::stdout())
+    {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            eprintln!("Couldn't write to stdout: {e}");
+            return;
+        }
+    };
+
+    // not being able to keep score shouldn't spoil the game, so these only warn
+    let store = StatsStore::new(stats::stats_path(env::var_os(stats::STATS_VAR)));
+    let mut stats = store.load().unwrap_or_else(|e| {
+        warn!("Couldn't read old stats, starting fresh: {e}");
+        Default::default()
+    });
+    if stats.record(level, &outcome) {
+        println!("New best on {level}!");
+    }
+    if let Err(e) = store.save(&stats) {
+        warn!("Couldn't save stats: {e}");
+    }
+
+    if outcome != LevelOutcome::GaveUp {
+        println!(
+            "{} wins in {} games, best on {level}: {}",
+            stats.wins,
+            stats.games,
+            stats
+                .best(level)
+                .map_or(String::from("-"), |best| best.to_string())
+        );
+    }
+}
@@ -0,0 +1,240 @@
+// Keeping Score Between Games
+
+/*
+Stats counts games, wins, and guesses, and remembers the best (fewest
+guesses) win for each difficulty. StatsStore saves them to a small text file,
+one `key=value` per line:
+
+    games=4
+    wins=3
+    attempts=17
+    best.easy=2
+    best.hard=9
+
+A missing file just means nobody has played yet, so load() gives back empty
+stats for it instead of an error. Lines it doesn't understand are skipped, so
+a hand edited or older file doesn't stop the game from starting.
+
+The file is chapter_2_stats.txt in the directory the game is run from, unless
+the CHAPTER_2_STATS environment variable names a different one:
+
+    CHAPTER_2_STATS=~/.guessing_stats cargo run -- hard
+
+The counters saturate instead of overflowing: a file that says attempts is
+already u32::MAX stays at u32::MAX, rather than panicking in a debug build (or
+wrapping round to a small number in a release one).
+*/
+
+use crate::levels::{Difficulty, LevelOutcome};
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Stats {
+    pub games: u32,
+    pub wins: u32,
+    // guesses used, across every game (won or not)
+    pub attempts: u32,
+    best: HashMap<Difficulty, u32>,
+}
+
+impl Stats {
+    // true if this game set a new best for its level
+    pub fn record(&mut self, level: Difficulty, outcome: &LevelOutcome) -> bool {
+        self.games = self.games.saturating_add(1);
+        match *outcome {
+            LevelOutcome::Won { attempts } => {
+                self.wins = self.wins.saturating_add(1);
+                self.attempts = self.attempts.saturating_add(attempts);
+                // only strictly fewer guesses is a new best, tying it isn't
+                match self.best.get(&level) {
+                    Some(&best) if attempts >= best => false,
+                    _ => {
+                        self.best.insert(level, attempts);
+                        true
+                    }
+                }
+            }
+            LevelOutcome::Lost => {
+                self.attempts = self.attempts.saturating_add(level.max_attempts());
+                false
+            }
+            LevelOutcome::GaveUp => false,
+        }
+    }
+
+    pub fn best(&self, level: Difficulty) -> Option<u32> {
+        self.best.get(&level).copied()
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = format!(
+            "games={}\nwins={}\nattempts={}\n",
+            self.games, self.wins, self.attempts
+        );
+        // ALL for a fixed order, a HashMap's order changes from run to run
+        for level in Difficulty::ALL {
+            if let Some(best) = self.best(level) {
+                text.push_str(&format!("best.{}={}\n", level, best));
+            }
+        }
+        text
+    }
+
+    pub fn from_text(text: &str) -> Stats {
+        let mut stats = Stats::default();
+        for line in text.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let Ok(value) = value.trim().parse::<u32>() else {
+                continue;
+            };
+            match key.trim() {
+                "games" => stats.games = value,
+                "wins" => stats.wins = value,
+                "attempts" => stats.attempts = value,
+                key => {
+                    if let Some(Ok(level)) = key.strip_prefix("best.").map(str::parse) {
+                        stats.best.insert(level, value);
+                    }
+                }
+            }
+        }
+        stats
+    }
+}
+
+pub const STATS_VAR: &str = "CHAPTER_2_STATS";
+const DEFAULT_FILE: &str = "chapter_2_stats.txt";
+
+// the value of STATS_VAR if it's set (main passes env::var_os(STATS_VAR)),
+// otherwise the default file, relative to the current directory
+pub fn stats_path(var: Option<OsString>) -> PathBuf {
+    match var {
+        Some(path) if !path.is_empty() => PathBuf::from(path),
+        _ => PathBuf::from(DEFAULT_FILE),
+    }
+}
+
+pub struct StatsStore {
+    path: PathBuf,
+}
+
+impl StatsStore {
+    pub fn new(path: impl AsRef<Path>) -> StatsStore {
+        StatsStore {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    pub fn load(&self) -> io::Result<Stats> {
+        match fs::read_to_string(&self.path) {
+            Ok(text) => Ok(Stats::from_text(&text)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Stats::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, stats: &Stats) -> io::Result<()> {
+        fs::write(&self.path, stats.to_text())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chapter_11::temp::TempDir;
+
+    #[test]
+    fn records_wins_losses_and_best() {
+        let mut stats = Stats::default();
+        assert!(stats.record(Difficulty::Easy, &LevelOutcome::Won { attempts: 4 }));
+        assert!(stats.record(Difficulty::Easy, &LevelOutcome::Won { attempts: 2 }));
+        assert!(!stats.record(Difficulty::Easy, &LevelOutcome::Won { attempts: 3 }));
+        // equalling the best isn't a new one
+        assert!(!stats.record(Difficulty::Easy, &LevelOutcome::Won { attempts: 2 }));
+        assert!(!stats.record(Difficulty::Hard, &LevelOutcome::Lost));
+        assert!(!stats.record(Difficulty::Normal, &LevelOutcome::GaveUp));
+
+        assert_eq!(stats.games, 6);
+        assert_eq!(stats.wins, 4);
+        assert_eq!(stats.attempts, 4 + 2 + 3 + 2 + 10);
+        assert_eq!(stats.best(Difficulty::Easy), Some(2));
+        assert_eq!(stats.best(Difficulty::Hard), None);
+    }
+
+    #[test]
+    fn counters_saturate_instead_of_overflowing() {
+        let mut stats =
+            Stats::from_text("games=4294967295\nwins=4294967295\nattempts=4294967290\n");
+        stats.record(Difficulty::Easy, &LevelOutcome::Won { attempts: 9 });
+        stats.record(Difficulty::Hard, &LevelOutcome::Lost);
+        assert_eq!(stats.games, u32::MAX);
+        assert_eq!(stats.wins, u32::MAX);
+        assert_eq!(stats.attempts, u32::MAX);
+    }
+
+    #[test]
+    fn text_round_trip() {
+        let mut stats = Stats::default();
+        stats.record(Difficulty::Hard, &LevelOutcome::Won { attempts: 9 });
+        stats.record(Difficulty::Easy, &LevelOutcome::Won { attempts: 2 });
+        let text = stats.to_text();
+        assert_eq!(
+            text,
+            "games=2\nwins=2\nattempts=11\nbest.easy=2\nbest.hard=9\n"
+        );
+        assert_eq!(Stats::from_text(&text), stats);
+    }
+
+    #[test]
+    fn junk_lines_are_skipped() {
+        let stats =
+            Stats::from_text("games=3\nnonsense\nwins=lots\nbest.extreme=1\nbest.normal=6\n");
+        assert_eq!(stats.games, 3);
+        assert_eq!(stats.wins, 0);
+        assert_eq!(stats.best(Difficulty::Normal), Some(6));
+    }
+
+    #[test]
+    fn missing_file_is_empty_stats() {
+        let dir = TempDir::new("chapter_2_stats_missing");
+        let store = StatsStore::new(dir.path().join("stats.txt"));
+        assert_eq!(store.load().unwrap(), Stats::default());
+    }
+
+    #[test]
+    fn saved_stats_load_back() {
+        let dir = TempDir::new("chapter_2_stats_saved");
+        let store = StatsStore::new(dir.path().join("stats.txt"));
+
+        let mut stats = store.load().unwrap();
+        stats.record(Difficulty::Normal, &LevelOutcome::Won { attempts: 6 });
+        store.save(&stats).unwrap();
+
+        // a later run starts from what was saved
+        let mut later = store.load().unwrap();
+        assert_eq!(later, stats);
+        later.record(Difficulty::Normal, &LevelOutcome::Won { attempts: 5 });
+        store.save(&later).unwrap();
+        assert_eq!(store.load().unwrap().best(Difficulty::Normal), Some(5));
+    }
+
+    #[test]
+    fn stats_path_from_the_environment() {
+        assert_eq!(stats_path(None), PathBuf::from("chapter_2_stats.txt"));
+        // set but empty is the same as not set
+        assert_eq!(
+            stats_path(Some(OsString::new())),
+            PathBuf::from("chapter_2_stats.txt")
+        );
+        assert_eq!(
+            stats_path(Some(OsString::from("/home/ferris/stats"))),
+            PathBuf::from("/home/ferris/stats")
+        );
+    }
+}
@@ -0,0 +1,69 @@
+// The Server's Routes
+
+/*
+Everything the server answers, as one Router. main.rs only passes requests in
+and writes responses out, so these are tested here by calling handle directly.
+*/
+
+use crate::router::{Response, Router};
+use domain::Rectangle;
+
+pub fn routes() -> Router {
+    Router::new()
+        .get("/", |_, _| Response::ok("Hello!"))
+        // /rect/30/50 -> the area of a 30x50 rectangle, computed by the
+        // shared Rectangle from the domain crate
+        .get("/rect/:width/:height", |_, params| {
+            match (params.parse("width"), params.parse("height")) {
+                (Some(width), Some(height)) => {
+                    let rect = Rectangle { width, height };
+                    Response::ok(format!("{} has an area of {}", rect, rect.area()))
+                }
+                _ => Response::new(400, "width and height have to be whole numbers"),
+            }
+        })
+        // /greet?name=Ferris, with a default when there's no name
+        .get("/greet", |req, _| {
+            let name = req.query_param("name").unwrap_or("stranger");
+            Response::ok(format!("Hello, {}!", name))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::router::Request;
+
+    fn get(target: &str) -> Response {
+        let line = format!("GET {} HTTP/1.1", target);
+        routes().handle(&Request::parse(&line).unwrap())
+    }
+
+    #[test]
+    fn home_page() {
+        assert_eq!(get("/"), Response::ok("Hello!"));
+    }
+
+    #[test]
+    fn rectangle_area() {
+        assert_eq!(
+            get("/rect/30/50").body,
+            "a 30x50 rectangle has an area of 1500"
+        );
+        assert_eq!(get("/rect/0/7").body, "a 0x7 rectangle has an area of 0");
+    }
+
+    #[test]
+    fn rectangle_needs_numbers() {
+        assert_eq!(get("/rect/wide/50").status, 400);
+        assert_eq!(get("/rect/-3/50").status, 400);
+        assert_eq!(get("/rect/30").status, 404);
+    }
+
+    #[test]
+    fn greeting_from_the_query_string() {
+        assert_eq!(get("/greet?name=Ferris").body, "Hello, Ferris!");
+        assert_eq!(get("/greet?name=Ann+Lee").body, "Hello, Ann Lee!");
+        assert_eq!(get("/greet").body, "Hello, stranger!");
+    }
+}
@@ -0,0 +1,6 @@
+// Chapter 20: Final Project: Building a Multithreaded Web Server (library)
+// main.rs accepts connections and writes responses, this lib holds the parts
+// that don't need a socket, so they can be tested on their own
+
+pub mod app;
+pub mod router;
@@ -0,0 +1,86 @@
+// Chapter 20: Final Project: Building a Multithreaded Web Server
+
+/*
+The plan for the web server:
+    1. learn a bit about TCP and HTTP
+    2. listen for TCP connections on a socket
+    3. parse a small number of HTTP requests
+    4. create a proper HTTP response
+    5. improve the throughput of our server with a thread pool
+
+TCP is the lower level protocol that describes how information gets from one
+server to another, without saying what that information is. HTTP builds on
+top of it by defining the contents of requests and responses. Both are
+request-response protocols: a client initiates requests and a server listens
+and responds.
+
+Instead of the book's if/else on the request line, requests go through the
+Router in router.rs, and the routes themselves are in app.rs. Try:
+
+    cargo run
+    curl http://127.0.0.1:7878/
+    curl http://127.0.0.1:7878/rect/30/50
+    curl "http://127.0.0.1:7878/greet?name=Ferris"
+*/
+
+use chapter_20::app;
+use chapter_20::router::{Request, Response, Router};
+use domain::prelude::*;
+use std::io::{prelude::*, BufReader, ErrorKind};
+use std::net::{TcpListener, TcpStream};
+use std::process;
+
+fn main() {
+    if let Err(e) = serve("127.0.0.1:7878") {
+        error!("The server couldn't start: {e}");
+        process::exit(1);
+    }
+}
+
+fn serve(address: &str) -> Result<(), ExerciseError> {
+    // bind works like new, returning a Result because binding can fail (the
+    // port is taken, or needs admin rights, ports below 1024 do). The book
+    // unwraps it, here it goes back to main to be reported
+    let listener = TcpListener::bind(address)?;
+    let router = app::routes();
+
+    // incoming() gives a stream per connection *attempt*, which is why each
+    // one is a Result. One failed connection shouldn't stop the server, so
+    // these errors are only printed
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                warn!("A connection failed: {e}");
+                continue;
+            }
+        };
+
+        if let Err(e) = handle_connection(stream, &router) {
+            warn!("Couldn't answer a request: {e}");
+        }
+    }
+    Ok(())
+}
+
+fn handle_connection(mut stream: TcpStream, router: &Router) -> Result<(), ExerciseError> {
+    let buf_reader = BufReader::new(&mut stream);
+    // only the request line matters to us: GET /path?query HTTP/1.1
+    let response = match buf_reader.lines().next() {
+        Some(Ok(line)) => match Request::parse(&line) {
+            Ok(request) => router.handle(&request),
+            Err(e) => Response::new(400, format!("{e}")),
+        },
+        // lines() wants UTF-8, and a request line that isn't is still a bad
+        // request the client should hear about
+        Some(Err(e)) if e.kind() == ErrorKind::InvalidData => {
+            Response::new(400, "the request line isn't valid UTF-8")
+        }
+        Some(Err(e)) => return Err(e.into()),
+        // the client hung up without sending anything, nobody to answer
+        None => return Ok(()),
+    };
+
+    stream.write_all(response.to_http().as_bytes())?;
+    Ok(())
+}
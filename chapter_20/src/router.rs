@@ -0,0 +1,471 @@
+// 20.1 (continued) Routing Requests
+
+/*
+The book's server picks a response with an if on the whole request line:
+
+    if request_line == "GET / HTTP/1.1" { ... } else { ... }
+
+which works for one page. The Router here matches each request against a list
+of routes instead. A route is a method, a path pattern, and a handler closure:
+
+    router.get("/rect/:width/:height", |_req, params| ...)
+
+Pattern segments starting with : capture whatever is in that position of the
+path, and the handler gets them by name in Params. Routes are tried in the
+order they were added, the first match wins.
+
+Nothing in here touches a socket: a Request comes from parsing the request
+line, and a Response knows how to turn itself into HTTP text. So the tests can
+build requests from strings and check what the router answers.
+*/
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Method {
+    Get,
+    Post,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    MissingPart(&'static str),
+    UnknownMethod(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::MissingPart(part) => write!(f, "request line has no {}", part),
+            ParseError::UnknownMethod(method) => write!(f, "unknown method {}", method),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl FromStr for Method {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Method, ParseError> {
+        match s {
+            "GET" => Ok(Method::Get),
+            "POST" => Ok(Method::Post),
+            other => Err(ParseError::UnknownMethod(other.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Request {
+    pub method: Method,
+    pub path: String,
+    pub query: HashMap<String, String>,
+}
+
+impl Request {
+    // parses a request line like "GET /greet?name=Ferris HTTP/1.1"
+    pub fn parse(request_line: &str) -> Result<Request, ParseError> {
+        let mut parts = request_line.split_whitespace();
+        let method = parts.next().ok_or(ParseError::MissingPart("method"))?;
+        let target = parts.next().ok_or(ParseError::MissingPart("path"))?;
+
+        let (path, query) = match target.split_once('?') {
+            Some((path, query)) => (path, parse_query(query)),
+            None => (target, HashMap::new()),
+        };
+
+        Ok(Request {
+            method: method.parse()?,
+            path: path.to_string(),
+            query,
+        })
+    }
+
+    pub fn query_param(&self, name: &str) -> Option<&str> {
+        self.query.get(name).map(String::as_str)
+    }
+}
+
+// "a=1&b=two+words&c=%21" -> {a: "1", b: "two words", c: "!"}. A key with no
+// = gets an empty value, and the last one wins if a key appears twice
+pub fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) => (decode_query(key), decode_query(value)),
+            None => (decode_query(pair), String::new()),
+        })
+        .collect()
+}
+
+/*
+URLs can't contain spaces or some punctuation, so browsers encode them: %XX
+is the byte with hex value XX. The bytes are collected first and turned into
+a String at the end, since one character can be several %XX bytes in UTF-8
+(é is %C3%A9). A % that isn't followed by two hex digits is left as it is.
+
+Query strings (and only query strings, it comes from HTML forms) also use +
+for a space. In a path a + is just a +, so /files/c++ stays c++. A real +
+in a query is written %2B, which is why the + swap happens before the %XX
+decoding and not after.
+*/
+fn decode_query(text: &str) -> String {
+    decode(&text.replace('+', " "))
+}
+
+fn decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => match bytes.get(i + 1..i + 3).and_then(hex_byte) {
+                Some(byte) => {
+                    decoded.push(byte);
+                    i += 2;
+                }
+                None => decoded.push(b'%'),
+            },
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn hex_byte(digits: &[u8]) -> Option<u8> {
+    let text = std::str::from_utf8(digits).ok()?;
+    u8::from_str_radix(text, 16).ok()
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    pub fn new(status: u16, body: impl Into<String>) -> Response {
+        Response {
+            status,
+            body: body.into(),
+        }
+    }
+
+    pub fn ok(body: impl Into<String>) -> Response {
+        Response::new(200, body)
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "BAD REQUEST",
+            404 => "NOT FOUND",
+            405 => "METHOD NOT ALLOWED",
+            _ => "UNKNOWN",
+        }
+    }
+
+    // the same format as the book's responses: status line, a Content-Length
+    // header so the client knows where the body ends, a blank line, the body
+    pub fn to_http(&self) -> String {
+        format!(
+            "HTTP/1.1 {} {}\r\nContent-Length: {}\r\n\r\n{}",
+            self.status,
+            self.reason(),
+            self.body.len(),
+            self.body
+        )
+    }
+}
+
+// the :name segments a route captured
+#[derive(Debug, Default, PartialEq)]
+pub struct Params {
+    values: HashMap<String, String>,
+}
+
+impl Params {
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(String::as_str)
+    }
+
+    // the captured text parsed into any FromStr type, None if it's missing or
+    // doesn't parse
+    pub fn parse<T: FromStr>(&self, name: &str) -> Option<T> {
+        self.get(name)?.parse().ok()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Segment {
+    Literal(String),
+    Param(String),
+}
+
+// Send + Sync so a Router could be shared between the threads of a pool
+type Handler = Box<dyn Fn(&Request, &Params) -> Response + Send + Sync>;
+
+struct Route {
+    method: Method,
+    segments: Vec<Segment>,
+    handler: Handler,
+}
+
+// "/rect/:w/:h" -> [Literal("rect"), Param("w"), Param("h")]. Empty segments
+// are dropped, so a trailing slash doesn't matter
+fn segments(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/').filter(|segment| !segment.is_empty())
+}
+
+impl Route {
+    fn new(method: Method, pattern: &str, handler: Handler) -> Route {
+        let segments = segments(pattern)
+            .map(|segment| match segment.strip_prefix(':') {
+                Some(name) => Segment::Param(name.to_string()),
+                None => Segment::Literal(segment.to_string()),
+            })
+            .collect();
+        Route {
+            method,
+            segments,
+            handler,
+        }
+    }
+
+    // the captured params if the path fits this route's pattern
+    fn match_path(&self, path: &str) -> Option<Params> {
+        let parts: Vec<&str> = segments(path).collect();
+        if parts.len() != self.segments.len() {
+            return None;
+        }
+        let mut params = Params::default();
+        for (segment, part) in self.segments.iter().zip(parts) {
+            match segment {
+                Segment::Literal(literal) if literal == part => {}
+                Segment::Literal(_) => return None,
+                Segment::Param(name) => {
+                    params.values.insert(name.clone(), decode(part));
+                }
+            }
+        }
+        Some(params)
+    }
+}
+
+#[derive(Default)]
+pub struct Router {
+    routes: Vec<Route>,
+}
+
+impl Router {
+    pub fn new() -> Router {
+        Router::default()
+    }
+
+    // takes and returns self so routes can be chained onto Router::new()
+    pub fn route<F>(mut self, method: Method, pattern: &str, handler: F) -> Router
+    where
+        F: Fn(&Request, &Params) -> Response + Send + Sync + 'static,
+    {
+        self.routes
+            .push(Route::new(method, pattern, Box::new(handler)));
+        self
+    }
+
+    pub fn get<F>(self, pattern: &str, handler: F) -> Router
+    where
+        F: Fn(&Request, &Params) -> Response + Send + Sync + 'static,
+    {
+        self.route(Method::Get, pattern, handler)
+    }
+
+    pub fn post<F>(self, pattern: &str, handler: F) -> Router
+    where
+        F: Fn(&Request, &Params) -> Response + Send + Sync + 'static,
+    {
+        self.route(Method::Post, pattern, handler)
+    }
+
+    // 404 if no route has this path, 405 if one does but not for this method
+    pub fn handle(&self, request: &Request) -> Response {
+        let mut path_matched = false;
+        for route in self.routes.iter() {
+            if let Some(params) = route.match_path(&request.path) {
+                if route.method == request.method {
+                    return (route.handler)(request, &params);
+                }
+                path_matched = true;
+            }
+        }
+        if path_matched {
+            Response::new(405, "method not allowed")
+        } else {
+            Response::new(404, format!("nothing at {}", request.path))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(line: &str) -> Request {
+        Request::parse(line).unwrap()
+    }
+
+    fn router() -> Router {
+        Router::new()
+            .get("/", |_, _| Response::ok("home"))
+            .get("/users/:id", |_, params| {
+                Response::ok(format!("user {}", params.get("id").unwrap()))
+            })
+            .get("/users/new", |_, _| Response::ok("never reached"))
+            .post("/users", |_, _| Response::ok("created"))
+            .get("/add/:a/:b", |_, params| {
+                match (params.parse::<i32>("a"), params.parse::<i32>("b")) {
+                    (Some(a), Some(b)) => Response::ok((a + b).to_string()),
+                    _ => Response::new(400, "not numbers"),
+                }
+            })
+            .get("/echo", |req, _| {
+                Response::ok(req.query_param("text").unwrap_or("").to_string())
+            })
+    }
+
+    #[test]
+    fn parses_request_lines() {
+        let req = request("GET /greet?name=Ferris&loud HTTP/1.1");
+        assert_eq!(req.method, Method::Get);
+        assert_eq!(req.path, "/greet");
+        assert_eq!(req.query_param("name"), Some("Ferris"));
+        assert_eq!(req.query_param("loud"), Some(""));
+        assert_eq!(req.query_param("missing"), None);
+
+        assert_eq!(request("POST /users HTTP/1.1").method, Method::Post);
+    }
+
+    #[test]
+    fn bad_request_lines() {
+        assert_eq!(Request::parse(""), Err(ParseError::MissingPart("method")));
+        assert_eq!(Request::parse("GET"), Err(ParseError::MissingPart("path")));
+        assert_eq!(
+            Request::parse("BREW /pot HTTP/1.1"),
+            Err(ParseError::UnknownMethod(String::from("BREW")))
+        );
+        assert_eq!(
+            ParseError::UnknownMethod(String::from("BREW")).to_string(),
+            "unknown method BREW"
+        );
+    }
+
+    #[test]
+    fn query_strings_are_decoded() {
+        let query = parse_query("text=two+words&bang=%21&accent=caf%C3%A9&bad=%zz&a=1&a=2");
+        assert_eq!(query["text"], "two words");
+        assert_eq!(query["bang"], "!");
+        assert_eq!(query["accent"], "café");
+        // not a valid escape, kept as it was
+        assert_eq!(query["bad"], "%zz");
+        assert_eq!(query["a"], "2");
+        assert_eq!(parse_query("sum=1%2B1")["sum"], "1+1");
+        assert!(parse_query("").is_empty());
+        assert_eq!(decode("100%"), "100%");
+    }
+
+    #[test]
+    fn routes_by_path_and_method() {
+        let router = router();
+        assert_eq!(
+            router.handle(&request("GET / HTTP/1.1")),
+            Response::ok("home")
+        );
+        assert_eq!(
+            router.handle(&request("POST /users HTTP/1.1")),
+            Response::ok("created")
+        );
+        assert_eq!(
+            router.handle(&request("GET /echo?text=hi+there HTTP/1.1")),
+            Response::ok("hi there")
+        );
+    }
+
+    #[test]
+    fn captures_path_params() {
+        let router = router();
+        assert_eq!(
+            router.handle(&request("GET /users/42 HTTP/1.1")).body,
+            "user 42"
+        );
+        // percent encoding in a path segment is decoded too
+        assert_eq!(
+            router
+                .handle(&request("GET /users/ann%20lee HTTP/1.1"))
+                .body,
+            "user ann lee"
+        );
+        // but + is only a space in query strings
+        assert_eq!(
+            router.handle(&request("GET /users/c++ HTTP/1.1")).body,
+            "user c++"
+        );
+        assert_eq!(router.handle(&request("GET /add/2/40 HTTP/1.1")).body, "42");
+        assert_eq!(router.handle(&request("GET /add/2/x HTTP/1.1")).status, 400);
+    }
+
+    #[test]
+    fn first_matching_route_wins() {
+        // /users/:id was added before /users/new, so it catches "new" too
+        assert_eq!(
+            router().handle(&request("GET /users/new HTTP/1.1")).body,
+            "user new"
+        );
+    }
+
+    #[test]
+    fn trailing_slashes_do_not_matter() {
+        let router = router();
+        assert_eq!(
+            router.handle(&request("GET /users/7/ HTTP/1.1")).body,
+            "user 7"
+        );
+        assert_eq!(router.handle(&request("GET // HTTP/1.1")).body, "home");
+    }
+
+    #[test]
+    fn not_found_and_wrong_method() {
+        let router = router();
+        let missing = router.handle(&request("GET /nope HTTP/1.1"));
+        assert_eq!(missing.status, 404);
+        assert_eq!(missing.body, "nothing at /nope");
+        // the path is right, the method isn't
+        assert_eq!(router.handle(&request("GET /users HTTP/1.1")).status, 405);
+        assert_eq!(router.handle(&request("POST / HTTP/1.1")).status, 405);
+        // /users/42/posts has one segment too many for /users/:id
+        assert_eq!(
+            router
+                .handle(&request("GET /users/42/posts HTTP/1.1"))
+                .status,
+            404
+        );
+    }
+
+    #[test]
+    fn http_response_text() {
+        assert_eq!(
+            Response::ok("hello").to_http(),
+            "HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nhello"
+        );
+        assert_eq!(
+            Response::new(404, "").to_http(),
+            "HTTP/1.1 404 NOT FOUND\r\nContent-Length: 0\r\n\r\n"
+        );
+        // the length is in bytes, not chars
+        assert!(Response::ok("é").to_http().contains("Content-Length: 2"));
+    }
+}
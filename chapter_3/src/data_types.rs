@@ -1,7 +1,9 @@
-use std::io;
+use domain::parse_input;
+use domain::prelude::*;
+use std::io::{self, BufRead};
 // 3.2 Data Types
 
-pub fn _run() {
+pub fn run() -> Result<(), ExerciseError> {
     // Scalar Type: represents a single value.
     // 4 primary types: integers, floating-points, booleans, characters
 
@@ -104,13 +106,60 @@ pub fn _run() {
     // accessing non-existent indexes of an array and potentially undesired
     // parts of memory
 
+    /*
+    the book's version reads the index and goes straight to array[index]:
+
+        let index: usize = index.trim().parse().expect("Index entered was not a number");
+        let element = array[index];
+
+    an index > 4 panics with "index out of bounds", and so does typing
+    anything that isn't a number. Here read_index and element_at return an
+    ExerciseError instead, and `?` hands it back to main to report
+    */
     let array = [1, 2, 3, 4, 5];
-    println!("Please enter an index. (indexes > 4 will cause a panic...)");
-    let mut index = String::new();
-    io::stdin()
-        .read_line(&mut index)
-        .expect("Failed to read line");
-    let index: usize = index.trim().parse().expect("Index entered was not a number");
-    let element = array[index];
+    println!("Please enter an index.");
+    let index = read_index(&mut io::stdin().lock())?;
+    let element = element_at(&array, index)?;
     println!("the value of the element at index {index} is: {element}");
+    Ok(())
+}
+
+// one line from `input`, as an index
+pub fn read_index(input: &mut impl BufRead) -> Result<usize, ExerciseError> {
+    let mut index = String::new();
+    input.read_line(&mut index)?;
+    parse_input(&index, "an index (a whole number, 0 or more)")
+}
+
+// get() is the checked version of [], it gives back None instead of panicking
+pub fn element_at(array: &[i32], index: usize) -> Result<i32, ExerciseError> {
+    array.get(index).copied().ok_or(ExerciseError::OutOfBounds {
+        index,
+        len: array.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_an_index() {
+        assert_eq!(read_index(&mut "3\n".as_bytes()).unwrap(), 3);
+        let err = read_index(&mut "three\n".as_bytes()).unwrap_err();
+        assert!(matches!(err, ExerciseError::BadInput { .. }), "{err}");
+        // nothing typed at all
+        assert!(read_index(&mut "".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn elements_past_the_end_are_an_error() {
+        let array = [1, 2, 3, 4, 5];
+        assert_eq!(element_at(&array, 4).unwrap(), 5);
+        let err = element_at(&array, 5).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "index 5 is out of bounds, there are only 5 elements"
+        );
+    }
 }
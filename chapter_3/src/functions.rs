@@ -1,6 +1,6 @@
 // 3.3 Functions
 
-pub fn _run() {
+pub fn run() {
     // naming conventions for functions and variables is snake_case.
     println!("Hello from functions.rs");
 
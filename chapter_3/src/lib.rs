@@ -0,0 +1,43 @@
+// Chapter 3: Common Programming Concepts (library)
+// The walkthroughs for variables, data types, functions and control flow.
+// main.rs calls whichever one it's on, exercises() has the rest for the runner
+
+pub mod control_flow;
+pub mod data_types;
+pub mod functions;
+pub mod variables;
+
+use domain::Section;
+
+// every section, in the book's order. The runner (and anything else that wants
+// them all) goes through this list. data_types reads an index from stdin, and
+// a bad one is the ExerciseError its run returns, so it goes in as it is
+pub fn exercises() -> Vec<Section> {
+    vec![
+        Section {
+            name: "variables",
+            run: || {
+                variables::run();
+                Ok(())
+            },
+        },
+        Section {
+            name: "data_types",
+            run: data_types::run,
+        },
+        Section {
+            name: "functions",
+            run: || {
+                functions::run();
+                Ok(())
+            },
+        },
+        Section {
+            name: "control_flow",
+            run: || {
+                control_flow::run();
+                Ok(())
+            },
+        },
+    ]
+}
@@ -1,12 +1,12 @@
-mod control_flow;
-mod data_types;
-mod functions;
-mod variables;
 // Chapter 3: Common Programming Concepts
 
 fn main() {
-    // variables::run();
-    // data_types::run();
-    // functions::run();
-    control_flow::run();
+    // chapter_3::variables::run();
+    // data_types reads an index from stdin, a bad one comes back as an error
+    // instead of a panic:
+    // if let Err(e) = chapter_3::data_types::run() {
+    //     eprintln!("data_types: {e}");
+    // }
+    // chapter_3::functions::run();
+    chapter_3::control_flow::run();
 }
@@ -1,6 +1,6 @@
 // 3.1 Variables and Mutability
 
-pub fn _run() {
+pub fn run() {
   /*
   this block of code doesn't compile because variables are immutable by default and so cannot be reassigned
   let x = 5;
@@ -0,0 +1,37 @@
+// Chapter 4: Understanding Ownership (library)
+// The walkthroughs for ownership and moves, references and borrowing, and
+// slices. main.rs has them all commented out, the runner uses exercises()
+
+pub mod ownership;
+pub mod references_borrowing;
+pub mod slice;
+
+use domain::Section;
+
+// every section that runs without any input, in the book's order. The runner
+// (and anything else that wants them all) goes through this list
+pub fn exercises() -> Vec<Section> {
+    vec![
+        Section {
+            name: "ownership",
+            run: || {
+                ownership::run();
+                Ok(())
+            },
+        },
+        Section {
+            name: "references_borrowing",
+            run: || {
+                references_borrowing::run();
+                Ok(())
+            },
+        },
+        Section {
+            name: "slice",
+            run: || {
+                slice::run();
+                Ok(())
+            },
+        },
+    ]
+}
@@ -1,9 +1,7 @@
-mod ownership;
-mod references_borrowing;
-mod slice;
 // Chapter 4: Understanding Ownership
+
 fn main() {
-    // ownership::run();
-    // references_borrowing::run();
-    // slice::run();
+    // chapter_4::ownership::run();
+    // chapter_4::references_borrowing::run();
+    // chapter_4::slice::run();
 }
@@ -0,0 +1,305 @@
+// 5.3 (continued) Rectangles That Know Where They Are
+
+/*
+A Rectangle only has a size. To ask whether two of them bump into each other
+they also need a position, so PlacedRect wraps one (a struct can hold another
+struct, 5.1) together with the x and y of its top left corner. Like on a
+screen, x grows to the right and y grows downwards.
+
+The edges are worked out in i64: x is an i32 and width is a u32, and
+i32::MAX + u32::MAX doesn't fit in either of them.
+
+Edges are treated like the ranges in 3.5, start included and end excluded: a
+rectangle at x 0 with width 10 covers the columns 0 up to (not including) 10.
+So two rectangles side by side, one ending at 10 and the next starting at 10,
+don't overlap, they touch.
+
+A rectangle with a width or height of 0 is allowed. It covers no area at all,
+so it can never overlap anything or contain a point, but it can still touch.
+*/
+
+use domain::prelude::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct PlacedRect {
+    pub x: i32,
+    pub y: i32,
+    pub rect: Rectangle,
+}
+
+pub fn run() {
+    let player = PlacedRect::new(0, 0, 10, 10);
+    let wall = PlacedRect::new(8, 2, 20, 5);
+    let floor = PlacedRect::new(0, 10, 30, 2);
+
+    println!("player overlaps the wall? {}", player.overlaps(&wall));
+    println!("player touches the floor? {}", player.touches_edge(&floor));
+    println!(
+        "is (9, 3) inside the player? {}",
+        player.contains_point(9, 3)
+    );
+    if let Some((dx, dy)) = player.minimum_translation_vector(&wall) {
+        println!("to get out of the wall, move the player by ({dx}, {dy})");
+    }
+}
+
+impl PlacedRect {
+    pub fn new(x: i32, y: i32, width: u32, height: u32) -> PlacedRect {
+        PlacedRect {
+            x,
+            y,
+            rect: Rectangle { width, height },
+        }
+    }
+
+    pub fn left(&self) -> i64 {
+        self.x as i64
+    }
+
+    pub fn right(&self) -> i64 {
+        self.x as i64 + self.rect.width as i64
+    }
+
+    pub fn top(&self) -> i64 {
+        self.y as i64
+    }
+
+    pub fn bottom(&self) -> i64 {
+        self.y as i64 + self.rect.height as i64
+    }
+
+    // how much the two share along each axis: more than 0 is an overlap, 0
+    // means the edges line up exactly, less than 0 is a gap between them
+    fn shared_width(&self, other: &PlacedRect) -> i64 {
+        self.right().min(other.right()) - self.left().max(other.left())
+    }
+
+    fn shared_height(&self, other: &PlacedRect) -> i64 {
+        self.bottom().min(other.bottom()) - self.top().max(other.top())
+    }
+
+    // true if there's some area inside both. Edges meeting isn't enough, and
+    // a rectangle with no width or height has no area to share
+    pub fn overlaps(&self, other: &PlacedRect) -> bool {
+        self.shared_width(other) > 0 && self.shared_height(other) > 0
+    }
+
+    // the left and top edges count as inside, the right and bottom don't
+    pub fn contains_point(&self, x: i32, y: i32) -> bool {
+        let (x, y) = (x as i64, y as i64);
+        self.left() <= x && x < self.right() && self.top() <= y && y < self.bottom()
+    }
+
+    // true if the two meet without overlapping: everything they have in common
+    // is on the edges of both. That includes only meeting at a corner. A zero
+    // size rectangle sitting inside a bigger one doesn't touch it, it's not on
+    // any of the bigger one's edges
+    pub fn touches_edge(&self, other: &PlacedRect) -> bool {
+        let (width, height) = (self.shared_width(other), self.shared_height(other));
+        if width < 0 || height < 0 {
+            return false;
+        }
+        // what they have in common is a rectangle too, starting at (x, y). It's
+        // on one of r's edges if it has no width and lines up with r's left or
+        // right, or has no height and lines up with r's top or bottom
+        let x = self.left().max(other.left());
+        let y = self.top().max(other.top());
+        let on_an_edge_of = |r: &PlacedRect| {
+            (width == 0 && (x == r.left() || x == r.right()))
+                || (height == 0 && (y == r.top() || y == r.bottom()))
+        };
+        on_an_edge_of(self) && on_an_edge_of(other)
+    }
+
+    // The shortest move (dx, dy) that takes self out of other, leaving them
+    // touching. There are only four candidates, pushing self out through each
+    // side of other, and the shortest wins (the first of them on a tie, in the
+    // order left, right, up, down). None if they don't overlap to begin with
+    pub fn minimum_translation_vector(&self, other: &PlacedRect) -> Option<(i64, i64)> {
+        if !self.overlaps(other) {
+            return None;
+        }
+        let moves = [
+            (other.left() - self.right(), 0),
+            (other.right() - self.left(), 0),
+            (0, other.top() - self.bottom()),
+            (0, other.bottom() - self.top()),
+        ];
+        moves.into_iter().min_by_key(|(dx, dy)| dx.abs() + dy.abs())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // every rectangle with a corner in 0..4 and sides of 0..3, 144 of them
+    fn every_small_rect() -> Vec<PlacedRect> {
+        let mut rects = Vec::new();
+        for x in 0..4 {
+            for y in 0..4 {
+                for width in 0..3 {
+                    for height in 0..3 {
+                        rects.push(PlacedRect::new(x, y, width, height));
+                    }
+                }
+            }
+        }
+        rects
+    }
+
+    // The brute force versions check points instead of doing arithmetic on
+    // edges. Coordinates are doubled so the points halfway between whole
+    // numbers can be checked too: (2x+1)/2 is the middle of the unit square
+    // starting at x
+    fn in_closed(r: &PlacedRect, px: i64, py: i64) -> bool {
+        2 * r.left() <= px && px <= 2 * r.right() && 2 * r.top() <= py && py <= 2 * r.bottom()
+    }
+
+    fn in_interior(r: &PlacedRect, px: i64, py: i64) -> bool {
+        2 * r.left() < px && px < 2 * r.right() && 2 * r.top() < py && py < 2 * r.bottom()
+    }
+
+    fn doubled_points() -> impl Iterator<Item = (i64, i64)> {
+        (-2..=16).flat_map(|px| (-2..=16).map(move |py| (px, py)))
+    }
+
+    fn moved(r: &PlacedRect, (dx, dy): (i64, i64)) -> PlacedRect {
+        PlacedRect {
+            x: (r.x as i64 + dx) as i32,
+            y: (r.y as i64 + dy) as i32,
+            rect: r.rect,
+        }
+    }
+
+    #[test]
+    fn examples() {
+        let a = PlacedRect::new(0, 0, 10, 10);
+        assert!(a.overlaps(&PlacedRect::new(5, 5, 10, 10)));
+        assert!(!a.overlaps(&PlacedRect::new(10, 0, 10, 10)));
+        assert!(a.touches_edge(&PlacedRect::new(10, 0, 10, 10)));
+        assert!(a.touches_edge(&PlacedRect::new(10, 10, 1, 1)));
+        assert!(!a.touches_edge(&PlacedRect::new(11, 0, 10, 10)));
+        assert!(a.contains_point(0, 0));
+        assert!(a.contains_point(9, 9));
+        assert!(!a.contains_point(10, 5));
+        assert_eq!(
+            a.minimum_translation_vector(&PlacedRect::new(8, -5, 20, 20)),
+            Some((-2, 0))
+        );
+    }
+
+    #[test]
+    fn zero_size_rectangles() {
+        let big = PlacedRect::new(0, 0, 10, 10);
+        let dot = PlacedRect::new(5, 5, 0, 0);
+        let line = PlacedRect::new(3, 0, 0, 10);
+        for empty in [dot, line] {
+            assert!(!empty.overlaps(&big));
+            assert!(!big.overlaps(&empty));
+            assert!(!empty.overlaps(&empty));
+            assert_eq!(empty.minimum_translation_vector(&big), None);
+        }
+        assert!(!dot.contains_point(5, 5));
+        // inside the big one isn't on its edge...
+        assert!(!dot.touches_edge(&big));
+        assert!(!line.touches_edge(&big));
+        // ...but on the edge is
+        assert!(PlacedRect::new(10, 5, 0, 0).touches_edge(&big));
+        assert!(PlacedRect::new(0, 0, 0, 10).touches_edge(&big));
+        // a dot touches itself, it's all edge
+        assert!(dot.touches_edge(&dot));
+    }
+
+    #[test]
+    fn edges_far_from_zero() {
+        let far = PlacedRect::new(i32::MAX, i32::MAX, u32::MAX, u32::MAX);
+        assert_eq!(far.right(), i32::MAX as i64 + u32::MAX as i64);
+        assert!(far.overlaps(&far));
+        assert!(far.contains_point(i32::MAX, i32::MAX));
+        let left = PlacedRect::new(i32::MIN, 0, u32::MAX, 1);
+        assert!(!left.overlaps(&far));
+    }
+
+    #[test]
+    fn overlaps_matches_brute_force() {
+        let rects = every_small_rect();
+        for a in &rects {
+            for b in &rects {
+                let expected = doubled_points()
+                    .any(|(px, py)| in_interior(a, px, py) && in_interior(b, px, py));
+                assert_eq!(a.overlaps(b), expected, "{:?} {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn touches_edge_matches_brute_force() {
+        let rects = every_small_rect();
+        for a in &rects {
+            for b in &rects {
+                let shared: Vec<(i64, i64)> = doubled_points()
+                    .filter(|&(px, py)| in_closed(a, px, py) && in_closed(b, px, py))
+                    .collect();
+                let expected = !shared.is_empty()
+                    && shared
+                        .iter()
+                        .all(|&(px, py)| !in_interior(a, px, py) && !in_interior(b, px, py));
+                assert_eq!(a.touches_edge(b), expected, "{:?} {:?}", a, b);
+                // touching and overlapping never both happen
+                assert!(!(a.touches_edge(b) && a.overlaps(b)));
+            }
+        }
+    }
+
+    #[test]
+    fn contains_point_matches_brute_force() {
+        for r in every_small_rect() {
+            for x in -1..8 {
+                for y in -1..8 {
+                    // (x, y) is inside when the unit square starting there is
+                    let expected = in_interior(&r, 2 * x as i64 + 1, 2 * y as i64 + 1);
+                    assert_eq!(r.contains_point(x, y), expected, "{:?} ({}, {})", r, x, y);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn minimum_translation_vector_matches_brute_force() {
+        let rects = every_small_rect();
+        for a in &rects {
+            for b in &rects {
+                let Some(mtv) = a.minimum_translation_vector(b) else {
+                    assert!(!a.overlaps(b));
+                    continue;
+                };
+                // it's along one axis, and moving by it separates them
+                assert!(mtv.0 == 0 || mtv.1 == 0, "{:?}", mtv);
+                let after = moved(a, mtv);
+                assert!(!after.overlaps(b), "{:?} {:?} {:?}", a, b, mtv);
+                assert!(after.touches_edge(b), "{:?} {:?} {:?}", a, b, mtv);
+                // and no shorter move along one axis would do
+                let shortest = (1..=8)
+                    .find(|&distance| {
+                        [(-distance, 0), (distance, 0), (0, -distance), (0, distance)]
+                            .iter()
+                            .any(|&step| !moved(a, step).overlaps(b))
+                    })
+                    .unwrap();
+                assert_eq!(mtv.0.abs() + mtv.1.abs(), shortest, "{:?} {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn overlapping_and_touching_are_symmetric() {
+        let rects = every_small_rect();
+        for a in &rects {
+            for b in &rects {
+                assert_eq!(a.overlaps(b), b.overlaps(a));
+                assert_eq!(a.touches_edge(b), b.touches_edge(a));
+            }
+        }
+    }
+}
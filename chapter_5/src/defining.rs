@@ -2,7 +2,7 @@
 // Structs are like tuples where you name the values (they're objects)
 // properties of a struct are called "fields"
 
-pub fn _run() {
+pub fn run() {
     //defined using the "struct" keyword and provide the field names and types
     struct User {
         active: bool,
@@ -80,8 +80,11 @@ pub fn _run() {
 
     // Using Tuple Structs without Named Fields to Create Different Types
     // You can create structs that look similar to tuples
-    // example:
+    // example (nothing reads the fields here, so the dead code lint is
+    // switched off for these two):
+    #[allow(dead_code)]
     struct Color (i32, i32, i32);
+    #[allow(dead_code)]
     struct Point (i32, i32, i32);
 
     let _black = Color(0, 0, 0,);
@@ -0,0 +1,45 @@
+// Chapter 5: Using Structs to Structure Related Data (library)
+// The walkthroughs that build a struct up from nothing, the Rectangle example
+// program and its methods, and collision.rs's placed rectangles
+
+pub mod collision;
+pub mod defining;
+pub mod example_program;
+pub mod method_syntax;
+
+use domain::Section;
+
+// every section that runs without any input, in the book's order. The runner
+// (and anything else that wants them all) goes through this list
+pub fn exercises() -> Vec<Section> {
+    vec![
+        Section {
+            name: "defining",
+            run: || {
+                defining::run();
+                Ok(())
+            },
+        },
+        Section {
+            name: "example_program",
+            run: || {
+                example_program::run();
+                Ok(())
+            },
+        },
+        Section {
+            name: "method_syntax",
+            run: || {
+                method_syntax::run();
+                Ok(())
+            },
+        },
+        Section {
+            name: "collision",
+            run: || {
+                collision::run();
+                Ok(())
+            },
+        },
+    ]
+}
@@ -1,10 +1,8 @@
-mod defining;
-mod example_program;
-mod method_syntax;
 // Chapter 5: Using Structs to Structure Related Data
 
 fn main() {
-    // defining::run();
-    // example_program::run();
-    method_syntax::run();
+    // chapter_5::defining::run();
+    // chapter_5::example_program::run();
+    chapter_5::method_syntax::run();
+    chapter_5::collision::run();
 }
@@ -1,7 +1,7 @@
 // 6.1 Defining Enums
 // a way of saying a value is one of a possible set of values
 
-pub fn _run() {
+pub fn run() {
     // an example using IP Address types (4 or 6)
     enum IpAddrKind { // notice the lack of snake case here
         V4,
@@ -33,7 +33,10 @@ pub fn _run() {
     // this looks ok, however, we can represent the same concept, more concisely,
     // using just an enum. Rather than an enum inside a struct, we can put the data
     // directly into each enum variant. This new definition lets us know that each
-    // variant will have associated String values
+    // variant will have associated String values. Nothing reads them back
+    // out yet (that's what match is for, in 6.2), so the dead code lint is
+    // switched off for the enums below
+    #[allow(dead_code)]
     enum IpAddr {
         V4(String),
         V6(String),
@@ -46,6 +49,7 @@ pub fn _run() {
 
     // Another advantage of using an enum rather than a struct is the ability to
     // give each variant unique types
+    #[allow(dead_code)]
     enum IpAddr2 {
         V4(u8, u8, u8, u8),
         V6(String),
@@ -76,6 +80,7 @@ pub fn _run() {
     */
 
     // another example with a variety of types
+    #[allow(dead_code)]
     enum Message {
         _Quit,
         _Move { x: i32, y: i32 },
@@ -0,0 +1,354 @@
+// 6.2 (continued) A Dice Game
+
+/*
+match_flow.rs ends with a dice roll: 3 adds a fancy hat, 7 takes it away, and
+anything else goes to a catch-all arm. This grows that into a small game:
+
+  - every turn a player rolls some six sided dice (with an Rng from the domain
+    crate, so a seeded one plays the same game every time)
+  - the dice are scored with match arms:
+        a straight (3 or more dice in a row, like 2 3 4)   20 points
+        pairs                                               5 points each
+        anything else                                       0 points
+  - the total of the dice is matched like the book's dice_roll: 3 puts a fancy
+    hat on, 7 takes it off. A player wearing a hat scores double
+  - the scoreboard is a HashMap from player name to their score (8.3)
+*/
+
+use domain::prelude::*;
+use std::collections::HashMap;
+
+pub fn run(rng: &mut dyn Rng) {
+    let mut game = Game::new(&["ann", "bob"], 3);
+    for _round in 1..=3 {
+        for name in ["ann", "bob"] {
+            let turn = game.take_turn(name, rng).unwrap();
+            println!(
+                "{name} rolled {:?}: {:?} for {} points{}",
+                turn.dice,
+                turn.score.hand,
+                turn.points,
+                match turn.score.hat {
+                    HatChange::PutOn => ", and put on a fancy hat",
+                    HatChange::TakeOff => ", and lost their fancy hat",
+                    HatChange::Keep => "",
+                }
+            );
+        }
+    }
+    for (name, score) in game.scoreboard() {
+        let hat = if score.wearing_hat {
+            " (in a fancy hat)"
+        } else {
+            ""
+        };
+        println!("{name}: {} points{hat}", score.points);
+    }
+}
+
+pub fn roll_dice(count: usize, rng: &mut dyn Rng) -> Vec<u8> {
+    (0..count).map(|_| rng.roll(6)).collect()
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Hand {
+    Straight,
+    Pairs(u32),
+    Nothing,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum HatChange {
+    PutOn,
+    TakeOff,
+    Keep,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Score {
+    pub hand: Hand,
+    pub hat: HatChange,
+}
+
+// every die one more than the one before, once they're sorted
+fn is_straight(sorted: &[u8]) -> bool {
+    sorted.len() >= 3 && sorted.windows(2).all(|pair| pair[1] == pair[0] + 1)
+}
+
+// how many pairs: three 4s is one pair (and a spare), four 4s is two
+fn count_pairs(sorted: &[u8]) -> u32 {
+    let mut counts: HashMap<u8, u32> = HashMap::new();
+    for die in sorted {
+        *counts.entry(*die).or_insert(0) += 1;
+    }
+    counts.values().map(|count| count / 2).sum()
+}
+
+pub fn hand(dice: &[u8]) -> Hand {
+    let mut sorted = dice.to_vec();
+    sorted.sort();
+
+    match (is_straight(&sorted), count_pairs(&sorted)) {
+        (true, _) => Hand::Straight,
+        (false, 0) => Hand::Nothing,
+        (false, pairs) => Hand::Pairs(pairs),
+    }
+}
+
+// the book's dice_roll match, returning what happened instead of calling
+// add_fancy_hat and friends
+pub fn hat_change(total: u32) -> HatChange {
+    match total {
+        3 => HatChange::PutOn,
+        7 => HatChange::TakeOff,
+        _ => HatChange::Keep,
+    }
+}
+
+pub fn score(dice: &[u8]) -> Score {
+    let total = dice.iter().map(|die| *die as u32).sum();
+    Score {
+        hand: hand(dice),
+        hat: hat_change(total),
+    }
+}
+
+impl Hand {
+    pub fn points(&self) -> u32 {
+        match self {
+            Hand::Straight => 20,
+            Hand::Pairs(pairs) => 5 * pairs,
+            Hand::Nothing => 0,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct PlayerScore {
+    pub points: u32,
+    pub wearing_hat: bool,
+    pub turns: u32,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Turn {
+    pub dice: Vec<u8>,
+    pub score: Score,
+    // what the turn was worth, after the hat
+    pub points: u32,
+}
+
+pub struct Game {
+    dice_per_turn: usize,
+    scores: HashMap<String, PlayerScore>,
+}
+
+impl Game {
+    pub fn new(players: &[&str], dice_per_turn: usize) -> Game {
+        Game {
+            dice_per_turn,
+            scores: players
+                .iter()
+                .map(|name| (name.to_string(), PlayerScore::default()))
+                .collect(),
+        }
+    }
+
+    // None if `name` isn't playing
+    pub fn take_turn(&mut self, name: &str, rng: &mut dyn Rng) -> Option<Turn> {
+        let dice = roll_dice(self.dice_per_turn, rng);
+        self.record(name, dice)
+    }
+
+    // scores dice that were already rolled, so tests can choose them
+    pub fn record(&mut self, name: &str, dice: Vec<u8>) -> Option<Turn> {
+        let player = self.scores.get_mut(name)?;
+        let score = score(&dice);
+
+        // the hat changes first, so rolling a 3 pays off straight away
+        match score.hat {
+            HatChange::PutOn => player.wearing_hat = true,
+            HatChange::TakeOff => player.wearing_hat = false,
+            HatChange::Keep => (),
+        }
+        let points = match player.wearing_hat {
+            true => score.hand.points() * 2,
+            false => score.hand.points(),
+        };
+        player.points += points;
+        player.turns += 1;
+
+        Some(Turn {
+            dice,
+            score,
+            points,
+        })
+    }
+
+    // highest score first, ties in name order so it's the same every time
+    pub fn scoreboard(&self) -> Vec<(&str, PlayerScore)> {
+        let mut board: Vec<(&str, PlayerScore)> = self
+            .scores
+            .iter()
+            .map(|(name, score)| (name.as_str(), *score))
+            .collect();
+        board.sort_by(|a, b| b.1.points.cmp(&a.1.points).then(a.0.cmp(b.0)));
+        board
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score_of(game: &Game, name: &str) -> Option<PlayerScore> {
+        game.scoreboard()
+            .into_iter()
+            .find(|(player, _)| *player == name)
+            .map(|(_, score)| score)
+    }
+
+    // every possible roll of `count` dice: 6^count of them
+    fn every_roll(count: usize) -> Vec<Vec<u8>> {
+        let mut rolls = vec![vec![]];
+        for _ in 0..count {
+            rolls = rolls
+                .into_iter()
+                .flat_map(|roll| {
+                    (1..=6).map(move |die| {
+                        let mut next = roll.clone();
+                        next.push(die);
+                        next
+                    })
+                })
+                .collect();
+        }
+        rolls
+    }
+
+    #[test]
+    fn every_roll_of_two_dice() {
+        let rolls = every_roll(2);
+        assert_eq!(rolls.len(), 36);
+        for dice in rolls {
+            let expected_hand = if dice[0] == dice[1] {
+                Hand::Pairs(1)
+            } else {
+                // two dice are never a straight
+                Hand::Nothing
+            };
+            let expected_hat = match dice[0] + dice[1] {
+                3 => HatChange::PutOn,
+                7 => HatChange::TakeOff,
+                _ => HatChange::Keep,
+            };
+            assert_eq!(
+                score(&dice),
+                Score {
+                    hand: expected_hand,
+                    hat: expected_hat
+                },
+                "{:?}",
+                dice
+            );
+        }
+    }
+
+    #[test]
+    fn every_roll_of_three_dice() {
+        let mut straights = 0;
+        let mut pairs = 0;
+        for dice in every_roll(3) {
+            let mut sorted = dice.clone();
+            sorted.sort();
+            let hand = hand(&dice);
+            if sorted[0] + 1 == sorted[1] && sorted[1] + 1 == sorted[2] {
+                assert_eq!(hand, Hand::Straight, "{:?}", dice);
+                straights += 1;
+            } else if sorted[0] == sorted[1] || sorted[1] == sorted[2] {
+                // three of a kind is still only one pair
+                assert_eq!(hand, Hand::Pairs(1), "{:?}", dice);
+                pairs += 1;
+            } else {
+                assert_eq!(hand, Hand::Nothing, "{:?}", dice);
+            }
+        }
+        // 4 straights (123 to 456) in 6 orders each, and 216 - 120 rolls
+        // where at least two dice match
+        assert_eq!(straights, 24);
+        assert_eq!(pairs, 96);
+    }
+
+    #[test]
+    fn hats_only_change_on_3_and_7() {
+        for total in 0..=36 {
+            let expected = match total {
+                3 => HatChange::PutOn,
+                7 => HatChange::TakeOff,
+                _ => HatChange::Keep,
+            };
+            assert_eq!(hat_change(total), expected, "total {}", total);
+        }
+    }
+
+    #[test]
+    fn bigger_hands() {
+        assert_eq!(hand(&[4, 4, 4, 4]), Hand::Pairs(2));
+        assert_eq!(hand(&[1, 1, 2, 2, 6]), Hand::Pairs(2));
+        assert_eq!(hand(&[5, 2, 4, 6, 3]), Hand::Straight);
+        assert_eq!(hand(&[1, 2, 3, 5]), Hand::Nothing);
+        assert_eq!(hand(&[]), Hand::Nothing);
+        assert_eq!(Hand::Pairs(2).points(), 10);
+        assert_eq!(Hand::Straight.points(), 20);
+        assert_eq!(Hand::Nothing.points(), 0);
+    }
+
+    #[test]
+    fn a_hat_doubles_points_until_it_comes_off() {
+        let mut game = Game::new(&["ann"], 2);
+        // 1 + 2 = 3 puts the hat on
+        assert_eq!(game.record("ann", vec![1, 2]).unwrap().points, 0);
+        // a pair of 2s, doubled
+        assert_eq!(game.record("ann", vec![2, 2]).unwrap().points, 10);
+        // 3 + 4 = 7 takes it off, before 3 and 4 score (they're nothing anyway)
+        game.record("ann", vec![3, 4]).unwrap();
+        assert_eq!(game.record("ann", vec![6, 6]).unwrap().points, 5);
+
+        let ann = score_of(&game, "ann").unwrap();
+        assert_eq!((ann.points, ann.turns, ann.wearing_hat), (15, 4, false));
+    }
+
+    #[test]
+    fn strangers_cant_play() {
+        let mut game = Game::new(&["ann"], 2);
+        assert_eq!(game.record("zed", vec![1, 1]), None);
+        assert!(game.take_turn("zed", &mut XorShift::new(1)).is_none());
+        assert_eq!(score_of(&game, "zed"), None);
+    }
+
+    #[test]
+    fn scoreboard_is_sorted() {
+        let mut game = Game::new(&["cat", "ann", "bob"], 2);
+        game.record("bob", vec![5, 5]);
+        let names: Vec<&str> = game.scoreboard().iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, vec!["bob", "ann", "cat"]);
+    }
+
+    #[test]
+    fn the_same_seed_plays_the_same_game() {
+        let play = |seed| {
+            let mut game = Game::new(&["ann", "bob"], 3);
+            let mut rng = XorShift::new(seed);
+            for _ in 0..10 {
+                game.take_turn("ann", &mut rng).unwrap();
+                game.take_turn("bob", &mut rng).unwrap();
+            }
+            game.scoreboard()
+                .into_iter()
+                .map(|(name, score)| (name.to_string(), score))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(play(42), play(42));
+        assert!(play(42).iter().all(|(_, score)| score.turns == 10));
+    }
+}
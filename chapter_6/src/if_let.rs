@@ -1,6 +1,6 @@
 // 6.3 Concise Control Flow with `if let`
 
-pub fn _run() {
+pub fn run() {
     // if let is a shorthand way to handle values that match one pattern and ignore the rest
     // example:
     let config_max = Some(3u8);
@@ -19,19 +19,9 @@ pub fn _run() {
     // checking that `match` enforces, so using it is a bit of a balancing act.
 
     // We can also add an `else` block to an `if let`. let's look at an example
-    #[derive(Debug)]
-    enum UsState {
-        _Alabama,
-        _Alaska,
-        Utah,
-        _Washington,
-    }
-    enum Coin {
-        _Penny,
-        _Nickel,
-        Dime,
-        Quarter(UsState),
-    }
+    // (the same Coin and UsState as match_flow.rs, from the domain crate)
+    use domain::prelude::*;
+
     let coin1 = Coin::Quarter(UsState::Utah);
     let coin2 = Coin::Dime;
     fn coin_check(coin: Coin) {
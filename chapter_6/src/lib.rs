@@ -0,0 +1,53 @@
+// Chapter 6: Enums and Pattern Matching (library)
+// The walkthroughs for defining enums, match and if let, a dice game whose
+// rolls and scores are all enums, and a tokenizer built on match
+
+pub mod defining_enums;
+pub mod dice_game;
+pub mod if_let;
+pub mod match_flow;
+pub mod tokenizer;
+
+use domain::{Section, XorShift};
+
+// every section that runs without any input, in the book's order. The runner
+// (and anything else that wants them all) goes through this list
+pub fn exercises() -> Vec<Section> {
+    vec![
+        Section {
+            name: "defining_enums",
+            run: || {
+                defining_enums::run();
+                Ok(())
+            },
+        },
+        Section {
+            name: "match_flow",
+            run: || {
+                match_flow::run();
+                Ok(())
+            },
+        },
+        Section {
+            name: "if_let",
+            run: || {
+                if_let::run();
+                Ok(())
+            },
+        },
+        Section {
+            name: "dice_game",
+            run: || {
+                dice_game::run(&mut XorShift::from_time());
+                Ok(())
+            },
+        },
+        Section {
+            name: "tokenizer",
+            run: || {
+                tokenizer::run();
+                Ok(())
+            },
+        },
+    ]
+}
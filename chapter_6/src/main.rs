@@ -1,10 +1,18 @@
-mod defining_enums;
-mod match_flow;
-mod if_let;
 // Chapter 6: Enums and Pattern Matching
 
+use std::{env, process};
+
 fn main() {
-    // defining_enums::run();
-    // match_flow::run();
-    // if_let::run();
+    // chapter_6::defining_enums::run();
+    // chapter_6::match_flow::run();
+    // chapter_6::if_let::run();
+
+    // `cargo run -- --seed 42` plays the same dice game every time
+    let mut args: Vec<String> = env::args().collect();
+    let seed = domain::take_seed(&mut args).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        process::exit(1);
+    });
+    chapter_6::dice_game::run(&mut domain::XorShift::from_seed(seed));
+    chapter_6::tokenizer::run();
 }
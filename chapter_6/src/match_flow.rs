@@ -1,26 +1,24 @@
 // 6.2 The Match Control Flow Construct
 
-pub fn _run() {
-    // a good analogy for thinking about `match` is a coin sorting machine
-    #[derive(Debug)]
-    enum UsState {
-        _Alabama,
-        _Alaska,
-        Utah,
-        _Washington,
-    }
-    enum Coin {
-        _Penny,
-        _Nickel,
-        _Dime,
-        Quarter(UsState),
-    }
+// Rng, XorShift, Coin and UsState all come in through the domain prelude
+use domain::prelude::*;
+
+// the dice below are rolled with whatever Rng is passed in. run rolls with
+// one seeded from the clock, a seeded XorShift rolls the same every time
+pub fn run() {
+    run_with(&mut XorShift::from_time());
+}
+
+pub fn run_with(rng: &mut dyn Rng) {
+    // a good analogy for thinking about `match` is a coin sorting machine.
+    // Coin and UsState come from the domain crate, which also gives Coin a
+    // value_in_cents method. This is the hand written version of it:
 
     fn value_in_cents(coin: Coin) -> u8 {
         match coin {
-            Coin::_Penny => 1, // each of these are "arms"
-            Coin::_Nickel => 5, // pattern to match => code to run
-            Coin::_Dime => 10,
+            Coin::Penny => 1, // each of these are "arms"
+            Coin::Nickel => 5, // pattern to match => code to run
+            Coin::Dime => 10,
             // the state variable added here binds to the value of the Quarter variant (the state of the coin)
             Coin::Quarter(state) => { // if multiple lines, curly braces required
                 println!("A Quarter! from {:?}", state);
@@ -37,7 +35,7 @@ pub fn _run() {
     //Another useful feature of match arms is that they can bind to the parts 
     // of the values that match the pattern. This is how we can extract values out of enum variants.
     
-    // adding some data to our Quarter variant of Coin above ^^
+    // that's the data in the Quarter variant of Coin above ^^
 
     // Matching with Option<T>
     // Write a function that takes an Option<i32>, if there is a value, add 1
@@ -72,7 +70,9 @@ pub fn _run() {
     // Using enums, we can also take special actions for a few particular values,
     // but for all other values take one default action.
 
-    let dice_roll = 9;
+    // the book just picks 9, here it's two six sided dice added together
+    let dice_roll = rng.roll(6) + rng.roll(6);
+    println!("you rolled {dice_roll}");
     match dice_roll {
         3 => add_fancy_hat(),
         7 => remove_fancy_hat(),
@@ -104,4 +104,6 @@ pub fn _run() {
         7 => remove_fancy_hat(),
         _ => (),
     } 
+
+    // dice_game.rs grows this into a whole game with scoring and a scoreboard
 }
\ No newline at end of file
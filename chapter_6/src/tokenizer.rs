@@ -0,0 +1,195 @@
+// 6.1 (continued) Enums Holding Slices: a Tokenizer
+
+/*
+A tokenizer is the first step of reading code (or a calculator's input, or a
+config file): it chops text up into pieces and says what kind of piece each
+one is. That "one of a few kinds, each with its own data" is exactly what an
+enum is for (6.1), and the pieces themselves are string slices (4.3).
+
+Each Token borrows its text from the input instead of copying it into a new
+String. That's what the 'a on Token<'a> says: a Token can't outlive the &str
+it was cut from (10.3). In return, tokenizing never allocates anything except
+the Vec the tokens go in.
+
+    "x = 1.5 + y2;"  ->  Word("x") Punct('=') Number("1.5") Punct('+')
+                         Word("y2") Punct(';')
+
+The rules:
+  - a Word starts with a letter or _, then letters, digits and _
+  - a Number is digits, with an optional . and more digits (a . with no digit
+    after it is Punct('.'), so "3." is Number("3"), Punct('.'))
+  - whitespace separates tokens and is dropped
+  - any other char is a Punct on its own
+*/
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Token<'a> {
+    Word(&'a str),
+    Number(&'a str),
+    Punct(char),
+}
+
+pub fn run() {
+    let input = "let total = price * 1.5 + 20;";
+    let tokens = tokenize(input);
+    println!("{input}");
+    for token in &tokens {
+        match token {
+            Token::Word(word) => println!("  word:   {word}"),
+            Token::Number(number) => println!("  number: {number}"),
+            Token::Punct(c) => println!("  punct:  {c}"),
+        }
+    }
+    if let Some(word) = longest_word(&tokens) {
+        println!("the longest word is {word}");
+    }
+}
+
+pub fn tokenize<'a>(input: &'a str) -> Vec<Token<'a>> {
+    let mut tokens = Vec::new();
+    let mut rest = input.trim_start();
+
+    // peek at the first char to decide what kind of token comes next, then
+    // split it off the front of `rest`
+    while let Some(c) = rest.chars().next() {
+        let (token, after) = match c {
+            c if c.is_alphabetic() || c == '_' => {
+                let (word, after) = split_while(rest, |c| c.is_alphanumeric() || c == '_');
+                (Token::Word(word), after)
+            }
+            '0'..='9' => {
+                let (number, after) = split_number(rest);
+                (Token::Number(number), after)
+            }
+            // len_utf8, not 1: the char might be more than one byte
+            _ => (Token::Punct(c), &rest[c.len_utf8()..]),
+        };
+        tokens.push(token);
+        rest = after.trim_start();
+    }
+
+    tokens
+}
+
+// splits `s` where `keep` first says no. Both halves borrow from `s`: with
+// only one reference coming in, the elision rules (10.3) give them its lifetime
+// without writing 'a out
+fn split_while(s: &str, keep: impl Fn(char) -> bool) -> (&str, &str) {
+    let end = s.find(|c| !keep(c)).unwrap_or(s.len());
+    s.split_at(end)
+}
+
+fn split_number(s: &str) -> (&str, &str) {
+    let (whole, after) = split_while(s, |c| c.is_ascii_digit());
+    match after.strip_prefix('.') {
+        Some(fraction) if fraction.starts_with(|c: char| c.is_ascii_digit()) => {
+            let (digits, _) = split_while(fraction, |c| c.is_ascii_digit());
+            s.split_at(whole.len() + 1 + digits.len())
+        }
+        _ => (whole, after),
+    }
+}
+
+// The &str that comes back borrows from the *input*, not from `tokens`: the
+// signature ties it to the 'a inside Token<'a>, not to the &[..] around them.
+// So the Vec of tokens can be dropped and the word is still good
+pub fn longest_word<'a>(tokens: &[Token<'a>]) -> Option<&'a str> {
+    let mut longest: Option<&'a str> = None;
+    for token in tokens {
+        if let Token::Word(word) = token {
+            match longest {
+                Some(current) if current.len() >= word.len() => (),
+                _ => longest = Some(word),
+            }
+        }
+    }
+    longest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Token::{Number, Punct, Word};
+
+    #[test]
+    fn words_numbers_and_punctuation() {
+        assert_eq!(
+            tokenize("x = 1.5 + y2;"),
+            vec![
+                Word("x"),
+                Punct('='),
+                Number("1.5"),
+                Punct('+'),
+                Word("y2"),
+                Punct(';')
+            ]
+        );
+    }
+
+    #[test]
+    fn whitespace_only_separates() {
+        assert_eq!(tokenize(""), vec![]);
+        assert_eq!(tokenize(" \t\n "), vec![]);
+        assert_eq!(tokenize("a+b"), tokenize("  a \n+\tb  "));
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(tokenize("42"), vec![Number("42")]);
+        assert_eq!(tokenize("3.14"), vec![Number("3.14")]);
+        // a . needs a digit after it to be part of the number
+        assert_eq!(tokenize("3."), vec![Number("3"), Punct('.')]);
+        assert_eq!(tokenize(".5"), vec![Punct('.'), Number("5")]);
+        assert_eq!(
+            tokenize("1.2.3"),
+            vec![Number("1.2"), Punct('.'), Number("3")]
+        );
+        // a word can't start with a digit, so this is two tokens
+        assert_eq!(tokenize("12abc"), vec![Number("12"), Word("abc")]);
+    }
+
+    #[test]
+    fn words() {
+        assert_eq!(
+            tokenize("_private snake_case"),
+            vec![Word("_private"), Word("snake_case")]
+        );
+        assert_eq!(tokenize("don't"), vec![Word("don"), Punct('\''), Word("t")]);
+    }
+
+    #[test]
+    fn any_utf8() {
+        // letters from any language are letters, anything else is punctuation,
+        // even when it's 4 bytes long
+        assert_eq!(
+            tokenize("café Здравствуйте 🦀!"),
+            vec![Word("café"), Word("Здравствуйте"), Punct('🦀'), Punct('!')]
+        );
+    }
+
+    #[test]
+    fn tokens_borrow_from_the_input() {
+        let input = String::from("hello world");
+        let tokens = tokenize(&input);
+        // the slice inside the token is part of `input`, not a copy of it
+        if let Word(word) = tokens[1] {
+            assert_eq!(word.as_ptr(), input[6..].as_ptr());
+        } else {
+            panic!("expected a word, got {:?}", tokens[1]);
+        }
+    }
+
+    #[test]
+    fn longest_word_outlives_the_tokens() {
+        let input = String::from("a bb 100000 ccc dd");
+        let longest = {
+            let tokens = tokenize(&input);
+            longest_word(&tokens)
+            // tokens is dropped here, longest only borrows from input
+        };
+        assert_eq!(longest, Some("ccc"));
+        assert_eq!(longest_word(&tokenize("1 + 2")), None);
+        // ties go to the first one
+        assert_eq!(longest_word(&tokenize("ab cd")), Some("ab"));
+    }
+}
@@ -2,11 +2,144 @@
 // lib.rs is a special Rust file. it is not compiled down to an executable like
 // a binary crate is.
 
-// lets create a lib module that provides the functionality of a restaurant
+// lets create a lib module that provides the functionality of a restaurant.
+// hosting started out as two empty functions, now it's a Host that owns the
+// waitlist and the tables. Everything inside front_of_house is private unless
+// marked pub, and front_of_house itself is private, so the `pub use` below is
+// what lets code outside the crate reach hosting (see 7.4 in use_keyword.rs)
 mod front_of_house {
     pub mod hosting {
-        pub fn _add_to_waitlist() {}
-        fn _seat_at_table() {}
+        use std::collections::VecDeque;
+
+        #[derive(Debug, PartialEq)]
+        pub struct Seating {
+            pub party: String,
+            pub table: u32,
+        }
+
+        struct Party {
+            name: String,
+            size: u32,
+        }
+
+        struct Table {
+            number: u32,
+            seats: u32,
+            // the party sitting there, if any
+            party: Option<String>,
+        }
+
+        // the fields are private, so the only way to change the waitlist or
+        // the tables is through the methods, which keep them consistent
+        pub struct Host {
+            waitlist: VecDeque<Party>,
+            tables: Vec<Table>,
+        }
+
+        impl Host {
+            // one table per entry, numbered from 1 in the order given
+            pub fn new(table_sizes: &[u32]) -> Host {
+                Host {
+                    waitlist: VecDeque::new(),
+                    tables: table_sizes
+                        .iter()
+                        .zip(1..)
+                        .map(|(&seats, number)| Table {
+                            number,
+                            seats,
+                            party: None,
+                        })
+                        .collect(),
+                }
+            }
+
+            // returns the party's place in line, 1 is next
+            pub fn add_to_waitlist(&mut self, name: &str, size: u32) -> usize {
+                self.waitlist.push_back(Party {
+                    name: String::from(name),
+                    size,
+                });
+                self.waitlist.len()
+            }
+
+            /*
+            Goes down the waitlist in order and seats every party that fits
+            at a free table, choosing the smallest table that's big enough
+            so the big tables are kept for big parties. A party with no table
+            that fits keeps its place, and the parties behind it can still be
+            seated if a smaller table suits them.
+            */
+            pub fn seat_waiting(&mut self) -> Vec<Seating> {
+                let mut seated = Vec::new();
+                let mut still_waiting = VecDeque::new();
+
+                while let Some(party) = self.waitlist.pop_front() {
+                    match self.free_table_for(party.size) {
+                        Some(index) => seated.push(self.seat_at_table(index, party)),
+                        None => still_waiting.push_back(party),
+                    }
+                }
+                self.waitlist = still_waiting;
+                seated
+            }
+
+            /*
+            Seats only the party at `place` in line (1 is next, the same
+            numbering add_to_waitlist returns) if a table fits, ahead of
+            anyone else waiting. It goes by place rather than by name since two
+            parties can have the same name. None leaves the party where it was
+            */
+            pub fn seat(&mut self, place: usize) -> Option<Seating> {
+                let size = self.waitlist.get(place.checked_sub(1)?)?.size;
+                let index = self.free_table_for(size)?;
+                let party = self.waitlist.remove(place - 1)?;
+                Some(self.seat_at_table(index, party))
+            }
+
+            // the party that was at the table, None if it was already free or
+            // there's no such table
+            pub fn clear_table(&mut self, number: u32) -> Option<String> {
+                self.tables
+                    .iter_mut()
+                    .find(|table| table.number == number)
+                    .and_then(|table| table.party.take())
+            }
+
+            pub fn waiting(&self) -> Vec<&str> {
+                self.waitlist
+                    .iter()
+                    .map(|party| party.name.as_str())
+                    .collect()
+            }
+
+            pub fn free_tables(&self) -> Vec<u32> {
+                self.tables
+                    .iter()
+                    .filter(|table| table.party.is_none())
+                    .map(|table| table.number)
+                    .collect()
+            }
+
+            // the book's private seat_at_table: only the Host decides who sits
+            // where, so it isn't pub
+            fn seat_at_table(&mut self, index: usize, party: Party) -> Seating {
+                let table = &mut self.tables[index];
+                table.party = Some(party.name.clone());
+                Seating {
+                    party: party.name,
+                    table: table.number,
+                }
+            }
+
+            fn free_table_for(&self, size: u32) -> Option<usize> {
+                self.tables
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, table)| table.party.is_none() && table.seats >= size)
+                    .min_by_key(|(_, table)| table.seats)
+                    .map(|(index, _)| index)
+            }
+        }
     }
 
     mod serving {
@@ -16,6 +149,10 @@ mod front_of_house {
     }
 }
 
+pub use crate::front_of_house::hosting;
+
+use domain::Section;
+
 // // Two ways to call the add to waitlist function: relative and absolute
 // pub fn eat_at_restaurant() {
 //     // Absolute Path
@@ -63,7 +200,18 @@ mod back_of_house {
     }
 }
 
-pub fn eat_at_restaurant() {
+// A party arrives, waits for the host to seat them, and orders breakfast.
+// Returns where they were seated, or None if there's no table for them yet (in
+// which case they stay on the waitlist). Only the new party is seated, anyone
+// already waiting is left for seat_waiting
+pub fn eat_at_restaurant(
+    host: &mut hosting::Host,
+    party: &str,
+    size: u32,
+) -> Option<hosting::Seating> {
+    let place = host.add_to_waitlist(party, size);
+    let seating = host.seat(place)?;
+
     // Order a breakfast in the summer with Rye Toast
     let mut meal = back_of_house::Breakfast::summer("Rye");
     // change our mind about what bread we'd like
@@ -80,4 +228,133 @@ pub fn eat_at_restaurant() {
     // see the Appetizer enum in back_of_house above
     let _order1 = back_of_house::Appetizer::Soup;
     let _order2 = back_of_house::Appetizer::Salad;
-}
\ No newline at end of file
+
+    Some(seating)
+}
+
+// what main does with the library: one party at a restaurant with a table for
+// two and a table for four
+pub fn run() {
+    let mut host = hosting::Host::new(&[2, 4]);
+    match eat_at_restaurant(&mut host, "Ferris", 2) {
+        Some(seating) => println!("{} is at table {}", seating.party, seating.table),
+        None => println!("still waiting for a table"),
+    }
+}
+
+// for the runner. use_keyword.rs stays in the binary crate, it's about using
+// this library from the outside
+pub fn exercises() -> Vec<Section> {
+    vec![Section {
+        name: "eat_at_restaurant",
+        run: || {
+            run();
+            Ok(())
+        },
+    }]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::hosting::{Host, Seating};
+    use super::*;
+
+    fn seating(party: &str, table: u32) -> Seating {
+        Seating {
+            party: String::from(party),
+            table,
+        }
+    }
+
+    #[test]
+    fn seats_in_waitlist_order() {
+        let mut host = Host::new(&[2, 4]);
+        assert_eq!(host.add_to_waitlist("Ferris", 2), 1);
+        assert_eq!(host.add_to_waitlist("Corro", 2), 2);
+        assert_eq!(host.waiting(), vec!["Ferris", "Corro"]);
+
+        assert_eq!(
+            host.seat_waiting(),
+            vec![seating("Ferris", 1), seating("Corro", 2)]
+        );
+        assert!(host.waiting().is_empty());
+        assert!(host.free_tables().is_empty());
+    }
+
+    #[test]
+    fn smallest_table_that_fits() {
+        let mut host = Host::new(&[6, 2, 4]);
+        host.add_to_waitlist("pair", 2);
+        host.add_to_waitlist("trio", 3);
+        assert_eq!(
+            host.seat_waiting(),
+            vec![seating("pair", 2), seating("trio", 3)]
+        );
+        assert_eq!(host.free_tables(), vec![1]);
+    }
+
+    #[test]
+    fn big_parties_wait_without_holding_up_the_line() {
+        let mut host = Host::new(&[2]);
+        host.add_to_waitlist("reunion", 12);
+        host.add_to_waitlist("date", 2);
+        assert_eq!(host.seat_waiting(), vec![seating("date", 1)]);
+        assert_eq!(host.waiting(), vec!["reunion"]);
+    }
+
+    #[test]
+    fn clearing_a_table_frees_it() {
+        let mut host = Host::new(&[2]);
+        host.add_to_waitlist("first", 2);
+        host.add_to_waitlist("second", 2);
+        host.seat_waiting();
+        assert_eq!(host.waiting(), vec!["second"]);
+
+        assert_eq!(host.clear_table(1), Some(String::from("first")));
+        assert_eq!(host.clear_table(1), None);
+        assert_eq!(host.clear_table(9), None);
+        assert_eq!(host.seat_waiting(), vec![seating("second", 1)]);
+    }
+
+    #[test]
+    fn seat_one_party_by_place() {
+        let mut host = Host::new(&[2, 4]);
+        host.add_to_waitlist("Ferris", 8);
+        host.add_to_waitlist("Ferris", 3);
+        host.add_to_waitlist("Corro", 2);
+        // two parties called Ferris, the place says which one
+        assert_eq!(host.seat(2), Some(seating("Ferris", 2)));
+        assert_eq!(host.waiting(), vec!["Ferris", "Corro"]);
+        // no table for 8, and no one at place 0 or 3
+        assert_eq!(host.seat(1), None);
+        assert_eq!(host.seat(0), None);
+        assert_eq!(host.seat(3), None);
+        assert_eq!(host.waiting(), vec!["Ferris", "Corro"]);
+    }
+
+    #[test]
+    fn eat_at_restaurant_seats_only_the_new_party() {
+        let mut host = Host::new(&[2, 4]);
+        host.add_to_waitlist("Ferris", 6);
+        host.add_to_waitlist("Corro", 2);
+        assert_eq!(
+            eat_at_restaurant(&mut host, "Ferris", 4),
+            Some(seating("Ferris", 2))
+        );
+        // Corro fits at table 1 but is still waiting, and so is the other Ferris
+        assert_eq!(host.waiting(), vec!["Ferris", "Corro"]);
+        assert_eq!(host.free_tables(), vec![1]);
+    }
+
+    #[test]
+    fn eat_at_restaurant_gets_a_seat() {
+        let mut host = Host::new(&[4]);
+        assert_eq!(
+            eat_at_restaurant(&mut host, "Ferris", 3),
+            Some(seating("Ferris", 1))
+        );
+        // the only table is taken now
+        assert_eq!(eat_at_restaurant(&mut host, "Corro", 2), None);
+        assert_eq!(host.waiting(), vec!["Corro"]);
+    }
+}
@@ -3,4 +3,8 @@ mod use_keyword;
 
 fn main() {
     use_keyword::run();
+
+    // the library crate, used from the binary crate in the same package. main
+    // can only see what lib.rs made pub, same as any other crate would
+    chapter_7::run();
 }
@@ -2,21 +2,20 @@
 // `use` lets us create a shortcut to a path so we don't have to type
 // crate::front_of_house::hosting::serve_order() for example every time we want 
 // to call serve_order()
-mod front_of_house {
-    pub mod hosting {
-        pub fn _add_to_waitlist() {}
-    }
-}
-
-use front_of_house::hosting;
-
-pub fn _eat_at_restaurant() {
-    hosting::_add_to_waitlist();
+// here the module is lib.rs's real front_of_house::hosting. lib.rs re-exports
+// it (see `pub use` further down), so from this binary crate it's
+// chapter_7::hosting
+use chapter_7::hosting;
+
+pub fn join_waitlist() {
+    let mut host = hosting::Host::new(&[]);
+    let place = host.add_to_waitlist("Corro", 4);
+    println!("Corro is number {} on the waitlist", place);
 }
 
 // this `use` expression only applies to the scope that it is in
-// if, for example, we move the eat_at_restaurant function into a new child
-// module, our code will fail to compile because the use and hosting::add_...
+// if, for example, we move the join_waitlist function into a new child
+// module, our code will fail to compile because the use and hosting::Host
 // call are not in the same scope anymore
 
 // notice we specified the use path to the parent module, and not to the function itself
@@ -27,6 +26,8 @@ pub fn _eat_at_restaurant() {
 use std::collections::HashMap;
 
 pub fn run() {
+    join_waitlist();
+
     let mut map = HashMap::new();
     map.insert(1, 2);
     println!("the map: {:?}", map);
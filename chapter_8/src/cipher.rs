@@ -0,0 +1,181 @@
+// 8.2 (continued) A Caesar Cipher
+
+/*
+A Caesar cipher moves every letter a fixed number of places along the
+alphabet: with a shift of 3, a becomes d, b becomes e, and x wraps round to a.
+Decoding is the same move backwards. ROT13 is the special case of a shift of
+13: half of 26, so doing it twice gets the original text back.
+
+Only the 26 ASCII letters (each case) are rotated. Everything else, digits,
+spaces, punctuation, and any other UTF-8 like 'é', 'Ж' or '🦀', is passed
+through untouched. That's why this works on chars and not bytes: 'é' is two
+bytes in UTF-8, and shifting either byte on its own would leave something that
+isn't valid UTF-8 at all (see "Bytes and Scalar Values" in strings.rs).
+*/
+
+use std::io::{self, Write};
+
+pub fn run() {
+    run_to(&mut io::stdout()).unwrap();
+}
+
+pub fn run_to(out: &mut impl Write) -> io::Result<()> {
+    let message = "Meet me at the café at 7, bring 🦀!";
+    let secret = encode(message, 3);
+    writeln!(out, "shifted by 3: {secret}")?;
+    writeln!(out, "and back: {}", decode(&secret, 3))?;
+    writeln!(out, "rot13: {}", rot13(message))?;
+    writeln!(out, "rot13 twice: {}", rot13(&rot13(message)))?;
+    Ok(())
+}
+
+// moves one char `shift` places, wrapping from z back to a. A negative shift
+// moves backwards, and any shift works: 29 is the same as 3, and -1 as 25
+pub fn shift_char(c: char, shift: i32) -> char {
+    let base = match c {
+        'a'..='z' => b'a',
+        'A'..='Z' => b'A',
+        _ => return c,
+    };
+    // rem_euclid is % that's never negative: (-1).rem_euclid(26) is 25 where
+    // -1 % 26 is -1
+    let shift = shift.rem_euclid(26) as u8;
+    let offset = (c as u8 - base + shift) % 26;
+    (base + offset) as char
+}
+
+pub fn encode(text: &str, shift: i32) -> String {
+    text.chars().map(|c| shift_char(c, shift)).collect()
+}
+
+// shifting back by `shift` is shifting forwards by what's left of 26, which
+// also avoids negating i32::MIN (-i32::MIN doesn't fit in an i32)
+pub fn decode(text: &str, shift: i32) -> String {
+    encode(text, 26 - shift.rem_euclid(26))
+}
+
+pub fn rot13(text: &str) -> String {
+    encode(text, 13)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chapter_11::capture::capture_lines;
+    use chapter_11::property::{for_all, i32_in, Both, Generator};
+    use domain::Rng;
+
+    #[test]
+    fn shifts_letters_and_wraps() {
+        assert_eq!(encode("abc xyz", 3), "def abc");
+        assert_eq!(encode("Hello", 1), "Ifmmp");
+        assert_eq!(encode("abc", -1), "zab");
+        assert_eq!(encode("abc", 29), "def");
+        assert_eq!(decode("def abc", 3), "abc xyz");
+        // case is kept
+        assert_eq!(encode("aA zZ", 1), "bB aA");
+    }
+
+    #[test]
+    fn rot13_is_its_own_inverse() {
+        assert_eq!(rot13("Hello, World!"), "Uryyb, Jbeyq!");
+        assert_eq!(rot13("Uryyb, Jbeyq!"), "Hello, World!");
+    }
+
+    #[test]
+    fn everything_but_ascii_letters_passes_through() {
+        let untouched = "0123456789 !?.,{}[] éàüß Здравствуйте नमस्ते 🦀";
+        for shift in -30..=30 {
+            assert_eq!(encode(untouched, shift), untouched);
+        }
+        assert_eq!(encode("café 🦀", 1), "dbgé 🦀");
+    }
+
+    #[test]
+    fn extreme_shifts() {
+        assert_eq!(encode("abc", i32::MAX), encode("abc", i32::MAX % 26));
+        assert_eq!(decode(&encode("abc", i32::MIN), i32::MIN), "abc");
+    }
+
+    #[test]
+    fn run_prints_the_walkthrough() {
+        let lines = capture_lines(|mut out| run_to(&mut out));
+        assert_eq!(
+            lines,
+            vec![
+                "shifted by 3: Phhw ph dw wkh fdié dw 7, eulqj 🦀!",
+                "and back: Meet me at the café at 7, bring 🦀!",
+                "rot13: Zrrg zr ng gur pnsé ng 7, oevat 🦀!",
+                "rot13 twice: Meet me at the café at 7, bring 🦀!",
+            ]
+        );
+    }
+
+    // Round Trip Properties
+    // chapter_11's for_all only knows how to make numbers, so this teaches it
+    // to make text: a mix of ASCII letters, things that must not change, and
+    // multi-byte UTF-8. Shrinking drops one char at a time, so a failure is
+    // reported on the shortest text that still fails
+    struct Text;
+
+    const ALPHABET: &[char] = &[
+        'a', 'm', 'z', 'A', 'M', 'Z', 'q', 'Q', ' ', '0', '9', '!', '{', '`', '[', '@', 'é', 'Ж',
+        'न', '🦀', '\n',
+    ];
+
+    impl Generator for Text {
+        type Value = String;
+
+        fn generate(&self, rng: &mut dyn Rng) -> String {
+            let len = rng.below(40);
+            (0..len)
+                .map(|_| ALPHABET[rng.below(ALPHABET.len() as u64) as usize])
+                .collect()
+        }
+
+        fn shrink(&self, text: &String) -> Vec<String> {
+            (0..text.chars().count())
+                .map(|skip| {
+                    text.chars()
+                        .enumerate()
+                        .filter(|(i, _)| *i != skip)
+                        .map(|(_, c)| c)
+                        .collect()
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn decode_undoes_encode() {
+        for_all(Both(Text, i32_in(-1000, 1000)), |(text, shift)| {
+            decode(&encode(text, *shift), *shift) == *text
+        });
+    }
+
+    #[test]
+    fn rot13_twice_is_the_original() {
+        for_all(Text, |text| rot13(&rot13(text)) == *text);
+    }
+
+    #[test]
+    fn only_ascii_letters_change() {
+        for_all(Both(Text, i32_in(-1000, 1000)), |(text, shift)| {
+            let encoded = encode(text, *shift);
+            // same number of chars and the same number of bytes
+            encoded.len() == text.len()
+                && text.chars().zip(encoded.chars()).all(|(before, after)| {
+                    before.is_ascii_alphabetic() == after.is_ascii_alphabetic()
+                        && before.is_ascii_uppercase() == after.is_ascii_uppercase()
+                        && (before.is_ascii_alphabetic() || before == after)
+                })
+        });
+    }
+
+    #[test]
+    fn a_shift_of_26_changes_nothing() {
+        for_all(Text, |text| {
+            encode(text, 26) == *text && encode(text, 0) == *text
+        });
+    }
+}
@@ -5,27 +5,33 @@
 // which determines how it places these keys and values into memory.
 // This is equivalent to an Object in JavaScript
 
+use std::collections::HashMap; // least used, so isn't included in the prelude
+use std::io::{self, Write};
+
+// run_to and the print_ functions write to `out` instead of calling println!,
+// so the tests at the bottom can capture what they print. run keeps printing
+// to stdout
 pub fn run() {
+    run_to(&mut io::stdout()).unwrap();
+}
+
+pub fn run_to(out: &mut impl Write) -> io::Result<()> {
     // Creating a New Hash Map
-    use std::collections::HashMap; // least used, so isn't included in the prelude
 
     let mut scores = HashMap::new();
     scores.insert(String::from("Blue"), 10);
     scores.insert(String::from("Yellow"), 50);
-    println!("Hash Map of team scores: {:?}", scores);
+    writeln!(out, "Hash Map of team scores: {:?}", scores)?;
 
     // Hash Maps are like vectors in that they must contain homogenous
     // All keys must be of the same type, same with values.
 
     // Accessing Values in a Hash Map
     let team_name = String::from("Blue");
-    let team_score = scores.get(&team_name);
-    println!("the score for the {team_name} team is: {:?}", team_score);
+    print_team_score(&scores, &team_name, out)?;
 
     // we can iterate over Hash Maps as well in Rust, nice! It looks like:
-    for (key, value) in &scores {
-        println!("{}: {}", key, value);
-    }
+    print_scores(&scores, out)?;
     // this will print the keys in arbitrary order since Hash Maps aren't ordered
 
     // Hash Maps and Ownership
@@ -36,7 +42,7 @@ pub fn run() {
 
     let mut map = HashMap::new();
     map.insert(field_name, field_value);
-    println!("map: {:?}", map);
+    writeln!(out, "map: {:?}", map)?;
     // field_name and field_value are invalid at this point, trying to use them
     // will result in a compilation error at this point.
     // we can pass references in, though that comes with potential issues discussed in Ch 10
@@ -46,18 +52,38 @@ pub fn run() {
     // Overwrite, inserting a new value into an existing string
     // up above the key "Blue" already holds the value 10, let's overwrite it
     scores.insert(String::from("Blue"), 42);
-    println!("Originally blue team had 10 points, that got overwritten to {:?}", scores.get("Blue"));
+    writeln!(out, "Originally blue team had 10 points, that got overwritten to {:?}", scores.get("Blue"))?;
 
     // Adding a key only if it isn't present already
     // special method for this called `entry`
     let existing = scores.entry(String::from("Yellow")).or_insert(33); // Yellow exists, so nothing happens
-    println!("entry returns a mutable reference for entries that exist: {existing}");
+    writeln!(out, "entry returns a mutable reference for entries that exist: {existing}")?;
     let not_existing = scores.entry(String::from("Green")).or_insert(60); // Green doesn't exist, insert it with value 60
-    println!("when an entry doesn't exist, it returns: {not_existing}. The inserted value");
-    println!("The scores hash map should now have blue, yellow and green: {:?}", scores);
+    writeln!(out, "when an entry doesn't exist, it returns: {not_existing}. The inserted value")?;
+    writeln!(out, "The scores hash map should now have blue, yellow and green: {:?}", scores)?;
 
     // Updating a Value Based on the Old Value
     let text = "hello world wonderful world";
+    print_word_counts(text, out)
+}
+
+pub fn print_team_score(
+    scores: &HashMap<String, i32>,
+    team_name: &str,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    let team_score = scores.get(team_name);
+    writeln!(out, "the score for the {team_name} team is: {:?}", team_score)
+}
+
+pub fn print_scores(scores: &HashMap<String, i32>, out: &mut dyn Write) -> io::Result<()> {
+    for (key, value) in scores {
+        writeln!(out, "{}: {}", key, value)?;
+    }
+    Ok(())
+}
+
+pub fn word_counts(text: &str) -> HashMap<&str, i32> {
     let mut char_map = HashMap::new();
 
     for word in text.split_whitespace() {
@@ -65,5 +91,63 @@ pub fn run() {
         *count += 1;
     }
 
-    println!("char map: {:?}", char_map);
-}
\ No newline at end of file
+    char_map
+}
+
+pub fn print_word_counts(text: &str, out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "char map: {:?}", word_counts(text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chapter_11::capture::{capture, capture_lines};
+
+    fn scores() -> HashMap<String, i32> {
+        HashMap::from([(String::from("Blue"), 10), (String::from("Yellow"), 50)])
+    }
+
+    #[test]
+    fn prints_a_teams_score() {
+        let output = capture(|out| print_team_score(&scores(), "Blue", out));
+        assert_eq!(output, "the score for the Blue team is: Some(10)\n");
+    }
+
+    #[test]
+    fn prints_none_for_a_missing_team() {
+        let output = capture(|out| print_team_score(&scores(), "Red", out));
+        assert_eq!(output, "the score for the Red team is: None\n");
+    }
+
+    #[test]
+    fn prints_every_score() {
+        // a HashMap has no order, so sort the lines before comparing
+        let mut lines = capture_lines(|out| print_scores(&scores(), out));
+        lines.sort();
+        assert_eq!(lines, vec!["Blue: 10", "Yellow: 50"]);
+    }
+
+    #[test]
+    fn run_prints_the_walkthrough() {
+        // capture hands us a `&mut dyn Write`, and `&mut dyn Write` is itself
+        // a Write, so it can go in as run_to's `impl Write`
+        let lines = capture_lines(|mut out| run_to(&mut out));
+        assert_eq!(lines[1], "the score for the Blue team is: Some(10)");
+        assert!(lines.contains(&String::from(
+            "entry returns a mutable reference for entries that exist: 50"
+        )));
+        // two HashMaps can print in different orders, so just look for the count
+        let last = lines.last().unwrap();
+        assert!(last.starts_with("char map: ") && last.contains("\"world\": 2"));
+    }
+
+    #[test]
+    fn prints_word_counts() {
+        let output = capture(|out| print_word_counts("world", out));
+        assert_eq!(output, "char map: {\"world\": 1}\n");
+
+        let counts = word_counts("hello world wonderful world");
+        assert_eq!(counts["world"], 2);
+        assert_eq!(counts["hello"], 1);
+    }
+}
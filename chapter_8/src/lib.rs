@@ -0,0 +1,47 @@
+// Chapter 8: Common Collections (library)
+// The walkthroughs for vectors, strings and hash maps, a template engine the
+// strings walkthrough finishes with, and a Caesar cipher that puts all three
+// collections to work
+
+pub mod cipher;
+pub mod hash_maps;
+pub mod strings;
+pub mod template;
+pub mod vectors;
+
+use domain::Section;
+
+// every section that runs without any input, in the book's order. The runner
+// (and anything else that wants them all) goes through this list
+pub fn exercises() -> Vec<Section> {
+    vec![
+        Section {
+            name: "vectors",
+            run: || {
+                vectors::run();
+                Ok(())
+            },
+        },
+        Section {
+            name: "strings",
+            run: || {
+                strings::run();
+                Ok(())
+            },
+        },
+        Section {
+            name: "hash_maps",
+            run: || {
+                hash_maps::run();
+                Ok(())
+            },
+        },
+        Section {
+            name: "cipher",
+            run: || {
+                cipher::run();
+                Ok(())
+            },
+        },
+    ]
+}
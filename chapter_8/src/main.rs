@@ -4,12 +4,9 @@
 // is stored on the heap, which means the amount of data to be stored does not
 // need to be known at compile time
 
-mod vectors;
-mod strings;
-mod hash_maps;
-
 fn main() {
-    // vectors::run();
-    // strings::run();
-    hash_maps::run();
+    // chapter_8::vectors::run();
+    // chapter_8::strings::run();
+    chapter_8::hash_maps::run();
+    chapter_8::cipher::run();
 }
@@ -8,7 +8,18 @@
 
 // strings are implemented as a collection of bytes, plus some methods to provide
 // useful functionality when the bytes are interpreted as text.
-pub fn _run() {
+
+use crate::template::render;
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+// run_to writes to `out` instead of calling println!, so the tests at the
+// bottom can capture what it prints. run keeps printing to stdout
+pub fn run() {
+    run_to(&mut io::stdout()).unwrap();
+}
+
+pub fn run_to(out: &mut impl Write) -> io::Result<()> {
     /*
     What is a String?
     Rust only has 1 "string type" in the core, which is the "string slice" type: `str`,
@@ -25,16 +36,16 @@ pub fn _run() {
     // String shares a lot of operations as Vec<T> because a String
     // is actually a wrapper around a vector of bytes. One example is `new()`
     let mut s = String::new();
-    println!("what does just a new String look like? '{s}'");
+    writeln!(out, "what does just a new String look like? '{s}'")?;
     // we can load data into s now, some initial data to start the String with
     let data = "initial contents";
     s = data.to_string();
-    println!("and now we have loaded data into s: '{s}'");
+    writeln!(out, "and now we have loaded data into s: '{s}'")?;
     // this works too: s = "initial contents".to_string();
     // We transform "initial contents" from a str to a String
     // this is the same as:
     let s = String::from("initial contents");
-    println!("Another String from a str: '{s}'");
+    writeln!(out, "Another String from a str: '{s}'")?;
     // Strings are UTF-8, so any UTF-8 data work
     let _hello = String::from("السلام عليكم");
     let _hello = String::from("Dobrý den");
@@ -55,30 +66,31 @@ pub fn _run() {
     // Appending to a String with push_str and push
     // push_str appends a string slice
     let mut string = String::from("foo");
-    println!("our original String: {string}");
+    writeln!(out, "our original String: {string}")?;
     string.push_str("bar");
-    println!("Our String after push_str: {string}");
+    writeln!(out, "Our String after push_str: {string}")?;
     // push_str takes a slice because we don't necessarily want to take ownership
     let mut s1 = String::from("foo");
     let s2 = "bar";
     s1.push_str(s2); // <-- not taking ownership of s2
-    println!("s2 is {}", s2); // <-- so we can use it later
+    writeln!(out, "s2 is {}", s2)?; // <-- so we can use it later
 
     // the push method takes a single character (char) and adds it to a String
     let mut s3 = String::from("lo");
     s3.push('l');
-    println!("pushed char: 'l' on the end: {s3}");
+    writeln!(out, "pushed char: 'l' on the end: {s3}")?;
 
     // Concatenation with the + Operator or format! Macro
     {
         let s1 = String::from("Hello");
         let s2 = String::from(", world!");
-        println!("s1 is: {s1}");
-        println!("s2 is: {s2}");
+        writeln!(out, "s1 is: {s1}")?;
+        writeln!(out, "s2 is: {s2}")?;
         let s3 = s1 + &s2; // s1 is moved to here and can't be used again, s2 can;
-        println!(
+        writeln!(
+            out,
             "s1 + &s2 = {s3}. Also, s2 is still available because it was a reference, s2: {s2}"
-        );
+        )?;
     }
     // the `+` operator uses the `add` method whose signature looks like:
     // fn add(self, s: &str) -> String {}
@@ -97,7 +109,7 @@ pub fn _run() {
         let s3 = String::from("toe");
 
         let s = format!("{}-{}-{}", s1, s2, s3);
-        println!("Our three strings formatted together is: {s}");
+        writeln!(out, "Our three strings formatted together is: {s}")?;
     }
     // format! works a lot like println! except instead of printing the output on
     // the screen it returns a String with the contents. This call also doesn't take
@@ -149,7 +161,7 @@ pub fn _run() {
     // if we do want to "index into" a string, Rust requires us to be a bit more specific
     let hello = "Здравствуйте";
     let s = &hello[0..4];
-    println!("our slice from [0..4] is equivalent to: {s}");
+    writeln!(out, "our slice from [0..4] is equivalent to: {s}")?;
     // this is a slice of 4 bytes, 2 bytes per character = 2 characters.
     // If this wasn't a valid slice, eg [0..1] Rust would panic at runtime
     // this operation should be done with caution to avoid program crashes
@@ -159,11 +171,11 @@ pub fn _run() {
     // whether you want characters or bytes. For individual Unicode scalar values,
     // use the `chars` method
     for c in "Зд".chars() {
-        println!("Iterating over chars: {c}");
+        writeln!(out, "Iterating over chars: {c}")?;
     }
     // or we can iterate over raw bytes:
     for b in "Зд".bytes() {
-        println!("Iterating over the same as bytes: {b}");
+        writeln!(out, "Iterating over the same as bytes: {b}")?;
     }
 
     /*
@@ -173,4 +185,46 @@ pub fn _run() {
     complexity to the programmer, meaning they have to put more thought into
     handling them, but in doing so, helps avoid many bugs
     */
+
+    // Putting it together: a tiny template engine (render, in template.rs)
+    let mut vars = HashMap::new();
+    vars.insert("name", "Ferris");
+    vars.insert("lang", "Rust");
+    match render("Hello {name}, welcome to {lang}! {{not a key}}", &vars) {
+        Ok(greeting) => writeln!(out, "{greeting}")?,
+        Err(e) => writeln!(out, "template error: {e}")?,
+    }
+    if let Err(e) = render("Hello {nmae}", &vars) {
+        writeln!(out, "template error: {e}")?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chapter_11::capture::capture_lines;
+
+    #[test]
+    fn run_prints_the_walkthrough() {
+        let lines = capture_lines(|mut out| run_to(&mut out));
+        assert_eq!(lines[0], "what does just a new String look like? ''");
+        assert!(lines.contains(&String::from("Our String after push_str: foobar")));
+        assert!(lines.contains(&String::from(
+            "Our three strings formatted together is: tic-tac-toe"
+        )));
+        assert!(lines.contains(&String::from("our slice from [0..4] is equivalent to: Зд")));
+        // "Зд" is 2 chars but 4 bytes
+        let count = |prefix: &str| lines.iter().filter(|l| l.starts_with(prefix)).count();
+        assert_eq!(count("Iterating over chars"), 2);
+        assert_eq!(count("Iterating over the same as bytes"), 4);
+    }
+
+    #[test]
+    fn run_renders_a_template() {
+        let lines = capture_lines(|mut out| run_to(&mut out));
+        assert!(lines.contains(&String::from("Hello Ferris, welcome to Rust! {not a key}")));
+        assert_eq!(lines.last().unwrap(), "template error: no value for {nmae}");
+    }
 }
@@ -0,0 +1,162 @@
+// 8.2 (continued) A Tiny Template Engine
+
+/*
+format! needs its template at compile time. render does the same job for a
+template that only shows up at runtime (read from a file, typed in by a user):
+every {name} is swapped for vars["name"]. Like format!, {{ and }} are escapes
+for a literal { and }.
+
+Walking the template with char_indices instead of indexing into it keeps
+every slice on a char boundary (see Slicing Strings in strings.rs), so a
+template full of "Здравствуйте" works the same as one in ASCII. The names are
+slices of the template itself, so looking them up in the HashMap doesn't copy
+anything.
+*/
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
+pub enum TemplateError {
+    // {name} with no name in vars (an empty {} counts, its name is "")
+    UnknownKey(String),
+    // a { that never gets its }, `at` is its byte index in the template
+    Unclosed { at: usize },
+    // a } with no { before it
+    UnmatchedClose { at: usize },
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TemplateError::UnknownKey(name) => write!(f, "no value for {{{}}}", name),
+            TemplateError::Unclosed { at } => write!(f, "the {{ at byte {} is never closed", at),
+            TemplateError::UnmatchedClose { at } => {
+                write!(f, "the }} at byte {} has no {{ to close", at)
+            }
+        }
+    }
+}
+
+impl Error for TemplateError {}
+
+pub fn render(template: &str, vars: &HashMap<&str, &str>) -> Result<String, TemplateError> {
+    // the output is usually about as long as the template
+    let mut rendered = String::with_capacity(template.len());
+    let mut chars = template.char_indices().peekable();
+
+    while let Some((at, c)) = chars.next() {
+        let next = chars.peek().map(|&(_, next)| next);
+        match (c, next) {
+            // escapes: two braces in a row are one brace in the output
+            ('{', Some('{')) | ('}', Some('}')) => {
+                chars.next();
+                rendered.push(c);
+            }
+            ('{', _) => {
+                let start = at + 1;
+                let end = match template[start..].find('}') {
+                    Some(len) => start + len,
+                    None => return Err(TemplateError::Unclosed { at }),
+                };
+                let name = &template[start..end];
+                match vars.get(name) {
+                    Some(value) => rendered.push_str(value),
+                    None => return Err(TemplateError::UnknownKey(name.to_string())),
+                }
+                // skip past the name and its closing }
+                for (i, _) in chars.by_ref() {
+                    if i == end {
+                        break;
+                    }
+                }
+            }
+            ('}', _) => return Err(TemplateError::UnmatchedClose { at }),
+            (c, _) => rendered.push(c),
+        }
+    }
+
+    Ok(rendered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars<'a>(pairs: &[(&'a str, &'a str)]) -> HashMap<&'a str, &'a str> {
+        pairs.iter().cloned().collect()
+    }
+
+    #[test]
+    fn substitutes_placeholders() {
+        let vars = vars(&[("name", "Ferris"), ("count", "3")]);
+        assert_eq!(
+            render("{name} has {count} claws, {name}!", &vars),
+            Ok(String::from("Ferris has 3 claws, Ferris!"))
+        );
+        // nothing to substitute is fine too
+        assert_eq!(render("", &vars), Ok(String::new()));
+        assert_eq!(render("no braces", &vars), Ok(String::from("no braces")));
+        // placeholders right next to each other
+        assert_eq!(render("{count}{count}", &vars), Ok(String::from("33")));
+    }
+
+    #[test]
+    fn doubled_braces_are_escapes() {
+        let vars = vars(&[("x", "1")]);
+        assert_eq!(render("{{x}}", &vars), Ok(String::from("{x}")));
+        assert_eq!(render("{{{x}}}", &vars), Ok(String::from("{1}")));
+        assert_eq!(render("}}{{", &vars), Ok(String::from("}{")));
+    }
+
+    #[test]
+    fn unknown_keys_are_reported() {
+        let vars = vars(&[("name", "Ferris")]);
+        assert_eq!(
+            render("Hi {nmae}", &vars),
+            Err(TemplateError::UnknownKey(String::from("nmae")))
+        );
+        // names are matched exactly, spaces and all
+        assert_eq!(
+            render("Hi { name }", &vars),
+            Err(TemplateError::UnknownKey(String::from(" name ")))
+        );
+        assert_eq!(
+            render("{}", &vars),
+            Err(TemplateError::UnknownKey(String::new()))
+        );
+    }
+
+    #[test]
+    fn unbalanced_braces_are_errors() {
+        let vars = vars(&[("name", "Ferris")]);
+        assert_eq!(
+            render("Hi {name", &vars),
+            Err(TemplateError::Unclosed { at: 3 })
+        );
+        assert_eq!(
+            render("Hi name}", &vars),
+            Err(TemplateError::UnmatchedClose { at: 7 })
+        );
+        assert_eq!(
+            TemplateError::Unclosed { at: 3 }.to_string(),
+            "the { at byte 3 is never closed"
+        );
+    }
+
+    #[test]
+    fn works_with_any_utf8() {
+        // "Здравствуйте" is 2 bytes per char, so byte indexes aren't char
+        // indexes: the { below is at byte 25, not char 13
+        let vars = vars(&[("имя", "Мир"), ("emoji", "🦀")]);
+        assert_eq!(
+            render("Здравствуйте, {имя} {emoji}", &vars),
+            Ok(String::from("Здравствуйте, Мир 🦀"))
+        );
+        assert_eq!(
+            render("Здравствуйте {", &vars),
+            Err(TemplateError::Unclosed { at: 25 })
+        );
+    }
+}
@@ -2,13 +2,21 @@
 // Vec<t> : store multiple values in a single structure that puts all the values
 // next to each other in memory
 
-pub fn _run() {
+use std::io::{self, Write};
+
+// run_to writes to `out` instead of calling println!, so the tests at the
+// bottom can capture what it prints. run keeps printing to stdout
+pub fn run() {
+    run_to(&mut io::stdout()).unwrap();
+}
+
+pub fn run_to(out: &mut impl Write) -> io::Result<()> {
     // Creating a New Vector
     let v: Vec<i32> = Vec::new(); // providing type annotation here because we didn't provide initial values.
-    println!("our vector created without initial values: {:?}", v);
+    writeln!(out, "our vector created without initial values: {:?}", v)?;
     // Normally Rust can infer this though when values are provided. Use the vec! macro
     let v2 = vec![1, 2, 3];
-    println!("our vector created with initial values: {:?}", v2);
+    writeln!(out, "our vector created with initial values: {:?}", v2)?;
 
     // Updating a vector
     // we add values to a vector using the push method
@@ -17,7 +25,7 @@ pub fn _run() {
     v3.push(6);
     v3.push(7);
     v3.push(8);
-    println!("we pushed these values into this vector: {:?}", v3);
+    writeln!(out, "we pushed these values into this vector: {:?}", v3)?;
 
     // Reading Elements of Vectors
     // 2 ways: indexing or `get` method
@@ -25,14 +33,14 @@ pub fn _run() {
     // indexing
     let v4 = vec![1, 2, 3, 4, 5];
     let third: &i32 = &v4[2];
-    println!("the third element of {:?} is {third}", v4);
+    writeln!(out, "the third element of {:?} is {third}", v4)?;
 
     // get method
     let third: Option<&i32> = v4.get(2);
     match third {
-        Some(third) => println!("the third element is {}", third),
-        None => println!("there is no third element..."),
-    }
+        Some(third) => writeln!(out, "the third element is {}", third),
+        None => writeln!(out, "there is no third element..."),
+    }?;
 
     // these different methods allow us to tailor the behavior of the program
     // when we try accessing a non-existent index
@@ -49,7 +57,7 @@ pub fn _run() {
 
     v.push(6); <-- mutable borrow
 
-    println!("The first element is: {}", first); <-- another immutable borrow
+    writeln!(out, "The first element is: {}", first)?; <-- another immutable borrow
     */
 
     // This seems a bit weird at first glance since we're only getting a reference.
@@ -62,23 +70,26 @@ pub fn _run() {
     // using a for loop to get immutable references:
     let v6 = vec![100, 32, 57];
     for i in &v6 {
-        println!("{i}");
+        writeln!(out, "{i}")?;
     }
 
     // iterate over mutable references and make changes
     let mut v7 = vec![100, 32, 57];
-    println!("v7 before the loop: {:?}", v7);
+    writeln!(out, "v7 before the loop: {:?}", v7)?;
     for i in &mut v7 {
         *i += 50; // <-- * is the dereference operator and will be discussed more in Chapter 15
     }
-    println!("v7 after the loop: {:?}", v7);
+    writeln!(out, "v7 after the loop: {:?}", v7)?;
 
     // Using an enum to Store Multiple Types
     // Vectors can only hold a single type for all values. There are definitely 
     // cases where we want to be able to store a variety of types in a single vector though.
     // Variants of an enum are all under the same enum type though
 
+    // nothing reads the values back out of the cells here, {:?} only prints
+    // them, so the dead code lint is switched off for the enum
     #[derive(Debug)]
+    #[allow(dead_code)]
     enum SpreadsheetCell {
         Int(i32),
         Float(f64),
@@ -90,11 +101,42 @@ pub fn _run() {
         SpreadsheetCell::Float(10.12),
         SpreadsheetCell::Text(String::from("blue")),
     ];
-    println!("a vector with multiple types, thanks to enums: {:?}", row);
+    writeln!(
+        out,
+        "a vector with multiple types, thanks to enums: {:?}",
+        row
+    )?;
 
     // if you don't know the exhaustive list of types before runtime you would
     // want to use a Trait (more on that in Chapter 17)
 
     // there are plenty more vector methods other than just push. there's also
     // pop, to return the last element, amongst many others
-}
\ No newline at end of file
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chapter_11::capture::capture_lines;
+
+    #[test]
+    fn run_prints_the_walkthrough() {
+        let lines = capture_lines(|mut out| run_to(&mut out));
+        let has = |line: &str| lines.iter().any(|l| l == line);
+        assert!(has("our vector created without initial values: []"));
+        assert!(has("the third element is 3"));
+        assert!(has("v7 after the loop: [150, 82, 107]"));
+        assert!(has(
+            "a vector with multiple types, thanks to enums: [Int(3), Float(10.12), Text(\"blue\")]"
+        ));
+        // the for loop prints each element on its own line, in order
+        let looped: Vec<&str> = lines
+            .iter()
+            .map(String::as_str)
+            .filter(|l| ["100", "32", "57"].contains(l))
+            .collect();
+        assert_eq!(looped, ["100", "32", "57"]);
+    }
+}
@@ -0,0 +1,204 @@
+// 9.2 (continued) Propagating Errors: a File Backup Utility
+
+// A small but realistic use of everything in 9.2. backup() copies a file into a
+// backup directory and adds a timestamp to the name. Instead of returning a
+// plain io::Error we return our own error type, so the caller can tell *why*
+// the backup failed and decide what to do about it (instead of us panicking).
+
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug)]
+pub enum BackupError {
+    // the file we were asked to back up isn't there (or isn't a file)
+    MissingSource(PathBuf),
+    // we couldn't create the backup file in the destination directory
+    UnwritableDestination(PathBuf, io::Error),
+    // a backup with the same name already exists, we never overwrite it
+    Collision(PathBuf),
+    // anything else that went wrong while copying
+    Io(io::Error),
+}
+
+impl fmt::Display for BackupError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BackupError::MissingSource(path) => {
+                write!(f, "source file {} does not exist", path.display())
+            }
+            BackupError::UnwritableDestination(path, e) => {
+                write!(f, "can't write backup to {}: {}", path.display(), e)
+            }
+            BackupError::Collision(path) => {
+                write!(f, "backup {} already exists", path.display())
+            }
+            BackupError::Io(e) => write!(f, "backup failed: {}", e),
+        }
+    }
+}
+
+impl Error for BackupError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BackupError::UnwritableDestination(_, e) | BackupError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+// this is what lets `?` turn an io::Error into a BackupError for us
+impl From<io::Error> for BackupError {
+    fn from(error: io::Error) -> Self {
+        BackupError::Io(error)
+    }
+}
+
+pub fn backup(src: &Path, dst_dir: &Path) -> Result<PathBuf, BackupError> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    backup_with_timestamp(src, dst_dir, timestamp)
+}
+
+// split out from backup() so the timestamp can be fixed, which makes a
+// collision something we can actually reproduce
+pub fn backup_with_timestamp(
+    src: &Path,
+    dst_dir: &Path,
+    timestamp: u64,
+) -> Result<PathBuf, BackupError> {
+    if !src.is_file() {
+        return Err(BackupError::MissingSource(src.to_path_buf()));
+    }
+    if !dst_dir.is_dir() {
+        return Err(BackupError::UnwritableDestination(
+            dst_dir.to_path_buf(),
+            io::Error::new(ErrorKind::NotFound, "not a directory"),
+        ));
+    }
+
+    let file_name = src
+        .file_name()
+        .ok_or_else(|| BackupError::MissingSource(src.to_path_buf()))?;
+    let mut backup_name = file_name.to_os_string();
+    backup_name.push(format!(".{timestamp}"));
+    let dst = dst_dir.join(backup_name);
+
+    // open the source before creating anything, so a source we can't read
+    // fails here without leaving an empty backup behind (that would turn the
+    // next try into a Collision)
+    let mut src_file = File::open(src)?;
+
+    // create_new fails if the file is already there, which means checking for
+    // a collision and creating the file happen in a single step
+    let mut dst_file = match OpenOptions::new().write(true).create_new(true).open(&dst) {
+        Ok(file) => file,
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+            return Err(BackupError::Collision(dst));
+        }
+        Err(e) => return Err(BackupError::UnwritableDestination(dst, e)),
+    };
+
+    if let Err(e) = io::copy(&mut src_file, &mut dst_file) {
+        // don't leave a half written backup behind
+        let _ = fs::remove_file(&dst);
+        return Err(e.into());
+    }
+
+    Ok(dst)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chapter_11::temp::TempDir;
+
+    #[test]
+    fn copies_file_with_timestamp_suffix() {
+        let dir = TempDir::new("chapter_9_backup_copies");
+        let src = dir.file("notes.txt", "remember the milk");
+        let dst_dir = dir.path().join("backups");
+        fs::create_dir(&dst_dir).unwrap();
+
+        let dst = backup_with_timestamp(&src, &dst_dir, 1234).unwrap();
+
+        assert_eq!(dst, dst_dir.join("notes.txt.1234"));
+        assert_eq!(fs::read_to_string(&dst).unwrap(), "remember the milk");
+    }
+
+    #[test]
+    fn missing_source() {
+        let dir = TempDir::new("chapter_9_backup_missing");
+        let result = backup(&dir.path().join("nope.txt"), dir.path());
+        assert!(matches!(result, Err(BackupError::MissingSource(_))));
+    }
+
+    #[test]
+    fn unwritable_destination() {
+        let dir = TempDir::new("chapter_9_backup_unwritable");
+        let src = dir.file("notes.txt", "data");
+
+        let result = backup(&src, &dir.path().join("does_not_exist"));
+        assert!(matches!(
+            result,
+            Err(BackupError::UnwritableDestination(_, _))
+        ));
+
+        // a file where a directory should be is no better
+        let result = backup(&src, &src);
+        assert!(matches!(
+            result,
+            Err(BackupError::UnwritableDestination(_, _))
+        ));
+    }
+
+    #[test]
+    fn collision_does_not_overwrite() {
+        let dir = TempDir::new("chapter_9_backup_collision");
+        let src = dir.file("notes.txt", "first");
+        let dst_dir = dir.path().join("backups");
+        fs::create_dir(&dst_dir).unwrap();
+
+        let first = backup_with_timestamp(&src, &dst_dir, 1).unwrap();
+        fs::write(&src, "second").unwrap();
+        let result = backup_with_timestamp(&src, &dst_dir, 1);
+
+        match result {
+            Err(BackupError::Collision(path)) => assert_eq!(path, first),
+            other => panic!("expected a collision, got {:?}", other),
+        }
+        assert_eq!(fs::read_to_string(&first).unwrap(), "first");
+    }
+
+    // a source that's there but can't be read. Permissions are how we get one,
+    // which makes this a unix only test
+    #[cfg(unix)]
+    #[test]
+    fn unreadable_source_leaves_no_backup() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TempDir::new("chapter_9_backup_unreadable");
+        let src = dir.file("notes.txt", "secret");
+        let dst_dir = dir.path().join("backups");
+        fs::create_dir(&dst_dir).unwrap();
+        fs::set_permissions(&src, fs::Permissions::from_mode(0o000)).unwrap();
+        if File::open(&src).is_ok() {
+            // root can read it anyway, so there's nothing to test
+            return;
+        }
+
+        let result = backup_with_timestamp(&src, &dst_dir, 1);
+        assert!(matches!(result, Err(BackupError::Io(_))));
+        assert_eq!(fs::read_dir(&dst_dir).unwrap().count(), 0);
+
+        // once it's readable again the same backup works, no Collision
+        fs::set_permissions(&src, fs::Permissions::from_mode(0o644)).unwrap();
+        let dst = backup_with_timestamp(&src, &dst_dir, 1).unwrap();
+        assert_eq!(fs::read_to_string(dst).unwrap(), "secret");
+    }
+}
@@ -0,0 +1,15 @@
+// The chapter_9 commands again, with a different main. This one returns Exit
+// (exit_codes.rs), our own Termination type, so a script can tell a missing
+// file (74) from a bad guess (65) or a mistyped command (64)
+//
+//     cargo run --bin exit_codes -- guess 500; echo $?
+
+use std::env;
+
+use chapter_9::commands;
+use chapter_9::exit_codes::Exit;
+
+fn main() -> Exit {
+    let args: Vec<String> = env::args().skip(1).collect();
+    Exit::from(commands::run(&args))
+}
@@ -0,0 +1,35 @@
+// 9.3 (continued) The chapter_9 Commands
+
+// What the chapter_9 binaries do when they're given arguments:
+//   guess <1-100>               check a guess with the validation rules
+//   backup <file> <backup dir>  copy a file into a backup directory
+// Every failure comes back as a Box<dyn Error>, the caller decides how to
+// report it. main.rs lets `?` hand it to the standard library (exit code 1),
+// bin/exit_codes.rs picks an exit code for each kind of error.
+
+use std::error::Error;
+use std::path::Path;
+
+use crate::backup::backup;
+use crate::exit_codes::UsageError;
+use crate::validation::{Guess, GuessRules, ValidationErrors};
+
+pub const USAGE: &str = "chapter_9 [guess <1-100> | backup <file> <backup dir>]";
+
+pub fn run(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match args.as_slice() {
+        ["guess", value] => {
+            let guess = Guess::validate(value.parse()?).map_err(ValidationErrors)?;
+            println!("You guessed {}", guess.value());
+        }
+        ["backup", src, dst_dir] => {
+            let dst = backup(Path::new(src), Path::new(dst_dir))?;
+            println!("Backed up {} to {}", src, dst.display());
+        }
+        _ => return Err(Box::new(UsageError(String::from(USAGE)))),
+    }
+
+    Ok(())
+}
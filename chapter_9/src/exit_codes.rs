@@ -0,0 +1,125 @@
+// 9.3 (continued) Returning Errors from main
+
+// main can return a Result<(), E> instead of (), which lets us use `?` right in
+// main. The catch is that any Err ends the program with exit code 1, so a
+// script calling our binary can't tell a missing file from a bad guess.
+//
+// What main actually returns is anything that implements the
+// std::process::Termination trait. Result<(), E> is one such type, but we can
+// write our own: Exit wraps the Result from the commands (commands.rs) and
+// maps each kind of error to its own exit code. main.rs keeps the book's
+// Result<(), Box<dyn Error>>, src/bin/exit_codes.rs is the same commands
+// with Exit as the return type.
+//
+// The codes follow the old BSD sysexits.h convention, which a lot of command
+// line tools still use.
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::num::ParseIntError;
+use std::process::{ExitCode, Termination};
+
+use crate::backup::BackupError;
+use crate::validation::ValidationErrors;
+use domain::prelude::*;
+
+pub const USAGE_ERROR: u8 = 64; // EX_USAGE: the command was used incorrectly
+pub const VALIDATION_ERROR: u8 = 65; // EX_DATAERR: the input data was bad
+pub const IO_ERROR: u8 = 74; // EX_IOERR: an error while doing I/O
+
+#[derive(Debug)]
+pub struct UsageError(pub String);
+
+impl fmt::Display for UsageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "usage: {}", self.0)
+    }
+}
+
+impl Error for UsageError {}
+
+// A Box<dyn Error> has forgotten its concrete type, is::<T>() lets us ask
+// "are you actually an io::Error?" (downcast_ref::<T>() would hand it back too)
+pub fn exit_code(error: &(dyn Error + 'static)) -> u8 {
+    // ExerciseError is one type with a few kinds of error inside, so this one
+    // needs downcast_ref to look at which
+    if let Some(error) = error.downcast_ref::<ExerciseError>() {
+        return match error {
+            ExerciseError::Io(_) => IO_ERROR,
+            ExerciseError::BadInput { .. } | ExerciseError::OutOfBounds { .. } => VALIDATION_ERROR,
+        };
+    }
+
+    if error.is::<io::Error>() || error.is::<BackupError>() {
+        IO_ERROR
+    } else if error.is::<ValidationErrors>() || error.is::<ParseIntError>() {
+        VALIDATION_ERROR
+    } else if error.is::<UsageError>() {
+        USAGE_ERROR
+    } else {
+        1
+    }
+}
+
+pub struct Exit(pub Result<(), Box<dyn Error>>);
+
+impl Termination for Exit {
+    fn report(self) -> ExitCode {
+        match self.0 {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                ExitCode::from(exit_code(e.as_ref()))
+            }
+        }
+    }
+}
+
+impl From<Result<(), Box<dyn Error>>> for Exit {
+    fn from(result: Result<(), Box<dyn Error>>) -> Self {
+        Exit(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validation::{Guess, GuessRules};
+
+    #[test]
+    fn io_errors_get_their_own_code() {
+        let error: Box<dyn Error> = Box::new(io::Error::other("disk on fire"));
+        assert_eq!(exit_code(error.as_ref()), IO_ERROR);
+    }
+
+    #[test]
+    fn validation_errors_get_their_own_code() {
+        let error: Box<dyn Error> = Box::new(ValidationErrors(Guess::validate(0).unwrap_err()));
+        assert_eq!(exit_code(error.as_ref()), VALIDATION_ERROR);
+
+        let error: Box<dyn Error> = Box::new("abc".parse::<i32>().unwrap_err());
+        assert_eq!(exit_code(error.as_ref()), VALIDATION_ERROR);
+    }
+
+    #[test]
+    fn exercise_errors_pick_a_code_by_kind() {
+        let io: Box<dyn Error> = Box::new(ExerciseError::Io(io::Error::other("no hello.txt")));
+        assert_eq!(exit_code(io.as_ref()), IO_ERROR);
+
+        let index = ExerciseError::OutOfBounds { index: 9, len: 5 };
+        assert_eq!(exit_code(&index), VALIDATION_ERROR);
+        let input = ExerciseError::BadInput {
+            expected: "an index",
+            got: String::from("nine"),
+        };
+        assert_eq!(exit_code(&input), VALIDATION_ERROR);
+    }
+
+    #[test]
+    fn unknown_errors_fall_back_to_one() {
+        let error: Box<dyn Error> = Box::from("something else");
+        assert_eq!(exit_code(error.as_ref()), 1);
+        assert_eq!(exit_code(&UsageError(String::new())), USAGE_ERROR);
+    }
+}
@@ -0,0 +1,35 @@
+// Chapter 9: Error Handling (library)
+// Errors as values: validating input with Result instead of panicking, ? on
+// Options for lookups that may come up empty, a backup routine that reports
+// why it failed, and turning all of it into a process exit code. The
+// recoverable_with_result and when_to_panic walkthroughs are here too,
+// exercises() lists them for the runner
+
+pub mod backup;
+pub mod commands;
+pub mod exit_codes;
+pub mod option_lookup;
+pub mod recoverable_with_result;
+pub mod validation;
+pub mod when_to_panic;
+
+use domain::Section;
+
+// every walkthrough, for the runner. panicking.rs isn't one of them, panicking
+// is all it does. recoverable_with_result already returns an ExerciseError
+// (from opening or creating hello.txt), so its run goes in as it is
+pub fn exercises() -> Vec<Section> {
+    vec![
+        Section {
+            name: "recoverable_with_result",
+            run: recoverable_with_result::run,
+        },
+        Section {
+            name: "when_to_panic",
+            run: || {
+                when_to_panic::run();
+                Ok(())
+            },
+        },
+    ]
+}
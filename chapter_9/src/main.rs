@@ -1,11 +1,26 @@
 // Chapter 9: Error Handling
 
 mod panicking;
-mod recoverable_with_result;
-mod when_to_panic;
 
-fn main() {
-    // panicking::run();
-    // recoverable_with_result::run();
-    when_to_panic::run();
+use std::env;
+use std::error::Error;
+
+// The book's way of using ? in main: main returns a Result, and an Err is
+// printed (with Debug) and ends the program with exit code 1. For an exit code
+// per kind of error, see src/bin/exit_codes.rs
+//
+// with no arguments we run the walkthroughs like before, otherwise one of the
+// commands in commands.rs
+fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    if args.is_empty() {
+        // panicking::run();
+        // chapter_9::recoverable_with_result::run()?;
+        chapter_9::when_to_panic::run();
+        return Ok(());
+    }
+
+    chapter_9::commands::run(&args)?;
+    Ok(())
 }
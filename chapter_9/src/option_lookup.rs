@@ -0,0 +1,120 @@
+// 9.2 (continued) Using the `?` Operator with Option<T>
+
+// At the end of 9.2 we saw that `?` isn't just for Result, it also works on
+// Option<T> as long as the function it is used in returns an Option.
+// On a None, `?` returns None early from the whole function, on a Some it
+// unwraps the value and keeps going. This is really nice for lookups that
+// have to dig through a few layers of nested data.
+
+use std::collections::HashMap;
+
+// a tiny config: sections (like "db") that each hold key/value pairs (like "host")
+pub type Config = HashMap<String, HashMap<String, String>>;
+
+pub fn db_host(config: &Config) -> Option<&str> {
+    // each `?` bails out with None if the section or key isn't there
+    let host = config.get("db")?.get("host")?;
+    Some(host)
+}
+
+// the same idea, but with the section and key passed in
+pub fn lookup<'a>(config: &'a Config, section: &str, key: &str) -> Option<&'a str> {
+    let value = config.get(section)?.get(key)?;
+    Some(value)
+}
+
+// `?` can be used on any Option in the chain, including one we create by
+// converting a parse Result into an Option with ok()
+pub fn db_port(config: &Config) -> Option<u16> {
+    config.get("db")?.get("port")?.parse().ok()
+}
+
+/*
+What we can't do is use `?` on an Option inside a function that returns a Result
+(or the other way around). This won't compile:
+
+fn db_host_or_err(config: &Config) -> Result<&str, LookupError> {
+    let host = config.get("db")?.get("host")?; <-- `?` on an Option in a fn returning Result
+    Ok(host)
+}
+
+The compiler won't guess what error a None should turn into. We have to tell it
+explicitly, and ok_or does exactly that: Some(v) -> Ok(v), None -> Err(the error we gave it)
+*/
+
+#[derive(Debug, PartialEq)]
+pub enum LookupError {
+    MissingSection(String),
+    MissingKey(String),
+}
+
+pub fn require<'a>(config: &'a Config, section: &str, key: &str) -> Result<&'a str, LookupError> {
+    // ok_or builds the error every time, even on a hit. ok_or_else takes a
+    // closure instead, which only runs on a miss (closures are Chapter 13)
+    let values = config
+        .get(section)
+        .ok_or(LookupError::MissingSection(section.to_string()))?;
+    let value = values
+        .get(key)
+        .ok_or(LookupError::MissingKey(key.to_string()))?;
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> Config {
+        let mut db = HashMap::new();
+        db.insert(String::from("host"), String::from("localhost"));
+        db.insert(String::from("port"), String::from("5432"));
+
+        let mut cache = HashMap::new();
+        cache.insert(String::from("port"), String::from("not a number"));
+
+        let mut config = HashMap::new();
+        config.insert(String::from("db"), db);
+        config.insert(String::from("cache"), cache);
+        config
+    }
+
+    #[test]
+    fn db_host_hit() {
+        assert_eq!(db_host(&config()), Some("localhost"));
+    }
+
+    #[test]
+    fn db_host_missing_section() {
+        assert_eq!(db_host(&Config::new()), None);
+    }
+
+    #[test]
+    fn lookup_missing_key() {
+        let config = config();
+        assert_eq!(lookup(&config, "db", "port"), Some("5432"));
+        assert_eq!(lookup(&config, "cache", "host"), None);
+    }
+
+    #[test]
+    fn db_port_parses_or_returns_none() {
+        assert_eq!(db_port(&config()), Some(5432));
+
+        let mut config = config();
+        config.get_mut("db").unwrap().remove("port");
+        assert_eq!(db_port(&config), None);
+    }
+
+    #[test]
+    fn require_reports_which_part_was_missing() {
+        let config = config();
+        assert_eq!(require(&config, "db", "host"), Ok("localhost"));
+        assert_eq!(
+            require(&config, "queue", "host"),
+            Err(LookupError::MissingSection(String::from("queue")))
+        );
+        assert_eq!(
+            require(&config, "cache", "host"),
+            Err(LookupError::MissingKey(String::from("host")))
+        );
+    }
+}
@@ -9,4 +9,4 @@ pub fn _run() {
     // v[99];
     // this panics, we can see the backtrace (think stack trace) by using the
     // RUST_BACKTRACE env variable when using cargo run
-}
\ No newline at end of file
+}
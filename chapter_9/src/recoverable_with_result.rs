@@ -3,7 +3,11 @@
 // most errors aren't serious enough to require the program to crash, sometimes
 // failures are expected and can be easily handled
 
-pub fn _run() {
+use domain::prelude::*;
+
+// returns the errors the book panics on, so main can report them (and pick an
+// exit code, see exit_codes.rs) instead of crashing halfway through
+pub fn run() -> Result<(), ExerciseError> {
     /*
     remember the Result type looks like:
     enum Result<T, E> {
@@ -27,9 +31,9 @@ pub fn _run() {
     println!("{:?}, this won't print because we'll panic first", greeting_file);
      */
 
-     // Matching on Different Errors
-     use std::fs::{self, File};
-     use std::io::ErrorKind;
+    // Matching on Different Errors
+    use std::fs::{self, File};
+    use std::io::ErrorKind;
 
     let greeting_file_result = File::open("hello.txt");
     // we now have a Result, time to handle the possibilities
@@ -38,14 +42,15 @@ pub fn _run() {
         Err(error) => match error.kind() {
             ErrorKind::NotFound => match File::create("hello.txt") {
                 Ok(fc) => fc,
-                Err(e) => panic!("Problem creating the file: {:?}", e),
+                // the book: panic!("Problem creating the file: {:?}", e)
+                Err(e) => return Err(ExerciseError::Io(e)),
             },
-            other_error => {
-                panic!("Problem opening the file: {:?}", other_error);
-            }
+            // the book: other_error => panic!("Problem opening the file: {:?}", other_error)
+            // `error` itself is still ours here, error.kind() only borrowed it
+            _ => return Err(ExerciseError::Io(error)),
         },
     };
-    println!("{:?}, this won't print because we'll panic first", greeting_file);
+    println!("opened (or created) hello.txt: {:?}", greeting_file);
 
     /*
     A more concise way to write this using closures and other methods
@@ -133,5 +138,7 @@ pub fn _run() {
     // value that `?` is used on, because `?` may perform an early return.
     // So `?` can only be used in functions that return Result, Option,
     // or another type that implements FromResidual
+    // (see option_lookup.rs for `?` used on Option and converting with ok_or)
 
-}
\ No newline at end of file
+    Ok(())
+}
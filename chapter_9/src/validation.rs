@@ -0,0 +1,252 @@
+// 9.3 (continued) Creating Custom Types for Validation, with Composable Rules
+
+// The Guess type in when_to_panic.rs checks its one rule and panics on the
+// first problem it finds. That works, but it doesn't scale well once a value
+// has several rules, and a caller would usually rather hear about *every*
+// problem at once (think of a form that highlights all the bad fields).
+//
+// A Validator<T> holds a list of rules. Each rule is a closure (Chapter 13)
+// that looks at the value and returns Ok(()) or an Err with a message.
+// validate() runs all of them and only hands back a Validated<T> if every
+// rule passed, otherwise it returns all of the failures it found.
+
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
+pub struct RuleError {
+    // which rule failed, in the order they were added
+    pub rule: usize,
+    pub message: String,
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "rule {} failed: {}", self.rule, self.message)
+    }
+}
+
+// A Vec<RuleError> on its own doesn't implement the Error trait, wrapping it
+// lets all of the failures travel through `?` into a Box<dyn Error>
+#[derive(Debug, PartialEq)]
+pub struct ValidationErrors(pub Vec<RuleError>);
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let messages: Vec<&str> = self.0.iter().map(|e| e.message.as_str()).collect();
+        write!(f, "{}", messages.join("; "))
+    }
+}
+
+impl Error for ValidationErrors {}
+
+impl From<Vec<RuleError>> for ValidationErrors {
+    fn from(errors: Vec<RuleError>) -> Self {
+        ValidationErrors(errors)
+    }
+}
+
+// Having a Validated<T> means the value passed every rule of the Validator
+// that made it. There's no other way to create one, the same trick Guess uses
+// with its private field.
+#[derive(Debug, PartialEq)]
+pub struct Validated<T> {
+    value: T,
+}
+
+impl<T> Validated<T> {
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+type Rule<T> = Box<dyn Fn(&T) -> Result<(), String>>;
+
+pub struct Validator<T> {
+    rules: Vec<Rule<T>>,
+}
+
+impl<T> Validator<T> {
+    pub fn new() -> Validator<T> {
+        Validator { rules: Vec::new() }
+    }
+
+    // takes and returns self so rules can be chained:
+    // Validator::new().add_rule(...).add_rule(...)
+    pub fn add_rule<F>(mut self, rule: F) -> Validator<T>
+    where
+        F: Fn(&T) -> Result<(), String> + 'static,
+    {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    pub fn validate(&self, value: T) -> Result<Validated<T>, Vec<RuleError>> {
+        let errors: Vec<RuleError> = self
+            .rules
+            .iter()
+            .enumerate()
+            .filter_map(|(rule, check)| {
+                check(&value)
+                    .err()
+                    .map(|message| RuleError { rule, message })
+            })
+            .collect();
+
+        if errors.is_empty() {
+            Ok(Validated { value })
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl<T> Default for Validator<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Guess again, checked by a Validator instead of a hand written if + panic!
+// Guess itself comes from the domain crate, and the rules are added to it
+// with an extension trait (like IteratorExt in chapter_13's adaptors.rs), since
+// we can't write an impl Guess block outside the crate that defines Guess.
+// `use chapter_9::validation::GuessRules` and then Guess::validate(50) works.
+//
+// The book's bounds are 1 and 100, the _in versions take any others (the
+// guessing game in chapter_2 uses them for its difficulty levels). The rule
+// messages are GuessError's, so a value gets the same words from either check
+pub use domain::{Guess, GuessError};
+
+pub trait GuessRules {
+    fn validator() -> Validator<i32> {
+        Self::validator_in(Guess::MIN, Guess::MAX)
+    }
+
+    fn validator_in(min: i32, max: i32) -> Validator<i32> {
+        Validator::new()
+            .add_rule(move |v| {
+                if *v < min {
+                    Err(GuessError::TooLow { min, value: *v }.to_string())
+                } else {
+                    Ok(())
+                }
+            })
+            .add_rule(move |v| {
+                if *v > max {
+                    Err(GuessError::TooHigh { max, value: *v }.to_string())
+                } else {
+                    Ok(())
+                }
+            })
+    }
+
+    fn validate(value: i32) -> Result<Guess, Vec<RuleError>> {
+        Self::validate_in(value, Guess::MIN, Guess::MAX)
+    }
+
+    fn validate_in(value: i32, min: i32, max: i32) -> Result<Guess, Vec<RuleError>> {
+        let validated = Self::validator_in(min, max).validate(value)?;
+        // the validator's rules are the same bounds try_new_in checks, so
+        // this can't fail
+        Ok(Guess::try_new_in(validated.into_inner(), min, max)
+            .expect("the validator already checked the bounds"))
+    }
+}
+
+impl GuessRules for Guess {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guess_in_range() {
+        assert_eq!(Guess::validate(50).unwrap().value(), 50);
+        assert!(Guess::validate(1).is_ok());
+        assert!(Guess::validate(100).is_ok());
+    }
+
+    #[test]
+    fn guess_out_of_range() {
+        let errors = Guess::validate(0).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].rule, 0);
+
+        let errors = Guess::validate(101).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].message,
+            "The value provided was greater than 100, got: 101"
+        );
+    }
+
+    #[test]
+    fn guess_in_other_bounds() {
+        assert_eq!(Guess::validate_in(500, 1, 1000).unwrap().value(), 500);
+        assert!(Guess::validate_in(-5, -10, -1).is_ok());
+        let errors = Guess::validate_in(11, 1, 10).unwrap_err();
+        assert_eq!(
+            errors[0].message,
+            Guess::try_new_in(11, 1, 10).unwrap_err().to_string()
+        );
+        // min > max: nothing can pass both rules
+        assert_eq!(Guess::validate_in(5, 10, 1).unwrap_err().len(), 2);
+    }
+
+    #[test]
+    fn agrees_with_domain_try_new() {
+        // the rules and domain's own checks accept exactly the same values
+        for value in -5..=105 {
+            assert_eq!(
+                Guess::validate(value).ok(),
+                Guess::try_new(value).ok(),
+                "value {}",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn collects_every_failure() {
+        let validator = Validator::new()
+            .add_rule(|s: &String| {
+                if s.is_empty() {
+                    Err(String::from("empty"))
+                } else {
+                    Ok(())
+                }
+            })
+            .add_rule(|s| {
+                if s.len() < 8 {
+                    Err(String::from("too short"))
+                } else {
+                    Ok(())
+                }
+            })
+            .add_rule(|s| {
+                if s.chars().any(|c| c.is_ascii_digit()) {
+                    Ok(())
+                } else {
+                    Err(String::from("needs a digit"))
+                }
+            });
+
+        let errors = validator.validate(String::new()).unwrap_err();
+        let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, vec!["empty", "too short", "needs a digit"]);
+
+        let ok = validator.validate(String::from("hunter22")).unwrap();
+        assert_eq!(ok.value(), "hunter22");
+    }
+
+    #[test]
+    fn no_rules_always_passes() {
+        let validator: Validator<i32> = Validator::default();
+        assert_eq!(validator.validate(-5).unwrap().into_inner(), -5);
+    }
+}
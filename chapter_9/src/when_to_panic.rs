@@ -24,7 +24,7 @@ pub fn run() {
     }
 
     impl _Guess {
-        // new ensures we have a valid value before creating a new Guess instance 
+        // new ensures we have a valid value before creating a new Guess instance
         // using the provided value
         pub fn _new(value: i32) -> _Guess {
             if value < 1 || value > 100 {
@@ -45,4 +45,6 @@ pub fn run() {
 
     // Now we can create Functions that take or return a Guess, instead of an i32
     // which ensures our value will always be between 1 and 100
+    // (the finished Guess is in the domain crate, validation.rs checks it with
+    // a Validator that reports every rule that failed)
 }
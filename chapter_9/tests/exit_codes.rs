@@ -0,0 +1,76 @@
+// Spawn the real chapter_9 and exit_codes binaries and check the exit code
+// they report. Cargo sets CARGO_BIN_EXE_<name> for integration tests, pointing
+// at the built binary.
+
+use std::process::{Command, Output};
+
+use chapter_9::exit_codes::{IO_ERROR, USAGE_ERROR, VALIDATION_ERROR};
+
+fn chapter_9(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_chapter_9"))
+        .args(args)
+        .output()
+        .expect("failed to run the chapter_9 binary")
+}
+
+fn exit_codes(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_exit_codes"))
+        .args(args)
+        .output()
+        .expect("failed to run the exit_codes binary")
+}
+
+#[test]
+fn no_arguments_runs_the_walkthrough() {
+    assert!(chapter_9(&[]).status.success());
+}
+
+#[test]
+fn valid_guess_succeeds() {
+    let output = chapter_9(&["guess", "42"]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "You guessed 42\n");
+}
+
+#[test]
+fn main_returning_result_exits_with_one() {
+    // whatever went wrong, ? in main reports it as exit code 1
+    for args in [&["guess", "500"][..], &["guess", "forty two"], &["dance"]] {
+        let output = chapter_9(args);
+        assert_eq!(output.status.code(), Some(1), "{:?}", args);
+        assert!(String::from_utf8_lossy(&output.stderr).starts_with("Error: "));
+    }
+}
+
+#[test]
+fn both_binaries_run_the_same_command() {
+    let output = exit_codes(&["guess", "42"]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "You guessed 42\n");
+}
+
+#[test]
+fn invalid_guess_is_a_validation_error() {
+    let output = exit_codes(&["guess", "500"]);
+    assert_eq!(output.status.code(), Some(VALIDATION_ERROR as i32));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("greater than 100, got: 500"));
+
+    let output = exit_codes(&["guess", "forty two"]);
+    assert_eq!(output.status.code(), Some(VALIDATION_ERROR as i32));
+}
+
+#[test]
+fn missing_backup_source_is_an_io_error() {
+    let dir = std::env::temp_dir();
+    let output = exit_codes(&["backup", "definitely/not/here.txt", dir.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(IO_ERROR as i32));
+}
+
+#[test]
+fn unknown_command_is_a_usage_error() {
+    let output = exit_codes(&["dance"]);
+    assert_eq!(output.status.code(), Some(USAGE_ERROR as i32));
+    // with no arguments there are no walkthroughs to fall back on
+    let output = exit_codes(&[]);
+    assert_eq!(output.status.code(), Some(USAGE_ERROR as i32));
+}
@@ -0,0 +1,88 @@
+// Coin and UsState, from 6.2 and 6.3
+
+/*
+The coin sorting machine from the match walkthrough. Only quarters carry
+extra data, the state printed on the back.
+*/
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum UsState {
+    Alabama,
+    Alaska,
+    Utah,
+    Washington,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Coin {
+    Penny,
+    Nickel,
+    Dime,
+    Quarter(UsState),
+}
+
+impl Coin {
+    pub fn value_in_cents(&self) -> u8 {
+        match self {
+            Coin::Penny => 1,
+            Coin::Nickel => 5,
+            Coin::Dime => 10,
+            // any quarter is 25, whichever state it's from
+            Coin::Quarter(_) => 25,
+        }
+    }
+
+    // Some(state) for a quarter, None for everything else
+    pub fn state(&self) -> Option<UsState> {
+        if let Coin::Quarter(state) = self {
+            Some(*state)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values() {
+        let coins = [
+            Coin::Penny,
+            Coin::Nickel,
+            Coin::Dime,
+            Coin::Quarter(UsState::Alaska),
+        ];
+        let cents: Vec<u8> = coins.iter().map(Coin::value_in_cents).collect();
+        assert_eq!(cents, vec![1, 5, 10, 25]);
+    }
+
+    #[test]
+    fn only_quarters_have_a_state() {
+        assert_eq!(Coin::Quarter(UsState::Utah).state(), Some(UsState::Utah));
+        assert_eq!(Coin::Dime.state(), None);
+    }
+
+    #[test]
+    fn counting_coins_in_a_hashmap() {
+        use std::collections::HashMap;
+
+        let purse = [
+            Coin::Quarter(UsState::Utah),
+            Coin::Penny,
+            Coin::Quarter(UsState::Alaska),
+            Coin::Quarter(UsState::Utah),
+            Coin::Penny,
+        ];
+        let mut counts: HashMap<Coin, u32> = HashMap::new();
+        for coin in purse {
+            *counts.entry(coin).or_insert(0) += 1;
+        }
+        // quarters from different states are different keys
+        assert_eq!(counts[&Coin::Quarter(UsState::Utah)], 2);
+        assert_eq!(counts[&Coin::Quarter(UsState::Alaska)], 1);
+        assert_eq!(counts[&Coin::Penny], 2);
+        assert_eq!(counts.get(&Coin::Dime), None);
+    }
+}
@@ -0,0 +1,104 @@
+// Errors the Exercises Can Run Into
+
+/*
+Some of the walkthroughs read something the user controls: an index typed in
+at the keyboard, a file that may or may not be there, a port that may already
+be taken. The book reaches for .expect() there, which is fine for a first
+look, but it means a typo ends the whole program halfway through (9.3 "To
+panic! or Not to panic!": input from a user is an *expected* failure).
+
+ExerciseError is what those walkthroughs return instead, so each chapter's
+main can print what went wrong and carry on (or exit with a useful code).
+From<io::Error> lets `?` convert I/O errors on the way up (9.2).
+*/
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum ExerciseError {
+    // something typed in couldn't be turned into what we needed
+    BadInput { expected: &'static str, got: String },
+    // an index past the end of an array or Vec
+    OutOfBounds { index: usize, len: usize },
+    Io(io::Error),
+}
+
+impl fmt::Display for ExerciseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExerciseError::BadInput { expected, got } => {
+                write!(f, "expected {}, got {:?}", expected, got)
+            }
+            ExerciseError::OutOfBounds { index, len } => write!(
+                f,
+                "index {} is out of bounds, there are only {} elements",
+                index, len
+            ),
+            ExerciseError::Io(e) => write!(f, "I/O error: {}", e),
+        }
+    }
+}
+
+impl Error for ExerciseError {
+    // the io::Error underneath, for anyone who wants to look at it
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ExerciseError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ExerciseError {
+    fn from(e: io::Error) -> ExerciseError {
+        ExerciseError::Io(e)
+    }
+}
+
+// parses `input` (after trimming it), or says what was expected instead
+pub fn parse_input<T: std::str::FromStr>(
+    input: &str,
+    expected: &'static str,
+) -> Result<T, ExerciseError> {
+    input.trim().parse().map_err(|_| ExerciseError::BadInput {
+        expected,
+        got: input.trim().to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages() {
+        let bad = parse_input::<usize>(" ten\n", "an index").unwrap_err();
+        assert_eq!(bad.to_string(), "expected an index, got \"ten\"");
+        assert_eq!(
+            ExerciseError::OutOfBounds { index: 7, len: 5 }.to_string(),
+            "index 7 is out of bounds, there are only 5 elements"
+        );
+    }
+
+    #[test]
+    fn parses_trimmed_input() {
+        assert_eq!(parse_input::<usize>(" 3\n", "an index").unwrap(), 3);
+        assert!(parse_input::<usize>("-1", "an index").is_err());
+    }
+
+    #[test]
+    fn io_errors_convert_and_keep_their_source() {
+        fn open() -> Result<(), ExerciseError> {
+            Err(io::Error::new(io::ErrorKind::NotFound, "no hello.txt"))?
+        }
+        let err = open().unwrap_err();
+        assert!(matches!(err, ExerciseError::Io(_)));
+        assert_eq!(err.to_string(), "I/O error: no hello.txt");
+        assert!(err.source().is_some());
+        assert!(ExerciseError::OutOfBounds { index: 1, len: 0 }
+            .source()
+            .is_none());
+    }
+}
@@ -0,0 +1,71 @@
+// Something a Chapter Can Run
+
+/*
+Most chapters have a main.rs that calls each section's run() in turn. Once
+those run()s can fail (they return Result<(), ExerciseError>, see error.rs),
+something has to call them, report the ones that went wrong and keep going.
+Exercise is what that something needs to know about a section: what it's
+called and how to run it.
+
+Section is the implementation nearly every chapter wants, a name and a plain
+function (19.4 "Function Pointers": fn() is a type, and any fn item with a
+matching signature coerces to it):
+
+    Section { name: "data_types", run: data_types::run }
+
+A section that can't fail wraps its run in a closure that doesn't capture
+anything, those coerce to fn pointers too:
+
+    Section { name: "variables", run: || { variables::run(); Ok(()) } }
+*/
+
+use crate::ExerciseError;
+
+pub trait Exercise {
+    fn name(&self) -> &str;
+    fn run(&self) -> Result<(), ExerciseError>;
+}
+
+pub struct Section {
+    pub name: &'static str,
+    pub run: fn() -> Result<(), ExerciseError>,
+}
+
+impl Exercise for Section {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn run(&self) -> Result<(), ExerciseError> {
+        (self.run)()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fails() -> Result<(), ExerciseError> {
+        Err(ExerciseError::OutOfBounds { index: 3, len: 2 })
+    }
+
+    #[test]
+    fn sections_run_their_function() {
+        let sections: Vec<Box<dyn Exercise>> = vec![
+            Box::new(Section {
+                name: "fine",
+                run: || Ok(()),
+            }),
+            Box::new(Section {
+                name: "broken",
+                run: fails,
+            }),
+        ];
+        let failed: Vec<&str> = sections
+            .iter()
+            .filter(|section| section.run().is_err())
+            .map(|section| section.name())
+            .collect();
+        assert_eq!(failed, ["broken"]);
+    }
+}
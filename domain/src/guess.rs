@@ -0,0 +1,87 @@
+// Guess, from 9.3
+
+/*
+A number the guessing game is willing to accept. The field is private, so the
+only ways to get a Guess are through new (which panics on a bad value, the
+book's version) or try_new/try_new_in (which hand back a GuessError instead).
+Anything holding a Guess can skip checking the range again.
+
+Inside it's a Ranged<i32> (ranged.rs), which does the checking. Guess is a
+newtype around it rather than a type alias so it keeps the book's one argument
+Guess::new: an alias would share Ranged's impl blocks, and a second `new`
+next to Ranged::new(value, min, max) doesn't compile.
+
+The panic messages are the ones Chapter 11's should_panic tests look for.
+*/
+
+use crate::ranged::{RangeError, Ranged};
+
+pub type GuessError = RangeError<i32>;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Guess(Ranged<i32>);
+
+impl Guess {
+    pub const MIN: i32 = 1;
+    pub const MAX: i32 = 100;
+
+    pub fn new(value: i32) -> Guess {
+        Guess(Ranged::new(value, Guess::MIN, Guess::MAX))
+    }
+
+    pub fn try_new(value: i32) -> Result<Guess, GuessError> {
+        Guess::try_new_in(value, Guess::MIN, Guess::MAX)
+    }
+
+    // the same checks against other bounds, for games with a bigger or
+    // smaller range than 1 to 100
+    pub fn try_new_in(value: i32, min: i32, max: i32) -> Result<Guess, GuessError> {
+        Ranged::try_new(value, min, max).map(Guess)
+    }
+
+    pub fn value(&self) -> i32 {
+        self.0.value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_values_in_range() {
+        assert_eq!(Guess::new(1).value(), 1);
+        assert_eq!(Guess::new(100).value(), 100);
+        assert_eq!(Guess::try_new(42).unwrap().value(), 42);
+    }
+
+    #[test]
+    #[should_panic(expected = "less than 1, got: 0")]
+    fn below_range_panics() {
+        Guess::new(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "greater than 100, got: 101")]
+    fn above_range_panics() {
+        Guess::new(101);
+    }
+
+    #[test]
+    fn errors_say_which_bound() {
+        assert_eq!(
+            Guess::try_new(-3),
+            Err(GuessError::TooLow { min: 1, value: -3 })
+        );
+        assert_eq!(
+            Guess::try_new_in(11, 1, 10),
+            Err(GuessError::TooHigh { max: 10, value: 11 })
+        );
+        assert_eq!(Guess::try_new_in(500, 1, 1000).unwrap().value(), 500);
+        // a backwards range is an error, not a Guess nothing can equal
+        assert_eq!(
+            Guess::try_new_in(5, 10, 1),
+            Err(GuessError::EmptyRange { min: 10, max: 1 })
+        );
+    }
+}
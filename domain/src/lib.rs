@@ -0,0 +1,49 @@
+// Domain Types Shared Between Chapters
+
+/*
+Rectangle (Chapter 5), Coin (Chapter 6), Guess (Chapter 9) and Shoe (Chapter
+13) kept getting written again in every chapter that needed them, each copy a
+little different from the last. This crate holds one finished copy of each,
+and the chapters depend on it with a path dependency (7.1: a package can use
+any other crate, not just ones from crates.io):
+
+    [dependencies]
+    domain = { path = "../domain" }
+
+The modules are private and the types are re-exported from the root (7.4
+"Re-exporting Names with pub use"), so callers write domain::Rectangle instead
+of domain::rectangle::Rectangle.
+
+prelude is the one public module, for chapters that want the common types
+with a single `use domain::prelude::*;`.
+
+exercise.rs is the Exercise trait, how a chapter hands its sections to
+whatever runs them, and log.rs has the info!/warn!/error! macros for
+reporting along the way.
+
+rng.rs isn't a type from the book, it's the seedable random numbers every
+chapter that rolls dice or picks a secret number shares. error.rs is the
+error those chapters' walkthroughs return instead of panicking on bad input.
+ranged.rs is Guess with the i32 taken out: any value that has to stay between
+a min and a max.
+*/
+
+mod coin;
+mod error;
+mod exercise;
+mod guess;
+mod log;
+pub mod prelude;
+mod ranged;
+mod rectangle;
+mod rng;
+mod shoe;
+
+pub use coin::{Coin, UsState};
+pub use error::{parse_input, ExerciseError};
+pub use exercise::{Exercise, Section};
+pub use guess::{Guess, GuessError};
+pub use ranged::{RangeError, Ranged};
+pub use rectangle::Rectangle;
+pub use rng::{take_seed, Rng, XorShift};
+pub use shoe::Shoe;
@@ -0,0 +1,39 @@
+// Logging Macros
+
+/*
+info!, warn! and error! take the same arguments as println! and write one line
+to stderr with the level in front:
+
+    warn!("couldn't read {}, starting fresh", path);
+    // [warn] couldn't read stats.txt, starting fresh
+
+They go to stderr for the same reason chapter_12's errors do: anything the
+program prints as its actual output stays on stdout, so piping it to a file
+doesn't fill the file with messages (12.6).
+
+#[macro_export] puts a macro at the root of the crate no matter which module
+defines it, so these are domain::info! and friends, and prelude.rs re-exports
+them like any other name. $crate isn't needed in the expansions, they only
+call std's eprintln!.
+*/
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)*) => {
+        eprintln!("[info] {}", format_args!($($arg)*))
+    };
+}
+
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        eprintln!("[warn] {}", format_args!($($arg)*))
+    };
+}
+
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)*) => {
+        eprintln!("[error] {}", format_args!($($arg)*))
+    };
+}
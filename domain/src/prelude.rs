@@ -0,0 +1,21 @@
+// The Domain Prelude
+
+/*
+Rust puts a small set of names (Vec, String, Option, Some, ...) into every
+module without a `use`: that's the standard library's prelude. Crates copy the
+idea with a module that re-exports the things almost every user wants, so one
+glob import brings them all in:
+
+    use domain::prelude::*;
+
+Only the types the chapters keep reaching for are in here, along with the
+Exercise trait and Section for handing sections to a runner, and the logging
+macros. Anything else is still a `use domain::Shoe;` away. A glob import never
+clashes with a name the module defines itself: the module's own name wins.
+*/
+
+pub use crate::{
+    Coin, Exercise, ExerciseError, Guess, Ranged, Rectangle, Rng, Section, UsState, XorShift,
+};
+// the macros from log.rs, which #[macro_export] put at the crate root
+pub use crate::{error, info, warn};
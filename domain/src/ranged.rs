@@ -0,0 +1,215 @@
+// Ranged<T>: Guess for Any Type (10.1)
+
+/*
+Guess (guess.rs) started as an i32 that has to be between 1 and 100. Nothing
+about "has to be between min and max" is special to i32 though: a temperature
+(f64) or a letter grade (char) works the same way. All the checks need is to
+compare values (PartialOrd) and to copy them around (Copy), so those are the
+bounds on T.
+
+Unlike Guess's MIN and MAX consts, the bounds are stored with the value, so a
+Ranged<i32> from 1 to 10 and one from 1 to 1000 are both possible. Guess wraps
+a Ranged<i32> that always has 1 and 100.
+
+(Not to be confused with chapter_10's Bounded trait, which is about types
+with a smallest and largest value, like u8's 0 and 255.)
+
+Three ways in:
+    Ranged::new(value, min, max)       panics if value is out of range
+    Ranged::try_new(value, min, max)   hands back a RangeError instead
+    Ranged::clamp(value, min, max)     pulls value into range, can't fail
+*/
+
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Ranged<T> {
+    value: T,
+    min: T,
+    max: T,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum RangeError<T> {
+    TooLow { min: T, value: T },
+    TooHigh { max: T, value: T },
+    // min is bigger than max (or one of them is a NaN), nothing fits
+    EmptyRange { min: T, max: T },
+    // PartialOrd means some values can't be compared at all: f64::NAN isn't
+    // less than, greater than, or equal to anything
+    Unordered { value: T },
+}
+
+impl<T: fmt::Display> fmt::Display for RangeError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RangeError::TooLow { min, value } => {
+                write!(
+                    f,
+                    "The value provided was less than {}, got: {}",
+                    min, value
+                )
+            }
+            RangeError::TooHigh { max, value } => {
+                write!(
+                    f,
+                    "The value provided was greater than {}, got: {}",
+                    max, value
+                )
+            }
+            RangeError::EmptyRange { min, max } => {
+                write!(f, "Nothing fits between {} and {}", min, max)
+            }
+            RangeError::Unordered { value } => {
+                write!(f, "The value provided can't be compared, got: {}", value)
+            }
+        }
+    }
+}
+
+impl<T: fmt::Debug + fmt::Display> Error for RangeError<T> {}
+
+// min <= max, written with partial_cmp so a NaN bound counts as an empty range
+fn is_range<T: PartialOrd>(min: T, max: T) -> bool {
+    matches!(
+        min.partial_cmp(&max),
+        Some(Ordering::Less | Ordering::Equal)
+    )
+}
+
+impl<T: PartialOrd + Copy> Ranged<T> {
+    pub fn new(value: T, min: T, max: T) -> Ranged<T>
+    where
+        T: fmt::Display,
+    {
+        match Ranged::try_new(value, min, max) {
+            Ok(ranged) => ranged,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    pub fn try_new(value: T, min: T, max: T) -> Result<Ranged<T>, RangeError<T>> {
+        // partial_cmp gives None when two values can't be compared, so a NaN
+        // value is Unordered
+        if !is_range(min, max) {
+            return Err(RangeError::EmptyRange { min, max });
+        }
+        match (value.partial_cmp(&min), value.partial_cmp(&max)) {
+            (Some(Ordering::Less), _) => Err(RangeError::TooLow { min, value }),
+            (_, Some(Ordering::Greater)) => Err(RangeError::TooHigh { max, value }),
+            (Some(_), Some(_)) => Ok(Ranged { value, min, max }),
+            _ => Err(RangeError::Unordered { value }),
+        }
+    }
+
+    // like Ord::clamp: too low becomes min, too high becomes max. A value that
+    // can't be compared (NaN) becomes min too, so the result is always in
+    // range. Panics if the range is empty, the same as f64::clamp
+    pub fn clamp(value: T, min: T, max: T) -> Ranged<T> {
+        assert!(is_range(min, max), "clamp needs min <= max");
+        let value = match (value.partial_cmp(&min), value.partial_cmp(&max)) {
+            (_, Some(Ordering::Greater)) => max,
+            (Some(Ordering::Equal | Ordering::Greater), Some(_)) => value,
+            _ => min,
+        };
+        Ranged { value, min, max }
+    }
+
+    pub fn value(&self) -> T {
+        self.value
+    }
+
+    pub fn min(&self) -> T {
+        self.min
+    }
+
+    pub fn max(&self) -> T {
+        self.max
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers() {
+        let dice = Ranged::new(4, 1, 6);
+        assert_eq!((dice.value(), dice.min(), dice.max()), (4, 1, 6));
+        assert_eq!(
+            Ranged::try_new(7, 1, 6),
+            Err(RangeError::TooHigh { max: 6, value: 7 })
+        );
+        assert_eq!(
+            Ranged::try_new(-1, 0, 10),
+            Err(RangeError::TooLow { min: 0, value: -1 })
+        );
+        assert_eq!(Ranged::clamp(9, 1, 6).value(), 6);
+        assert_eq!(Ranged::clamp(-9, 1, 6).value(), 1);
+        assert_eq!(Ranged::clamp(3, 1, 6).value(), 3);
+    }
+
+    #[test]
+    fn floats() {
+        let celsius = Ranged::try_new(21.5, -10.0, 40.0).unwrap();
+        assert_eq!(celsius.value(), 21.5);
+        assert!(Ranged::try_new(40.01, -10.0, 40.0).is_err());
+        assert_eq!(Ranged::clamp(100.0, -10.0, 40.0).value(), 40.0);
+
+        // NaN is never in range, and clamping it lands on min
+        assert!(matches!(
+            Ranged::try_new(f64::NAN, 0.0, 1.0),
+            Err(RangeError::Unordered { .. })
+        ));
+        assert_eq!(Ranged::clamp(f64::NAN, 0.0, 1.0).value(), 0.0);
+        assert!(matches!(
+            Ranged::try_new(0.5, f64::NAN, 1.0),
+            Err(RangeError::EmptyRange { .. })
+        ));
+    }
+
+    #[test]
+    fn chars() {
+        let grade = Ranged::new('B', 'A', 'F');
+        assert_eq!(grade.value(), 'B');
+        assert_eq!(
+            Ranged::try_new('a', 'A', 'Z'),
+            Err(RangeError::TooHigh {
+                max: 'Z',
+                value: 'a'
+            })
+        );
+        assert_eq!(Ranged::clamp('!', 'a', 'z').value(), 'a');
+    }
+
+    #[test]
+    fn empty_ranges() {
+        assert_eq!(
+            Ranged::try_new(5, 10, 1),
+            Err(RangeError::EmptyRange { min: 10, max: 1 })
+        );
+        assert_eq!(
+            RangeError::EmptyRange { min: 10, max: 1 }.to_string(),
+            "Nothing fits between 10 and 1"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "clamp needs min <= max")]
+    fn clamp_panics_on_an_empty_range() {
+        Ranged::clamp(5, 10, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "greater than 1.5, got: 2")]
+    fn new_panics_with_the_error_message() {
+        Ranged::new(2.0, 0.5, 1.5);
+    }
+
+    #[test]
+    fn same_value_different_bounds_are_different() {
+        assert_ne!(Ranged::new(5, 1, 10), Ranged::new(5, 1, 100));
+    }
+}
@@ -0,0 +1,108 @@
+// Rectangle, from 5.2 and 5.3
+
+/*
+The Rectangle chapter_5/src/method_syntax.rs built one impl block at a time,
+all in one place. The methods still take &self: they only read the struct,
+they don't change it or take ownership of it.
+
+Chapter 5's example_program.rs and method_syntax.rs keep their own Rectangle,
+since writing it step by step is the point of those sections. Everything
+after that which only needs a rectangle uses this one: chapter_5's collision,
+chapter_10's Describe, chapter_11's tests, chapter_13's sorting,
+chapter_15's shapes and chapter_20's /rect route.
+
+Display lives here rather than in chapter_10: a trait from std on a type from
+this crate can only be implemented in this crate (the orphan rule, 10.2).
+
+The fields stay pub, every chapter that uses a Rectangle builds one with a
+struct literal, Rectangle { width: 30, height: 50 }.
+*/
+
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Rectangle {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Rectangle {
+    // an associated function (no self), used like a constructor:
+    // Rectangle::square(25)
+    pub fn square(size: u32) -> Rectangle {
+        Rectangle {
+            width: size,
+            height: size,
+        }
+    }
+
+    pub fn area(&self) -> u32 {
+        self.width * self.height
+    }
+
+    // true if `other` fits inside self with room to spare on both sides, so a
+    // rectangle can't hold one the same size as itself
+    pub fn can_hold(&self, other: &Rectangle) -> bool {
+        self.width > other.width && self.height > other.height
+    }
+}
+
+impl fmt::Display for Rectangle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a {}x{} rectangle", self.width, self.height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn area() {
+        let rect = Rectangle {
+            width: 30,
+            height: 50,
+        };
+        assert_eq!(rect.area(), 1500);
+        assert_eq!(Rectangle::square(0).area(), 0);
+    }
+
+    #[test]
+    fn square_has_equal_sides() {
+        assert_eq!(
+            Rectangle::square(25),
+            Rectangle {
+                width: 25,
+                height: 25
+            }
+        );
+    }
+
+    #[test]
+    fn can_hold() {
+        let rect = Rectangle {
+            width: 30,
+            height: 50,
+        };
+        let smaller = Rectangle {
+            width: 10,
+            height: 40,
+        };
+        let wider = Rectangle {
+            width: 60,
+            height: 45,
+        };
+        assert!(rect.can_hold(&smaller));
+        assert!(!rect.can_hold(&wider));
+        assert!(!rect.can_hold(&rect));
+    }
+
+    #[test]
+    fn displays_its_size() {
+        let rect = Rectangle {
+            width: 30,
+            height: 50,
+        };
+        assert_eq!(rect.to_string(), "a 30x50 rectangle");
+    }
+}
@@ -0,0 +1,184 @@
+// Random Numbers That Can Be Repeated
+
+/*
+The guessing game, the dice roll and the shirt giveaway all want a random
+number, and a test can't check anything about a number it can't predict. So
+instead of calling rand::thread_rng() wherever a number is needed, the code
+asks for "some Rng" (the same trick as chapter 2's Input and the writers in
+chapter 8) and the caller decides which one:
+
+  - XorShift::new(seed): the same seed always gives the same numbers, so a
+    test (or `--seed 42` on the command line) gets the same game every time
+  - XorShift::from_time(): seeded from the clock, for a normal run
+
+Everything except next_u64 has a default, so a new generator only has to say
+how to make one u64 (10.2 "Default Implementations").
+*/
+
+use std::ops::RangeInclusive;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub trait Rng {
+    fn next_u64(&mut self) -> u64;
+
+    // a number in 0..n. Using % is very slightly biased towards small numbers
+    // when n doesn't divide 2^64, which doesn't matter for dice and games
+    fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "below(0) has no numbers to pick from");
+        self.next_u64() % n
+    }
+
+    // a number in the range, both ends included, like rand's gen_range(1..=100)
+    fn gen_range(&mut self, range: RangeInclusive<u32>) -> u32 {
+        let (low, high) = (*range.start(), *range.end());
+        assert!(low <= high, "empty range {}..={}", low, high);
+        let span = high as u64 - low as u64 + 1;
+        (low as u64 + self.below(span)) as u32
+    }
+
+    // the same for i32, which can be negative
+    fn range_i32(&mut self, low: i32, high: i32) -> i32 {
+        assert!(low <= high, "empty range {}..={}", low, high);
+        let span = (high as i64 - low as i64 + 1) as u64;
+        (low as i64 + self.below(span) as i64) as i32
+    }
+
+    // one side of a die, 1..=sides
+    fn roll(&mut self, sides: u8) -> u8 {
+        self.gen_range(1..=sides as u32) as u8
+    }
+}
+
+// xorshift64: not good enough for anything secure, but tiny, fast, and the
+// same seed always produces the same numbers
+#[derive(Debug, Clone)]
+pub struct XorShift {
+    state: u64,
+}
+
+impl XorShift {
+    pub fn new(seed: u64) -> XorShift {
+        // an all zero state would only ever produce zeros
+        XorShift {
+            state: if seed == 0 {
+                0x2545_F491_4F6C_DD1D
+            } else {
+                seed
+            },
+        }
+    }
+
+    // different every run (unless two runs start in the same nanosecond)
+    pub fn from_time() -> XorShift {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_nanos() as u64);
+        XorShift::new(nanos)
+    }
+
+    // seeded if a seed was given, from the clock if not
+    pub fn from_seed(seed: Option<u64>) -> XorShift {
+        seed.map_or_else(XorShift::from_time, XorShift::new)
+    }
+}
+
+impl Rng for XorShift {
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+}
+
+// Finds `--seed N` (or `--seed=N`) in the arguments, takes it out so the rest
+// can be read as if it was never there, and returns N. Ok(None) means no seed
+// was given
+pub fn take_seed(args: &mut Vec<String>) -> Result<Option<u64>, String> {
+    let Some(at) = args
+        .iter()
+        .position(|arg| arg == "--seed" || arg.starts_with("--seed="))
+    else {
+        return Ok(None);
+    };
+
+    let flag = args.remove(at);
+    let value = match flag.strip_prefix("--seed=") {
+        Some(value) => value.to_string(),
+        None if at < args.len() => args.remove(at),
+        None => return Err(String::from("--seed needs a number after it")),
+    };
+    value
+        .parse()
+        .map(Some)
+        .map_err(|_| format!("--seed needs a whole number, got {:?}", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn same_seed_same_numbers() {
+        let mut first = XorShift::new(42);
+        let mut second = XorShift::new(42);
+        for _ in 0..10 {
+            assert_eq!(first.next_u64(), second.next_u64());
+        }
+        assert_ne!(XorShift::new(1).next_u64(), XorShift::new(2).next_u64());
+        // zero is swapped for another seed instead of getting stuck
+        assert_ne!(XorShift::new(0).next_u64(), 0);
+    }
+
+    #[test]
+    fn ranges_include_both_ends() {
+        let mut rng = XorShift::new(7);
+        let mut seen = [false; 6];
+        for _ in 0..1000 {
+            let side = rng.roll(6);
+            assert!((1..=6).contains(&side), "rolled {}", side);
+            seen[side as usize - 1] = true;
+            assert!((-3..=3).contains(&rng.range_i32(-3, 3)));
+        }
+        assert_eq!(seen, [true; 6]);
+        assert_eq!(rng.gen_range(5..=5), 5);
+    }
+
+    #[test]
+    fn works_through_a_trait_object() {
+        let mut rng = XorShift::new(3);
+        let expected = rng.clone().gen_range(1..=100);
+        let rng: &mut dyn Rng = &mut rng;
+        assert_eq!(rng.gen_range(1..=100), expected);
+    }
+
+    #[test]
+    fn seed_flag_is_taken_out_of_the_arguments() {
+        let mut list = args(&["game", "--seed", "42", "hard"]);
+        assert_eq!(take_seed(&mut list), Ok(Some(42)));
+        assert_eq!(list, args(&["game", "hard"]));
+
+        let mut list = args(&["game", "hard", "--seed=7"]);
+        assert_eq!(take_seed(&mut list), Ok(Some(7)));
+        assert_eq!(list, args(&["game", "hard"]));
+
+        let mut list = args(&["game", "hard"]);
+        assert_eq!(take_seed(&mut list), Ok(None));
+        assert_eq!(list.len(), 2);
+    }
+
+    #[test]
+    fn bad_seeds_are_errors() {
+        assert!(take_seed(&mut args(&["game", "--seed"])).is_err());
+        assert_eq!(
+            take_seed(&mut args(&["game", "--seed", "lots"])),
+            Err(String::from("--seed needs a whole number, got \"lots\""))
+        );
+    }
+}
@@ -0,0 +1,36 @@
+// Shoe, from 13.2
+
+/*
+The shoe from "Using Closures That Capture Their Environment", a size and a
+style. Clone so a catalog can be filtered more than once, Eq and Hash so shoes
+can go in a HashSet or be HashMap keys.
+*/
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Shoe {
+    pub size: u32,
+    pub style: String,
+}
+
+impl Shoe {
+    pub fn new(size: u32, style: &str) -> Shoe {
+        Shoe {
+            size,
+            style: String::from(style),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_copies_the_style() {
+        let style = String::from("boot");
+        let shoe = Shoe::new(10, &style);
+        drop(style);
+        assert_eq!(shoe.size, 10);
+        assert_eq!(shoe.style, "boot");
+    }
+}
@@ -0,0 +1,373 @@
+// Runner: Building Only the Chapters You Ask For (library)
+// main.rs runs the chapters, this lib knows which chapters exist, which of
+// them this build was compiled with, and how to run their sections
+
+/*
+Every chapter is its own crate, so building one chapter has never meant
+building the rest. The runner depends on all of them, though, and pulling in
+every chapter (and tokio, syn...) each time gets slow. So each chapter is an
+optional dependency behind its own feature (see Cargo.toml), and
+`cargo build --features "ch9 ch13"` compiles just those two.
+
+cfg!(feature = "ch9") is replaced with true or false at compile time, which is
+how the table below knows what went into this particular build. exercises()
+uses #[cfg] on its match arms instead, since an arm that names chapter_9
+can't even be compiled when chapter_9 isn't a dependency.
+
+Each chapter's lib has an exercises() listing its sections (domain's
+Section, a name and a fn() -> Result<(), ExerciseError>). run_all() runs
+them in order and keeps going past any that fail, so one bad section is
+reported without hiding the rest.
+*/
+
+use domain::prelude::*;
+
+pub struct Chapter {
+    pub number: u32,
+    pub title: &'static str,
+    // None for chapters with nothing for the runner to run, see elsewhere()
+    pub feature: Option<&'static str>,
+    pub compiled: bool,
+}
+
+pub const CHAPTERS: [Chapter; 19] = [
+    Chapter {
+        number: 2,
+        title: "Programming a Guessing Game",
+        feature: None,
+        compiled: false,
+    },
+    Chapter {
+        number: 3,
+        title: "Common Programming Concepts",
+        feature: Some("ch3"),
+        compiled: cfg!(feature = "ch3"),
+    },
+    Chapter {
+        number: 4,
+        title: "Understanding Ownership",
+        feature: Some("ch4"),
+        compiled: cfg!(feature = "ch4"),
+    },
+    Chapter {
+        number: 5,
+        title: "Using Structs to Structure Related Data",
+        feature: Some("ch5"),
+        compiled: cfg!(feature = "ch5"),
+    },
+    Chapter {
+        number: 6,
+        title: "Enums and Pattern Matching",
+        feature: Some("ch6"),
+        compiled: cfg!(feature = "ch6"),
+    },
+    Chapter {
+        number: 7,
+        title: "Managing Growing Projects with Packages, Crates, and Modules",
+        feature: Some("ch7"),
+        compiled: cfg!(feature = "ch7"),
+    },
+    Chapter {
+        number: 8,
+        title: "Common Collections",
+        feature: Some("ch8"),
+        compiled: cfg!(feature = "ch8"),
+    },
+    Chapter {
+        number: 9,
+        title: "Error Handling",
+        feature: Some("ch9"),
+        compiled: cfg!(feature = "ch9"),
+    },
+    Chapter {
+        number: 10,
+        title: "Generic Types, Traits, and Lifetimes",
+        feature: Some("ch10"),
+        compiled: cfg!(feature = "ch10"),
+    },
+    Chapter {
+        number: 11,
+        title: "Writing Automated Tests",
+        feature: None,
+        compiled: false,
+    },
+    Chapter {
+        number: 12,
+        title: "An I/O Project: Building a Command Line Program",
+        feature: None,
+        compiled: false,
+    },
+    Chapter {
+        number: 13,
+        title: "Functional Language Features: Iterators and Closures",
+        feature: Some("ch13"),
+        compiled: cfg!(feature = "ch13"),
+    },
+    Chapter {
+        number: 14,
+        title: "More About Cargo and Crates.io",
+        feature: None,
+        compiled: false,
+    },
+    Chapter {
+        number: 15,
+        title: "Smart Pointers",
+        feature: Some("ch15"),
+        compiled: cfg!(feature = "ch15"),
+    },
+    Chapter {
+        number: 16,
+        title: "Fearless Concurrency",
+        feature: Some("ch16"),
+        compiled: cfg!(feature = "ch16"),
+    },
+    Chapter {
+        number: 17,
+        title: "Object-Oriented Programming Features of Rust",
+        feature: Some("ch17"),
+        compiled: cfg!(feature = "ch17"),
+    },
+    Chapter {
+        number: 18,
+        title: "Patterns and Matching",
+        feature: Some("ch18"),
+        compiled: cfg!(feature = "ch18"),
+    },
+    Chapter {
+        number: 19,
+        title: "Advanced Features",
+        feature: Some("ch19"),
+        compiled: cfg!(feature = "ch19"),
+    },
+    Chapter {
+        number: 20,
+        title: "Final Project: Building a Multithreaded Web Server",
+        feature: None,
+        compiled: false,
+    },
+];
+
+#[derive(Debug, PartialEq)]
+pub enum Availability {
+    Compiled,
+    // it has sections to run, this build just didn't ask for it
+    NotSelected(&'static str),
+    Elsewhere(&'static str),
+    Unknown,
+}
+
+pub fn find(number: u32) -> Option<&'static Chapter> {
+    CHAPTERS.iter().find(|chapter| chapter.number == number)
+}
+
+// how to try one of the chapters the runner doesn't run
+pub fn elsewhere(number: u32) -> &'static str {
+    match number {
+        2 => "it's a game played on stdin, `cargo run` in chapter_2",
+        11 => "it's all tests, `cargo test` in chapter_11",
+        12 => "it's a command line tool, `cargo run -- to poem.txt` in chapter_12",
+        14 => "it's a workspace, `cargo run -p adder` in chapter_14",
+        20 => "it's a web server, `cargo run` in chapter_20",
+        _ => "",
+    }
+}
+
+pub fn availability(number: u32) -> Availability {
+    match find(number) {
+        None => Availability::Unknown,
+        Some(Chapter { compiled: true, .. }) => Availability::Compiled,
+        Some(Chapter {
+            feature: Some(feature),
+            ..
+        }) => Availability::NotSelected(feature),
+        Some(Chapter { feature: None, .. }) => Availability::Elsewhere(elsewhere(number)),
+    }
+}
+
+pub fn compiled_in() -> Vec<&'static Chapter> {
+    CHAPTERS.iter().filter(|chapter| chapter.compiled).collect()
+}
+
+// the sections of a chapter this build was compiled with, None for any other
+pub fn exercises(number: u32) -> Option<Vec<Section>> {
+    match number {
+        #[cfg(feature = "ch3")]
+        3 => Some(chapter_3::exercises()),
+        #[cfg(feature = "ch4")]
+        4 => Some(chapter_4::exercises()),
+        #[cfg(feature = "ch5")]
+        5 => Some(chapter_5::exercises()),
+        #[cfg(feature = "ch6")]
+        6 => Some(chapter_6::exercises()),
+        #[cfg(feature = "ch7")]
+        7 => Some(chapter_7::exercises()),
+        #[cfg(feature = "ch8")]
+        8 => Some(chapter_8::exercises()),
+        #[cfg(feature = "ch9")]
+        9 => Some(chapter_9::exercises()),
+        #[cfg(feature = "ch10")]
+        10 => Some(chapter_10::exercises()),
+        #[cfg(feature = "ch13")]
+        13 => Some(chapter_13::exercises()),
+        #[cfg(feature = "ch15")]
+        15 => Some(chapter_15::exercises()),
+        #[cfg(feature = "ch16")]
+        16 => Some(chapter_16::exercises()),
+        #[cfg(feature = "ch17")]
+        17 => Some(chapter_17::exercises()),
+        #[cfg(feature = "ch18")]
+        18 => Some(chapter_18::exercises()),
+        #[cfg(feature = "ch19")]
+        19 => Some(chapter_19::exercises()),
+        _ => None,
+    }
+}
+
+// runs every exercise in order and gives back the name and error of each one
+// that failed
+pub fn run_all<E: Exercise>(exercises: &[E]) -> Vec<(String, ExerciseError)> {
+    let mut failed = Vec::new();
+    for exercise in exercises {
+        if let Err(e) = exercise.run() {
+            failed.push((exercise.name().to_string(), e));
+        }
+    }
+    failed
+}
+
+// one line per chapter asked about, or a summary of the whole build when
+// none were
+pub fn report(asked: &[u32]) -> String {
+    if asked.is_empty() {
+        let compiled = compiled_in();
+        if compiled.is_empty() {
+            return String::from(
+                "no chapters compiled in, try: cargo run --features \"ch9 ch13\" (or --features all)",
+            );
+        }
+        let lines: Vec<String> = compiled
+            .iter()
+            .map(|chapter| format!("  {:>2}: {}", chapter.number, chapter.title))
+            .collect();
+        return format!("compiled in:\n{}", lines.join("\n"));
+    }
+
+    asked
+        .iter()
+        .map(|&number| match availability(number) {
+            Availability::Compiled => format!("chapter {}: compiled in", number),
+            Availability::NotSelected(feature) => format!(
+                "chapter {}: not in this build, add --features {}",
+                number, feature
+            ),
+            Availability::Elsewhere(how) => {
+                format!("chapter {}: nothing for the runner, {}", number, how)
+            }
+            Availability::Unknown => format!("chapter {}: no such chapter", number),
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn some_chapters_are_never_compiled() {
+        for number in [2, 11, 12, 14, 20] {
+            assert_eq!(
+                availability(number),
+                Availability::Elsewhere(elsewhere(number))
+            );
+            assert_eq!(exercises(number).map(|sections| sections.len()), None);
+        }
+        assert_eq!(
+            report(&[11]),
+            "chapter 11: nothing for the runner, it's all tests, `cargo test` in chapter_11"
+        );
+    }
+
+    #[test]
+    fn every_compiled_chapter_has_exercises() {
+        for chapter in compiled_in() {
+            let sections = exercises(chapter.number).unwrap();
+            assert!(!sections.is_empty(), "chapter {}", chapter.number);
+        }
+    }
+
+    fn out_of_bounds() -> Result<(), ExerciseError> {
+        Err(ExerciseError::OutOfBounds { index: 5, len: 3 })
+    }
+
+    #[test]
+    fn run_all_keeps_going_past_failures() {
+        let sections = [
+            Section {
+                name: "first",
+                run: out_of_bounds,
+            },
+            Section {
+                name: "fine",
+                run: || Ok(()),
+            },
+            Section {
+                name: "last",
+                run: out_of_bounds,
+            },
+        ];
+        let failed = run_all(&sections);
+        let names: Vec<&str> = failed.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["first", "last"]);
+        assert_eq!(
+            failed[0].1.to_string(),
+            "index 5 is out of bounds, there are only 3 elements"
+        );
+    }
+
+    #[test]
+    fn unknown_chapters() {
+        assert_eq!(availability(1), Availability::Unknown);
+        assert_eq!(availability(21), Availability::Unknown);
+        assert_eq!(report(&[42]), "chapter 42: no such chapter");
+    }
+
+    #[test]
+    fn table_is_in_order_and_complete() {
+        let numbers: Vec<u32> = CHAPTERS.iter().map(|chapter| chapter.number).collect();
+        assert_eq!(numbers, (2..=20).collect::<Vec<u32>>());
+        for chapter in CHAPTERS.iter() {
+            if let Some(feature) = chapter.feature {
+                assert_eq!(feature, format!("ch{}", chapter.number));
+            }
+        }
+    }
+
+    // these hold for whichever features the tests were built with:
+    //     cargo test
+    //     cargo test --features "ch9 ch13"
+    #[test]
+    fn chapter_nine_matches_its_feature() {
+        if cfg!(feature = "ch9") {
+            assert_eq!(availability(9), Availability::Compiled);
+            assert_eq!(report(&[9]), "chapter 9: compiled in");
+        } else {
+            assert_eq!(availability(9), Availability::NotSelected("ch9"));
+            assert_eq!(
+                report(&[9]),
+                "chapter 9: not in this build, add --features ch9"
+            );
+        }
+    }
+
+    #[test]
+    fn summary_lists_what_was_compiled() {
+        let summary = report(&[]);
+        if compiled_in().is_empty() {
+            assert!(summary.starts_with("no chapters compiled in"));
+        } else {
+            assert!(summary.starts_with("compiled in:\n"));
+            assert_eq!(summary.lines().count(), compiled_in().len() + 1);
+        }
+    }
+}
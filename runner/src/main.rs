@@ -0,0 +1,51 @@
+// Runner
+
+/*
+With no arguments, reports which chapters this build was compiled with. Given
+chapter numbers, runs every section of each one:
+
+    cargo run --features "ch9 ch13"
+    cargo run --features ch13 -- 8 13 16
+
+A section that fails is reported and the rest still run. So is a chapter that
+isn't in this build. Either way the runner exits with 1 at the end.
+*/
+
+use domain::prelude::*;
+use runner::Availability;
+use std::{env, process};
+
+fn main() {
+    let asked: Result<Vec<u32>, _> = env::args().skip(1).map(|arg| arg.parse()).collect();
+    let asked = asked.unwrap_or_else(|_| {
+        eprintln!("usage: runner [chapter numbers...]");
+        process::exit(1);
+    });
+    if asked.is_empty() {
+        println!("{}", runner::report(&asked));
+        return;
+    }
+
+    let mut ok = true;
+    for number in asked {
+        let Some(sections) = runner::exercises(number) else {
+            // the report says why, and which feature to add if that's all it is
+            match runner::availability(number) {
+                Availability::Elsewhere(_) => info!("{}", runner::report(&[number])),
+                _ => {
+                    error!("{}", runner::report(&[number]));
+                    ok = false;
+                }
+            }
+            continue;
+        };
+        println!("== chapter {}", number);
+        for (name, e) in runner::run_all(&sections) {
+            error!("chapter {} {}: {}", number, name, e);
+            ok = false;
+        }
+    }
+    if !ok {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,56 @@
+// Spawn the real runner and check that a section returning an ExerciseError
+// is reported and turns into exit code 1, without stopping the sections after
+// it. chapter_3's data_types reads its index from stdin, so these only build
+// with that chapter in:
+//     cargo test --features ch3
+
+#![cfg(feature = "ch3")]
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn runner(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_runner"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to run the runner binary");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn a_good_index_runs_cleanly() {
+    let output = runner(&["3"], "2\n");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("the value of the element at index 2 is: 3"));
+}
+
+#[test]
+fn a_bad_index_is_reported_and_the_rest_still_run() {
+    let output = runner(&["3"], "9\n");
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains(
+        "[error] chapter 3 data_types: index 9 is out of bounds, there are only 5 elements"
+    ));
+    // control_flow comes after data_types
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("LIFTOFF"), "{stdout}");
+}
+
+#[test]
+fn not_a_number_is_reported() {
+    let output = runner(&["3"], "three\n");
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("[error] chapter 3 data_types: expected an index"));
+}